@@ -0,0 +1,88 @@
+use chrono::NaiveDate;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use tempfile::TempDir;
+
+use devlog::{
+    models::{annotation::Annotations, entry::Entry, entry_id::EntryId},
+    storage::Storage,
+    tui::tree::builder::TreeBuilder,
+    utils::fixtures::VaultGenerator,
+};
+
+/// Number of entries in the synthetic vault, roughly 27 years of daily entries
+const VAULT_SIZE: usize = 10_000;
+
+fn synthetic_vault() -> (Storage, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let storage = Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+    let last_day = NaiveDate::from_ymd_opt(2025, 9, 20).unwrap();
+
+    VaultGenerator::new(42)
+        .populate(&storage, VAULT_SIZE, last_day)
+        .expect("Failed to generate vault");
+
+    (storage, temp_dir)
+}
+
+fn bench_vault(c: &mut Criterion) {
+    let (storage, _temp_dir) = synthetic_vault();
+    let mut generator = VaultGenerator::new(7);
+    let contents: Vec<String> = (0..VAULT_SIZE).map(|_| generator.entry_content()).collect();
+
+    let mut group = c.benchmark_group("vault_10k");
+    group.sample_size(10);
+
+    group.bench_function("tree_build", |b| {
+        let builder = TreeBuilder::new(storage.clone());
+        b.iter(|| black_box(builder.build_tree().unwrap()))
+    });
+
+    group.bench_function("annotation_parse", |b| {
+        b.iter(|| {
+            for content in &contents {
+                black_box(Annotations::parse(content));
+            }
+        })
+    });
+
+    group.bench_function("frontmatter_roundtrip", |b| {
+        let entries: Vec<Entry> = contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                let date = NaiveDate::from_num_days_from_ce_opt(730_000 + i as i32).unwrap();
+                Entry::new(EntryId::from_date(date), content.clone())
+            })
+            .collect();
+
+        b.iter(|| {
+            for entry in &entries {
+                let serialized = storage.serialize_entry(entry).unwrap();
+                black_box(storage.deserialize_entry(&entry.id, &serialized).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("search", |b| {
+        b.iter(|| {
+            // Full content scan, the way a search without an index works
+            let matches = storage
+                .list_entries()
+                .unwrap()
+                .into_iter()
+                .filter(|id| {
+                    storage
+                        .load_entry(id)
+                        .is_ok_and(|entry| entry.content.contains("incident"))
+                })
+                .count();
+            black_box(matches)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_vault);
+criterion_main!(benches);
@@ -0,0 +1,299 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
+use color_eyre::eyre::{Result, bail, eyre};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::config::AiConfig;
+use crate::error::{DevlogError, ErrorKind};
+use crate::export::ExportedEntry;
+use crate::publish::{Api, expect_success};
+
+/// Azure OpenAI REST API version requests are made against
+pub const AZURE_API_VERSION: &str = "2024-06-01";
+
+/// Replacement for text matching a redaction rule
+const REDACTED: &str = "[redacted]";
+
+const INSTRUCTIONS: &str = "You summarize the developer journal entries you are given, \
+e.g. for a quarter or a sprint. Write Markdown with the sections Highlights, Projects, \
+Collaboration and Open threads. Only use facts from the entries, and name the dates of \
+the entries they come from.";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    OpenAi,
+    /// Azure OpenAI, `model` is the name of the deployment
+    Azure,
+    /// A model served by Ollama, on this machine unless `endpoint` says otherwise
+    #[default]
+    Ollama,
+}
+
+impl Provider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::OpenAi => "OpenAI",
+            Provider::Azure => "Azure OpenAI",
+            Provider::Ollama => "Ollama",
+        }
+    }
+}
+
+/// Where chat requests for `config` go
+pub fn chat_url(config: &AiConfig) -> Result<String> {
+    let model = model(config)?;
+    let endpoint = config
+        .endpoint
+        .as_deref()
+        .map(|url| url.trim_end_matches('/'));
+    Ok(match config.provider {
+        Provider::OpenAi => format!(
+            "{}/chat/completions",
+            endpoint.unwrap_or("https://api.openai.com/v1")
+        ),
+        Provider::Azure => {
+            let Some(endpoint) = endpoint else {
+                bail!(DevlogError::new(
+                    ErrorKind::Validation,
+                    "Set ai.endpoint to the Azure OpenAI resource first, e.g. `devlog config set ai.endpoint https://team.openai.azure.com`"
+                ));
+            };
+            format!(
+                "{}/openai/deployments/{}/chat/completions?api-version={}",
+                endpoint, model, AZURE_API_VERSION
+            )
+        }
+        Provider::Ollama => format!("{}/api/chat", endpoint.unwrap_or("http://localhost:11434")),
+    })
+}
+
+pub fn model(config: &AiConfig) -> Result<&str> {
+    match &config.model {
+        Some(model) => Ok(model),
+        None => bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "Set ai.model first, e.g. `devlog config set ai.model llama3.1`"
+        )),
+    }
+}
+
+/// Whether `url` points at this machine, so nothing sent to it leaves it
+pub fn is_local(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Rules text has to pass before it's sent to a model: matches of the `ai.redact`
+/// patterns are replaced with `[redacted]`, and with `ai.redact_people` every `@person`
+/// becomes `@person-1`, `@person-2` and so on, the same number in every entry.
+pub struct Redactor {
+    rules: Vec<Regex>,
+    people: bool,
+}
+
+impl Redactor {
+    pub fn new(config: &AiConfig) -> Result<Self> {
+        let rules = config
+            .redact
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| {
+                    DevlogError::new(
+                        ErrorKind::Validation,
+                        format!("Invalid redaction rule '{}' in ai.redact: {}", pattern, e),
+                    )
+                    .into()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            rules,
+            people: config.redact_people,
+        })
+    }
+
+    /// The entries as one Markdown document, oldest first, redacted
+    pub fn document(&self, entries: &[ExportedEntry]) -> Result<String> {
+        let mut aliases: BTreeMap<&str, String> = BTreeMap::new();
+        let mut document = String::new();
+        for entry in entries {
+            let mut content = entry.content.clone();
+            if self.people {
+                let mut people: Vec<&str> = entry
+                    .annotations
+                    .people
+                    .iter()
+                    .map(String::as_str)
+                    .collect();
+                // `@alice-b` before `@alice`, so a name never replaces part of a longer one
+                people.sort_by_key(|person| std::cmp::Reverse(person.len()));
+                for person in people {
+                    let next = aliases.len() + 1;
+                    let alias = aliases
+                        .entry(person)
+                        .or_insert_with(|| format!("person-{}", next));
+                    let mention = Regex::new(&format!(r"@{}\b", regex::escape(person)))
+                        .map_err(|e| eyre!("Failed to redact @{}: {}", person, e))?;
+                    content = mention
+                        .replace_all(&content, format!("@{}", alias))
+                        .into_owned();
+                }
+            }
+            for rule in &self.rules {
+                content = rule.replace_all(&content, REDACTED).into_owned();
+            }
+            document.push_str(&format!("## {}\n\n{}\n\n", entry.date, content.trim_end()));
+        }
+        Ok(document)
+    }
+}
+
+/// Ask the model of `config` at `url` to summarize `document`
+pub fn summarize(api: &dyn Api, config: &AiConfig, url: &str, document: &str) -> Result<String> {
+    let messages = json!([
+        { "role": "system", "content": INSTRUCTIONS },
+        { "role": "user", "content": document },
+    ]);
+    let body = match config.provider {
+        Provider::OpenAi | Provider::Azure => json!({
+            "model": model(config)?,
+            "messages": messages,
+        }),
+        Provider::Ollama => json!({
+            "model": model(config)?,
+            "messages": messages,
+            "stream": false,
+        }),
+    };
+
+    let (status, response) = api.send("POST", url, Some(&body))?;
+    let response = expect_success(status, response, "summarize the entries")?;
+    let content = match config.provider {
+        Provider::OpenAi | Provider::Azure => &response["choices"][0]["message"]["content"],
+        Provider::Ollama => &response["message"]["content"],
+    };
+    match content {
+        Value::String(summary) if !summary.trim().is_empty() => Ok(summary.trim().to_string()),
+        _ => bail!(
+            "{} returned no summary: {}",
+            config.provider.name(),
+            response
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use crate::publish::tests::FakeApi;
+
+    fn entry(id: &str, content: &str) -> ExportedEntry {
+        ExportedEntry::from(Entry::new(id.parse().unwrap(), content.to_string()))
+    }
+
+    #[test]
+    fn test_local_endpoints() {
+        for url in [
+            "http://localhost:11434",
+            "http://127.0.0.1:8080/v1",
+            "http://[::1]:11434/api/chat",
+            "http://user@LOCALHOST/",
+        ] {
+            assert!(is_local(url), "{}", url);
+        }
+        for url in [
+            "https://api.openai.com/v1",
+            "https://team.openai.azure.com",
+            "http://localhost.example.com",
+            "http://192.168.1.20:11434",
+        ] {
+            assert!(!is_local(url), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_redaction_rules_and_people() {
+        let config = AiConfig {
+            redact: vec![r"ACME-\d+".to_string()],
+            redact_people: true,
+            ..AiConfig::default()
+        };
+        let redactor = Redactor::new(&config).unwrap();
+        let document = redactor
+            .document(&[
+                entry("20250920", "Paired with @alice-b and @alice on ACME-42"),
+                entry("20250921", "@alice reviewed it"),
+            ])
+            .unwrap();
+        assert_eq!(
+            document,
+            "## 2025-09-20\n\nPaired with @person-1 and @person-2 on [redacted]\n\n\
+             ## 2025-09-21\n\n@person-2 reviewed it\n\n"
+        );
+
+        let invalid = AiConfig {
+            redact: vec!["(".to_string()],
+            ..AiConfig::default()
+        };
+        assert!(Redactor::new(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_summarize_with_each_provider() {
+        let mut config = AiConfig {
+            model: Some("gpt-4o-mini".to_string()),
+            provider: Provider::OpenAi,
+            ..AiConfig::default()
+        };
+        let api = FakeApi::new(vec![(
+            200,
+            json!({ "choices": [{ "message": { "content": "## Highlights\n" } }] }),
+        )]);
+        let url = chat_url(&config).unwrap();
+        assert_eq!(url, "https://api.openai.com/v1/chat/completions");
+        assert_eq!(
+            summarize(&api, &config, &url, "## 2025-09-20").unwrap(),
+            "## Highlights"
+        );
+
+        config.provider = Provider::Azure;
+        assert!(chat_url(&config).is_err());
+        config.endpoint = Some("https://team.openai.azure.com/".to_string());
+        assert_eq!(
+            chat_url(&config).unwrap(),
+            format!(
+                "https://team.openai.azure.com/openai/deployments/gpt-4o-mini/chat/completions?api-version={}",
+                AZURE_API_VERSION
+            )
+        );
+
+        config.provider = Provider::Ollama;
+        config.endpoint = None;
+        let api = FakeApi::new(vec![
+            (200, json!({ "message": { "content": "Busy week" } })),
+            (200, json!({ "message": { "content": " " } })),
+        ]);
+        let url = chat_url(&config).unwrap();
+        assert_eq!(
+            summarize(&api, &config, &url, "## 2025-09-20").unwrap(),
+            "Busy week"
+        );
+        assert!(summarize(&api, &config, &url, "## 2025-09-20").is_err());
+        let requests = api.requests.borrow();
+        assert_eq!(requests[0].1, "http://localhost:11434/api/chat");
+        assert_eq!(requests[0].2.as_ref().unwrap()["stream"], json!(false));
+    }
+}
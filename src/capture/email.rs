@@ -0,0 +1,139 @@
+use chrono::{DateTime, Local, NaiveDate};
+use color_eyre::eyre::{Context, Result};
+use mailparse::{MailHeaderMap, ParsedMail};
+
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+/// Tag added to everything captured from email
+pub const EMAIL_TAG: &str = "+email";
+
+/// The parts of an email that end up in an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedEmail {
+    /// Day the email was sent on in local time, today if the date is missing
+    pub date: NaiveDate,
+    pub subject: Option<String>,
+    pub body: String,
+}
+
+impl CapturedEmail {
+    /// Parse a raw RFC 5322 message, preferring the plain text part of multipart emails
+    pub fn parse(raw: &[u8]) -> Result<Self> {
+        let mail = mailparse::parse_mail(raw).wrap_err("Failed to parse email")?;
+
+        let date = mail
+            .headers
+            .get_first_value("Date")
+            .and_then(|date| mailparse::dateparse(&date).ok())
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .map_or_else(
+                || Local::now().date_naive(),
+                |date| date.with_timezone(&Local).date_naive(),
+            );
+        let subject = mail
+            .headers
+            .get_first_value("Subject")
+            .map(|subject| subject.trim().to_string())
+            .filter(|subject| !subject.is_empty());
+
+        let body = plain_text_body(&mail)
+            .unwrap_or_default()
+            .replace("\r\n", "\n")
+            .trim()
+            .to_string();
+
+        Ok(Self {
+            date,
+            subject,
+            body,
+        })
+    }
+
+    /// Markdown appended to the entry, tagged with `+email`
+    pub fn to_markdown(&self) -> String {
+        let title = match &self.subject {
+            Some(subject) => format!("**{}** {}", subject, EMAIL_TAG),
+            None => EMAIL_TAG.to_string(),
+        };
+        if self.body.is_empty() {
+            title
+        } else {
+            format!("{}\n\n{}", title, self.body)
+        }
+    }
+
+    /// Append the email to the entry of the day it was sent
+    pub fn save(&self, storage: &Storage) -> Result<EntryId> {
+        let id = EntryId::from_date(self.date);
+        storage.append_to_entry(&id, &self.to_markdown())?;
+        Ok(id)
+    }
+}
+
+fn plain_text_body(mail: &ParsedMail) -> Option<String> {
+    if mail.subparts.is_empty() {
+        let is_text = mail.ctype.mimetype.starts_with("text/");
+        return is_text.then(|| mail.get_body().ok()).flatten();
+    }
+
+    // Plain text first, anything textual as a fallback
+    mail.subparts
+        .iter()
+        .find(|part| part.ctype.mimetype == "text/plain")
+        .and_then(|part| part.get_body().ok())
+        .or_else(|| mail.subparts.iter().find_map(plain_text_body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multipart_email() {
+        let raw = "From: me@example.com\r\n\
+            To: me+devlog@example.com\r\n\
+            Subject: Fixed the flaky test\r\n\
+            Date: Sat, 20 Sep 2025 12:00:00 +0000\r\n\
+            Content-Type: multipart/alternative; boundary=\"b\"\r\n\
+            \r\n\
+            --b\r\n\
+            Content-Type: text/plain; charset=utf-8\r\n\
+            \r\n\
+            It was a timezone issue with @alice\r\n\
+            --b\r\n\
+            Content-Type: text/html\r\n\
+            \r\n\
+            <p>It was a timezone issue</p>\r\n\
+            --b--\r\n";
+
+        let email = CapturedEmail::parse(raw.as_bytes()).unwrap();
+
+        assert_eq!(email.subject.as_deref(), Some("Fixed the flaky test"));
+        assert_eq!(email.body, "It was a timezone issue with @alice");
+        assert_eq!(
+            email.to_markdown(),
+            "**Fixed the flaky test** +email\n\nIt was a timezone issue with @alice"
+        );
+    }
+
+    #[test]
+    fn test_save_appends_to_day_of_email() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let storage = Storage::new_with_base_dir(temp_dir.path()).unwrap();
+        let email = CapturedEmail {
+            date: NaiveDate::from_ymd_opt(2025, 9, 20).unwrap(),
+            subject: None,
+            body: "quick note".to_string(),
+        };
+
+        let id = email.save(&storage).unwrap();
+        email.save(&storage).unwrap();
+
+        assert_eq!(id.to_string(), "20250920");
+        assert_eq!(
+            storage.load_entry(&id).unwrap().content,
+            "+email\n\nquick note\n\n+email\n\nquick note"
+        );
+    }
+}
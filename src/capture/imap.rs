@@ -0,0 +1,83 @@
+use color_eyre::eyre::{Context, Result, eyre};
+
+use crate::capture::email::CapturedEmail;
+use crate::config::{ImapConfig, secrets::SecretStore};
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+/// Secret holding the IMAP password, see `devlog config set-secret`
+pub const PASSWORD_SECRET: &str = "capture.imap.password";
+
+/// Append every unread message sent to the capture address to its day's entry.
+///
+/// Messages are only marked as read once their entry was saved, so a failed
+/// poll picks them up again next time. Messages that can't be read as text are
+/// skipped with a warning, and marked as read and flagged so they're found later.
+pub fn poll(storage: &Storage, config: &ImapConfig) -> Result<Vec<EntryId>> {
+    let missing = |key: &str| {
+        DevlogError::new(
+            ErrorKind::Validation,
+            format!("Set capture.imap.{} to capture email", key),
+        )
+    };
+    let host = config.host.as_deref().ok_or_else(|| missing("host"))?;
+    let username = config
+        .username
+        .as_deref()
+        .ok_or_else(|| missing("username"))?;
+    let address = config
+        .address
+        .as_deref()
+        .ok_or_else(|| missing("address"))?;
+    let password = SecretStore.get(PASSWORD_SECRET)?.ok_or_else(|| {
+        eyre!(
+            "No IMAP password stored, run `devlog config set-secret {}`",
+            PASSWORD_SECRET
+        )
+    })?;
+
+    let tls = native_tls::TlsConnector::new().wrap_err("Failed to set up TLS")?;
+    let client = imap::connect((host, config.port), host, &tls)
+        .wrap_err_with(|| format!("Failed to connect to {}:{}", host, config.port))?;
+    let mut session = client
+        .login(username, &password)
+        .map_err(|(e, _)| e)
+        .wrap_err("IMAP login failed")?;
+    session
+        .select(&config.mailbox)
+        .wrap_err_with(|| format!("Failed to open mailbox {}", config.mailbox))?;
+
+    let mut uids: Vec<u32> = session
+        .uid_search(format!("UNSEEN TO \"{}\"", address.replace('"', "")))?
+        .into_iter()
+        .collect();
+    uids.sort_unstable();
+
+    let mut captured = Vec::new();
+    for uid in uids {
+        // PEEK leaves the message unread until it is safely stored
+        let messages = session.uid_fetch(uid.to_string(), "BODY.PEEK[]")?;
+        let mut flags = "+FLAGS (\\Seen)";
+        for message in messages.iter() {
+            let Some(raw) = message.body() else {
+                continue;
+            };
+            match CapturedEmail::parse(raw) {
+                Ok(email) => captured.push(email.save(storage)?),
+                // One bad message must not hold up the ones after it on every poll
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Skipped message {}, flagged in the mailbox: {}",
+                        uid, e
+                    );
+                    flags = "+FLAGS (\\Seen \\Flagged)";
+                }
+            }
+        }
+        session.uid_store(uid.to_string(), flags)?;
+    }
+
+    session.logout()?;
+    Ok(captured)
+}
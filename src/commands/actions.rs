@@ -0,0 +1,52 @@
+use chrono::Local;
+use color_eyre::eyre::Result;
+
+use crate::models::action::{Action, Owner};
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+/// List open `->` action items, overdue ones first. `waiting_on` and `owed_by` keep
+/// the actions of one owner, e.g. `--waiting-on alice` or `--owed-by me`.
+pub fn execute(
+    storage: &Storage,
+    waiting_on: Option<String>,
+    owed_by: Option<String>,
+) -> Result<()> {
+    let owners: Vec<Owner> = waiting_on
+        .iter()
+        .chain(owed_by.iter())
+        .map(|name| Owner::parse(name))
+        .collect();
+    let actions: Vec<(EntryId, Action)> = storage
+        .index()?
+        .actions()
+        .into_iter()
+        .filter(|(_, action)| owners.iter().all(|owner| action.owner == *owner))
+        .collect();
+
+    if actions.is_empty() {
+        println!("No open action items");
+        return Ok(());
+    }
+
+    let today = Local::now().date_naive();
+    let (overdue, open): (Vec<_>, Vec<_>) = actions
+        .iter()
+        .partition(|(_, action)| action.is_overdue(today));
+    for (heading, actions) in [("Overdue", overdue), ("Open", open)] {
+        if actions.is_empty() {
+            continue;
+        }
+        println!("{} ({})\n", heading, actions.len());
+        for (id, action) in actions {
+            let due = action
+                .due
+                .map(|due| format!("  due {}", due))
+                .unwrap_or_default();
+            println!("{}  {}: {}{}", id, action.owner, action.task, due);
+        }
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use color_eyre::eyre::{Result, bail, eyre};
+
+use crate::ai::{self, Redactor};
+use crate::config::{ConfigStore, secrets::SecretStore};
+use crate::error::{DevlogError, ErrorKind};
+use crate::export;
+use crate::models::entry_id::EntryRange;
+use crate::publish::HttpApi;
+use crate::status;
+use crate::storage::Storage;
+
+/// Secret holding the API key of OpenAI and Azure OpenAI, see `devlog config set-secret`
+const API_KEY_SECRET: &str = "ai.api_key";
+
+#[derive(clap::Subcommand)]
+pub enum AiSubcommand {
+    /// Summarize the entries of a quarter or sprint with the model configured under `ai`
+    Summarize {
+        /// Entries to summarize, e.g. 20250701..20250930
+        #[arg(long, value_name = "YYYYMMDD..YYYYMMDD")]
+        range: EntryRange,
+        /// Refuse to send entries anywhere but this machine, e.g. a local Ollama
+        #[arg(long)]
+        local_only: bool,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: AiSubcommand) -> Result<()> {
+    let config = ConfigStore::new(storage.config_path()).load()?.ai;
+    match subcmd {
+        AiSubcommand::Summarize { range, local_only } => {
+            // Checked before anything is read, so a remote endpoint never sees a request
+            let url = ai::chat_url(&config)?;
+            if (local_only || config.local_only) && !ai::is_local(&url) {
+                bail!(DevlogError::new(
+                    ErrorKind::Validation,
+                    format!(
+                        "{} is not on this machine, refusing to send entries with --local-only",
+                        url
+                    )
+                ));
+            }
+
+            let entries = export::collect(storage, range.start, range.end)?;
+            let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+                bail!(DevlogError::new(
+                    ErrorKind::NotFound,
+                    "No entries in the selected range"
+                ));
+            };
+            let document = Redactor::new(&config)?.document(&entries)?;
+            let path = storage
+                .summaries_path()
+                .join(format!("ai-{}-{}.md", first.id, last.id));
+
+            if storage.writer().is_dry_run() {
+                status!(
+                    "Would send {} entries ({} characters, redacted) to {}",
+                    entries.len(),
+                    document.chars().count(),
+                    url
+                );
+                return Ok(());
+            }
+
+            let headers = match config.provider {
+                ai::Provider::OpenAi => vec![("Authorization", format!("Bearer {}", api_key()?))],
+                ai::Provider::Azure => vec![("api-key", api_key()?)],
+                ai::Provider::Ollama => Vec::new(),
+            };
+            status!(
+                "Sending {} entries to {} ({})",
+                entries.len(),
+                config.provider.name(),
+                ai::model(&config)?
+            );
+            let summary = ai::summarize(&HttpApi::new(headers)?, &config, &url, &document)?;
+
+            let markdown = format!(
+                "# Summary {} to {}\n\n_Generated by {} ({}) from {} entries._\n\n{}\n",
+                first.date,
+                last.date,
+                ai::model(&config)?,
+                config.provider.name(),
+                entries.len(),
+                summary
+            );
+            storage.writer().create_dir_all(&storage.summaries_path())?;
+            storage.writer().write_atomic(&path, markdown)?;
+            status!("Summary written to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn api_key() -> Result<String> {
+    SecretStore.get(API_KEY_SECRET)?.ok_or_else(|| {
+        eyre!(
+            "No API key stored, run `devlog config set-secret {}`",
+            API_KEY_SECRET
+        )
+    })
+}
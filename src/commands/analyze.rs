@@ -0,0 +1,216 @@
+use std::collections::BTreeSet;
+
+use color_eyre::eyre::Result;
+
+use crate::config::Config;
+use crate::models::annotation::AnnotationKind;
+use crate::storage::Storage;
+
+/// Entries above this many words are hard to skim and slow to open
+const HUGE_ENTRY_WORDS: usize = 3000;
+/// Suggest annotating once more than this share of entries has no annotations
+const UNANNOTATED_SHARE: f64 = 0.5;
+/// Vaults with at least this many entries are worth backing up automatically
+const BACKUP_MIN_ENTRIES: usize = 30;
+/// Huge entries listed by id, the rest are counted
+const LISTED_IDS: usize = 5;
+
+/// Something to improve about the vault, with the command fixing it if there is one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+impl Suggestion {
+    fn new(message: String, fix: Option<&str>) -> Self {
+        Self {
+            message,
+            fix: fix.map(str::to_string),
+        }
+    }
+}
+
+/// Print an overview of the vault and suggestions to improve it. Only counts and
+/// entry ids are printed, never content or annotations, so the report can be shared.
+pub fn execute(storage: &Storage, config: &Config) -> Result<()> {
+    let suggestions = analyze(storage, config)?;
+    let index = storage.index()?;
+    let words: usize = index.entries.values().map(|entry| entry.words).sum();
+    println!("Entries: {}", index.entries.len());
+    println!("Words:   {}", words);
+    if let (Some(first), Some(last)) = (index.entries.keys().next(), index.entries.keys().last()) {
+        println!("Range:   {} to {}", first, last);
+    }
+    println!();
+
+    if suggestions.is_empty() {
+        println!("No suggestions, the vault is in good shape.");
+    }
+    for suggestion in suggestions {
+        println!("- {}", suggestion.message);
+        if let Some(fix) = suggestion.fix {
+            println!("  Fix: {}", fix);
+        }
+    }
+    Ok(())
+}
+
+pub fn analyze(storage: &Storage, config: &Config) -> Result<Vec<Suggestion>> {
+    let mut suggestions = Vec::new();
+
+    let misplaced = storage.misplaced_entries()?;
+    if !misplaced.is_empty() {
+        suggestions.push(Suggestion::new(
+            format!(
+                "{} entry file(s) are in subdirectories of the entries directory, devlog only \
+                 reads entries kept directly in it",
+                misplaced.len()
+            ),
+            Some("devlog migrate flatten"),
+        ));
+    }
+
+    let index = storage.index()?;
+    let total = index.entries.len();
+    if total == 0 {
+        return Ok(suggestions);
+    }
+
+    let huge: Vec<String> = index
+        .entries
+        .iter()
+        .filter(|(_, entry)| entry.words > HUGE_ENTRY_WORDS)
+        .map(|(id, _)| id.to_string())
+        .collect();
+    if !huge.is_empty() {
+        let mut listed = huge[..huge.len().min(LISTED_IDS)].join(" ");
+        if huge.len() > LISTED_IDS {
+            listed.push_str(&format!(" and {} more", huge.len() - LISTED_IDS));
+        }
+        suggestions.push(Suggestion::new(
+            format!(
+                "{} entries are over {} words ({}), consider splitting them or moving \
+                 reference material to the scratch pad",
+                huge.len(),
+                HUGE_ENTRY_WORDS,
+                listed
+            ),
+            None,
+        ));
+    }
+
+    let unannotated = index
+        .entries
+        .values()
+        .filter(|entry| entry.annotations.is_empty())
+        .count();
+    if unannotated as f64 > total as f64 * UNANNOTATED_SHARE {
+        suggestions.push(Suggestion::new(
+            format!(
+                "{} of {} entries have no @people, ::projects or +tags, annotating them makes \
+                 digests, project profiles and `devlog tags` useful",
+                unannotated, total
+            ),
+            None,
+        ));
+    }
+
+    let profiles: BTreeSet<String> = storage
+        .list_projects()?
+        .into_iter()
+        .map(|profile| profile.name)
+        .collect();
+    let unprofiled = index
+        .annotation_usage(AnnotationKind::Project)
+        .into_iter()
+        .filter(|usage| !profiles.contains(&usage.value))
+        .count();
+    if unprofiled > 0 {
+        suggestions.push(Suggestion::new(
+            format!(
+                "{} project(s) mentioned in entries have no profile yet",
+                unprofiled
+            ),
+            Some("devlog project rebuild"),
+        ));
+    }
+
+    if !config.backup.auto && total >= BACKUP_MIN_ENTRIES {
+        suggestions.push(Suggestion::new(
+            format!("{} entries and no automatic backups", total),
+            Some("devlog backup auto enable"),
+        ));
+    }
+
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_analyze_suggestions() {
+        let (storage, _temp_dir) = create_test_storage();
+        let huge = "word ".repeat(HUGE_ENTRY_WORDS + 1);
+        storage
+            .save_entry(&Entry::new("20250915".parse().unwrap(), huge))
+            .unwrap();
+        let nested = storage
+            .entry_path(&"20250916".parse().unwrap())
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("2025");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("20250916.md"), "::search").unwrap();
+
+        let suggestions = analyze(&storage, &Config::default()).unwrap();
+        let messages: Vec<&str> = suggestions.iter().map(|s| s.message.as_str()).collect();
+
+        assert!(messages[0].starts_with("1 entry file(s) are in subdirectories"));
+        assert_eq!(
+            suggestions[0].fix.as_deref(),
+            Some("devlog migrate flatten")
+        );
+        assert!(messages[1].contains("over 3000 words (20250915)"));
+        assert!(messages[2].starts_with("1 of 1 entries have no"));
+        assert_eq!(suggestions.len(), 3);
+    }
+
+    #[test]
+    fn test_analyze_fixes() {
+        let (storage, _temp_dir) = create_test_storage();
+        for day in 1..=BACKUP_MIN_ENTRIES {
+            let id = format!("202508{:02}", day).parse().unwrap();
+            storage
+                .save_entry(&Entry::new(id, "::search".to_string()))
+                .unwrap();
+        }
+        fs::remove_dir_all(storage.projects_path()).unwrap();
+
+        let fixes: Vec<Option<String>> = analyze(&storage, &Config::default())
+            .unwrap()
+            .into_iter()
+            .map(|suggestion| suggestion.fix)
+            .collect();
+        assert_eq!(
+            fixes,
+            vec![
+                Some("devlog project rebuild".to_string()),
+                Some("devlog backup auto enable".to_string())
+            ]
+        );
+    }
+}
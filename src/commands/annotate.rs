@@ -0,0 +1,151 @@
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::{
+    annotation::Annotation,
+    entry::{self, Entry},
+    entry_id::{EntryId, EntryRange},
+};
+use crate::search;
+use crate::status;
+use crate::storage::{Storage, focus::FocusSession};
+use crate::utils::output;
+
+#[derive(clap::Subcommand)]
+pub enum AnnotateSubcommand {
+    /// Add an annotation like `+migration` to every matching entry that lacks it
+    Add {
+        /// Annotation to add: +tag, @person or ::project
+        annotation: Annotation,
+        #[command(flatten)]
+        selection: Selection,
+    },
+    /// Remove every mention of an annotation from the matching entries
+    Remove {
+        /// Annotation to remove: +tag, @person or ::project
+        annotation: Annotation,
+        #[command(flatten)]
+        selection: Selection,
+    },
+}
+
+/// Which entries a bulk annotation change applies to
+#[derive(clap::Args)]
+pub struct Selection {
+    /// Only entries in this range, e.g. 20250901..20250915, either end may be left out
+    #[arg(long, value_name = "YYYYMMDD..YYYYMMDD")]
+    range: Option<EntryRange>,
+    /// Only entries containing this text, ignoring case
+    #[arg(long, value_name = "TEXT")]
+    where_contains: Option<String>,
+    /// Apply the change without asking, e.g. from scripts
+    #[arg(short, long)]
+    yes: bool,
+}
+
+pub fn execute(storage: &Storage, subcmd: AnnotateSubcommand) -> Result<()> {
+    let (annotation, selection, add) = match subcmd {
+        AnnotateSubcommand::Add {
+            annotation,
+            selection,
+        } => (annotation, selection, true),
+        AnnotateSubcommand::Remove {
+            annotation,
+            selection,
+        } => (annotation, selection, false),
+    };
+
+    let changes = changes(storage, &annotation, &selection, add)?;
+    if changes.is_empty() {
+        status!("No entries to change");
+        return Ok(());
+    }
+
+    let now = Local::now().fixed_offset();
+    if let Some(session) = FocusSession::load(storage, now)?
+        && let Some(message) = changes
+            .iter()
+            .find_map(|(entry, _)| session.blocks(&entry.id, now))
+    {
+        bail!(DevlogError::new(ErrorKind::Validation, message));
+    }
+
+    let (verb, preposition) = if add {
+        ("Add", "to")
+    } else {
+        ("Remove", "from")
+    };
+    println!(
+        "{} {} {} {} entries:",
+        verb,
+        annotation,
+        preposition,
+        changes.len()
+    );
+    for (entry, line) in &changes {
+        println!("  {}  {}", entry.id, line);
+    }
+
+    if !selection.yes {
+        match output::confirm("Apply?") {
+            Some(true) => {}
+            Some(false) => {
+                status!("Nothing was changed");
+                return Ok(());
+            }
+            None => bail!(DevlogError::new(
+                ErrorKind::Validation,
+                "Not a terminal, pass --yes to apply the change"
+            )),
+        }
+    }
+
+    let entries: Vec<_> = changes.into_iter().map(|(entry, _)| entry).collect();
+    storage.save_entries(&entries)?;
+    status!("Updated {} entries", entries.len());
+    Ok(())
+}
+
+/// The selected entries the annotation changes, edited, each with the line that shows
+/// why it was selected
+fn changes(
+    storage: &Storage,
+    annotation: &Annotation,
+    selection: &Selection,
+    add: bool,
+) -> Result<Vec<(Entry, String)>> {
+    let mut ids: Vec<EntryId> = storage
+        .list_entries()?
+        .into_iter()
+        .filter(|id| selection.range.is_none_or(|range| range.contains(id)))
+        .collect();
+    ids.sort();
+
+    let mut changes = Vec::new();
+    for id in ids {
+        let mut entry = storage.load_entry(&id)?;
+        let line = match &selection.where_contains {
+            Some(text) => match entry
+                .content
+                .lines()
+                .find(|line| search::is_match(line, text))
+            {
+                Some(line) => entry::preview(line),
+                None => continue,
+            },
+            None => entry.preview(),
+        };
+
+        let edited = if add {
+            annotation.add_to(&entry.content)
+        } else {
+            annotation.remove_from(&entry.content)
+        };
+        if let Some(content) = edited {
+            entry.update_content(content);
+            changes.push((entry, line));
+        }
+    }
+    Ok(changes)
+}
@@ -0,0 +1,33 @@
+use color_eyre::eyre::Result;
+
+use crate::models::annotation::AnnotationKind;
+use crate::storage::Storage;
+
+/// Print every value of an annotation kind, most frequent first, with the
+/// number of mentions and the entries mentioning it
+pub fn execute(storage: &Storage, kind: AnnotationKind, dates: bool) -> Result<()> {
+    let usage = storage.annotation_usage(kind)?;
+    let width = usage
+        .iter()
+        .map(|usage| usage.value.chars().count())
+        .max()
+        .unwrap_or(0)
+        + kind.prefix().len();
+
+    for usage in usage {
+        let name = format!("{}{}", kind.prefix(), usage.value);
+        if dates {
+            let entries: Vec<String> = usage.entries.iter().map(ToString::to_string).collect();
+            println!(
+                "{:<width$}  {:>4}  {}",
+                name,
+                usage.count,
+                entries.join(" "),
+                width = width
+            );
+        } else {
+            println!("{:<width$}  {:>4}", name, usage.count, width = width);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::{fs, path::Path};
+
+use chrono::Local;
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::{Storage, attachment::pasted_image_name};
+use crate::utils::clipboard;
+
+/// Attach a file, or the image on the clipboard, to entry `id` and link it at the end of the entry
+pub fn execute(
+    storage: &Storage,
+    id: EntryId,
+    file: Option<&Path>,
+    from_clipboard: bool,
+) -> Result<()> {
+    let (name, contents) = match file {
+        Some(file) if !from_clipboard => {
+            let name = file
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .ok_or_else(|| {
+                    DevlogError::new(
+                        ErrorKind::Validation,
+                        format!("{} is not a file", file.display()),
+                    )
+                })?;
+            let contents =
+                fs::read(file).wrap_err_with(|| format!("Failed to read {}", file.display()))?;
+            (name, contents)
+        }
+        _ => {
+            let Some(image) = clipboard::read_image()? else {
+                bail!(DevlogError::new(
+                    ErrorKind::Validation,
+                    "The clipboard holds no image"
+                ));
+            };
+            let name = pasted_image_name(Local::now().naive_local(), image.extension);
+            (name, image.contents)
+        }
+    };
+
+    let attachment = storage.save_attachment(&id, &name, contents)?;
+    storage.append_to_entry(&id, &attachment.markdown())?;
+    status!("Attached {} to {}", attachment.path.display(), id);
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use std::path::PathBuf;
+
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+
+use crate::config::ConfigStore;
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::{
+    Storage,
+    backup::{list_backups, read_backup_entry},
+};
+
+#[derive(clap::Subcommand)]
+pub enum BackupSubcommand {
+    /// Create an archive of the vault right away
+    Now,
+    /// List archives, oldest first
+    List,
+    /// Print one entry from an archive, or put it back with --restore
+    Show {
+        /// Archive path, or its file name in the backup directory
+        archive: PathBuf,
+        /// Entry to look at
+        #[arg(long, value_name = "YYYYMMDD")]
+        id: EntryId,
+        /// Replace the current entry, its events and attachments with the archived ones
+        #[arg(long)]
+        restore: bool,
+    },
+    /// List the earlier versions kept of an entry, newest first, or put one back
+    /// with --restore
+    Versions {
+        /// Entry to look at
+        #[arg(value_name = "YYYYMMDD")]
+        id: EntryId,
+        /// Number of the version to restore, as listed
+        #[arg(long, value_name = "N")]
+        restore: Option<usize>,
+    },
+    /// Manage weekly automatic backups
+    Auto {
+        #[command(subcommand)]
+        subcmd: AutoSubcommand,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum AutoSubcommand {
+    /// Take a weekly backup after any devlog command once the last one is a week old
+    Enable {
+        /// Number of archives to keep
+        #[arg(long)]
+        keep: Option<usize>,
+        /// Directory to write archives to
+        #[arg(long, value_name = "DIR")]
+        location: Option<PathBuf>,
+    },
+    /// Stop taking automatic backups, existing archives are kept
+    Disable,
+}
+
+pub fn execute(storage: &Storage, subcmd: BackupSubcommand) -> Result<()> {
+    let store = ConfigStore::new(storage.config_path()).with_writer(storage.writer().clone());
+
+    match subcmd {
+        BackupSubcommand::Now => {
+            let config = store.load()?.backup;
+            let dir = storage.backup_dir(&config);
+            let path = storage.create_backup(&dir, Local::now().naive_local())?;
+            storage.rotate_backups(&dir, config.keep.max(1))?;
+            status!("Backup written to {}", path.display());
+        }
+        BackupSubcommand::List => {
+            for backup in list_backups(&storage.backup_dir(&store.load()?.backup))? {
+                println!(
+                    "{}  {}",
+                    backup.created.format("%Y-%m-%d %H:%M"),
+                    backup.path.display()
+                );
+            }
+        }
+        BackupSubcommand::Show {
+            archive,
+            id,
+            restore,
+        } => {
+            // A bare file name refers to the backup directory
+            let archive = if archive.exists() {
+                archive
+            } else {
+                storage.backup_dir(&store.load()?.backup).join(archive)
+            };
+
+            if restore {
+                let restored = storage.restore_from_backup(&archive, &id)?;
+                status!("Restored {} file(s) of entry {}", restored.len(), id);
+                return Ok(());
+            }
+
+            let files = read_backup_entry(&archive, &id)?;
+            let Some((_, content)) = files.iter().find(|(path, _)| path.starts_with("entries"))
+            else {
+                bail!(DevlogError::new(
+                    ErrorKind::NotFound,
+                    format!("Entry '{}' is not in {}", id, archive.display())
+                ));
+            };
+            let entry = storage.deserialize_entry(&id, &String::from_utf8_lossy(content))?;
+            println!("{}", entry);
+            for (path, _) in files
+                .iter()
+                .filter(|(path, _)| !path.starts_with("entries"))
+            {
+                status!("Also archived: {}", path.display());
+            }
+        }
+        BackupSubcommand::Versions { id, restore } => {
+            let versions = storage.entry_versions(&id)?;
+            let Some(number) = restore else {
+                if versions.is_empty() {
+                    status!(
+                        "No earlier versions of {}, set storage.keep_versions to keep them",
+                        id
+                    );
+                }
+                for (number, version) in versions.iter().enumerate() {
+                    println!(
+                        "{:>3}  {}  {}",
+                        number + 1,
+                        version.saved.format("%Y-%m-%d %H:%M:%S"),
+                        version.path.display()
+                    );
+                }
+                return Ok(());
+            };
+            let Some(version) = number.checked_sub(1).and_then(|n| versions.get(n)) else {
+                bail!(DevlogError::new(
+                    ErrorKind::NotFound,
+                    format!("Entry '{}' has no version {}", id, number)
+                ));
+            };
+            storage.restore_version(&id, version)?;
+            status!(
+                "Restored {} as saved before {}",
+                id,
+                version.saved.format("%Y-%m-%d %H:%M:%S")
+            );
+        }
+        BackupSubcommand::Auto {
+            subcmd: AutoSubcommand::Enable { keep, location },
+        } => {
+            let config = store.update(|config| {
+                config.backup.auto = true;
+                if let Some(keep) = keep {
+                    config.backup.keep = keep;
+                }
+                if location.is_some() {
+                    config.backup.location = location;
+                }
+                Ok(())
+            })?;
+            status!(
+                "Weekly backups enabled, keeping {} in {}",
+                config.backup.keep,
+                storage.backup_dir(&config.backup).display()
+            );
+        }
+        BackupSubcommand::Auto {
+            subcmd: AutoSubcommand::Disable,
+        } => {
+            store.update(|config| {
+                config.backup.auto = false;
+                Ok(())
+            })?;
+            status!("Weekly backups disabled");
+        }
+    }
+
+    Ok(())
+}
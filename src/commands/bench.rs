@@ -0,0 +1,161 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
+
+use chrono::Local;
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::search;
+use crate::status;
+use crate::storage::Storage;
+use crate::tui::tree::{builder::TreeBuilder, flattener::TreeFlattener};
+use crate::utils::fixtures::VaultGenerator;
+
+/// Searched for by `bench self`: a word most vaults contain, and one none does
+const COMMON_WORD: &str = "the";
+const MISSING_WORD: &str = "zqxjkvbw";
+
+#[derive(clap::Subcommand)]
+pub enum BenchSubcommand {
+    /// Create a synthetic vault for profiling and benchmarking
+    Generate {
+        /// Directory to create the vault in (must not contain entries yet)
+        #[arg(long, value_name = "DIR")]
+        output: PathBuf,
+        /// Number of daily entries to generate
+        #[arg(long, default_value_t = 10_000)]
+        entries: usize,
+        /// Seed for the generator, the same seed produces the same vault
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+    },
+    /// Time devlog on your own vault, printing a report to paste into a performance
+    /// bug report. Only sizes and timings are included, never entry content.
+    #[command(name = "self")]
+    Report {
+        /// Times every measurement is repeated, the median is reported
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: BenchSubcommand) -> Result<()> {
+    match subcmd {
+        BenchSubcommand::Generate {
+            output,
+            entries,
+            seed,
+        } => generate(storage, output, entries, seed),
+        BenchSubcommand::Report { runs } => report(storage, runs.max(1)),
+    }
+}
+
+fn generate(storage: &Storage, output: PathBuf, entries: usize, seed: u64) -> Result<()> {
+    let vault = Storage::new_with_base_dir(&output)?.with_dry_run(storage.writer().is_dry_run());
+    if !vault.list_entries()?.is_empty() {
+        bail!(
+            "{} already contains entries, choose an empty directory",
+            output.display()
+        );
+    }
+
+    status!("Generating {} entries in {}...", entries, output.display());
+    VaultGenerator::new(seed).populate(&vault, entries, Local::now().date_naive())?;
+
+    status!("Synthetic vault created: {}", vault.data_path().display());
+    Ok(())
+}
+
+fn report(storage: &Storage, runs: usize) -> Result<()> {
+    status!("Timing devlog on {}...", storage.data_path().display());
+    let exe = env::current_exe().wrap_err("Failed to find the devlog executable")?;
+
+    let timings = [
+        (
+            "Startup (`devlog --version`)",
+            measure(runs, || {
+                process::Command::new(&exe)
+                    .arg("--version")
+                    .output()
+                    .wrap_err("Failed to run devlog")
+                    .map(drop)
+            })?,
+        ),
+        (
+            "Index refresh",
+            measure(runs, || storage.index().map(drop))?,
+        ),
+        (
+            "Tree build",
+            measure(runs, || {
+                let nodes = TreeBuilder::new(storage.clone()).build_tree()?;
+                TreeFlattener::flatten(&nodes);
+                Ok(())
+            })?,
+        ),
+        (
+            "Search, common word",
+            measure(runs, || search::search(storage, COMMON_WORD).map(drop))?,
+        ),
+        (
+            "Search, no match",
+            measure(runs, || search::search(storage, MISSING_WORD).map(drop))?,
+        ),
+    ];
+
+    let index = storage.index()?;
+    let entries = storage.list_entries()?;
+    let mut bytes = 0;
+    for id in &entries {
+        bytes += fs::metadata(storage.entry_path(id)?).map_or(0, |m| m.len());
+    }
+    let words: usize = index.entries.values().map(|entry| entry.words).sum();
+
+    println!("## devlog performance report\n");
+    println!("| | |\n|---|---|");
+    println!("| Version | {} |", env!("CARGO_PKG_VERSION"));
+    println!("| Platform | {} {} |", env::consts::OS, env::consts::ARCH);
+    println!("| Entries | {} |", entries.len());
+    println!("| Words | {} |", words);
+    println!("| Vault size | {:.1} MB |", bytes as f64 / 1_000_000.0);
+    println!("\n| Measurement | Median | Min | Max |\n|---|---|---|---|");
+    for (name, timing) in &timings {
+        println!(
+            "| {} | {} | {} | {} |",
+            name,
+            millis(timing.median),
+            millis(timing.min),
+            millis(timing.max)
+        );
+    }
+    println!("\n{} runs each, no entry content is included.", runs);
+    Ok(())
+}
+
+struct Timing {
+    median: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+fn measure(runs: usize, mut run: impl FnMut() -> Result<()>) -> Result<Timing> {
+    let mut durations = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        run()?;
+        durations.push(start.elapsed());
+    }
+    durations.sort();
+    Ok(Timing {
+        median: durations[durations.len() / 2],
+        min: durations[0],
+        max: durations[durations.len() - 1],
+    })
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
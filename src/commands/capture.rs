@@ -0,0 +1,55 @@
+use std::{
+    io::{self, Read},
+    thread,
+    time::Duration,
+};
+
+use color_eyre::eyre::{Context, Result};
+
+use crate::capture::{email::CapturedEmail, imap};
+use crate::config::ConfigStore;
+use crate::status;
+use crate::storage::Storage;
+
+#[derive(clap::Subcommand)]
+pub enum CaptureSubcommand {
+    /// Append a raw email piped on stdin, e.g. from a mail filter
+    Email,
+    /// Append unread messages sent to the capture address from the configured mailbox
+    Imap {
+        /// Keep polling instead of exiting after one pass
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between two polls with --watch
+        #[arg(long, default_value_t = 300)]
+        interval: u64,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: CaptureSubcommand) -> Result<()> {
+    match subcmd {
+        CaptureSubcommand::Email => {
+            let mut raw = Vec::new();
+            io::stdin()
+                .read_to_end(&mut raw)
+                .wrap_err("Failed to read email from stdin")?;
+            let id = CapturedEmail::parse(&raw)?.save(storage)?;
+            status!("Email captured in entry {}", id);
+        }
+        CaptureSubcommand::Imap { watch, interval } => {
+            let config = ConfigStore::new(storage.config_path()).load()?;
+            loop {
+                let captured = imap::poll(storage, &config.capture.imap)?;
+                if !captured.is_empty() {
+                    status!("Captured {} email(s)", captured.len());
+                }
+                if !watch {
+                    break;
+                }
+                thread::sleep(Duration::from_secs(interval.max(1)));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use std::{env, io, path::Path};
+
+use clap::ValueEnum;
+use clap_complete::{
+    engine::{ArgValueCandidates, CompletionCandidate},
+    env::Shells,
+};
+use color_eyre::eyre::{Result, eyre};
+
+use crate::config::{ConfigStore, PROFILE_ENV};
+use crate::models::annotation::AnnotationKind;
+use crate::storage::{CONFIG_DIR_ENV, DATA_DIR_ENV, Storage};
+
+/// Environment variable the shell scripts use to ask devlog for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Elvish,
+    Fish,
+    Powershell,
+    Zsh,
+}
+
+/// Print the script that registers devlog completions with the given shell.
+///
+/// The script calls back into devlog on every completion, so values such as
+/// `--tag` candidates always reflect the current vault.
+pub fn execute(shell: Shell) -> Result<()> {
+    let name = shell
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default();
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&name)
+        .ok_or_else(|| eyre!("Unsupported shell: {}", name))?;
+
+    let bin = env!("CARGO_PKG_NAME");
+    completer.write_registration(COMPLETE_VAR, bin, bin, bin, &mut io::stdout())?;
+    Ok(())
+}
+
+/// Completion candidates for an annotation value argument,
+/// e.g. `#[arg(long, add = annotation_candidates(AnnotationKind::Tag))]`.
+/// Projects closed with `devlog project close` aren't offered.
+pub fn annotation_candidates(kind: AnnotationKind) -> ArgValueCandidates {
+    candidates_from(kind, || {
+        let args: Vec<String> = env::args_os()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        open_storage(&args)
+    })
+}
+
+/// The vault of the command line being completed, picked like `main` does: the config
+/// in DEVLOG_CONFIG_DIR, the profile of `--profile` or DEVLOG_PROFILE, and `--data-dir`
+/// or DEVLOG_DATA_DIR over both
+fn open_storage(args: &[String]) -> Result<Storage> {
+    let mut storage = Storage::new()?;
+    if let Some(config_dir) = env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        storage = storage.with_config_dir(Path::new(&config_dir))?;
+    }
+    let config = ConfigStore::new(storage.config_path())
+        .load()
+        .unwrap_or_default();
+
+    let requested = flag_value(args, "--profile")
+        .or_else(|| env::var(PROFILE_ENV).ok().filter(|name| !name.is_empty()));
+    if let Some((name, profile)) = config.active_profile(requested.as_deref())? {
+        storage = storage.with_profile(name, &profile.data_path)?;
+    }
+    let data_dir = flag_value(args, "--data-dir")
+        .or_else(|| env::var(DATA_DIR_ENV).ok().filter(|dir| !dir.is_empty()));
+    if let Some(data_dir) = data_dir {
+        storage = storage.with_data_dir(Path::new(&data_dir))?;
+    }
+    Ok(storage)
+}
+
+/// Value of `--flag value` or `--flag=value` in `args`, the last one wins as in clap
+fn flag_value(args: &[String], flag: &str) -> Option<String> {
+    let mut value = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            value = args.next().cloned();
+        } else if let Some(inline) = arg
+            .strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            value = Some(inline.to_string());
+        }
+    }
+    value.filter(|value| !value.is_empty())
+}
+
+/// The values of `kind` in the vault `open` returns, read from its index on every completion
+fn candidates_from(
+    kind: AnnotationKind,
+    open: impl Fn() -> Result<Storage> + Send + Sync + 'static,
+) -> ArgValueCandidates {
+    ArgValueCandidates::new(move || {
+        // Completion must never fail loudly, an empty list is the best fallback
+        open()
+            .and_then(|storage| {
+                let mut values = storage.annotation_values(kind)?;
+                if kind == AnnotationKind::Project {
+                    let closed = storage.closed_projects()?;
+                    values.retain(|value| !closed.contains(value));
+                }
+                Ok(values)
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(CompletionCandidate::new)
+            .collect::<Vec<_>>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use clap::{Arg, Command};
+    use clap_complete::engine;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_annotation_flags_complete_indexed_values() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let base = temp_dir.path().to_path_buf();
+        let storage = Storage::new_with_base_dir(&base).expect("Failed to create storage");
+        storage
+            .save_entry(&Entry::new(
+                "20250920".parse().unwrap(),
+                "+rust +release with @alice on ::billing".to_string(),
+            ))
+            .unwrap();
+
+        let open = move || Storage::new_with_base_dir(&base);
+        let mut cmd = Command::new("devlog")
+            .arg(
+                Arg::new("tag")
+                    .long("tag")
+                    .add(candidates_from(AnnotationKind::Tag, open.clone())),
+            )
+            .arg(
+                Arg::new("person")
+                    .long("person")
+                    .add(candidates_from(AnnotationKind::Person, open)),
+            );
+        let complete = |cmd: &mut Command, args: &[&str]| -> Vec<String> {
+            let args = args.iter().map(Into::into).collect();
+            engine::complete(cmd, args, 2, None)
+                .unwrap()
+                .iter()
+                .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        assert_eq!(
+            complete(&mut cmd, &["devlog", "--tag", "r"]),
+            vec!["release", "rust"]
+        );
+        assert_eq!(
+            complete(&mut cmd, &["devlog", "--person", ""]),
+            vec!["alice"]
+        );
+    }
+
+    #[test]
+    fn test_flag_value_reads_both_spellings() {
+        let args: Vec<String> = [
+            "devlog",
+            "--",
+            "devlog",
+            "--profile",
+            "work",
+            "list",
+            "--data-dir=/tmp/x",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+        assert_eq!(flag_value(&args, "--profile"), Some("work".to_string()));
+        assert_eq!(flag_value(&args, "--data-dir"), Some("/tmp/x".to_string()));
+        assert_eq!(flag_value(&args, "--here"), None);
+    }
+}
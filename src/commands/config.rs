@@ -1,4 +1,19 @@
-use color_eyre::Result;
+use std::{fs, path::PathBuf};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use dialoguer::{Confirm, Input, Password};
+
+use crate::config::{
+    Config, ConfigStore, DEFAULT_PROFILE, ProfileConfig, SyncConfig,
+    secrets::{self, SecretStore},
+};
+use crate::error::{DevlogError, ErrorKind};
+use crate::status;
+use crate::storage::Storage;
+use crate::utils::editor;
 
 #[derive(clap::Subcommand)]
 pub enum ConfigSubcommand {
@@ -7,6 +22,42 @@ pub enum ConfigSubcommand {
         /// Cloud sync provider (azure, aws, gcp, etc)
         provider: String,
     },
+    /// Print a value by its dotted path, e.g. `sync.enabled` or `editor`
+    Get {
+        /// Dotted path of the key or section
+        key: String,
+    },
+    /// Set a value by its dotted path, e.g. `devlog config set sync.enabled true`
+    Set {
+        /// Dotted path of the key
+        key: String,
+        /// New value, converted to the type of the key
+        value: String,
+    },
+    /// Export the configuration to set up devlog on another machine
+    Export {
+        /// Replace credentials with a placeholder, they are prompted for on import
+        #[arg(long)]
+        redact_secrets: bool,
+        /// Write the bundle to a file instead of stdout
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Replace the configuration with an exported bundle
+    Import {
+        /// Bundle created by `devlog config export`
+        file: PathBuf,
+    },
+    /// Store a credential in the OS keychain, e.g. `sync.connection_string`
+    SetSecret {
+        /// Name of the secret, prompted for without echo
+        name: String,
+    },
+    /// Keep separate journals, e.g. for work and personal notes
+    Profile {
+        #[command(subcommand)]
+        subcmd: ProfileSubcommand,
+    },
     /// Open config file in editor
     Edit,
     /// Show current configuration
@@ -15,16 +66,255 @@ pub enum ConfigSubcommand {
     Reset,
 }
 
-pub fn execute(subcmd: Option<ConfigSubcommand>) -> Result<()> {
+#[derive(clap::Subcommand)]
+pub enum ProfileSubcommand {
+    /// Add a profile with its own entries directory and sync settings
+    Add {
+        /// Name to select the profile with, e.g. `work`
+        name: String,
+        /// Directory of the profile's entries, created if missing
+        #[arg(long, value_name = "DIR")]
+        data_path: PathBuf,
+        /// Folder shared between machines to sync the profile with
+        #[arg(long, value_name = "DIR")]
+        sync_remote: Option<PathBuf>,
+    },
+    /// List the profiles, marking the one in use
+    List,
+    /// Use a profile from now on, `default` for the journal outside of profiles
+    Switch {
+        /// Name of the profile
+        name: String,
+    },
+}
+
+/// `active` is the profile this invocation runs with, `None` for the default journal
+pub fn execute(
+    storage: &Storage,
+    subcmd: Option<ConfigSubcommand>,
+    active: Option<&str>,
+) -> Result<()> {
+    let store = ConfigStore::new(storage.config_path()).with_writer(storage.writer().clone());
+
     match subcmd {
-        None => println!("Configure devlog interactively"),
+        None => configure_interactively(&store)?,
         Some(ConfigSubcommand::Sync { provider }) => {
-            println!("Configure sync provider: {}", provider)
+            store.update(|config| {
+                config.sync.enabled = true;
+                config.sync.provider = Some(provider.clone());
+                Ok(())
+            })?;
+            status!("Sync provider set to: {}", provider);
+        }
+        Some(ConfigSubcommand::Get { key }) => match store.load()?.get(&key)? {
+            // Print plain values so scripts can use them directly
+            toml::Value::String(value) => println!("{}", value),
+            toml::Value::Table(table) => print!("{}", toml::to_string_pretty(&table)?),
+            value => println!("{}", value),
+        },
+        Some(ConfigSubcommand::Set { key, value }) => {
+            store.update(|config| config.set(&key, &value))?;
+            status!("{} = {}", key, value);
+        }
+        Some(ConfigSubcommand::Export {
+            redact_secrets,
+            output,
+        }) => {
+            let bundle = store.load()?.export(redact_secrets)?;
+            match output {
+                Some(path) => {
+                    storage.writer().write(&path, bundle)?;
+                    status!("Configuration exported to {}", path.display());
+                }
+                None => print!("{}", bundle),
+            }
+        }
+        Some(ConfigSubcommand::Import { file }) => {
+            let raw = fs::read_to_string(&file)
+                .wrap_err_with(|| format!("Failed to read {}", file.display()))?;
+            let config = Config::import(&raw, |key| {
+                Ok(Password::new()
+                    .with_prompt(format!("Value for {}", key))
+                    .interact()?)
+            })?;
+
+            let (_, version) = store.load_for_update()?;
+            store.save(&config, version)?;
+            // Older bundles carry credentials in plain text
+            secrets::migrate_plaintext_secrets(&store, |name, value| SecretStore.set(name, value))?;
+            status!("Configuration imported from {}", file.display());
+        }
+        Some(ConfigSubcommand::SetSecret { name }) => {
+            let value = Password::new()
+                .with_prompt(format!("Value for {}", name))
+                .interact()?;
+            SecretStore.set(&name, &value)?;
+            status!("Stored {} in the OS keychain", name);
+        }
+        Some(ConfigSubcommand::Profile { subcmd }) => profile(&store, subcmd, active)?,
+        Some(ConfigSubcommand::Edit) => {
+            // Make sure there is a file to open
+            if !store.path().exists() {
+                store.update(|_| Ok(()))?;
+            }
+            editor::open_file(store.path())?;
+        }
+        Some(ConfigSubcommand::Show) => {
+            let config = store.load()?;
+            println!("# {}\n", store.path().display());
+            print!("{}", toml::to_string_pretty(&config)?);
+        }
+        Some(ConfigSubcommand::Reset) => {
+            let (_, version) = store.load_for_update()?;
+            store.save(&Config::default(), version)?;
+            status!("Configuration reset to defaults");
         }
-        Some(ConfigSubcommand::Edit) => println!("Configure edit"),
-        Some(ConfigSubcommand::Show) => println!("Current configuration"),
-        Some(ConfigSubcommand::Reset) => println!("Reset to default settings"),
     }
 
     Ok(())
 }
+
+fn profile(store: &ConfigStore, subcmd: ProfileSubcommand, active: Option<&str>) -> Result<()> {
+    match subcmd {
+        ProfileSubcommand::Add {
+            name,
+            data_path,
+            sync_remote,
+        } => {
+            validate_profile_name(&name)?;
+            let data_path = std::path::absolute(&data_path)
+                .wrap_err_with(|| format!("Invalid data path {}", data_path.display()))?;
+            let sync = match sync_remote {
+                Some(remote) => SyncConfig {
+                    enabled: true,
+                    provider: Some("folder".to_string()),
+                    remote: Some(remote),
+                    connection_string: None,
+                },
+                None => SyncConfig::default(),
+            };
+            store.update(|config| {
+                if config.profiles.contains_key(&name) {
+                    bail!(DevlogError::new(
+                        ErrorKind::Conflict,
+                        format!("Profile '{}' already exists", name)
+                    ));
+                }
+                config.profiles.insert(
+                    name.clone(),
+                    ProfileConfig {
+                        data_path: data_path.clone(),
+                        sync,
+                    },
+                );
+                Ok(())
+            })?;
+            status!(
+                "Added profile {} with entries in {}, use it with `devlog --profile {}` or \
+                 `devlog config profile switch {}`",
+                name,
+                data_path.display(),
+                name,
+                name
+            );
+        }
+        ProfileSubcommand::List => {
+            let config = store.load()?;
+            let marker = |name: &str| {
+                if active.unwrap_or(DEFAULT_PROFILE) == name {
+                    "*"
+                } else {
+                    " "
+                }
+            };
+            let width = config
+                .profiles
+                .keys()
+                .map(|name| name.chars().count())
+                .chain([DEFAULT_PROFILE.len()])
+                .max()
+                .unwrap_or(0);
+
+            println!("{} {}", marker(DEFAULT_PROFILE), DEFAULT_PROFILE);
+            for (name, profile) in &config.profiles {
+                let sync = match (&profile.sync.remote, profile.sync.enabled) {
+                    (Some(remote), true) => format!("  sync: {}", remote.display()),
+                    _ => String::new(),
+                };
+                println!(
+                    "{} {:<width$}  {}{}",
+                    marker(name),
+                    name,
+                    profile.data_path.display(),
+                    sync,
+                    width = width
+                );
+            }
+        }
+        ProfileSubcommand::Switch { name } => {
+            store.update(|config| {
+                if name != DEFAULT_PROFILE && !config.profiles.contains_key(&name) {
+                    bail!(DevlogError::new(
+                        ErrorKind::NotFound,
+                        format!(
+                            "No profile named '{}', see `devlog config profile list`",
+                            name
+                        )
+                    ));
+                }
+                config.profile = (name != DEFAULT_PROFILE).then(|| name.clone());
+                Ok(())
+            })?;
+            status!("Switched to profile {}", name);
+        }
+    }
+    Ok(())
+}
+
+/// Profile names are used as directory names, so they follow the rules of note names
+fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = name.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_');
+    if !valid {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            format!(
+                "Invalid profile name '{}': use lowercase letters, digits, `-` and `_`, \
+                 starting with a letter",
+                name
+            )
+        ));
+    }
+    if name == DEFAULT_PROFILE {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "'default' names the journal outside of profiles"
+        ));
+    }
+    Ok(())
+}
+
+fn configure_interactively(store: &ConfigStore) -> Result<()> {
+    // The version is checked on save, so a config written by another devlog
+    // process while the prompts are open is never silently overwritten
+    let (mut config, version) = store.load_for_update()?;
+
+    config.sync.enabled = Confirm::new()
+        .with_prompt("Enable cloud sync?")
+        .default(config.sync.enabled)
+        .interact()?;
+
+    if config.sync.enabled {
+        let provider: String = Input::new()
+            .with_prompt("Cloud sync provider (azure, aws, gcp, etc)")
+            .with_initial_text(config.sync.provider.clone().unwrap_or_default())
+            .interact_text()?;
+        config.sync.provider = Some(provider);
+    }
+
+    store.save(&config, version)?;
+    status!("Configuration saved to {}", store.path().display());
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use chrono::Local;
+use color_eyre::eyre::Result;
+
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::Storage;
+
+pub fn execute(storage: &Storage, id: EntryId) -> Result<()> {
+    storage.trash_entry(&id, Local::now().naive_local())?;
+    status!(
+        "Entry {} moved to the trash, `devlog trash restore {}` brings it back",
+        id,
+        id
+    );
+    Ok(())
+}
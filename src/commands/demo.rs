@@ -0,0 +1,113 @@
+use std::{fs, path::PathBuf};
+
+use chrono::Local;
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::status;
+use crate::storage::Storage;
+use crate::tui::app::launch_tui;
+use crate::utils::fixtures::VaultGenerator;
+
+#[derive(clap::Subcommand)]
+pub enum DemoSubcommand {
+    /// Create a vault filled with generated entries and open it in the TUI
+    Seed {
+        /// Number of days of entries to generate, ending today
+        #[arg(long, default_value_t = 365)]
+        days: usize,
+        /// Directory to create the vault in (defaults to a new temporary directory)
+        #[arg(long, value_name = "DIR")]
+        output: Option<PathBuf>,
+        /// Seed for the generator, the same seed produces the same vault
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+        /// Only create the vault, don't launch the TUI
+        #[arg(long)]
+        no_tui: bool,
+        /// Delete the vault again when the TUI is closed, to try devlog without keeping
+        /// anything
+        #[arg(long, conflicts_with_all = ["output", "no_tui"])]
+        ephemeral: bool,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: DemoSubcommand) -> Result<()> {
+    match subcmd {
+        DemoSubcommand::Seed {
+            days,
+            output,
+            seed,
+            no_tui,
+            ephemeral,
+        } => seed_vault(storage, days, output, seed, no_tui, ephemeral),
+    }
+}
+
+fn seed_vault(
+    storage: &Storage,
+    days: usize,
+    output: Option<PathBuf>,
+    seed: u64,
+    no_tui: bool,
+    ephemeral: bool,
+) -> Result<()> {
+    // The vault is only deleted after the TUI, and never a directory the user picked
+    if ephemeral && (no_tui || output.is_some()) {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "--ephemeral only works with a generated directory and the TUI, drop --output and --no-tui"
+        ));
+    }
+    if ephemeral && storage.writer().is_dry_run() {
+        status!(
+            "Would generate {} days of entries and open them in the TUI",
+            days
+        );
+        return Ok(());
+    }
+
+    // Never touch the user's real vault, demos always live in their own directory
+    let output = output.unwrap_or_else(|| {
+        std::env::temp_dir().join(format!("devlog-demo-{}-{}", seed, std::process::id()))
+    });
+    if ephemeral {
+        // Fails if the directory is already there, so only what's created here is deleted
+        fs::create_dir(&output)
+            .wrap_err_with(|| format!("Failed to create the demo vault {}", output.display()))?;
+    }
+
+    let vault = Storage::new_with_base_dir(&output)?.with_dry_run(storage.writer().is_dry_run());
+    if !vault.list_entries()?.is_empty() {
+        bail!(
+            "{} already contains entries, choose an empty directory",
+            output.display()
+        );
+    }
+
+    status!(
+        "Generating {} days of entries in {}...",
+        days,
+        output.display()
+    );
+    let generated = VaultGenerator::new(seed).populate(&vault, days, Local::now().date_naive());
+    if !ephemeral {
+        generated?;
+        status!("Demo vault created: {}", vault.data_path().display());
+        if no_tui || storage.writer().is_dry_run() {
+            return Ok(());
+        }
+        return launch_tui(&vault);
+    }
+
+    status!(
+        "Demo vault created: {}, it is deleted when the TUI is closed",
+        vault.data_path().display()
+    );
+    // Gone with whatever was changed in the TUI, also when generating or the TUI failed
+    let result = generated.and_then(|()| launch_tui(&vault));
+    fs::remove_dir_all(&output)
+        .wrap_err_with(|| format!("Failed to delete the demo vault {}", output.display()))?;
+    status!("Demo vault deleted");
+    result
+}
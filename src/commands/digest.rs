@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use chrono::{Days, Local, NaiveDate, SubsecRound};
+use color_eyre::eyre::Result;
+
+use crate::config::ConfigStore;
+use crate::digest::{self, schedule::WeeklySchedule};
+use crate::status;
+use crate::storage::Storage;
+
+#[derive(clap::Subcommand)]
+pub enum DigestSubcommand {
+    /// Print the digest of the seven days up to yesterday
+    Show {
+        /// Last day to include instead of yesterday
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        until: Option<NaiveDate>,
+    },
+    /// Compile the digest weekly, e.g. `--weekly monday 9:00` for the week up to Sunday
+    Schedule {
+        /// Weekday and local time to run at
+        #[arg(long, num_args = 2, value_names = ["DAY", "HH:MM"], required = true)]
+        weekly: Vec<String>,
+        /// Directory to write digests to
+        #[arg(long, value_name = "DIR")]
+        location: Option<PathBuf>,
+        /// Shell command to pipe each digest to, e.g. `mail -s "Devlog week" me@example.com`
+        #[arg(long)]
+        command: Option<String>,
+    },
+    /// Stop compiling the digest, written digests and the run log are kept
+    Unschedule,
+    /// Compile, write and send the digest now
+    Run {
+        /// Only run when the scheduled time has passed since the last run, for cron
+        #[arg(long)]
+        if_due: bool,
+    },
+    /// Show past runs, oldest first
+    Log,
+}
+
+pub fn execute(storage: &Storage, subcmd: DigestSubcommand) -> Result<()> {
+    let store = ConfigStore::new(storage.config_path()).with_writer(storage.writer().clone());
+    let yesterday = Local::now().date_naive() - Days::new(1);
+
+    match subcmd {
+        DigestSubcommand::Show { until } => {
+            print!(
+                "{}",
+                digest::compile(storage, until.unwrap_or(yesterday))?.markdown
+            );
+        }
+        DigestSubcommand::Schedule {
+            weekly,
+            location,
+            command,
+        } => {
+            let now = Local::now();
+            let since = now.fixed_offset().trunc_subsecs(0);
+            let schedule = WeeklySchedule::parse(&weekly[0], &weekly[1], since)?;
+            let config = store.update(|config| {
+                config.digest.schedule = Some(schedule);
+                if location.is_some() {
+                    config.digest.location = location;
+                }
+                if command.is_some() {
+                    config.digest.command = command;
+                }
+                Ok(())
+            })?;
+            status!(
+                "Weekly digest scheduled, next on {} into {}",
+                schedule.next_run(&now).format("%A %Y-%m-%d %H:%M %:z"),
+                storage.digest_dir(&config.digest).display()
+            );
+        }
+        DigestSubcommand::Unschedule => {
+            store.update(|config| {
+                config.digest.schedule = None;
+                Ok(())
+            })?;
+            status!("Weekly digest unscheduled");
+        }
+        DigestSubcommand::Run { if_due } => {
+            let config = store.load()?.digest;
+            let run = if if_due {
+                storage.digest_if_due(&config, Local::now())?
+            } else {
+                Some(storage.run_digest(&config, yesterday)?)
+            };
+            if let Some(path) = run.and_then(|run| run.path) {
+                status!("Digest written to {}", path.display());
+            }
+        }
+        DigestSubcommand::Log => {
+            for run in storage.digest_runs()? {
+                let outcome = match &run.error {
+                    Some(error) => format!("failed: {}", error),
+                    None => "ok".to_string(),
+                };
+                println!(
+                    "{}  {} to {}  {}",
+                    run.at.format("%Y-%m-%d %H:%M %:z"),
+                    run.start,
+                    run.end,
+                    outcome
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
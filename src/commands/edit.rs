@@ -1,23 +1,141 @@
-use color_eyre::eyre::{Context, Result};
+use std::{fs, path::Path};
 
-use crate::storage::Storage;
+use chrono::Local;
+use color_eyre::eyre::{Context, Result, bail};
+use regex::Regex;
+
+use crate::editing::{EditorBuffer, buffer::TextBuffer, patch};
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::models::note::{AnyId, NoteId};
+use crate::status;
+use crate::storage::{Storage, focus::FocusSession};
 use crate::utils::editor;
 
-pub fn execute(storage: &Storage, id: String) -> Result<()> {
+/// A change made from a script or hook instead of the editor
+#[derive(Debug, Clone, Copy)]
+pub enum ScriptedEdit<'a> {
+    /// Apply a unified diff
+    Patch(&'a Path),
+    /// Replace every match of a regex, `$1` and `${name}` refer to its groups
+    Replace {
+        pattern: &'a str,
+        replacement: &'a str,
+    },
+}
+
+/// Edit an entry or note in the editor, or without opening anything with a scripted edit
+pub fn execute(storage: &Storage, id: AnyId, script: Option<ScriptedEdit>) -> Result<()> {
+    match id {
+        // Sub-entries are edited as part of their day's entry
+        AnyId::Entry(entry_ref) => edit_entry(storage, entry_ref.id, script),
+        AnyId::Note(id) => edit_note(storage, id, script),
+    }
+}
+
+/// The edited content, None when a scripted edit left it as it was
+fn edited(content: &str, script: Option<ScriptedEdit>) -> Result<Option<String>> {
+    let edited = match script {
+        None => return editor::launch_editor(Some(content)).map(Some),
+        Some(ScriptedEdit::Patch(path)) => {
+            let diff = fs::read_to_string(path)
+                .wrap_err_with(|| format!("Failed to read patch {}", path.display()))?;
+            let mut buffer = TextBuffer::new(content);
+            patch::apply(&mut buffer, &patch::parse(&diff)?)?;
+            buffer.text()
+        }
+        Some(ScriptedEdit::Replace {
+            pattern,
+            replacement,
+        }) => {
+            let regex = Regex::new(pattern).map_err(|e| {
+                DevlogError::new(ErrorKind::Validation, format!("Invalid regex: {}", e))
+            })?;
+            regex.replace_all(content, replacement).into_owned()
+        }
+    };
+
+    // Scripts run unattended, so never let one wipe out what was written
+    if edited.trim().is_empty() {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "The edit would leave nothing behind, nothing was saved"
+        ));
+    }
+    Ok((edited != content).then_some(edited))
+}
+
+fn edit_entry(storage: &Storage, id: EntryId, script: Option<ScriptedEdit>) -> Result<()> {
+    let now = Local::now().fixed_offset();
+    if let Some(message) =
+        FocusSession::load(storage, now)?.and_then(|session| session.blocks(&id, now))
+    {
+        bail!(DevlogError::new(ErrorKind::Validation, message));
+    }
+
     // load existing entry
     let mut entry = storage
         .load_entry(&id)
         .wrap_err_with(|| format!("Entry '{}' not found", id))?;
 
-    println!("Editing entry {id}");
+    status!("Editing entry {id}");
 
-    // Launch editor with existing content
-    let new_content = editor::launch_editor(Some(&entry.content))?;
+    // Launch editor with existing content, or run the scripted edit on it
+    let Some(new_content) = edited(&entry.content, script)? else {
+        status!("Nothing to change in {}", id);
+        return Ok(());
+    };
 
     // Update entry
     entry.update_content(new_content);
     storage.save_entry(&entry)?;
 
-    println!("Entry updated successfully: {}", id);
+    status!("Entry updated successfully: {}", id);
     Ok(())
 }
+
+fn edit_note(storage: &Storage, id: NoteId, script: Option<ScriptedEdit>) -> Result<()> {
+    let now = Local::now().fixed_offset();
+    if let Some(message) =
+        FocusSession::load(storage, now)?.and_then(|session| session.blocks_notes())
+    {
+        bail!(DevlogError::new(ErrorKind::Validation, message));
+    }
+
+    let mut note = storage.load_note(&id)?;
+    status!("Editing note {id}");
+
+    let Some(new_content) = edited(&note.content, script)? else {
+        status!("Nothing to change in {}", id);
+        return Ok(());
+    };
+    note.update_content(new_content);
+    storage.save_note(&note)?;
+
+    status!("Note updated successfully: {}", id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace<'a>(pattern: &'a str, replacement: &'a str) -> Option<ScriptedEdit<'a>> {
+        Some(ScriptedEdit::Replace {
+            pattern,
+            replacement,
+        })
+    }
+
+    #[test]
+    fn test_replace_edits() {
+        let content = "Paired with @Alice on ::devlog";
+        assert_eq!(
+            edited(content, replace(r"@(\w+)", "@${1}_smith")).unwrap(),
+            Some("Paired with @Alice_smith on ::devlog".to_string())
+        );
+        assert_eq!(edited(content, replace("@bob", "@carol")).unwrap(), None);
+        assert!(edited(content, replace("(", "")).is_err());
+        assert!(edited(content, replace(".*", "")).is_err());
+    }
+}
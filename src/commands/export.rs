@@ -0,0 +1,54 @@
+use std::{fs, path::PathBuf};
+
+use color_eyre::eyre::Result;
+
+use crate::export::{self, ExportFormat, mermaid, pandoc};
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::Storage;
+
+/// Write the entries between `since` and `until` as one document, to stdout or
+/// `output`. With `pandoc_format`, the Markdown export is converted by pandoc, with
+/// mermaid diagrams as images where mermaid-cli is installed.
+pub fn execute(
+    storage: &Storage,
+    format: ExportFormat,
+    since: Option<EntryId>,
+    until: Option<EntryId>,
+    pandoc_format: Option<String>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let entries = export::collect(storage, since, until)?;
+
+    if let Some(to) = pandoc_format {
+        let markdown = export::render(&entries, ExportFormat::Markdown)?;
+        if storage.writer().is_dry_run() {
+            status!(
+                "Would convert {} entries to {} with pandoc",
+                entries.len(),
+                to
+            );
+            return Ok(());
+        }
+        let (markdown, images) = mermaid::with_images(&markdown);
+        let converted = pandoc::convert(&markdown, &to, output.as_deref());
+        for image in images {
+            let _ = fs::remove_file(image);
+        }
+        converted?;
+        if let Some(path) = output {
+            status!("Exported {} entries to {}", entries.len(), path.display());
+        }
+        return Ok(());
+    }
+
+    let document = export::render(&entries, format)?;
+    match output {
+        Some(path) => {
+            storage.writer().write(&path, document)?;
+            status!("Exported {} entries to {}", entries.len(), path.display());
+        }
+        None => print!("{}", document),
+    }
+    Ok(())
+}
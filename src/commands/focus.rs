@@ -0,0 +1,70 @@
+use chrono::Local;
+use color_eyre::eyre::Result;
+
+use crate::config::FocusConfig;
+use crate::status;
+use crate::storage::{Storage, focus::FocusSession};
+
+#[derive(clap::Subcommand)]
+pub enum FocusSubcommand {
+    /// Start a focus timer, replacing a running one
+    Start {
+        /// Length of the session, `focus.minutes` if unset
+        #[arg(long)]
+        minutes: Option<u32>,
+        /// Only allow editing today's entry until the timer ends
+        #[arg(long, conflicts_with = "no_lock")]
+        lock: bool,
+        /// Allow editing every entry even if `focus.lock_entries` is set
+        #[arg(long)]
+        no_lock: bool,
+    },
+    /// End the running session early
+    Stop,
+    /// Show the time left in the running session
+    Status,
+}
+
+pub fn execute(storage: &Storage, config: &FocusConfig, subcmd: FocusSubcommand) -> Result<()> {
+    let now = Local::now().fixed_offset();
+
+    match subcmd {
+        FocusSubcommand::Start {
+            minutes,
+            lock,
+            no_lock,
+        } => {
+            let lock_entries = (config.lock_entries || lock) && !no_lock;
+            let session =
+                FocusSession::new(now, minutes.unwrap_or(config.minutes).max(1), lock_entries);
+            session.save(storage)?;
+            status!(
+                "Focusing until {}{}",
+                session.until.format("%H:%M"),
+                if lock_entries {
+                    ", only today's entry can be edited"
+                } else {
+                    ""
+                }
+            );
+        }
+        FocusSubcommand::Stop => {
+            FocusSession::clear(storage)?;
+            status!("Focus session ended");
+        }
+        FocusSubcommand::Status => match FocusSession::load(storage, now)? {
+            Some(session) => println!(
+                "{} min left, until {}{}",
+                session.remaining_minutes(now),
+                session.until.format("%H:%M"),
+                if session.lock_entries {
+                    " (locked)"
+                } else {
+                    ""
+                }
+            ),
+            None => status!("No focus session running"),
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use color_eyre::eyre::{Context, Result};
+use regex::Regex;
+use serde_json::json;
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::note::AnyId;
+use crate::search;
+use crate::status;
+use crate::storage::Storage;
+
+/// Print the lines of every entry and note matching the regex `pattern` as `id:line:text`, or
+/// as one JSON object per line with the byte ranges of the matches. With
+/// `files_with_matches` only the paths of the matching files are printed.
+pub fn execute(
+    storage: &Storage,
+    pattern: &str,
+    json: bool,
+    files_with_matches: bool,
+) -> Result<()> {
+    let regex = Regex::new(pattern)
+        .map_err(|e| DevlogError::new(ErrorKind::Validation, format!("Invalid regex: {}", e)))?;
+    let found = search::grep(storage, &regex)?;
+    for unreadable in &found.unreadable {
+        eprintln!("Warning: {}", unreadable);
+    }
+    let results = found.matches;
+    // Scripts get empty output rather than a message they'd have to filter out
+    if results.is_empty() && !json && !files_with_matches {
+        status!("No entries match '{}'", pattern);
+        return Ok(());
+    }
+
+    if files_with_matches {
+        let mut ids: Vec<_> = results.iter().map(|line| &line.entry).collect();
+        ids.dedup();
+        for id in ids {
+            let path = match id {
+                AnyId::Entry(entry_ref) => storage.entry_path(&entry_ref.id)?,
+                AnyId::Note(id) => storage.note_path(id),
+            };
+            if json {
+                println!("{}", json!({ "entry": id, "path": path }));
+            } else {
+                println!("{}", path.display());
+            }
+        }
+        return Ok(());
+    }
+
+    for line in &results {
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string(line).wrap_err("Failed to serialize match")?
+            );
+        } else {
+            println!("{}:{}:{}", line.entry, line.line, line.text);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::Storage;
+use crate::utils::diff;
+
+/// Print the event log of an entry, numbered for `devlog revert`, each event with the
+/// lines it changed. `deep` goes back past the snapshot of a compacted log.
+pub fn execute(storage: &Storage, id: EntryId, deep: bool) -> Result<()> {
+    let events = if deep {
+        storage.deep_events(&id)?
+    } else {
+        storage.events(&id)?
+    };
+    if events.is_empty() {
+        bail!(DevlogError::new(
+            ErrorKind::NotFound,
+            format!("Entry '{}' has no history", id)
+        ));
+    }
+
+    let mut previous: Option<&str> = None;
+    for (number, event) in events.iter().enumerate() {
+        println!(
+            "{:>3}  {}  {}{}",
+            number + 1,
+            event.at.format("%Y-%m-%d %H:%M:%S"),
+            event.kind,
+            event
+                .detail
+                .as_ref()
+                .map(|detail| format!(" {}", detail))
+                .unwrap_or_default()
+        );
+        match &event.content {
+            Some(content) => {
+                for line in diff::changed_lines(previous.unwrap_or_default(), content) {
+                    println!("     {}", line);
+                }
+                previous = Some(content);
+            }
+            // Tag, attachment and conflict events explain the change before them
+            None if !event.kind.changes_content() => {}
+            None => println!("     (content not recorded)"),
+        }
+    }
+    Ok(())
+}
+
+/// Put an entry back to the content it had after event `number` of `devlog history`,
+/// or of `devlog history --deep`
+pub fn revert(storage: &Storage, id: EntryId, number: usize, deep: bool) -> Result<()> {
+    storage.revert_entry(&id, number, deep)?;
+    status!(
+        "Reverted {} to event {}, `devlog history {}` shows the change",
+        id,
+        number,
+        id
+    );
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{Result, bail};
+
+use crate::config::HookConfig;
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::{Storage, writer::Writer};
+use crate::utils::{git, template};
+
+/// Marks the hook line written by `devlog hook install`, so it is only added once
+const HOOK_MARKER: &str = "# Added by devlog hook install";
+const HOOK_COMMAND: &str = "devlog hook capture >/dev/null 2>&1 || true";
+
+#[derive(clap::Subcommand)]
+pub enum HookSubcommand {
+    /// Add a post-commit hook to the current git repository that logs every commit
+    Install,
+    /// Add the last commit of the current repository to today's entry, run by the hook
+    Capture,
+}
+
+pub fn execute(storage: &Storage, config: &HookConfig, subcmd: HookSubcommand) -> Result<()> {
+    match subcmd {
+        HookSubcommand::Install => {
+            let path = hooks_dir()?.join("post-commit");
+            if install(storage.writer(), &path)? {
+                status!("Installed post-commit hook at {}", path.display());
+            } else {
+                status!(
+                    "The post-commit hook at {} already runs devlog",
+                    path.display()
+                );
+            }
+        }
+        HookSubcommand::Capture => {
+            let repo = git::repo_name(&git::toplevel()?);
+            let branch = git::run(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+            let hash = git::run(&["rev-parse", "--short", "HEAD"])?;
+            let message = git::run(&["log", "-1", "--format=%s"])?;
+
+            let item = template::render(
+                &config.commit_template,
+                &[
+                    ("message", &message),
+                    ("repo", &repo),
+                    ("branch", &branch),
+                    ("hash", &hash),
+                ],
+            );
+            let id = EntryId::today();
+            storage.append_list_item(&id, &item)?;
+            status!("Added commit {} to {}", hash, id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the hook, or add the devlog line to an existing shell hook.
+/// Returns false when the hook already runs devlog.
+fn install(writer: &Writer, path: &Path) -> Result<bool> {
+    let script = match fs::read_to_string(path) {
+        Ok(existing) if existing.contains(HOOK_MARKER) => return Ok(false),
+        Ok(existing) => {
+            let shebang = existing.lines().next().unwrap_or_default();
+            if !(shebang.starts_with("#!") && shebang.ends_with("sh")) {
+                bail!(DevlogError::new(
+                    ErrorKind::Conflict,
+                    format!(
+                        "{} is not a shell script, add `{}` to it yourself",
+                        path.display(),
+                        HOOK_COMMAND
+                    )
+                ));
+            }
+            format!(
+                "{}\n{}\n{}\n",
+                existing.trim_end(),
+                HOOK_MARKER,
+                HOOK_COMMAND
+            )
+        }
+        Err(_) => format!("#!/bin/sh\n{}\n{}\n", HOOK_MARKER, HOOK_COMMAND),
+    };
+
+    if let Some(dir) = path.parent() {
+        writer.create_dir_all(dir)?;
+    }
+    writer.write_executable(path, script)?;
+    Ok(true)
+}
+
+/// Hooks directory of the current repository, honouring `core.hooksPath`
+fn hooks_dir() -> Result<PathBuf> {
+    Ok(PathBuf::from(git::run(&[
+        "rev-parse",
+        "--git-path",
+        "hooks",
+    ])?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_adds_to_existing_hook_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("post-commit");
+        fs::write(&path, "#!/bin/bash\nrun-linter\n").unwrap();
+
+        assert!(install(&Writer::new(false), &path).unwrap());
+        assert!(!install(&Writer::new(false), &path).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!(
+                "#!/bin/bash\nrun-linter\n{}\n{}\n",
+                HOOK_MARKER, HOOK_COMMAND
+            )
+        );
+    }
+
+    #[test]
+    fn test_install_refuses_other_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("post-commit");
+        fs::write(&path, "#!/usr/bin/env python3\nprint()\n").unwrap();
+        assert!(install(&Writer::new(false), &path).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_install_leaves_hook_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("post-commit");
+        fs::write(&path, "#!/bin/sh\nrun-linter\n").unwrap();
+
+        assert!(install(&Writer::new(true), &path).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "#!/bin/sh\nrun-linter\n"
+        );
+        assert!(
+            install(
+                &Writer::new(true),
+                &temp_dir.path().join("hooks/post-commit")
+            )
+            .unwrap()
+        );
+        assert!(!temp_dir.path().join("hooks").exists());
+    }
+}
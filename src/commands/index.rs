@@ -0,0 +1,21 @@
+use color_eyre::eyre::Result;
+
+use crate::status;
+use crate::storage::Storage;
+
+#[derive(clap::Subcommand)]
+pub enum IndexSubcommand {
+    /// Re-read every entry, e.g. if the index looks wrong
+    Rebuild,
+}
+
+pub fn execute(storage: &Storage, subcmd: IndexSubcommand) -> Result<()> {
+    match subcmd {
+        IndexSubcommand::Rebuild => {
+            let index = storage.rebuild_index()?;
+            status!("Indexed {} entries", index.entries.len());
+        }
+    }
+
+    Ok(())
+}
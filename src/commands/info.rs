@@ -0,0 +1,63 @@
+use std::env::consts;
+
+use color_eyre::eyre::Result;
+
+use crate::config::{ConfigStore, secrets::SecretStore};
+use crate::storage::{Storage, index::IndexStatus};
+use crate::utils::editor;
+
+/// Print an environment report to paste into bug reports
+pub fn execute(storage: &Storage) -> Result<()> {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    println!("devlog {}", env!("CARGO_PKG_VERSION"));
+    println!("Build: {} ({}-{})", profile, consts::OS, consts::ARCH);
+
+    let config_store = ConfigStore::new(storage.config_path());
+    println!("\nPaths:");
+    println!("  Config: {}", config_store.path().display());
+    println!("  Data:   {}", storage.data_path().display());
+    println!("  Cache:  {}", storage.cache_path().display());
+    println!("  State:  {}", storage.state_path().display());
+
+    // Keep reporting after failures, broken setups are what this is run for
+    println!("\nEntries:");
+    match storage.list_entries() {
+        Ok(ids) => {
+            println!("  Count: {}", ids.len());
+            if let (Some(first), Some(last)) = (ids.iter().min(), ids.iter().max()) {
+                println!("  Range: {} to {}", first, last);
+            }
+        }
+        Err(e) => println!("  Error: {}", e),
+    }
+    let index = match storage.index_status() {
+        Ok(IndexStatus::Missing) => "not built yet".to_string(),
+        Ok(IndexStatus::Stale(count)) => format!("{} changed entries, updated on next use", count),
+        Ok(IndexStatus::Fresh) => "up to date".to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+    println!("  Index: {}", index);
+
+    println!("\nSync:");
+    match config_store.load() {
+        Ok(config) if config.sync.enabled => {
+            let provider = config.sync.provider.as_deref().unwrap_or("not set");
+            println!("  Provider: {}", provider);
+            let credential = match SecretStore.get("sync.connection_string") {
+                Ok(Some(_)) => "stored".to_string(),
+                Ok(None) => "missing".to_string(),
+                Err(e) => format!("unavailable ({})", e),
+            };
+            println!("  Credentials: {}", credential);
+        }
+        Ok(_) => println!("  Disabled"),
+        Err(e) => println!("  Error: {}", e),
+    }
+
+    println!("\nEditor: {}", editor::find_available_editor());
+    Ok(())
+}
@@ -1,32 +1,340 @@
-use color_eyre::eyre::Result;
+use std::str::FromStr;
 
-use crate::storage::Storage;
+use chrono::{Days, Local, NaiveDate};
+use color_eyre::eyre::{Report, Result, bail};
+
+use crate::commands::completions::annotation_candidates;
+use crate::config::ColumnConfig;
+use crate::models::annotation::AnnotationKind;
+use crate::models::entry_id::EntryId;
+use crate::models::frontmatter::FieldMatch;
+use crate::search;
+use crate::stats::columns::{self, Column};
+use crate::status;
+use crate::storage::{Storage, index::IndexedEntry, repository::EntryRepository};
 use crate::tui::app::launch_tui;
 
-pub fn execute(storage: &Storage, interactive: bool) -> Result<()> {
+/// Entries listed when no filter is given
+const RECENT_ENTRIES: usize = 20;
+
+/// Which entries `devlog list` prints, every filter given has to match
+#[derive(Debug, Default, clap::Args)]
+pub struct ListFilter {
+    /// First entry to list, e.g. 20250901
+    #[arg(long, value_name = "YYYYMMDD", conflicts_with = "last")]
+    from: Option<EntryId>,
+    /// Last entry to list
+    #[arg(long, value_name = "YYYYMMDD")]
+    to: Option<EntryId>,
+    /// Only entries of the last days or weeks including today, e.g. 7d or 2w
+    #[arg(long, value_name = "PERIOD")]
+    last: Option<Period>,
+    /// Only entries tagged with this `+tag`, may be repeated
+    #[arg(
+        long = "tag",
+        value_name = "TAG",
+        add = annotation_candidates(AnnotationKind::Tag)
+    )]
+    tags: Vec<String>,
+    /// Only entries mentioning this `@person`, may be repeated
+    #[arg(
+        long = "person",
+        value_name = "PERSON",
+        add = annotation_candidates(AnnotationKind::Person)
+    )]
+    people: Vec<String>,
+    /// Only entries referencing this `::project`, may be repeated
+    #[arg(
+        long = "project",
+        value_name = "PROJECT",
+        add = annotation_candidates(AnnotationKind::Project)
+    )]
+    projects: Vec<String>,
+    /// Only entries containing this text, ignoring case
+    #[arg(long, value_name = "TEXT")]
+    contains: Option<String>,
+    /// Only entries with this custom frontmatter field, e.g. mood=tired, may be repeated
+    #[arg(long = "where", value_name = "FIELD=VALUE")]
+    fields: Vec<FieldMatch>,
+}
+
+/// A number of days counting back from today, `7d` or `2w`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    days: u64,
+}
+
+impl Period {
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || format!("Invalid period '{}': expected e.g. 7d or 2w", value);
+        let Some(unit) = value.chars().last() else {
+            bail!(invalid());
+        };
+        let Ok(count) = value[..value.len() - unit.len_utf8()].parse::<u64>() else {
+            bail!(invalid());
+        };
+        let days = match unit {
+            'd' => count,
+            'w' => count.saturating_mul(7),
+            _ => bail!(invalid()),
+        };
+        if days == 0 {
+            bail!(invalid());
+        }
+        Ok(Self { days })
+    }
+
+    /// The first entry in the period ending with `today`
+    pub fn start(&self, today: EntryId) -> EntryId {
+        EntryId::from_date(
+            today
+                .date()
+                .checked_sub_days(Days::new(self.days - 1))
+                .unwrap_or(NaiveDate::MIN),
+        )
+    }
+}
+
+impl FromStr for Period {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl ListFilter {
+    /// Also require entries to reference `project`, for `--here`
+    pub fn with_project(mut self, project: &str) -> Self {
+        self.projects.push(project.to_string());
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.from.is_none()
+            && self.to.is_none()
+            && self.last.is_none()
+            && self.tags.is_empty()
+            && self.people.is_empty()
+            && self.projects.is_empty()
+            && self.contains.is_none()
+            && self.fields.is_empty()
+    }
+
+    /// Whether the entry is in range and has every annotation and field asked for;
+    /// `contains` needs the content and is checked separately
+    fn matches(&self, id: &EntryId, entry: &IndexedEntry, today: EntryId) -> bool {
+        let annotations = &entry.annotations;
+        let start = self.from.or(self.last.map(|last| last.start(today)));
+        let mentions = |kind: AnnotationKind, wanted: &[String]| {
+            wanted.iter().all(|value| {
+                let value = value.strip_prefix(kind.prefix()).unwrap_or(value);
+                annotations.values(kind).iter().any(|v| v == value)
+            })
+        };
+        start.is_none_or(|start| start <= *id)
+            && self.to.is_none_or(|to| *id <= to)
+            && mentions(AnnotationKind::Tag, &self.tags)
+            && mentions(AnnotationKind::Person, &self.people)
+            && mentions(AnnotationKind::Project, &self.projects)
+            && self.fields.iter().all(|field| field.matches(&entry.fields))
+    }
+}
+
+pub fn execute(
+    storage: &Storage,
+    interactive: bool,
+    filter: ListFilter,
+    columns: &[ColumnConfig],
+) -> Result<()> {
     if interactive {
         launch_tui(storage)?;
     } else {
-        display_list(storage)?;
+        display_list(storage, &filter, &columns::from_config(columns)?)?;
     }
 
     Ok(())
 }
 
-fn display_list(storage: &Storage) -> Result<()> {
-    let entries = storage.list_entries()?;
+fn display_list(storage: &Storage, filter: &ListFilter, columns: &[Box<dyn Column>]) -> Result<()> {
+    if !filter.is_empty() {
+        return display_matching(storage, filter, columns);
+    }
 
-    println!("Recent entries (last 20)\n");
+    display_recent(storage, columns)?;
 
-    for entry_id in entries.iter().take(20) {
-        // Load the entry to get its content
-        let preview = match storage.load_entry(&entry_id) {
-            Ok(entry) => entry.preview(),
-            Err(_) => "(error reading entry)".to_string(),
-        };
+    let notes = storage.list_notes()?;
+    if !notes.is_empty() {
+        println!("\nNotes\n");
+    }
+    for id in notes {
+        match storage.load_note(&id) {
+            Ok(note) => println!("{}  {}", id, note.preview()),
+            Err(_) => println!("{}  (error reading note)", id),
+        }
+    }
+
+    Ok(())
+}
 
-        println!("{}  {}", entry_id, preview);
+/// The most recent entries, newest first
+fn display_recent(repository: &impl EntryRepository, columns: &[Box<dyn Column>]) -> Result<()> {
+    let index = repository.index()?;
+
+    println!("Recent entries (last {})\n", RECENT_ENTRIES);
+    print_header(columns);
+
+    // Previews come from the index, entries that couldn't be read aren't in it
+    for entry_id in repository.list_entries()?.iter().take(RECENT_ENTRIES) {
+        match index.get(entry_id) {
+            Some(entry) => print_row(repository, entry_id, entry, columns),
+            None => println!("{}  (error reading entry)", entry_id),
+        }
+    }
+    Ok(())
+}
+
+/// Every entry matching the filter, newest first
+fn display_matching(
+    repository: &impl EntryRepository,
+    filter: &ListFilter,
+    columns: &[Box<dyn Column>],
+) -> Result<()> {
+    let index = repository.index()?;
+    let today = EntryId::from_date(Local::now().date_naive());
+
+    let mut rows = Vec::new();
+    for entry_id in repository.list_entries()? {
+        let Some(entry) = index.get(&entry_id) else {
+            continue;
+        };
+        if !filter.matches(&entry_id, entry, today) {
+            continue;
+        }
+        if let Some(text) = &filter.contains
+            && !search::is_match(&repository.load_entry(&entry_id)?.content, text)
+        {
+            continue;
+        }
+        rows.push((entry_id, entry));
     }
 
+    if rows.is_empty() {
+        status!("No matching entries");
+        return Ok(());
+    }
+    print_header(columns);
+    for (entry_id, entry) in rows {
+        print_row(repository, &entry_id, entry, columns);
+    }
     Ok(())
 }
+
+fn print_header(columns: &[Box<dyn Column>]) {
+    let custom: String = columns
+        .iter()
+        .map(|column| format!("{:>1$}  ", column.name(), column_width(column.as_ref())))
+        .collect();
+    println!(
+        "{:<8}  {:>4}  {:>6}  {:>8}  {}PREVIEW",
+        "ID", "TAGS", "PEOPLE", "PROJECTS", custom
+    );
+}
+
+fn print_row(
+    repository: &impl EntryRepository,
+    id: &EntryId,
+    entry: &IndexedEntry,
+    columns: &[Box<dyn Column>],
+) {
+    // Only columns like regex counts need more than the index
+    let content = columns
+        .iter()
+        .any(|column| column.needs_content())
+        .then(|| repository.load_entry(id).ok())
+        .flatten()
+        .map(|entry| entry.content);
+    let custom: String = columns
+        .iter()
+        .map(|column| {
+            let content = content.as_deref().filter(|_| column.needs_content());
+            format!(
+                "{:>1$}  ",
+                column.value(entry, content),
+                column_width(column.as_ref())
+            )
+        })
+        .collect();
+
+    let annotations = &entry.annotations;
+    println!(
+        "{:<8}  {:>4}  {:>6}  {:>8}  {}{}",
+        id,
+        annotations.tags.len(),
+        annotations.people.len(),
+        annotations.projects.len(),
+        custom,
+        entry.preview
+    );
+}
+
+/// Custom columns are as wide as their header, at least 4 characters
+fn column_width(column: &dyn Column) -> usize {
+    column.name().chars().count().max(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::annotation::Annotations;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_parse_period() {
+        assert_eq!(Period::parse("7d").unwrap().days, 7);
+        assert_eq!(Period::parse("2w").unwrap().days, 14);
+        for value in ["", "d", "0d", "7", "7m", "-1d"] {
+            assert!(Period::parse(value).is_err(), "{}", value);
+        }
+
+        let today = EntryId::parse("20250920").unwrap();
+        assert_eq!(Period::parse("7d").unwrap().start(today), "20250914");
+        assert_eq!(Period::parse("1d").unwrap().start(today), "20250920");
+    }
+
+    #[test]
+    fn test_filter_matches_range_and_annotations() {
+        let today = EntryId::parse("20250920").unwrap();
+        let entry = IndexedEntry {
+            annotations: Annotations::parse("Paired with @alice on ::search +rust +perf"),
+            fields: BTreeMap::from([("mood".to_string(), "tired".to_string())]),
+            ..IndexedEntry::default()
+        };
+        let filter = ListFilter {
+            last: Some(Period::parse("7d").unwrap()),
+            tags: vec!["+rust".to_string(), "perf".to_string()],
+            people: vec!["alice".to_string()],
+            ..ListFilter::default()
+        };
+
+        assert!(filter.matches(&today, &entry, today));
+        assert!(!filter.matches(&EntryId::parse("20250913").unwrap(), &entry, today));
+
+        let filter = ListFilter {
+            projects: vec!["::devlog".to_string()],
+            ..ListFilter::default()
+        };
+        assert!(!filter.matches(&today, &entry, today));
+
+        let filter = ListFilter {
+            fields: vec![FieldMatch::parse("mood=Tired").unwrap()],
+            ..ListFilter::default()
+        };
+        assert!(filter.matches(&today, &entry, today));
+        let filter = ListFilter {
+            fields: vec![FieldMatch::parse("mood=rested").unwrap()],
+            ..ListFilter::default()
+        };
+        assert!(!filter.matches(&today, &entry, today));
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::models::meeting;
+use crate::status;
+use crate::storage::Storage;
+use crate::utils::editor;
+
+#[derive(clap::Subcommand)]
+pub enum MeetingSubcommand {
+    /// Take notes of a meeting in today's entry
+    Start {
+        /// What the meeting is about, e.g. "Design review"
+        title: String,
+        /// Attendees, projects and tags, e.g. @alice ::search_engine +planning
+        #[arg(value_name = "ANNOTATION", allow_hyphen_values = true)]
+        annotations: Vec<String>,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: MeetingSubcommand) -> Result<()> {
+    match subcmd {
+        MeetingSubcommand::Start { title, annotations } => start(storage, &title, &annotations),
+    }
+}
+
+fn start(storage: &Storage, title: &str, annotations: &[String]) -> Result<()> {
+    if let Some(other) = annotations
+        .iter()
+        .find(|a| !["@", "::", "+"].iter().any(|prefix| a.starts_with(prefix)) || a.len() < 2)
+    {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            format!(
+                "'{}' is not an annotation, expected @person, ::project or +tag",
+                other
+            )
+        ));
+    }
+
+    let started = Local::now();
+    let notes =
+        editor::launch_editor(Some(&meeting::template(title, annotations, started.time())))?;
+    let notes = meeting::stamp_action_items(&notes, Local::now().time());
+    if notes.trim().is_empty() {
+        status!("No notes were taken, the meeting was not saved");
+        return Ok(());
+    }
+
+    let id = EntryId::from_date(started.date_naive());
+    storage.append_to_entry(&id, &notes)?;
+    let actions = notes
+        .lines()
+        .filter(|line| line.trim_start().starts_with("- [ ] ->"))
+        .count();
+    status!(
+        "Meeting notes added to {} with {} action item(s)",
+        id,
+        actions
+    );
+    Ok(())
+}
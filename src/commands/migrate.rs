@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::status;
+use crate::storage::{Storage, legacy::LegacyOutcome};
+
+#[derive(clap::Subcommand)]
+pub enum MigrateSubcommand {
+    /// Import the `events/` and `entries/` of the event-sourcing prototype, keeping
+    /// each entry's history. Entries already in the vault are left alone.
+    LegacyEvents {
+        /// Prototype vault to import, `~/.devlog` by default
+        #[arg(long)]
+        from: Option<PathBuf>,
+    },
+    /// Move entry files from subdirectories of the entries directory, e.g. a
+    /// year/month layout, up into it so devlog lists them again
+    Flatten,
+}
+
+pub fn execute(storage: &Storage, subcmd: MigrateSubcommand) -> Result<()> {
+    match subcmd {
+        MigrateSubcommand::LegacyEvents { from } => {
+            let Some(source) = from.or_else(Storage::legacy_path) else {
+                bail!(DevlogError::new(
+                    ErrorKind::NotFound,
+                    "No home directory to find ~/.devlog in, pass --from"
+                ));
+            };
+            let report = storage.migrate_legacy(&source)?;
+
+            for (id, outcome) in &report.entries {
+                match outcome {
+                    LegacyOutcome::Imported => {}
+                    LegacyOutcome::MarkdownKept => println!(
+                        "{}: markdown was edited after its last event, kept the markdown",
+                        id
+                    ),
+                    LegacyOutcome::Replayed => println!(
+                        "{}: markdown was missing or behind its events, replayed the events",
+                        id
+                    ),
+                    LegacyOutcome::Skipped => {
+                        println!("{}: already in the vault, left alone", id)
+                    }
+                }
+            }
+            if report.unreadable_events > 0 {
+                eprintln!(
+                    "Warning: skipped {} unreadable event line(s)",
+                    report.unreadable_events
+                );
+            }
+            let migrated = report
+                .entries
+                .iter()
+                .filter(|(_, outcome)| *outcome != LegacyOutcome::Skipped)
+                .count();
+            status!("Migrated {} entries from {}", migrated, source.display());
+        }
+        MigrateSubcommand::Flatten => {
+            let (moved, kept) = storage.flatten_entries()?;
+            for path in &kept {
+                eprintln!(
+                    "Warning: {} left in place, its day already has an entry",
+                    path.display()
+                );
+            }
+            status!(
+                "Moved {} entry file(s) into the entries directory",
+                moved.len()
+            );
+        }
+    }
+    Ok(())
+}
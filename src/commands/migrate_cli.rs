@@ -0,0 +1,69 @@
+use std::{fs, path::PathBuf};
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::status;
+use crate::storage::Storage;
+use crate::utils::deprecation;
+
+/// Shell startup files that usually define aliases, relative to the home directory
+const SHELL_FILES: &[&str] = &[
+    ".bashrc",
+    ".bash_aliases",
+    ".bash_profile",
+    ".profile",
+    ".zshrc",
+    ".config/fish/config.fish",
+];
+
+/// Rewrite deprecated devlog invocations in shell startup files and the given scripts
+pub fn execute(storage: &Storage, scripts: Vec<PathBuf>, write: bool) -> Result<()> {
+    let home = dirs::home_dir().ok_or_else(|| eyre!("Could not determine home directory"))?;
+    let mut files: Vec<PathBuf> = SHELL_FILES.iter().map(|file| home.join(file)).collect();
+    files.extend(scripts);
+
+    let mut changed_files = 0;
+    for path in files.iter().filter(|path| path.is_file()) {
+        let Ok(content) = fs::read_to_string(path) else {
+            eprintln!("Skipping {}: not a readable text file", path.display());
+            continue;
+        };
+
+        let mut changed = false;
+        let rewritten: Vec<String> = content
+            .split('\n')
+            .enumerate()
+            .map(|(idx, line)| match deprecation::rewrite_line(line) {
+                Some(new_line) => {
+                    println!("{}:{}", path.display(), idx + 1);
+                    println!("  - {}", line.trim());
+                    println!("  + {}", new_line.trim());
+                    changed = true;
+                    new_line
+                }
+                None => line.to_string(),
+            })
+            .collect();
+
+        if changed {
+            changed_files += 1;
+            if write {
+                let mut backup = path.clone().into_os_string();
+                backup.push(".devlog-bak");
+                let backup = PathBuf::from(backup);
+                storage.writer().write(&backup, &content)?;
+                storage.writer().write(path, rewritten.join("\n"))?;
+            }
+        }
+    }
+
+    match (changed_files, write) {
+        (0, _) => status!("No deprecated devlog invocations found"),
+        (count, true) => status!("Updated {} file(s), originals saved as *.devlog-bak", count),
+        (count, false) => status!(
+            "{} file(s) to update, run again with --write to apply",
+            count
+        ),
+    }
+    Ok(())
+}
@@ -1,5 +1,41 @@
+pub mod actions;
+pub mod ai;
+pub mod analyze;
+pub mod annotate;
+pub mod annotations;
+pub mod attach;
+pub mod backup;
+pub mod bench;
+pub mod capture;
+pub mod completions;
 pub mod config;
+pub mod delete;
+pub mod demo;
+pub mod digest;
 pub mod edit;
+pub mod export;
+pub mod focus;
+pub mod grep;
+pub mod history;
+pub mod hook;
+pub mod index;
+pub mod info;
 pub mod list;
+pub mod meeting;
+pub mod migrate;
+pub mod migrate_cli;
+pub mod move_section;
 pub mod new;
+pub mod preview;
+pub mod project;
+pub mod publish;
+pub mod prompt_hook;
+pub mod scratch;
+pub mod search;
+pub mod serve;
 pub mod show;
+pub mod stats;
+pub mod sync;
+pub mod tmux_status;
+pub mod todo;
+pub mod trash;
@@ -0,0 +1,47 @@
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryRef;
+use crate::status;
+use crate::storage::{Storage, focus::FocusSession, section::SectionTarget};
+
+/// Move a section of `from` to another entry or project ledger. A sub-entry id like
+/// `20250920-1432` names its section itself, otherwise `heading` is required.
+pub fn execute(
+    storage: &Storage,
+    from: EntryRef,
+    heading: Option<String>,
+    to: SectionTarget,
+) -> Result<()> {
+    let heading = match (heading, from.time) {
+        (Some(heading), _) => heading,
+        (None, Some(time)) => time.format("%H:%M").to_string(),
+        (None, None) => bail!(DevlogError::new(
+            ErrorKind::Validation,
+            format!(
+                "Name the section to move, e.g. `devlog move-section {} \"Design review\" --to {}`",
+                from.id, to
+            )
+        )),
+    };
+
+    let now = Local::now().fixed_offset();
+    if let Some(session) = FocusSession::load(storage, now)? {
+        let destination = match &to {
+            SectionTarget::Entry(id) => Some(id),
+            SectionTarget::Project(_) => None,
+        };
+        if let Some(message) = [Some(&from.id), destination]
+            .into_iter()
+            .flatten()
+            .find_map(|id| session.blocks(id, now))
+        {
+            bail!(DevlogError::new(ErrorKind::Validation, message));
+        }
+    }
+
+    let moved = storage.move_section(&from.id, &heading, &to)?;
+    status!("Moved '{}' from {} to {}", moved, from.id, to);
+    Ok(())
+}
@@ -1,33 +1,404 @@
+use crate::commands::edit;
+use crate::config::{Config, ConfigStore, FieldConfig};
+use crate::error::{DevlogError, ErrorKind};
 use crate::models::entry::Entry;
+use crate::models::entry_id::{EntryId, EntryRef};
+use crate::models::frontmatter;
+use crate::models::note::{AnyId, Note, NoteId};
+use crate::models::todo;
+use crate::status;
 use crate::storage::Storage;
-use crate::utils::editor;
+use crate::utils::{editor, output, workspace::Workspace};
 
-use chrono::Local;
-use color_eyre::eyre::{Ok, Result};
+use chrono::{Local, Utc};
+use color_eyre::eyre::{Context, Ok, Result, bail};
+use dialoguer::Input;
+use serde_yaml::{Mapping, Value};
+use std::io::{self, IsTerminal, Read};
 
-pub fn execute(storage: &Storage, id: Option<String>) -> Result<()> {
-    println!("Creating new entry...");
+/// Where the content of a new entry comes from when the editor is skipped
+pub enum Content {
+    Editor,
+    Message(String),
+    Stdin,
+}
+
+impl Content {
+    fn read(self) -> Result<Option<String>> {
+        let text = match self {
+            Content::Editor => return Ok(None),
+            Content::Message(text) => text,
+            Content::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .wrap_err("Failed to read entry from stdin")?;
+                text
+            }
+        };
+        if text.trim().is_empty() {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                "Nothing to write, the content is empty"
+            ));
+        }
+        Ok(Some(text))
+    }
+}
+
+/// Create an entry or note. With `append` the content goes at the end of an existing one
+/// instead of under a new `## HH:MM` heading or into a fresh file. With `force` an existing
+/// one is replaced instead of opened for editing. With `carry_forward` a new entry starts
+/// with the open todos of the previous one. With a `workspace` the content mentions its
+/// project.
+pub fn execute(
+    storage: &Storage,
+    id: Option<AnyId>,
+    content: Content,
+    append: bool,
+    force: bool,
+    carry_forward: bool,
+    workspace: Option<&Workspace>,
+) -> Result<()> {
+    let mut message = content.read()?;
+    if let (Some(text), Some(workspace)) = (&mut message, workspace) {
+        *text = workspace.mention_in(text);
+    }
+    match id {
+        Some(AnyId::Note(id)) => new_note(storage, id, message, append, force),
+        Some(AnyId::Entry(entry_ref)) if entry_ref.time.is_some() => bail!(DevlogError::new(
+            ErrorKind::Validation,
+            format!(
+                "Sub-entries get the current time, add one with `devlog new {} -m \"...\"`",
+                entry_ref.id
+            )
+        )),
+        Some(AnyId::Entry(entry_ref)) => new_entry(
+            storage,
+            entry_ref.id,
+            message,
+            append,
+            force,
+            carry_forward,
+            workspace,
+        ),
+        None => new_entry(
+            storage,
+            EntryId::today(),
+            message,
+            append,
+            force,
+            carry_forward,
+            workspace,
+        ),
+    }
+}
 
-    let entry_id = match id {
-        Some(id) => id,
-        None => Local::now().format("%Y%m%d").to_string(),
+/// The repository `devlog new` runs in, when its project should be mentioned without
+/// `--here`. Repositories in `workspaces` are, new ones are asked about once and the
+/// answer is kept in the config. Nothing is asked when stdin isn't a terminal.
+pub fn detect_workspace(storage: &Storage, config: &Config) -> Result<Option<Workspace>> {
+    let detection = &config.project_detection;
+    let Some(workspace) = detection
+        .enabled
+        .then(|| Workspace::current(&config.workspaces))
+        .flatten()
+    else {
+        return Ok(None);
     };
+    if workspace.mapped {
+        return Ok(Some(workspace));
+    }
+    if detection.ignored.iter().any(|key| workspace.matches(key)) {
+        return Ok(None);
+    }
+    let question = format!(
+        "Mention ::{} in entries written in {}?",
+        workspace.project, workspace.repo
+    );
+    let Some(mention) = output::confirm(&question) else {
+        return Ok(None);
+    };
+
+    let store = ConfigStore::new(storage.config_path()).with_writer(storage.writer().clone());
+    store.update(|config| {
+        if mention {
+            config
+                .workspaces
+                .insert(workspace.key(), workspace.project.clone());
+        } else {
+            config.project_detection.ignored.push(workspace.key());
+        }
+        Ok(())
+    })?;
+    Ok(mention.then_some(workspace))
+}
 
-    if storage.load_entry(&entry_id).is_ok() {
-        println!(
-            "Entry for {} already exists. Use 'devlog edit --id {}' to modify it.",
-            entry_id, entry_id
-        );
+/// Ask for the configured frontmatter `fields` of the entry or note `devlog new` just
+/// wrote, with their current values to keep. An empty answer removes a field. Nothing
+/// is asked when the entry wasn't written or stdin isn't a terminal.
+pub fn prompt_fields(storage: &Storage, id: Option<&AnyId>, fields: &[FieldConfig]) -> Result<()> {
+    if fields.is_empty() {
+        status!("No fields to ask for, add them as [[fields]] to the config");
+        return Ok(());
+    }
+    if !io::stdin().is_terminal() {
         return Ok(());
     }
+    match id {
+        Some(AnyId::Note(id)) => {
+            if !storage.note_path(id).exists() {
+                return Ok(());
+            }
+            let mut note = storage.load_note(id)?;
+            if ask_fields(fields, &mut note.extra)? {
+                note.updated_at = Utc::now();
+                storage.save_note(&note)?;
+            }
+        }
+        id => {
+            let id = match id {
+                Some(AnyId::Entry(entry_ref)) => entry_ref.id,
+                _ => EntryId::today(),
+            };
+            if !storage.entry_path(&id)?.exists() {
+                return Ok(());
+            }
+            let mut entry = storage.load_entry(&id)?;
+            if ask_fields(fields, &mut entry.extra)? {
+                entry.updated_at = Utc::now();
+                storage.save_entry(&entry)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether any of the `fields` in `extra` changed
+fn ask_fields(fields: &[FieldConfig], extra: &mut Mapping) -> Result<bool> {
+    let before = extra.clone();
+    let current = frontmatter::fields(extra);
+    for field in fields.iter().filter(|field| !field.name.trim().is_empty()) {
+        let name = field.name.trim();
+        let answer: String = Input::new()
+            .with_prompt(field.prompt.as_deref().unwrap_or(name))
+            .with_initial_text(current.get(name).cloned().unwrap_or_default())
+            .allow_empty(true)
+            .validate_with(|answer: &String| {
+                let answer = answer.trim();
+                let valid = !field.number || answer.is_empty() || answer.parse::<f64>().is_ok();
+                valid.then_some(()).ok_or("Expected a number")
+            })
+            .interact_text()?;
+
+        let answer = answer.trim();
+        let key = Value::from(name);
+        if answer.is_empty() {
+            extra.remove(&key);
+        } else if field.number {
+            // Whole numbers are written without a `.0`
+            let number = match answer.parse::<i64>().ok() {
+                Some(number) => Value::from(number),
+                None => Value::from(answer.parse::<f64>()?),
+            };
+            extra.insert(key, number);
+        } else {
+            extra.insert(key, Value::from(answer));
+        }
+    }
+    Ok(*extra != before)
+}
+
+fn new_entry(
+    storage: &Storage,
+    entry_id: EntryId,
+    message: Option<String>,
+    append: bool,
+    force: bool,
+    carry_forward: bool,
+    workspace: Option<&Workspace>,
+) -> Result<()> {
+    if append {
+        let text = match message {
+            Some(message) => message,
+            None => editor::launch_editor(None)?,
+        };
+        if text.trim().is_empty() {
+            status!("Nothing was written, {} is unchanged", entry_id);
+            return Ok(());
+        }
+        storage.append_to_entry(&entry_id, text.trim())?;
+        status!("Appended to entry {}", entry_id);
+        return Ok(());
+    }
+
+    if let Some(message) = message.as_ref().filter(|_| !force) {
+        let time = Local::now().time();
+        storage.append_sub_entry(&entry_id, time, message)?;
+        status!("Added sub-entry {}", EntryRef::sub_entry(entry_id, time));
+        return Ok(());
+    }
+
+    let exists = storage.entry_path(&entry_id)?.exists();
+    if exists && !force {
+        return match output::confirm(&format!(
+            "Entry {} already exists, open it for editing?",
+            entry_id
+        )) {
+            Some(true) => edit::execute(storage, AnyId::Entry(entry_id.into()), None),
+            Some(false) => {
+                status!("Entry {} is unchanged", entry_id);
+                Ok(())
+            }
+            None => bail!(DevlogError::new(
+                ErrorKind::Conflict,
+                format!(
+                    "Entry {} already exists, use `devlog edit {}` to modify it or --force to replace it",
+                    entry_id, entry_id
+                )
+            )),
+        };
+    }
+
+    if !exists {
+        status!("Creating new entry...");
+    }
+
+    let previous = if carry_forward {
+        previous_todos(storage, &entry_id)?
+    } else {
+        None
+    };
+    let carried = previous.as_ref().map(|(_, todos)| {
+        todos
+            .iter()
+            .map(|todo| format!("- [ ] {}", todo))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    let template = match (carried, workspace) {
+        (Some(carried), Some(workspace)) => Some(format!("{}\n\n::{}", carried, workspace.project)),
+        (None, Some(workspace)) => Some(format!("::{}", workspace.project)),
+        (carried, None) => carried,
+    };
 
-    // Launch editor with template
-    let content = editor::launch_editor(None)?;
+    // Launch editor with template, unless the content of a replacement was given
+    let content = match message {
+        Some(message) => message,
+        None => editor::launch_editor(template.as_deref())?,
+    };
+    if exists && content.trim().is_empty() {
+        status!("Nothing was written, {} is unchanged", entry_id);
+        return Ok(());
+    }
 
     // Create and save entry
-    let entry = Entry::new(entry_id.clone(), content);
-    storage.save_entry(&entry)?;
+    let entry = Entry::new(entry_id, content);
+    if exists {
+        storage.replace_entry(&entry)?;
+    } else {
+        storage.save_entry(&entry)?;
+    }
+    if let Some((previous_id, todos)) = previous {
+        carry_todos(storage, &previous_id, &todos, &entry)?;
+    }
+
+    if exists {
+        status!("Entry replaced: {}", entry_id);
+    } else {
+        status!("Entry created successfully: {}", entry_id);
+    }
+    Ok(())
+}
+
+/// The latest entry before `id` and its open todos, if it has any
+fn previous_todos(storage: &Storage, id: &EntryId) -> Result<Option<(EntryId, Vec<String>)>> {
+    let index = storage.index()?;
+    Ok(index
+        .entries
+        .range(..*id)
+        .next_back()
+        .filter(|(_, indexed)| !indexed.todos.is_empty())
+        .map(|(previous, indexed)| (*previous, indexed.todos.clone())))
+}
 
-    println!("Entry created successfully: {}", entry_id);
+/// Mark the todos of `from` that made it into `to` as carried forward, so they are
+/// only open in one place. Todos deleted in the editor stay open where they were.
+fn carry_todos(storage: &Storage, from: &EntryId, todos: &[String], to: &Entry) -> Result<()> {
+    let kept = todo::open_todos(&to.content);
+    let mut previous = storage.load_entry(from)?;
+    let mut content = previous.content.clone();
+    // Last first, marking a todo doesn't move the ones before it
+    for (n, _) in todos
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, t)| kept.contains(t))
+    {
+        if let Some(marked) = todo::mark_todo(&content, n, todo::CARRIED) {
+            content = marked;
+        }
+    }
+    if content != previous.content {
+        previous.update_content(content);
+        storage.save_entry(&previous)?;
+    }
+    Ok(())
+}
+
+fn new_note(
+    storage: &Storage,
+    id: NoteId,
+    message: Option<String>,
+    append: bool,
+    force: bool,
+) -> Result<()> {
+    let message = match message {
+        None if append => Some(editor::launch_editor(None)?),
+        message => message,
+    };
+    if let Some(message) = message.as_ref().filter(|_| !force) {
+        if message.trim().is_empty() {
+            status!("Nothing was written, note {} is unchanged", id);
+            return Ok(());
+        }
+        storage.append_to_note(&id, message.trim())?;
+        status!("Added to note {}", id);
+        return Ok(());
+    }
+
+    let exists = storage.note_path(&id).exists();
+    if exists && !force {
+        return match output::confirm(&format!("Note {} already exists, open it for editing?", id)) {
+            Some(true) => edit::execute(storage, AnyId::Note(id), None),
+            Some(false) => {
+                status!("Note {} is unchanged", id);
+                Ok(())
+            }
+            None => bail!(DevlogError::new(
+                ErrorKind::Conflict,
+                format!(
+                    "Note {} already exists, use `devlog edit {}` to modify it or --force to replace it",
+                    id, id
+                )
+            )),
+        };
+    }
+
+    let content = match message {
+        Some(message) => message,
+        None => editor::launch_editor(None)?,
+    };
+    if exists && content.trim().is_empty() {
+        status!("Nothing was written, note {} is unchanged", id);
+        return Ok(());
+    }
+    storage.save_note(&Note::new(id.clone(), content))?;
+
+    if exists {
+        status!("Note replaced: {}", id);
+    } else {
+        status!("Note created successfully: {}", id);
+    }
     Ok(())
 }
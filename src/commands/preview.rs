@@ -0,0 +1,21 @@
+use std::net::TcpListener;
+
+use color_eyre::eyre::{Context, Result};
+
+use crate::models::entry_id::EntryId;
+use crate::serve::preview::PreviewServer;
+use crate::status;
+use crate::storage::Storage;
+
+/// Serve entry `id` rendered to HTML on localhost until stopped
+pub fn execute(storage: &Storage, id: EntryId, port: u16, watch: bool) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .wrap_err_with(|| format!("Failed to listen on port {}", port))?;
+    status!(
+        "Previewing {} on http://127.0.0.1:{}{}, press Ctrl+C to stop",
+        id,
+        port,
+        if watch { ", reloading on save" } else { "" }
+    );
+    PreviewServer::new(storage, id, watch).run(listener)
+}
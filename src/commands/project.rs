@@ -0,0 +1,245 @@
+use std::{
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
+
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+use dialoguer::Input;
+
+use crate::commands::completions::annotation_candidates;
+use crate::error::{DevlogError, ErrorKind};
+use crate::export::{ExportFormat, ExportedEntry, handoff};
+use crate::models::annotation::AnnotationKind;
+use crate::models::project::{ProjectProfile, Retrospective};
+use crate::status;
+use crate::storage::Storage;
+
+/// Days without a mention after which `devlog project stale` lists a project
+pub const DEFAULT_STALE_DAYS: u64 = 30;
+
+#[derive(clap::Subcommand)]
+pub enum ProjectSubcommand {
+    /// Show a project's summary, impact and the entries mentioning it
+    Show {
+        /// Project name, with or without `::`
+        #[arg(add = annotation_candidates(AnnotationKind::Project))]
+        name: String,
+    },
+    /// List projects with their number of mentions and summary
+    List,
+    /// List projects no entry has mentioned for a while, the longest quiet first
+    Stale {
+        /// Days without a mention after which a project is stale
+        #[arg(long, default_value_t = DEFAULT_STALE_DAYS)]
+        days: u64,
+    },
+    /// Compile a handoff document: the project's profile and every entry mentioning it
+    Export {
+        /// Project name, with or without `::`
+        #[arg(add = annotation_candidates(AnnotationKind::Project))]
+        name: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Recompute every project's references from the entries
+    Rebuild,
+    /// Close a finished project: ask for its outcome, impact and lessons, add them to the
+    /// ledger and write a final summary
+    Close {
+        /// Project name, with or without `::`
+        #[arg(add = annotation_candidates(AnnotationKind::Project))]
+        name: String,
+        /// What came out of the project, asked for if not given
+        #[arg(long)]
+        outcome: Option<String>,
+        /// The difference it made, asked for if not given
+        #[arg(long)]
+        impact: Option<String>,
+        /// What to do the same or differently next time, asked for if not given
+        #[arg(long)]
+        lessons: Option<String>,
+    },
+    /// Open a closed project again
+    Reopen {
+        /// Project name, with or without `::`
+        name: String,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: ProjectSubcommand) -> Result<()> {
+    let prefix = AnnotationKind::Project.prefix();
+
+    match subcmd {
+        ProjectSubcommand::Show { name } => {
+            let profile = load(storage, &name)?;
+
+            println!("{}{}\n", prefix, profile.name);
+            for (label, text) in [("Summary", profile.summary()), ("Impact", profile.impact())] {
+                if !text.is_empty() {
+                    println!("{}:\n{}\n", label, text);
+                }
+            }
+            let index = storage.index()?;
+            println!("Mentioned in {} entries:", profile.references.len());
+            for id in &profile.references {
+                let preview = index.get(id).map_or("", |entry| entry.preview.as_str());
+                println!("  {}  {}", id.date(), preview);
+            }
+            status!(
+                "\nWrite the summary and impact in {}",
+                storage
+                    .projects_path()
+                    .join(format!("{}.md", profile.name))
+                    .display()
+            );
+        }
+        ProjectSubcommand::List => {
+            let profiles = storage.list_projects()?;
+            let width = profiles
+                .iter()
+                .map(|profile| profile.name.chars().count())
+                .max()
+                .unwrap_or(0)
+                + prefix.len();
+
+            for profile in profiles {
+                let last = profile
+                    .references
+                    .last()
+                    .map_or("-".to_string(), |id| id.date().to_string());
+                let summary = profile.summary();
+                let line = format!(
+                    "{:<width$}  {:>4}  {:<10}  {}",
+                    format!("{}{}", prefix, profile.name),
+                    profile.references.len(),
+                    last,
+                    summary.lines().next().unwrap_or(""),
+                    width = width
+                );
+                println!("{}", line.trim_end());
+            }
+        }
+        ProjectSubcommand::Stale { days } => {
+            let today = Local::now().date_naive();
+            let closed = storage.closed_projects()?;
+            let mut stale = storage.index()?.stale_projects(today, days);
+            stale.retain(|(project, _)| !closed.contains(project));
+            if stale.is_empty() {
+                status!("Every project was mentioned in the last {} days", days);
+            }
+            let width = stale
+                .iter()
+                .map(|(project, _)| project.chars().count())
+                .max()
+                .unwrap_or(0)
+                + prefix.len();
+            for (project, last) in stale {
+                println!(
+                    "{:<width$}  {}  {:>4} days ago",
+                    format!("{}{}", prefix, project),
+                    last.date(),
+                    (today - last.date()).num_days(),
+                    width = width
+                );
+            }
+        }
+        ProjectSubcommand::Export {
+            name,
+            format,
+            output,
+        } => {
+            let profile = load(storage, &name)?;
+            let entries = profile
+                .references
+                .iter()
+                .map(|id| storage.load_entry(id).map(ExportedEntry::from))
+                .collect::<Result<Vec<_>>>()?;
+            let document = handoff::render(&profile, &entries, format)?;
+            match output {
+                Some(path) => {
+                    storage.writer().write(&path, document)?;
+                    status!(
+                        "Handoff of {} entries written to {}",
+                        entries.len(),
+                        path.display()
+                    );
+                }
+                None => print!("{}", document),
+            }
+        }
+        ProjectSubcommand::Close {
+            name,
+            outcome,
+            impact,
+            lessons,
+        } => {
+            let name = load(storage, &name)?.name;
+            let retrospective = Retrospective {
+                outcome: answer(outcome, "Outcome")?,
+                impact: answer(impact, "Impact")?,
+                lessons: answer(lessons, "Lessons")?,
+            };
+            let profile =
+                storage.close_project(&name, Local::now().date_naive(), &retrospective)?;
+
+            let entries = profile
+                .references
+                .iter()
+                .map(|id| storage.load_entry(id).map(ExportedEntry::from))
+                .collect::<Result<Vec<_>>>()?;
+            let document = handoff::render(&profile, &entries, ExportFormat::Markdown)?;
+            let path = storage
+                .summaries_path()
+                .join(format!("project-{}.md", profile.name));
+            storage.writer().create_dir_all(&storage.summaries_path())?;
+            storage.writer().write(&path, document)?;
+            status!(
+                "Closed {}{}, its summary is in {}",
+                prefix,
+                profile.name,
+                path.display()
+            );
+        }
+        ProjectSubcommand::Reopen { name } => {
+            let profile = storage.reopen_project(&load(storage, &name)?.name)?;
+            status!("Reopened {}{}", prefix, profile.name);
+        }
+        ProjectSubcommand::Rebuild => {
+            let count = storage.rebuild_projects()?;
+            status!("Updated {} project profile(s)", count);
+        }
+    }
+
+    Ok(())
+}
+
+/// An answer given as an option, or else asked for. Nothing is asked when stdin isn't a
+/// terminal, the question is left unanswered instead.
+fn answer(given: Option<String>, question: &str) -> Result<String> {
+    match given {
+        Some(answer) => Ok(answer),
+        None if !io::stdin().is_terminal() => Ok(String::new()),
+        None => Ok(Input::new()
+            .with_prompt(question)
+            .allow_empty(true)
+            .interact_text()?),
+    }
+}
+
+/// Load the profile of `name`, given with or without `::`
+fn load(storage: &Storage, name: &str) -> Result<ProjectProfile> {
+    let prefix = AnnotationKind::Project.prefix();
+    let name = name.strip_prefix(prefix).unwrap_or(name);
+    match storage.load_project(name)? {
+        Some(profile) => Ok(profile),
+        None => bail!(DevlogError::new(
+            ErrorKind::NotFound,
+            format!("Project '{}{}' isn't mentioned in any entry", prefix, name)
+        )),
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+
+use chrono::{Local, Timelike};
+use color_eyre::eyre::Result;
+
+use crate::config::PromptConfig;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum PromptShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Without a shell, print the indicator for today's entry, or nothing.
+/// With a shell, print the script that adds the indicator to its prompt,
+/// e.g. `eval "$(devlog prompt-hook bash)"`.
+pub fn execute(storage: &Storage, config: &PromptConfig, shell: Option<PromptShell>) -> Result<()> {
+    match shell {
+        Some(shell) => print!("{}", init_script(shell)),
+        None => {
+            // Runs before every prompt: one stat and the entry index, never the whole vault
+            let now = Local::now();
+            let id = EntryId::from_date(now.date_naive());
+            let exists = fs::metadata(storage.entry_path(&id)?).is_ok();
+            let words = storage.load_index()?.get(&id).map(|entry| entry.words);
+            if let Some(symbol) = indicator(config, exists, words, now.hour()) {
+                println!("{}", symbol);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn indicator(config: &PromptConfig, exists: bool, words: Option<usize>, hour: u32) -> Option<&str> {
+    if hour < config.after_hour {
+        return None;
+    }
+    if !exists {
+        return Some(&config.missing_symbol);
+    }
+    // An entry written outside devlog isn't in the index yet, don't nag about it
+    (words.unwrap_or(usize::MAX) < config.min_words).then_some(config.short_symbol.as_str())
+}
+
+fn init_script(shell: PromptShell) -> &'static str {
+    match shell {
+        PromptShell::Bash => {
+            r#"__devlog_prompt() { DEVLOG_PROMPT="$(command devlog prompt-hook 2>/dev/null)"; }
+PROMPT_COMMAND="__devlog_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+PS1='${DEVLOG_PROMPT:+$DEVLOG_PROMPT }'"$PS1"
+"#
+        }
+        PromptShell::Zsh => {
+            r#"__devlog_prompt() { DEVLOG_PROMPT="$(command devlog prompt-hook 2>/dev/null)" }
+autoload -Uz add-zsh-hook
+add-zsh-hook precmd __devlog_prompt
+setopt prompt_subst
+PROMPT='${DEVLOG_PROMPT:+$DEVLOG_PROMPT }'"$PROMPT"
+"#
+        }
+        PromptShell::Fish => {
+            r#"functions -q __devlog_original_prompt; or functions -c fish_prompt __devlog_original_prompt
+function fish_prompt
+    set -l devlog_prompt (command devlog prompt-hook 2>/dev/null)
+    test -n "$devlog_prompt"; and echo -n "$devlog_prompt "
+    __devlog_original_prompt
+end
+"#
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator_thresholds() {
+        let config = PromptConfig {
+            after_hour: 9,
+            ..PromptConfig::default()
+        };
+
+        assert_eq!(indicator(&config, false, None, 8), None);
+        assert_eq!(indicator(&config, false, None, 9), Some("✎"));
+        assert_eq!(indicator(&config, true, Some(10), 12), Some("✎…"));
+        assert_eq!(indicator(&config, true, Some(50), 12), None);
+        assert_eq!(indicator(&config, true, None, 12), None);
+    }
+}
@@ -0,0 +1,151 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{Days, Local};
+use color_eyre::eyre::{Result, bail, eyre};
+
+use crate::config::{ConfigStore, PublishConfig, secrets::SecretStore};
+use crate::digest;
+use crate::error::{DevlogError, ErrorKind};
+use crate::export::{self, ExportedEntry};
+use crate::models::entry_id::EntryId;
+use crate::publish::{
+    Document, HttpApi, Outcome, PublishTarget, Published,
+    confluence::Confluence,
+    notion::{self, Notion},
+};
+use crate::status;
+use crate::storage::Storage;
+
+/// Secrets holding the API tokens, see `devlog config set-secret`
+const CONFLUENCE_TOKEN_SECRET: &str = "publish.confluence_token";
+const NOTION_TOKEN_SECRET: &str = "publish.notion_token";
+
+/// Which entries to publish
+pub struct Selection {
+    pub ids: Vec<EntryId>,
+    pub since: Option<EntryId>,
+    pub until: Option<EntryId>,
+    /// Publish the weekly digest up to `until` or yesterday instead of entries
+    pub digest: bool,
+}
+
+/// Publish the selected entries, or today's, as one page each. Pages published
+/// before are updated in place.
+pub fn execute(storage: &Storage, target: PublishTarget, selection: Selection) -> Result<()> {
+    let config = ConfigStore::new(storage.config_path()).load()?.publish;
+    let documents = documents(storage, selection)?;
+
+    if storage.writer().is_dry_run() {
+        for document in &documents {
+            status!("Would publish '{}' to {}", document.title, target.name());
+        }
+        return Ok(());
+    }
+
+    let api = HttpApi::new(headers(target, &config)?)?;
+    let mut published = Published::load(storage)?;
+    for document in &documents {
+        let known_page = published.page(target, &document.key);
+        let outcome = match target {
+            PublishTarget::Confluence => {
+                let confluence = &config.confluence;
+                Confluence::new(
+                    &api,
+                    required(&confluence.url, "publish.confluence.url")?,
+                    required(&confluence.space, "publish.confluence.space")?,
+                    confluence.parent_id.clone(),
+                )
+                .publish(document, known_page)?
+            }
+            PublishTarget::Notion => Notion::new(
+                &api,
+                required(&config.notion.database_id, "publish.notion.database_id")?,
+            )
+            .publish(document, known_page)?,
+        };
+
+        published.set_page(target, &document.key, outcome.page_id().to_string());
+        // Saved after every page, so a failure halfway doesn't lose track of the others
+        published.save(storage)?;
+        match outcome {
+            Outcome::Created(_) => status!("Created '{}'", document.title),
+            Outcome::Updated(_) => status!("Updated '{}'", document.title),
+        }
+    }
+    Ok(())
+}
+
+fn documents(storage: &Storage, selection: Selection) -> Result<Vec<Document>> {
+    if selection.digest {
+        let end = selection
+            .until
+            .map_or(Local::now().date_naive() - Days::new(1), |until| {
+                until.date()
+            });
+        let digest = digest::compile(storage, end)?;
+        let entries = export::collect(
+            storage,
+            Some(EntryId::from_date(digest.start)),
+            Some(EntryId::from_date(digest.end)),
+        )?;
+        return Ok(vec![Document::digest(&digest, &entries)]);
+    }
+
+    let entries = if !selection.ids.is_empty() {
+        selection
+            .ids
+            .iter()
+            .map(|id| storage.load_entry(id).map(ExportedEntry::from))
+            .collect::<Result<Vec<_>>>()?
+    } else if selection.since.is_some() || selection.until.is_some() {
+        export::collect(storage, selection.since, selection.until)?
+    } else {
+        vec![ExportedEntry::from(storage.load_entry(&EntryId::today())?)]
+    };
+    if entries.is_empty() {
+        bail!(DevlogError::new(
+            ErrorKind::NotFound,
+            "No entries in the selected range"
+        ));
+    }
+    Ok(entries.iter().map(Document::entry).collect())
+}
+
+fn headers(target: PublishTarget, config: &PublishConfig) -> Result<Vec<(&'static str, String)>> {
+    let secret = match target {
+        PublishTarget::Confluence => CONFLUENCE_TOKEN_SECRET,
+        PublishTarget::Notion => NOTION_TOKEN_SECRET,
+    };
+    let token = SecretStore.get(secret)?.ok_or_else(|| {
+        eyre!(
+            "No {} token stored, run `devlog config set-secret {}`",
+            target.name(),
+            secret
+        )
+    })?;
+
+    Ok(match target {
+        // Confluence Cloud takes the API token with the account's email, Server and
+        // Data Center a personal access token on its own
+        PublishTarget::Confluence => match &config.confluence.email {
+            Some(email) => vec![(
+                "Authorization",
+                format!("Basic {}", STANDARD.encode(format!("{}:{}", email, token))),
+            )],
+            None => vec![("Authorization", format!("Bearer {}", token))],
+        },
+        PublishTarget::Notion => vec![
+            ("Authorization", format!("Bearer {}", token)),
+            ("Notion-Version", notion::API_VERSION.to_string()),
+        ],
+    })
+}
+
+fn required<'a>(value: &'a Option<String>, key: &str) -> Result<&'a str> {
+    match value {
+        Some(value) => Ok(value),
+        None => bail!(DevlogError::new(
+            ErrorKind::Validation,
+            format!("Set {} first, e.g. `devlog config set {} ...`", key, key)
+        )),
+    }
+}
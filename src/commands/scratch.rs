@@ -0,0 +1,100 @@
+use std::collections::BTreeSet;
+
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::Storage;
+use crate::utils::editor;
+
+#[derive(clap::Subcommand)]
+pub enum ScratchSubcommand {
+    /// Print the scratch pad with line numbers
+    Show,
+    /// Append a line to the scratch pad
+    Add {
+        /// Text of the line
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+    },
+    /// Open the scratch pad in the editor
+    Edit,
+    /// Move lines into an entry, e.g. `devlog scratch move 2-4,7`
+    Move {
+        /// Line numbers as shown by `devlog scratch show`, ranges like `2-4` allowed
+        lines: String,
+        /// Entry to move the lines to, defaults to today
+        #[arg(long, value_name = "YYYYMMDD")]
+        to: Option<EntryId>,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: Option<ScratchSubcommand>) -> Result<()> {
+    match subcmd.unwrap_or(ScratchSubcommand::Show) {
+        ScratchSubcommand::Show => {
+            for (idx, line) in storage.load_scratch()?.lines().enumerate() {
+                println!("{:>3}  {}", idx + 1, line);
+            }
+        }
+        ScratchSubcommand::Add { text } => {
+            let scratch = storage.load_scratch()?;
+            let line = text.join(" ");
+            let updated = if scratch.is_empty() {
+                line
+            } else {
+                format!("{}\n{}", scratch.trim_end_matches('\n'), line)
+            };
+            storage.save_scratch(&updated)?;
+        }
+        ScratchSubcommand::Edit => editor::open_file(&storage.scratch_path())?,
+        ScratchSubcommand::Move { lines, to } => {
+            let id = to.unwrap_or_else(EntryId::today);
+            let moved = storage.move_scratch_lines(&parse_line_numbers(&lines)?, &id)?;
+            status!("Moved {} line(s) to entry {}", moved, id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse 1-based line numbers like `2-4,7` into 0-based indices
+fn parse_line_numbers(spec: &str) -> Result<BTreeSet<usize>> {
+    let invalid = || {
+        DevlogError::new(
+            ErrorKind::Validation,
+            format!(
+                "Invalid line numbers '{}', expected e.g. `3` or `2-4,7`",
+                spec
+            ),
+        )
+    };
+
+    let mut indices = BTreeSet::new();
+    for part in spec.split(',').map(str::trim) {
+        let (from, to) = part.split_once('-').unwrap_or((part, part));
+        let from: usize = from.trim().parse().map_err(|_| invalid())?;
+        let to: usize = to.trim().parse().map_err(|_| invalid())?;
+        if from == 0 || to < from {
+            bail!(invalid());
+        }
+        indices.extend(from - 1..to);
+    }
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_numbers() {
+        assert_eq!(
+            parse_line_numbers("2-4, 7").unwrap(),
+            BTreeSet::from([1, 2, 3, 6])
+        );
+        assert!(parse_line_numbers("0").is_err());
+        assert!(parse_line_numbers("4-2").is_err());
+        assert!(parse_line_numbers("a").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+
+use crate::export;
+use crate::models::frontmatter::FieldMatch;
+use crate::models::note::AnyId;
+use crate::search;
+use crate::status;
+use crate::storage::Storage;
+
+/// Print the lines of every entry and note containing `query`, or write them with
+/// `context` lines around them to `export_path` as one Markdown document.
+/// With `project` only entries referencing that `::project` are searched, with
+/// `fields` only entries whose frontmatter has every field asked for. Notes
+/// aren't indexed, so either leaves them out.
+pub fn execute(
+    storage: &Storage,
+    query: &str,
+    context: usize,
+    export_path: Option<PathBuf>,
+    project: Option<&str>,
+    fields: &[FieldMatch],
+) -> Result<()> {
+    let found = search::search(storage, query)?;
+    for unreadable in &found.unreadable {
+        eprintln!("Warning: {}", unreadable);
+    }
+    let mut results = found.matches;
+    if project.is_some() || !fields.is_empty() {
+        let index = storage.index()?;
+        results.retain(|matches| {
+            let AnyId::Entry(entry_ref) = &matches.id else {
+                return false;
+            };
+            index.get(&entry_ref.id).is_some_and(|entry| {
+                project
+                    .is_none_or(|project| entry.annotations.projects.iter().any(|p| p == project))
+                    && fields.iter().all(|field| field.matches(&entry.fields))
+            })
+        });
+    }
+    if results.is_empty() {
+        status!("No entries mention '{}'", query);
+        return Ok(());
+    }
+    let hits: usize = results.iter().map(|matches| matches.hits.len()).sum();
+
+    if let Some(path) = export_path {
+        let document = export::search::to_markdown(query, &results, context);
+        storage.writer().write(&path, document)?;
+        status!(
+            "Exported {} matches in {} entries to {}",
+            hits,
+            results.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    for matches in &results {
+        println!("{}", matches.id);
+        for &hit in &matches.hits {
+            println!("{:>5}: {}", hit + 1, matches.lines[hit]);
+        }
+    }
+    status!("{} matches in {} entries", hits, results.len());
+    Ok(())
+}
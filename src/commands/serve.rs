@@ -0,0 +1,52 @@
+use std::net::TcpListener;
+
+use chrono::Utc;
+use color_eyre::eyre::{Context, Result};
+
+use crate::serve::{Server, pairing::Pairing};
+use crate::status;
+use crate::storage::Storage;
+
+#[derive(clap::Subcommand)]
+pub enum ServeSubcommand {
+    /// Print a one-time code to pair the browser extension with
+    Pair,
+    /// Forget every paired extension
+    Revoke,
+}
+
+pub fn execute(storage: &Storage, port: u16, subcmd: Option<ServeSubcommand>) -> Result<()> {
+    match subcmd {
+        None => {
+            // Only reachable from this machine, the extension talks to localhost
+            let listener = TcpListener::bind(("127.0.0.1", port))
+                .wrap_err_with(|| format!("Failed to listen on port {}", port))?;
+            status!(
+                "Listening on http://127.0.0.1:{}, press Ctrl+C to stop",
+                port
+            );
+            Server::new(storage).run(listener)?;
+        }
+        Some(ServeSubcommand::Pair) => {
+            let mut pairing = Pairing::load(storage)?;
+            let code = pairing.new_code(Utc::now())?.clone();
+            pairing.save(storage)?;
+            println!("{}", code.code);
+            status!(
+                "Enter this code in the browser extension before {}",
+                code.expires_at
+                    .with_timezone(&chrono::Local)
+                    .format("%H:%M")
+            );
+        }
+        Some(ServeSubcommand::Revoke) => {
+            let mut pairing = Pairing::load(storage)?;
+            let revoked = pairing.tokens.len();
+            pairing.tokens.clear();
+            pairing.save(storage)?;
+            status!("Revoked {} paired extension(s)", revoked);
+        }
+    }
+
+    Ok(())
+}
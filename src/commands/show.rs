@@ -1,12 +1,222 @@
-use color_eyre::eyre::{Context, Ok, Result};
+use std::collections::BTreeMap;
 
-use crate::storage::Storage;
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use color_eyre::eyre::{Context, Ok, Result, bail};
+use serde::Serialize;
 
-pub fn execute(storage: &Storage, id: String) -> Result<()> {
-    let entry = storage
-        .load_entry(&id)
-        .wrap_err_with(|| format!("Entry '{}' not found", id))?;
+use crate::error::{DevlogError, ErrorKind};
+use crate::export::terminal;
+use crate::models::annotation::Annotations;
+use crate::models::display::DisplayOptions;
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryRef;
+use crate::models::frontmatter;
+use crate::models::note::AnyId;
+use crate::storage::{Storage, repository::EntryRepository};
 
-    println!("{}", entry);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ShowFormat {
+    /// Id and timestamps followed by the content
+    #[default]
+    Text,
+    /// The Markdown content only
+    Markdown,
+    /// The content rendered with terminal colors
+    Rendered,
+    /// Id, timestamps, parsed annotations, backlinks and content as JSON
+    Json,
+    /// Id, timestamps, parsed annotations, backlinks and content as YAML
+    Yaml,
+}
+
+/// An entry, sub-entry or note as scripts see it
+#[derive(Debug, Serialize)]
+struct Shown {
+    id: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    annotations: Annotations,
+    #[serde(skip_serializing_if = "DisplayOptions::is_default")]
+    display: DisplayOptions,
+    /// Custom frontmatter fields like `mood`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, String>,
+    /// Entries with a `[[...]]` link to this one, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    backlinks: Vec<String>,
+    content: String,
+    /// The plain text output, with a header fitting what is shown
+    #[serde(skip)]
+    text: String,
+}
+
+impl Shown {
+    fn new(
+        id: String,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+        display: DisplayOptions,
+        extra: &serde_yaml::Mapping,
+        content: String,
+    ) -> Self {
+        Self {
+            id,
+            created_at,
+            updated_at,
+            annotations: Annotations::parse(&content),
+            display,
+            fields: frontmatter::fields(extra),
+            backlinks: Vec::new(),
+            content,
+            text: String::new(),
+        }
+    }
+}
+
+/// `--as-of` instants: `YYYY-MM-DD HH:MM[:SS]` on the local clock, or RFC 3339
+pub fn parse_as_of(value: &str) -> Result<DateTime<FixedOffset>> {
+    let at = DateTime::parse_from_rfc3339(value).ok().or_else(|| {
+        let local = ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value.trim(), format).ok())?;
+        Local
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|at| at.fixed_offset())
+    });
+    match at {
+        Some(at) => Ok(at),
+        None => bail!(DevlogError::new(
+            ErrorKind::Validation,
+            format!(
+                "Invalid time '{}': expected YYYY-MM-DD HH:MM, e.g. \"2025-09-20 15:00\"",
+                value
+            )
+        )),
+    }
+}
+
+/// Print an entry, sub-entry or note, or with `as_of` an entry as it was at that instant
+pub fn execute(
+    storage: &Storage,
+    id: AnyId,
+    format: ShowFormat,
+    as_of: Option<DateTime<FixedOffset>>,
+) -> Result<()> {
+    // Links to a sub-entry count for its day
+    let key = match &id {
+        AnyId::Entry(entry_ref) => entry_ref.id.to_string(),
+        AnyId::Note(id) => id.to_string(),
+    };
+    let mut shown = match id {
+        AnyId::Entry(entry_ref) => show_entry(storage, entry_ref, as_of)?,
+        AnyId::Note(_) if as_of.is_some() => bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "Notes keep no history, --as-of only works for entries"
+        )),
+        AnyId::Note(id) => {
+            let note = storage.load_note(&id)?;
+            let text = note.to_string();
+            Shown {
+                text,
+                ..Shown::new(
+                    id.to_string(),
+                    note.created_at,
+                    note.updated_at,
+                    DisplayOptions::default(),
+                    &note.extra,
+                    note.content,
+                )
+            }
+        }
+    };
+    shown.backlinks = EntryRepository::index(storage)?
+        .backlinks(&key)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    if !shown.backlinks.is_empty() {
+        shown.text = format!(
+            "{}\n\nReferenced by: {}",
+            shown.text.trim_end(),
+            shown.backlinks.join(", ")
+        );
+    }
+
+    match format {
+        ShowFormat::Text => println!("{}", shown.text),
+        ShowFormat::Markdown => println!("{}", shown.content),
+        ShowFormat::Rendered => print!(
+            "{}",
+            terminal::markdown_to_ansi(&shown.display.apply_toc(&shown.content))
+        ),
+        ShowFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&shown).wrap_err("Failed to serialize entry")?
+        ),
+        ShowFormat::Yaml => print!(
+            "{}",
+            serde_yaml::to_string(&shown).wrap_err("Failed to serialize entry")?
+        ),
+    }
     Ok(())
 }
+
+fn show_entry(
+    repository: &impl EntryRepository,
+    entry_ref: EntryRef,
+    as_of: Option<DateTime<FixedOffset>>,
+) -> Result<Shown> {
+    let entry = match as_of {
+        Some(at) => repository.entry_at(&entry_ref.id, at)?,
+        None => repository
+            .load_entry(&entry_ref.id)
+            .wrap_err_with(|| format!("Entry '{}' not found", entry_ref.id))?,
+    };
+
+    let Some(time) = entry_ref.time else {
+        // The text output shows the table of contents the entry asks for
+        let text = Entry {
+            content: entry.display.apply_toc(&entry.content),
+            ..entry.clone()
+        }
+        .to_string();
+        return Ok(Shown {
+            text,
+            ..Shown::new(
+                entry.id.to_string(),
+                entry.created_at,
+                entry.updated_at,
+                entry.display,
+                &entry.extra,
+                entry.content,
+            )
+        });
+    };
+    let Some(sub_entry) = entry.sub_entry(time) else {
+        bail!(DevlogError::new(
+            ErrorKind::NotFound,
+            format!(
+                "Entry '{}' has no sub-entry at {}",
+                entry_ref.id,
+                time.format("%H:%M")
+            )
+        ));
+    };
+    let text = format!(
+        "Id: {}\n---\n\n{}",
+        entry_ref,
+        entry.display.apply_toc(&sub_entry.content)
+    );
+    Ok(Shown {
+        text,
+        ..Shown::new(
+            entry_ref.to_string(),
+            entry.created_at,
+            entry.updated_at,
+            entry.display,
+            &entry.extra,
+            sub_entry.content,
+        )
+    })
+}
@@ -0,0 +1,287 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use chrono::{Local, NaiveDate, Timelike};
+use color_eyre::eyre::Result;
+
+use crate::config::Config;
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::annotation::AnnotationKind;
+use crate::stats::{self, BurndownPoint, Overview, WeeklyHours};
+use crate::status;
+use crate::storage::{Storage, events::Event};
+
+const BAR_WIDTH: usize = 40;
+
+#[derive(clap::Subcommand)]
+pub enum StatsSubcommand {
+    /// Show at which hours of the day entries get written
+    Times,
+    /// Chart hours per project per week from `~1h30m` duration annotations
+    Chart {
+        /// Show the burndown of a goal from the `goals` config instead
+        #[arg(long, value_name = "PROJECT")]
+        goal: Option<String>,
+        /// Number of weeks to chart, ending with the current one
+        #[arg(long, default_value_t = 8)]
+        weeks: usize,
+        /// Print the chart as SVG instead of text
+        #[arg(long)]
+        svg: bool,
+        /// Write the chart to a file instead of stdout
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Run a stats subcommand, without one print the overview of every entry
+pub fn execute(
+    storage: &Storage,
+    config: &Config,
+    months: u32,
+    top: usize,
+    subcmd: Option<StatsSubcommand>,
+) -> Result<()> {
+    let Some(subcmd) = subcmd else {
+        let overview =
+            Overview::from_index(&storage.index()?, Local::now().date_naive(), months, top);
+        print!("{}", overview_text(&overview));
+        return Ok(());
+    };
+    match subcmd {
+        StatsSubcommand::Chart {
+            goal,
+            weeks,
+            svg,
+            output,
+        } => {
+            let index = storage.index()?;
+            let today = Local::now().date_naive();
+            let chart = match goal {
+                Some(project) => {
+                    let goal = config.goals.get(&project).ok_or_else(|| {
+                        DevlogError::new(
+                            ErrorKind::NotFound,
+                            format!(
+                                "No goal for '{}', add [goals.{}] to the config",
+                                project, project
+                            ),
+                        )
+                    })?;
+                    let points = stats::burndown(&index, &project, goal, today);
+                    if svg {
+                        stats::svg::burndown(&project, &points)
+                    } else {
+                        burndown_text(goal.hours, &points)
+                    }
+                }
+                None => {
+                    let hours = WeeklyHours::from_index(&index, today, weeks.max(1));
+                    if svg {
+                        stats::svg::weekly_hours(&hours)
+                    } else {
+                        weekly_hours_text(&hours)
+                    }
+                }
+            };
+
+            match output {
+                Some(path) => {
+                    storage.writer().write(&path, chart)?;
+                    status!("Chart written to {}", path.display());
+                }
+                None => print!("{}", chart),
+            }
+        }
+        StatsSubcommand::Times => {
+            let events: Vec<Event> = storage
+                .all_events()?
+                .into_iter()
+                .map(|(_, event)| event)
+                .collect();
+            let histogram = hour_histogram(&events);
+            let max = histogram.iter().copied().max().unwrap_or(0);
+            if max == 0 {
+                println!("No writing recorded yet");
+                return Ok(());
+            }
+
+            for (hour, count) in histogram.iter().enumerate() {
+                let bar = "█".repeat(count * BAR_WIDTH / max);
+                println!(
+                    "{:02}:00 {:<width$} {}",
+                    hour,
+                    bar,
+                    count,
+                    width = BAR_WIDTH
+                );
+            }
+            if let Some(peak) = (0..24).max_by_key(|hour| (histogram[*hour], usize::MAX - hour)) {
+                println!("\nYou write most often around {:02}:00", peak);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn overview_text(overview: &Overview) -> String {
+    if overview.entries == 0 {
+        return "No entries yet, start one with `devlog new`\n".to_string();
+    }
+    let days = if overview.current_streak == 1 {
+        "day"
+    } else {
+        "days"
+    };
+    let mut text = format!("Entries   {}\n", overview.entries);
+    text.push_str(&format!(
+        "Streak    {} {}, longest {}\n",
+        overview.current_streak, days, overview.longest_streak
+    ));
+    text.push_str(&format!(
+        "Words     {} in total, {} per entry\n",
+        overview.total_words, overview.average_words
+    ));
+
+    let max = overview
+        .entries_per_month
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0);
+    text.push_str("\nEntries per month\n");
+    for (month, count) in &overview.entries_per_month {
+        text.push_str(&format!(
+            "  {} {:<width$} {}\n",
+            month.format("%Y-%m"),
+            bar(*count as f64, max as f64),
+            count,
+            width = BAR_WIDTH
+        ));
+    }
+
+    for (title, kind, top) in [
+        ("Top tags", AnnotationKind::Tag, &overview.top_tags),
+        ("Top people", AnnotationKind::Person, &overview.top_people),
+        (
+            "Top projects",
+            AnnotationKind::Project,
+            &overview.top_projects,
+        ),
+    ] {
+        if top.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("\n{}\n", title));
+        for (value, count) in top {
+            text.push_str(&format!("  {:>4}  {}{}\n", count, kind.prefix(), value));
+        }
+    }
+    text
+}
+
+fn weekly_hours_text(hours: &WeeklyHours) -> String {
+    if hours.projects.is_empty() {
+        return "No time logged yet, add durations like `::project ~1h30m` to entries\n"
+            .to_string();
+    }
+    let max = hours
+        .projects
+        .iter()
+        .flat_map(|(_, weeks)| weeks.iter().copied())
+        .fold(0.0, f64::max);
+    let width = hours
+        .projects
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut text = String::new();
+    for (week_idx, week) in hours.weeks.iter().enumerate() {
+        text.push_str(&format!("Week of {}\n", week));
+        if hours.projects.iter().all(|(_, weeks)| weeks[week_idx] == 0.0) {
+            text.push_str("  nothing logged\n");
+        }
+        for (name, weeks) in &hours.projects {
+            let logged = weeks[week_idx];
+            if logged > 0.0 {
+                text.push_str(&format!(
+                    "  {:<width$} {} {:.1}h\n",
+                    name,
+                    bar(logged, max),
+                    logged,
+                    width = width
+                ));
+            }
+        }
+    }
+    text
+}
+
+fn burndown_text(target: u32, points: &[BurndownPoint]) -> String {
+    let max = f64::from(target);
+    let mut text = String::new();
+    for point in points {
+        let remaining = match point.remaining {
+            Some(hours) => format!("{} {:.1}h left", bar(hours, max), hours),
+            None => String::new(),
+        };
+        text.push_str(&format!(
+            "{}  ideal {:>5.1}h  {}\n",
+            point.day, point.ideal, remaining
+        ));
+    }
+    text
+}
+
+/// Horizontal bar of `value` scaled so `max` fills the full width
+fn bar(value: f64, max: f64) -> String {
+    if max <= 0.0 {
+        return String::new();
+    }
+    "█".repeat((value / max * BAR_WIDTH as f64).round() as usize)
+}
+
+/// Count on how many days something was written in each hour. Several saves in
+/// the same hour of a day, e.g. autosaves, count once.
+fn hour_histogram(events: &[Event]) -> [usize; 24] {
+    let sessions: BTreeSet<(NaiveDate, u32)> = events
+        .iter()
+        .map(|event| (event.at.date_naive(), event.at.hour()))
+        .collect();
+
+    let mut histogram = [0; 24];
+    for (_, hour) in sessions {
+        histogram[hour as usize] += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::events::EventKind;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_hour_histogram_counts_hours_once_per_day() {
+        let event = |at: &str| Event {
+            at: DateTime::parse_from_rfc3339(at).unwrap(),
+            kind: EventKind::Updated,
+            content: None,
+            detail: None,
+        };
+        let events = [
+            event("2025-09-20T21:05:00+02:00"),
+            event("2025-09-20T21:40:00+02:00"),
+            event("2025-09-21T21:10:00+02:00"),
+            event("2025-09-21T07:30:00-05:00"),
+        ];
+
+        let histogram = hour_histogram(&events);
+        assert_eq!(histogram[21], 2);
+        assert_eq!(histogram[7], 1);
+        assert_eq!(histogram.iter().sum::<usize>(), 3);
+    }
+}
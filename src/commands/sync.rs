@@ -0,0 +1,78 @@
+use color_eyre::eyre::Result;
+
+use crate::config::Config;
+use crate::status;
+use crate::storage::Storage;
+use crate::sync::{Action, Change, SyncEngine};
+
+pub fn execute(storage: &Storage, config: &Config, status_only: bool, delete: bool) -> Result<()> {
+    let engine = SyncEngine::from_config(storage, &config.sync)?.with_deletions(delete);
+
+    if status_only {
+        let plan = engine.plan()?;
+        if plan.is_empty() {
+            status!("Everything is in sync");
+        }
+        for change in &plan {
+            println!("{:<13} {}", describe(change.action), change.path);
+        }
+        print_deletions("Would delete", &plan);
+        return Ok(());
+    }
+
+    let report = engine.sync()?;
+    status!(
+        "Synced {} file{}",
+        report.changes.len(),
+        if report.changes.len() == 1 { "" } else { "s" }
+    );
+    print_deletions("Deleted", &report.changes);
+    for conflict in &report.conflicts {
+        match &conflict.copy {
+            Some(copy) => eprintln!(
+                "Conflict in {}: kept the other machine's version, yours is in {}",
+                conflict.path, copy
+            ),
+            None => eprintln!(
+                "Conflict in {}: both versions are kept between conflict markers, pick one in the TUI editor",
+                conflict.path
+            ),
+        }
+    }
+    if !report.skipped.is_empty() {
+        eprintln!(
+            "Kept {} deleted file{}, sync without --no-delete to delete them:",
+            report.skipped.len(),
+            if report.skipped.len() == 1 { "" } else { "s" }
+        );
+        for change in &report.skipped {
+            eprintln!("  {} {}", describe(change.action), change.path);
+        }
+    }
+    Ok(())
+}
+
+/// Summarize the deletions among `changes`, so nothing disappears unnoticed
+fn print_deletions(verb: &str, changes: &[Change]) {
+    let count = |action| changes.iter().filter(|c| c.action == action).count();
+    let (here, remote) = (count(Action::DeleteLocal), count(Action::DeleteRemote));
+    if here + remote > 0 {
+        status!(
+            "{} {} file(s) here and {} in the sync folder",
+            verb,
+            here,
+            remote
+        );
+    }
+}
+
+fn describe(action: Action) -> &'static str {
+    match action {
+        Action::Pull => "pull",
+        Action::Push => "push",
+        Action::DeleteLocal => "delete here",
+        Action::DeleteRemote => "delete remote",
+        Action::Merge => "merge",
+        Action::Forget => "forget",
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::Local;
+use color_eyre::eyre::Result;
+
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+/// Print a short status segment like `3d 120w 2todo 1overdue` for tmux or starship.
+///
+/// Only the entry index is read, which is kept current whenever an entry is
+/// saved, so this stays fast however large the vault is.
+pub fn execute(storage: &Storage, rebuild: bool) -> Result<()> {
+    let mut index = storage.load_index()?;
+    // First run, or entries were changed outside devlog
+    if rebuild || index.entries.is_empty() {
+        index = storage.rebuild_index()?;
+    }
+
+    let today = Local::now().date_naive();
+    let words = index
+        .get(&EntryId::from_date(today))
+        .map_or(0, |entry| entry.words);
+    let mut segment = format!("{}d {}w", index.streak(today), words);
+    let todos = index.open_todos();
+    if todos > 0 {
+        segment.push_str(&format!(" {}todo", todos));
+    }
+    let overdue = index
+        .actions()
+        .iter()
+        .filter(|(_, action)| action.is_overdue(today))
+        .count();
+    if overdue > 0 {
+        segment.push_str(&format!(" {}overdue", overdue));
+    }
+    println!("{}", segment);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::models::todo;
+use crate::status;
+use crate::storage::{Storage, focus::FocusSession};
+
+#[derive(clap::Subcommand)]
+pub enum TodoSubcommand {
+    /// List the open `- [ ]` todos of every entry, numbered for `devlog todo done`
+    List,
+    /// Check off a todo in its entry
+    Done {
+        /// Number of the todo in `devlog todo list`
+        number: usize,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: TodoSubcommand) -> Result<()> {
+    let index = storage.index()?;
+    let todos = index.todos();
+
+    match subcmd {
+        TodoSubcommand::List => {
+            if todos.is_empty() {
+                println!("No open todos");
+            }
+            for (number, (id, todo)) in todos.iter().enumerate() {
+                println!("{:>3}. {}  {}", number + 1, id, todo);
+            }
+        }
+        TodoSubcommand::Done { number } => {
+            let Some(&(id, text)) = number.checked_sub(1).and_then(|n| todos.get(n)) else {
+                bail!(DevlogError::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "There is no todo {}, `devlog todo list` shows {}",
+                        number,
+                        todos.len()
+                    )
+                ));
+            };
+            check_off(storage, id, number, &todos)?;
+            status!("Checked off '{}' in {}", text, id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check off todo `number` of the list, which is the nth open todo of its entry
+fn check_off(
+    storage: &Storage,
+    id: EntryId,
+    number: usize,
+    todos: &[(EntryId, &str)],
+) -> Result<()> {
+    let now = Local::now().fixed_offset();
+    if let Some(message) =
+        FocusSession::load(storage, now)?.and_then(|session| session.blocks(&id, now))
+    {
+        bail!(DevlogError::new(ErrorKind::Validation, message));
+    }
+
+    let in_entry = todos[..number - 1]
+        .iter()
+        .filter(|(other, _)| *other == id)
+        .count();
+    let mut entry = storage.load_entry(&id)?;
+    let Some(content) = todo::mark_todo(&entry.content, in_entry, todo::DONE) else {
+        bail!(DevlogError::new(
+            ErrorKind::Conflict,
+            format!("{} changed while reading its todos, please retry", id)
+        ));
+    };
+    entry.update_content(content);
+    storage.save_entry(&entry)
+}
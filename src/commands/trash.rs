@@ -0,0 +1,40 @@
+use color_eyre::eyre::Result;
+
+use crate::models::entry_id::EntryId;
+use crate::status;
+use crate::storage::Storage;
+
+#[derive(clap::Subcommand)]
+pub enum TrashSubcommand {
+    /// List deleted entries, oldest deletion first
+    List,
+    /// Put a deleted entry back, with its events and attachments
+    Restore {
+        /// Entry to restore, the most recently deleted copy if it was deleted more than once
+        #[arg(value_name = "YYYYMMDD")]
+        id: EntryId,
+    },
+}
+
+pub fn execute(storage: &Storage, subcmd: TrashSubcommand) -> Result<()> {
+    match subcmd {
+        TrashSubcommand::List => {
+            let trashed = storage.list_trash()?;
+            if trashed.is_empty() {
+                status!("The trash is empty");
+            }
+            for item in trashed {
+                println!(
+                    "{}  deleted {}",
+                    item.id,
+                    item.deleted.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+        TrashSubcommand::Restore { id } => {
+            storage.restore_from_trash(&id)?;
+            status!("Entry {} restored", id);
+        }
+    }
+    Ok(())
+}
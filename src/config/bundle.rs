@@ -0,0 +1,141 @@
+use color_eyre::eyre::{Context, Result};
+use toml::Value;
+
+use crate::config::Config;
+
+/// Placeholder for secrets left out of an exported config
+pub const REDACTED: &str = "<redacted>";
+
+/// Name suffixes of keys holding credentials
+const SECRET_KEY_SUFFIXES: &[&str] = &[
+    "secret",
+    "password",
+    "token",
+    "connection_string",
+    "access_key",
+];
+
+impl Config {
+    /// Serialize the config for another machine, optionally replacing every
+    /// credential with a placeholder
+    pub fn export(&self, redact_secrets: bool) -> Result<String> {
+        let mut root = Value::try_from(self).wrap_err("Failed to serialize config")?;
+        if redact_secrets {
+            redact(&mut root);
+        }
+
+        let body = toml::to_string_pretty(&root).wrap_err("Failed to serialize config")?;
+        Ok(format!(
+            "# devlog config bundle, set up another machine with `devlog config import <file>`\n\n{}",
+            body
+        ))
+    }
+
+    /// Parse an exported config, asking `prompt_secret` for every redacted credential
+    pub fn import<F>(raw: &str, mut prompt_secret: F) -> Result<Config>
+    where
+        F: FnMut(&str) -> Result<String>,
+    {
+        let mut root: Value = toml::from_str(raw).wrap_err("Failed to parse config bundle")?;
+
+        let mut redacted = Vec::new();
+        collect_redacted(&root, "", &mut redacted);
+        for key in redacted {
+            let secret = prompt_secret(&key)?;
+            if let Some(value) = lookup_mut(&mut root, &key) {
+                *value = Value::String(secret);
+            }
+        }
+
+        root.try_into().wrap_err("Invalid config bundle")
+    }
+}
+
+fn is_secret_key(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SECRET_KEY_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+fn redact(value: &mut Value) {
+    if let Value::Table(table) = value {
+        for (name, value) in table.iter_mut() {
+            if value.is_str() && is_secret_key(name) {
+                *value = Value::String(REDACTED.to_string());
+            } else {
+                redact(value);
+            }
+        }
+    }
+}
+
+fn collect_redacted(value: &Value, path: &str, redacted: &mut Vec<String>) {
+    match value {
+        Value::String(s) if s == REDACTED => redacted.push(path.to_string()),
+        Value::Table(table) => {
+            for (name, value) in table {
+                let path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                collect_redacted(value, &path, redacted);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn lookup_mut<'a>(root: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    key.split('.')
+        .try_fold(root, |value, part| value.get_mut(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secret_keys_only() {
+        let mut root: Value = toml::from_str(
+            "[sync]\nprovider = \"azure\"\nconnection_string = \"AccountKey=abc\"\n",
+        )
+        .unwrap();
+
+        redact(&mut root);
+
+        assert_eq!(root["sync"]["provider"].as_str(), Some("azure"));
+        assert_eq!(root["sync"]["connection_string"].as_str(), Some(REDACTED));
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let mut config = Config::default();
+        config.sync.enabled = true;
+        config.sync.provider = Some("azure".to_string());
+        config.editor.tab_width = 2;
+
+        let bundle = config.export(true).unwrap();
+        let imported = Config::import(&bundle, |_| unreachable!()).unwrap();
+
+        assert!(imported.sync.enabled);
+        assert_eq!(imported.sync.provider.as_deref(), Some("azure"));
+        assert_eq!(imported.editor.tab_width, 2);
+    }
+
+    #[test]
+    fn test_import_prompts_for_redacted_values() {
+        let bundle = format!("[sync]\nprovider = \"{}\"\n", REDACTED);
+
+        let mut prompted = Vec::new();
+        let imported = Config::import(&bundle, |key| {
+            prompted.push(key.to_string());
+            Ok("gcp".to_string())
+        })
+        .unwrap();
+
+        assert_eq!(prompted, vec!["sync.provider"]);
+        assert_eq!(imported.sync.provider.as_deref(), Some("gcp"));
+    }
+}
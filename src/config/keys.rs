@@ -0,0 +1,147 @@
+use color_eyre::eyre::{Context, Result, bail, eyre};
+use toml::{Table, Value};
+
+use crate::config::Config;
+use crate::error::{DevlogError, ErrorKind};
+
+impl Config {
+    /// Get a value by its dotted path, e.g. `sync.enabled` or a whole section like `editor`
+    pub fn get(&self, key: &str) -> Result<Value> {
+        let root = self.to_value()?;
+        lookup(&root, key).cloned().ok_or_else(|| {
+            DevlogError::new(
+                ErrorKind::NotFound,
+                format!("Config key '{}' is not set", key),
+            )
+            .into()
+        })
+    }
+
+    /// Set a value by its dotted path from its string form, e.g. `sync.enabled` to `true`.
+    ///
+    /// The value is converted to the type of the key, so `sync.enabled` only accepts
+    /// booleans and unknown keys are rejected.
+    pub fn set(&mut self, key: &str, raw: &str) -> Result<()> {
+        let mut root = self.to_value()?;
+        let parsed = match lookup(&root, key) {
+            Some(Value::Table(_)) => bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!("'{}' is a section, set one of its keys instead", key)
+            )),
+            Some(current) => parse_as(current, key, raw)?,
+            // Unset optional keys are strings, e.g. `sync.provider`
+            None => Value::String(raw.to_string()),
+        };
+
+        insert(&mut root, key, parsed)?;
+        let updated: Config = root.try_into().map_err(|e| {
+            DevlogError::new(
+                ErrorKind::Validation,
+                format!("Invalid value '{}' for '{}': {}", raw, key, e),
+            )
+        })?;
+
+        // Keys serde doesn't know about are silently dropped, catch them here
+        if lookup(&updated.to_value()?, key).is_none() {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!("Unknown config key '{}'", key)
+            ));
+        }
+
+        *self = updated;
+        Ok(())
+    }
+
+    fn to_value(&self) -> Result<Value> {
+        Value::try_from(self).wrap_err("Failed to serialize config")
+    }
+}
+
+fn lookup<'a>(root: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(root, |value, part| value.get(part))
+}
+
+fn insert(root: &mut Value, key: &str, value: Value) -> Result<()> {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(last) = parts.pop() else {
+        bail!("Config key must not be empty");
+    };
+
+    let mut table = root
+        .as_table_mut()
+        .ok_or_else(|| eyre!("Config root is not a table"))?;
+    for part in parts {
+        table = table
+            .entry(part)
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| eyre!("'{}' in '{}' is not a section", part, key))?;
+    }
+    table.insert(last.to_string(), value);
+    Ok(())
+}
+
+/// Parse `raw` into the same type as the current value of the key
+fn parse_as(current: &Value, key: &str, raw: &str) -> Result<Value> {
+    let parsed = match current {
+        Value::String(_) => Some(Value::String(raw.to_string())),
+        Value::Boolean(_) => raw.parse().ok().map(Value::Boolean),
+        Value::Integer(_) => raw.parse().ok().map(Value::Integer),
+        Value::Float(_) => raw.parse().ok().map(Value::Float),
+        _ => raw.parse::<Value>().ok(),
+    };
+
+    parsed.ok_or_else(|| {
+        DevlogError::new(
+            ErrorKind::Validation,
+            format!("'{}' expects a {}, got '{}'", key, current.type_str(), raw),
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_values_and_sections() {
+        let config = Config::default();
+
+        assert_eq!(config.get("sync.enabled").unwrap(), Value::Boolean(false));
+        assert!(config.get("editor").unwrap().is_table());
+        assert!(config.get("sync.provider").is_err());
+    }
+
+    #[test]
+    fn test_set_converts_to_key_type() {
+        let mut config = Config::default();
+
+        config.set("sync.enabled", "true").unwrap();
+        config.set("sync.provider", "azure").unwrap();
+        config.set("editor.tab_width", "2").unwrap();
+
+        assert!(config.sync.enabled);
+        assert_eq!(config.sync.provider.as_deref(), Some("azure"));
+        assert_eq!(config.editor.tab_width, 2);
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_values_and_keys() {
+        let mut config = Config::default();
+
+        let err = config.set("sync.enabled", "yes").unwrap_err();
+        assert!(err.to_string().contains("expects a boolean"));
+        assert_eq!(crate::error::exit_code(&err), 4);
+
+        let err = config.set("storage.line_ending", "dos").unwrap_err();
+        assert!(err.to_string().contains("Invalid value"));
+
+        let err = config.set("sync.unknown", "1").unwrap_err();
+        assert!(err.to_string().contains("Unknown config key"));
+
+        assert!(config.set("editor", "1").is_err());
+        assert!(!config.sync.enabled);
+    }
+}
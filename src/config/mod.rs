@@ -1,11 +1,605 @@
+use std::{
+    collections::{BTreeMap, hash_map::DefaultHasher},
+    fs,
+    hash::{Hash, Hasher},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use color_eyre::eyre::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::{
+    ai::Provider,
+    digest::schedule::WeeklySchedule,
+    error::{self, DevlogError},
+    storage::writer::Writer,
+    utils::{lock::FileLock, text::LineEnding},
+};
+
+mod bundle;
+mod keys;
+pub mod secrets;
+
+pub use bundle::REDACTED;
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Environment variable selecting a profile, like `--profile`
+pub const PROFILE_ENV: &str = "DEVLOG_PROFILE";
+
+/// Name that selects the journal outside of any profile
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
+    /// Profile used unless `--profile` or `DEVLOG_PROFILE` pick another, set with
+    /// `devlog config profile switch`
+    pub profile: Option<String>,
     pub sync: SyncConfig,
+    pub tui: TuiConfig,
+    pub editor: EditorConfig,
+    pub storage: StorageConfig,
+    pub capture: CaptureConfig,
+    pub prompt: PromptConfig,
+    pub backup: BackupConfig,
+    pub digest: DigestConfig,
+    pub publish: PublishConfig,
+    pub ai: AiConfig,
+    pub focus: FocusConfig,
+    pub hook: HookConfig,
+    pub todo: TodoConfig,
+    pub list: ListConfig,
+    /// Hour goals by project, e.g. `[goals.devlog]`, shown by `devlog stats chart --goal`
+    pub goals: BTreeMap<String, GoalConfig>,
+    /// Editor snippets by trigger word, expanded with Tab, e.g. `mtg = "## ${1:Topic}"`
+    pub snippets: BTreeMap<String, String>,
+    /// Separate journals by name, e.g. `[profiles.work]`
+    pub profiles: BTreeMap<String, ProfileConfig>,
+    /// `::project` of git repositories, by repository name, root path or `origin` remote,
+    /// e.g. `api = "payments"`. Unmapped repositories are their own project with `--here`.
+    pub workspaces: BTreeMap<String, String>,
+    pub project_detection: ProjectDetectionConfig,
+    /// Frontmatter fields `devlog new --prompt-fields` asks for, e.g. `[[fields]]`
+    pub fields: Vec<FieldConfig>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Config {
+    /// The profile to use, the `requested` one from `--profile` or `DEVLOG_PROFILE` over
+    /// the one switched to. `None` is the default journal.
+    pub fn active_profile(
+        &self,
+        requested: Option<&str>,
+    ) -> Result<Option<(&str, &ProfileConfig)>> {
+        let name = match requested.or(self.profile.as_deref()) {
+            None | Some(DEFAULT_PROFILE) => return Ok(None),
+            Some(name) => name,
+        };
+        match self.profiles.get_key_value(name) {
+            Some((name, profile)) => Ok(Some((name, profile))),
+            None => bail!(DevlogError::new(
+                error::ErrorKind::NotFound,
+                format!(
+                    "No profile named '{}', see `devlog config profile list`",
+                    name
+                )
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SyncConfig {
     pub enabled: bool,
+    /// Cloud sync provider (azure, aws, gcp, etc), only `folder` is supported so far
+    pub provider: Option<String>,
+    /// Folder shared between machines the `folder` provider syncs with
+    pub remote: Option<PathBuf>,
+    /// Plaintext credential written by older versions, moved to the OS keychain on startup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_string: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiConfig {
+    /// Interval in milliseconds between ticks driving timers, animations and autosave
+    pub tick_rate_ms: u64,
+    /// Fenced code blocks longer than this many lines are collapsed in the content
+    /// panel until expanded with Enter, 0 shows them in full
+    pub collapse_code_lines: usize,
+    /// Projects no entry mentioned for this many days are flagged as stale on the
+    /// dashboard, 0 flags none
+    pub stale_project_days: u64,
+    /// Pick files to attach or export to in the native file dialog, where there is a
+    /// display. Otherwise the path is typed in the footer.
+    pub file_dialog: bool,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate_ms: 250,
+            collapse_code_lines: 30,
+            stale_project_days: 0,
+            file_dialog: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorConfig {
+    /// Replace tabs with spaces in pasted text
+    pub expand_tabs: bool,
+    pub tab_width: usize,
+    /// Close brackets, backticks and `**` automatically while typing
+    pub auto_pairs: bool,
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        Self {
+            expand_tabs: true,
+            tab_width: 4,
+            auto_pairs: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Line endings of saved entries (lf, crlf or native), entries are always read either way
+    pub line_ending: LineEnding,
+    /// Earlier versions of an entry kept under `backups/<id>/` in the state directory
+    /// each time it's saved, 0 keeps none
+    pub keep_versions: usize,
+    /// Event logs longer than this are compacted: their events move to a gzipped
+    /// archive, `devlog history --deep` still shows them. 0 never compacts.
+    pub snapshot_events: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureConfig {
+    pub imap: ImapConfig,
+}
+
+/// Mailbox polled by `devlog capture imap`, the password is kept in the OS keychain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImapConfig {
+    pub host: Option<String>,
+    pub port: u16,
+    pub username: Option<String>,
+    pub mailbox: String,
+    /// Only messages sent to this address are captured, e.g. `me+devlog@example.com`
+    pub address: Option<String>,
+}
+
+impl Default for ImapConfig {
+    fn default() -> Self {
+        Self {
+            host: None,
+            port: 993,
+            username: None,
+            mailbox: "INBOX".to_string(),
+            address: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListConfig {
+    /// Computed columns added to the `devlog list` table, e.g. `[[list.columns]]`
+    pub columns: Vec<ColumnConfig>,
+}
+
+/// A computed `devlog list` column, `name = "MRS"` with one of the sources
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnConfig {
+    /// Header of the column
+    pub name: String,
+    #[serde(flatten)]
+    pub source: ColumnSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnSource {
+    /// Matches of a regex in the entry, e.g. `count = "MR!\\d+"`
+    Count(String),
+    /// Hours logged with duration annotations like `~1h30m` for a project, or `"*"`
+    /// for all of them
+    Hours(String),
+}
+
+/// A custom frontmatter field of entries, `name = "mood"` with an optional question
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldConfig {
+    pub name: String,
+    /// Asked instead of the name, e.g. `prompt = "How are you feeling?"`
+    pub prompt: Option<String>,
+    /// Answers are numbers like `hours = 7.5` instead of text
+    pub number: bool,
+}
+
+/// A journal of its own, with entries in `data_path` and separate sync settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    /// Directory of the profile's entries, notes and projects
+    pub data_path: PathBuf,
+    pub sync: SyncConfig,
+}
+
+/// Hours to log on a project with `~2h` duration annotations between two dates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoalConfig {
+    pub hours: u32,
+    pub start: NaiveDate,
+    pub due: NaiveDate,
+}
+
+/// Rotating archives written by `devlog backup`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Create a weekly archive after any command once the last one is a week old
+    pub auto: bool,
+    /// Directory of the archives, the state directory's `backups` if unset
+    pub location: Option<PathBuf>,
+    /// Number of archives to keep, older ones are deleted
+    pub keep: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            auto: false,
+            location: None,
+            keep: 8,
+        }
+    }
+}
+
+/// Weekly digest of the previous seven days, set up with `devlog digest schedule`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// When to compile the digest, none if unset
+    pub schedule: Option<WeeklySchedule>,
+    /// Directory of the digests, the state directory's `digests` if unset
+    pub location: Option<PathBuf>,
+    /// Shell command the digest is piped to, e.g. `mail -s "Devlog week" me@example.com`
+    pub command: Option<String>,
+}
+
+/// Focus timer started with `devlog focus start`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusConfig {
+    /// Length of a session
+    pub minutes: u32,
+    /// Block edits to entries other than today's while a session runs
+    pub lock_entries: bool,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 25,
+            lock_entries: false,
+        }
+    }
+}
+
+/// What `devlog hook capture` adds to today's entry after a git commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookConfig {
+    /// List item written for a commit, with `{message}`, `{repo}`, `{branch}` and `{hash}`
+    pub commit_template: String,
+}
+
+impl Default for HookConfig {
+    fn default() -> Self {
+        Self {
+            commit_template: "- {message} ::{repo} +commit ({branch} {hash})".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TodoConfig {
+    /// Start a new entry with the open todos of the previous one, marking them `- [>]` there
+    pub carry_forward: bool,
+}
+
+/// Mentioning the `::project` of the repository `devlog new` runs in without `--here`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectDetectionConfig {
+    /// Mention the project of repositories in `workspaces`, and ask about new ones
+    pub enabled: bool,
+    /// Repositories not to ask about again, by name, root path or `origin` remote
+    pub ignored: Vec<String>,
+}
+
+impl Default for ProjectDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignored: Vec::new(),
+        }
+    }
+}
+
+/// Where `devlog publish` sends pages, the API tokens are kept in the OS keychain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublishConfig {
+    pub confluence: ConfluenceConfig,
+    pub notion: NotionConfig,
+}
+
+/// Confluence space to publish to, the API token is the `publish.confluence_token` secret
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfluenceConfig {
+    /// e.g. `https://team.atlassian.net/wiki`
+    pub url: Option<String>,
+    /// Account of the API token on Confluence Cloud, unset for a personal access token
+    pub email: Option<String>,
+    /// Key of the space, e.g. `DEV`
+    pub space: Option<String>,
+    /// Page new pages are created under, the space root if unset
+    pub parent_id: Option<String>,
+}
+
+/// Notion database to publish to, the integration token is the `publish.notion_token` secret
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotionConfig {
+    pub database_id: Option<String>,
+}
+
+/// Model `devlog ai summarize` sends entries to, the API key of OpenAI and Azure OpenAI
+/// is the `ai.api_key` secret
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiConfig {
+    /// openai, azure or ollama
+    pub provider: Provider,
+    /// e.g. `gpt-4o-mini`, the deployment on Azure OpenAI or `llama3.1` on Ollama
+    pub model: Option<String>,
+    /// Base URL of the API, e.g. `https://team.openai.azure.com`. OpenAI and Ollama on
+    /// this machine are used if unset.
+    pub endpoint: Option<String>,
+    /// Regular expressions whose matches are replaced with `[redacted]` before sending,
+    /// e.g. `ACME-\d+` for ticket numbers
+    pub redact: Vec<String>,
+    /// Send `@person` mentions as `@person-1`, `@person-2` and so on
+    pub redact_people: bool,
+    /// Only ever send entries to this machine, like `--local-only` on every run
+    pub local_only: bool,
+}
+
+/// Indicator shown by `devlog prompt-hook` while today's entry is missing or short
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptConfig {
+    /// Entries with fewer words count as short, 0 only reports missing entries
+    pub min_words: usize,
+    /// Stay quiet before this hour of the day (0-23)
+    pub after_hour: u32,
+    pub missing_symbol: String,
+    pub short_symbol: String,
+}
+
+impl Default for PromptConfig {
+    fn default() -> Self {
+        Self {
+            min_words: 50,
+            after_hour: 0,
+            missing_symbol: "✎".to_string(),
+            short_symbol: "✎…".to_string(),
+        }
+    }
+}
+
+/// Fingerprint of the config file contents at the time it was loaded.
+/// `None` means the file did not exist yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigVersion(Option<u64>);
+
+/// Reads and writes `config.toml`, guarding writes against concurrent devlog processes
+pub struct ConfigStore {
+    path: PathBuf,
+    writer: Writer,
+}
+
+impl ConfigStore {
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            path: config_dir.join(CONFIG_FILE_NAME),
+            writer: Writer::default(),
+        }
+    }
+
+    /// Use the given writer for saves, e.g. the storage's dry-run aware writer
+    pub fn with_writer(mut self, writer: Writer) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    /// Get the path of the config file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the config, falling back to defaults when the file doesn't exist
+    pub fn load(&self) -> Result<Config> {
+        self.load_for_update().map(|(config, _)| config)
+    }
+
+    /// Load the config together with its version, to be passed back to `save`
+    pub fn load_for_update(&self) -> Result<(Config, ConfigVersion)> {
+        let raw = self.read_raw()?;
+        let version = ConfigVersion(raw.as_deref().map(fingerprint));
+        let config = match raw {
+            Some(raw) => toml::from_str(&raw)
+                .wrap_err_with(|| format!("Failed to parse {}", self.path.display()))?,
+            None => Config::default(),
+        };
+        Ok((config, version))
+    }
+
+    /// Save the config, refusing to overwrite changes made since `expected` was loaded
+    pub fn save(&self, config: &Config, expected: ConfigVersion) -> Result<()> {
+        // A dry run never writes, so it doesn't need to keep others out either
+        let _lock = if self.writer.is_dry_run() {
+            None
+        } else {
+            Some(FileLock::acquire(&self.path)?)
+        };
+
+        let current = ConfigVersion(self.read_raw()?.as_deref().map(fingerprint));
+        if current != expected {
+            bail!(DevlogError::new(
+                error::ErrorKind::Conflict,
+                format!(
+                    "{} was modified by another devlog process, please retry",
+                    self.path.display()
+                )
+            ));
+        }
+
+        let content = toml::to_string_pretty(config).wrap_err("Failed to serialize config")?;
+        self.writer
+            .write_atomic(&self.path, content)
+            .wrap_err_with(|| format!("Failed to save config to {}", self.path.display()))
+    }
+
+    /// Load, modify and write back the config as a single guarded operation
+    pub fn update<F>(&self, modify: F) -> Result<Config>
+    where
+        F: FnOnce(&mut Config) -> Result<()>,
+    {
+        let (mut config, version) = self.load_for_update()?;
+        modify(&mut config)?;
+        self.save(&config, version)?;
+        Ok(config)
+    }
+
+    fn read_raw(&self) -> Result<Option<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(raw) => Ok(Some(raw)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+}
+
+fn fingerprint(raw: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    raw.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_config_returns_defaults() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = ConfigStore::new(temp_dir.path());
+
+        let config = store.load().expect("Failed to load config");
+        assert!(!config.sync.enabled);
+        assert!(config.sync.provider.is_none());
+    }
+
+    #[test]
+    fn test_update_roundtrip() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = ConfigStore::new(temp_dir.path());
+
+        store
+            .update(|config| {
+                config.sync.enabled = true;
+                config.sync.provider = Some("azure".to_string());
+                Ok(())
+            })
+            .expect("Failed to update config");
+
+        let config = store.load().expect("Failed to load config");
+        assert!(config.sync.enabled);
+        assert_eq!(config.sync.provider.as_deref(), Some("azure"));
+        assert!(!temp_dir.path().join("config.toml.lock").exists());
+    }
+
+    #[test]
+    fn test_active_profile() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "work".to_string(),
+            ProfileConfig {
+                data_path: PathBuf::from("/journals/work"),
+                ..ProfileConfig::default()
+            },
+        );
+        assert!(config.active_profile(None).unwrap().is_none());
+
+        config.profile = Some("work".to_string());
+        let (name, profile) = config.active_profile(None).unwrap().unwrap();
+        assert_eq!(name, "work");
+        assert_eq!(profile.data_path, PathBuf::from("/journals/work"));
+        assert!(
+            config
+                .active_profile(Some(DEFAULT_PROFILE))
+                .unwrap()
+                .is_none()
+        );
+
+        let err = config.active_profile(Some("personal")).unwrap_err();
+        assert!(err.to_string().contains("No profile named 'personal'"));
+    }
+
+    #[test]
+    fn test_save_rejects_concurrent_modification() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = ConfigStore::new(temp_dir.path());
+
+        let (mut config, version) = store.load_for_update().expect("Failed to load config");
+
+        // Another process saves in between our load and save
+        store
+            .update(|other| {
+                other.sync.enabled = true;
+                Ok(())
+            })
+            .expect("Failed to update config");
+
+        config.sync.provider = Some("aws".to_string());
+        let err = store
+            .save(&config, version)
+            .expect_err("Stale save should be rejected");
+        assert!(
+            err.to_string()
+                .contains("modified by another devlog process")
+        );
+
+        // The other process's change is preserved
+        assert!(store.load().expect("Failed to load config").sync.enabled);
+    }
 }
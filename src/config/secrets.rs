@@ -0,0 +1,154 @@
+use std::env;
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::config::{Config, ConfigStore};
+
+/// Service name secrets are stored under in the OS keychain
+const SERVICE: &str = "devlog";
+
+/// Provider credentials, kept in the OS keychain instead of `config.toml`.
+///
+/// Headless servers without a keychain can provide each secret through an
+/// environment variable instead, e.g. `DEVLOG_SYNC_CONNECTION_STRING` for
+/// `sync.connection_string`. The environment variable wins when both exist.
+pub struct SecretStore;
+
+impl SecretStore {
+    /// Environment variable overriding the secret `name`
+    pub fn env_var(name: &str) -> String {
+        format!("DEVLOG_{}", name.to_uppercase().replace(['.', '-'], "_"))
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        if let Ok(value) = env::var(Self::env_var(name)) {
+            return Ok(Some(value));
+        }
+
+        match keyring::Entry::new(SERVICE, name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(self.unavailable(name, e)),
+        }
+    }
+
+    pub fn set(&self, name: &str, value: &str) -> Result<()> {
+        keyring::Entry::new(SERVICE, name)?
+            .set_password(value)
+            .map_err(|e| self.unavailable(name, e))
+    }
+
+    fn unavailable(&self, name: &str, err: keyring::Error) -> color_eyre::Report {
+        eyre!(
+            "Failed to access the OS keychain for '{}': {}. On machines without a keychain, set {} instead",
+            name,
+            err,
+            Self::env_var(name)
+        )
+    }
+}
+
+impl Config {
+    /// Remove credentials older versions stored in plain text, returning them
+    /// as `(secret name, value)` pairs
+    pub fn take_plaintext_secrets(&mut self) -> Vec<(String, String)> {
+        let mut secrets = Vec::new();
+        if let Some(value) = self.sync.connection_string.take() {
+            secrets.push(("sync.connection_string".to_string(), value));
+        }
+        secrets
+    }
+}
+
+/// Move plaintext credentials from `config.toml` into secure storage via `store_secret`,
+/// returns the number of migrated secrets.
+///
+/// The config is only rewritten once every secret has been stored, so a failing
+/// keychain never loses credentials.
+pub fn migrate_plaintext_secrets<F>(store: &ConfigStore, mut store_secret: F) -> Result<usize>
+where
+    F: FnMut(&str, &str) -> Result<()>,
+{
+    let (mut config, version) = store.load_for_update()?;
+    let secrets = config.take_plaintext_secrets();
+    if secrets.is_empty() {
+        return Ok(0);
+    }
+
+    for (name, value) in &secrets {
+        store_secret(name, value)?;
+    }
+    store.save(&config, version)?;
+    Ok(secrets.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_env_var_names() {
+        assert_eq!(
+            SecretStore::env_var("sync.connection_string"),
+            "DEVLOG_SYNC_CONNECTION_STRING"
+        );
+    }
+
+    #[test]
+    fn test_migrate_plaintext_secrets() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = ConfigStore::new(temp_dir.path());
+        fs::write(
+            store.path(),
+            "[sync]\nenabled = true\nprovider = \"azure\"\nconnection_string = \"AccountKey=abc\"\n",
+        )
+        .unwrap();
+
+        let mut stored = Vec::new();
+        let migrated = migrate_plaintext_secrets(&store, |name, value| {
+            stored.push((name.to_string(), value.to_string()));
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(migrated, 1);
+        assert_eq!(
+            stored,
+            vec![(
+                "sync.connection_string".to_string(),
+                "AccountKey=abc".to_string()
+            )]
+        );
+        let raw = fs::read_to_string(store.path()).unwrap();
+        assert!(!raw.contains("AccountKey"));
+        assert!(store.load().unwrap().sync.enabled);
+
+        // Nothing left to migrate on the next run
+        assert_eq!(
+            migrate_plaintext_secrets(&store, |_, _| unreachable!()).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_failed_migration_keeps_plaintext() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = ConfigStore::new(temp_dir.path());
+        fs::write(
+            store.path(),
+            "[sync]\nconnection_string = \"AccountKey=abc\"\n",
+        )
+        .unwrap();
+
+        let result = migrate_plaintext_secrets(&store, |_, _| Err(eyre!("no keychain")));
+
+        assert!(result.is_err());
+        assert!(
+            fs::read_to_string(store.path())
+                .unwrap()
+                .contains("AccountKey")
+        );
+    }
+}
@@ -0,0 +1,133 @@
+pub mod schedule;
+
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use chrono::{Days, NaiveDate};
+use color_eyre::eyre::Result;
+
+use crate::export::{self, ExportedEntry};
+use crate::models::annotation::AnnotationKind;
+use crate::models::entry_id::EntryId;
+use crate::models::time_log::parse_time_log;
+use crate::storage::Storage;
+
+/// Number of days a digest covers, ending the day before it runs
+pub const DIGEST_DAYS: u64 = 7;
+
+/// A compiled summary of the entries from `start` to `end`, both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub entries: usize,
+    pub markdown: String,
+}
+
+/// Compile the digest of the seven days ending with `end`
+pub fn compile(storage: &Storage, end: NaiveDate) -> Result<Digest> {
+    let start = end - Days::new(DIGEST_DAYS - 1);
+    let entries = export::collect(
+        storage,
+        Some(EntryId::from_date(start)),
+        Some(EntryId::from_date(end)),
+    )?;
+
+    Ok(Digest {
+        start,
+        end,
+        entries: entries.len(),
+        markdown: to_markdown(start, end, &entries),
+    })
+}
+
+fn to_markdown(start: NaiveDate, end: NaiveDate, entries: &[ExportedEntry]) -> String {
+    let words: usize = entries
+        .iter()
+        .map(|entry| entry.content.split_whitespace().count())
+        .sum();
+    let minutes: u32 = entries
+        .iter()
+        .flat_map(|entry| parse_time_log(&entry.content).into_values())
+        .sum();
+
+    let mut markdown = format!("# Devlog week of {} to {}\n\n", start, end);
+    markdown.push_str(&format!("{} entries, {} words", entries.len(), words));
+    if minutes > 0 {
+        markdown.push_str(&format!(", {:.1}h logged", minutes as f64 / 60.0));
+    }
+    markdown.push_str("\n\n");
+
+    for (kind, label) in [
+        (AnnotationKind::Project, "Projects"),
+        (AnnotationKind::Person, "People"),
+        (AnnotationKind::Tag, "Tags"),
+    ] {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for value in entries.iter().flat_map(|e| e.annotations.values(kind)) {
+            *counts.entry(value).or_default() += 1;
+        }
+        if counts.is_empty() {
+            continue;
+        }
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by_key(|(_, count)| Reverse(*count));
+        let listed: Vec<String> = counts
+            .iter()
+            .map(|(value, count)| format!("{}{} ({})", kind.prefix(), value, count))
+            .collect();
+        markdown.push_str(&format!("**{}:** {}\n\n", label, listed.join(", ")));
+    }
+
+    for entry in entries {
+        markdown.push_str(&format!("## {}\n\n", entry.date.format("%A, %e %B %Y")));
+        markdown.push_str(entry.content.trim_end());
+        markdown.push_str("\n\n");
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_compile_covers_the_seven_days() {
+        let (storage, _temp_dir) = create_test_storage();
+        for (id, content) in [
+            ("20250914", "Too early ::devlog"),
+            ("20250915", "Sync work ::devlog ~2h with @alice"),
+            ("20250921", "Docs ::devlog ::site ~1h"),
+            ("20250922", "Too late"),
+        ] {
+            let entry = Entry::new(id.parse().unwrap(), content.to_string());
+            storage.save_entry(&entry).unwrap();
+        }
+
+        let digest = compile(&storage, NaiveDate::from_ymd_opt(2025, 9, 21).unwrap()).unwrap();
+
+        assert_eq!(digest.start, NaiveDate::from_ymd_opt(2025, 9, 15).unwrap());
+        assert_eq!(digest.entries, 2);
+        assert!(
+            digest
+                .markdown
+                .starts_with("# Devlog week of 2025-09-15 to 2025-09-21\n")
+        );
+        assert!(digest.markdown.contains("2 entries, 10 words, 3.0h logged"));
+        assert!(
+            digest
+                .markdown
+                .contains("**Projects:** ::devlog (2), ::site (1)")
+        );
+        assert!(digest.markdown.contains("## Monday, 15 September 2025"));
+        assert!(!digest.markdown.contains("Too"));
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeDelta, TimeZone, Weekday,
+};
+use color_eyre::eyre::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DevlogError, ErrorKind};
+
+/// A weekly run at a local wall clock time, e.g. Monday 9:00.
+///
+/// The time stays 9:00 on the local clock across DST changes and time zones,
+/// only runs due after `since` count, so a new schedule doesn't fire for the past.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeeklySchedule {
+    pub weekday: Weekday,
+    pub time: NaiveTime,
+    pub since: DateTime<FixedOffset>,
+}
+
+impl WeeklySchedule {
+    /// Parse `monday` and `9:00` as given to `devlog digest schedule --weekly`
+    pub fn parse(weekday: &str, time: &str, since: DateTime<FixedOffset>) -> Result<Self> {
+        let Ok(weekday) = weekday.parse::<Weekday>() else {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!("Invalid weekday '{}', expected e.g. monday", weekday)
+            ));
+        };
+        let Ok(time) = NaiveTime::parse_from_str(time, "%H:%M") else {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!("Invalid time '{}', expected HH:MM, e.g. 9:00", time)
+            ));
+        };
+        Ok(Self {
+            weekday,
+            time,
+            since,
+        })
+    }
+
+    /// The latest scheduled time at or before `now`
+    pub fn last_run<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> DateTime<Tz> {
+        let today = now.date_naive();
+        let days_back =
+            (today.weekday().num_days_from_monday() + 7 - self.weekday.num_days_from_monday()) % 7;
+        let day = today - Days::new(days_back as u64);
+        let run = resolve(&now.timezone(), day.and_time(self.time));
+        if run <= *now {
+            run
+        } else {
+            resolve(&now.timezone(), (day - Days::new(7)).and_time(self.time))
+        }
+    }
+
+    /// The first scheduled time after `now`
+    pub fn next_run<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> DateTime<Tz> {
+        let last = self.last_run(now).date_naive();
+        resolve(&now.timezone(), (last + Days::new(7)).and_time(self.time))
+    }
+
+    /// Whether a run is due at `now`, given when the digest last ran
+    pub fn is_due<Tz: TimeZone>(&self, now: &DateTime<Tz>, last_ran: Option<DateTime<Tz>>) -> bool {
+        let run = self.last_run(now);
+        run >= self.since && last_ran.is_none_or(|last_ran| last_ran < run)
+    }
+
+    /// The last day covered by the run at `run`, the day before it
+    pub fn week_end<Tz: TimeZone>(run: &DateTime<Tz>) -> NaiveDate {
+        run.date_naive() - Days::new(1)
+    }
+}
+
+/// Map a wall clock time to an instant. When clocks go back the earlier of the two
+/// instants is used, when they go forward a time in the gap moves past it.
+fn resolve<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> DateTime<Tz> {
+    let mut local = local;
+    loop {
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => return time,
+            LocalResult::None => local += TimeDelta::minutes(30),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(offset: &FixedOffset, value: &str) -> DateTime<FixedOffset> {
+        offset.from_local_datetime(&value.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_last_and_next_run() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let since = at(&offset, "2025-09-01T00:00:00");
+        let schedule = WeeklySchedule::parse("monday", "9:00", since).unwrap();
+
+        // Wednesday: the last run was Monday morning
+        let now = at(&offset, "2025-09-17T12:00:00");
+        assert_eq!(schedule.last_run(&now), at(&offset, "2025-09-15T09:00:00"));
+        assert_eq!(schedule.next_run(&now), at(&offset, "2025-09-22T09:00:00"));
+
+        // Monday before 9:00 still belongs to the previous week
+        let now = at(&offset, "2025-09-22T08:59:00");
+        assert_eq!(schedule.last_run(&now), at(&offset, "2025-09-15T09:00:00"));
+        assert_eq!(
+            WeeklySchedule::week_end(&schedule.last_run(&now)),
+            NaiveDate::from_ymd_opt(2025, 9, 14).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_due() {
+        let offset = FixedOffset::east_opt(-5 * 3600).unwrap();
+        let schedule =
+            WeeklySchedule::parse("Mon", "09:00", at(&offset, "2025-09-17T10:00:00")).unwrap();
+
+        // Registered on a Wednesday, the Monday before doesn't count
+        assert!(!schedule.is_due(&at(&offset, "2025-09-20T10:00:00"), None));
+        let now = at(&offset, "2025-09-22T09:30:00");
+        assert!(schedule.is_due(&now, None));
+        assert!(schedule.is_due(&now, Some(at(&offset, "2025-09-15T09:00:00"))));
+        assert!(!schedule.is_due(&now, Some(at(&offset, "2025-09-22T09:01:00"))));
+
+        assert!(WeeklySchedule::parse("someday", "9:00", now).is_err());
+        assert!(WeeklySchedule::parse("monday", "25:00", now).is_err());
+    }
+}
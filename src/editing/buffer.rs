@@ -0,0 +1,980 @@
+use std::collections::BTreeMap;
+
+use crate::editing::{EditorBuffer, snippet::Snippet};
+
+/// Cursor position in a text buffer, `col` counts characters, not bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Cursor {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Indentation added or removed per nesting level of a list
+const LIST_INDENT: &str = "  ";
+
+/// Marker at the start of a markdown list item, e.g. `  - [ ] ` or `3. `
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ListMarker {
+    indent: String,
+    /// Bullet character, or the item number followed by `.` or `)`
+    bullet: String,
+    checkbox: Option<bool>,
+    /// Char offset where the item's text starts
+    content_col: usize,
+}
+
+impl ListMarker {
+    fn parse(line: &str) -> Option<Self> {
+        let indent: String = line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        let rest = &line[indent.len()..];
+
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let bullet_len = if digits > 0 {
+            rest[digits..]
+                .starts_with(['.', ')'])
+                .then_some(digits + 1)?
+        } else {
+            rest.starts_with(['-', '*', '+']).then_some(1)?
+        };
+        let bullet = rest[..bullet_len].to_string();
+        let after_bullet = rest[bullet_len..].strip_prefix(' ')?;
+
+        let checkbox = match after_bullet.get(..4) {
+            Some("[ ] ") => Some(false),
+            Some("[x] " | "[X] ") => Some(true),
+            _ if matches!(after_bullet, "[ ]" | "[x]" | "[X]") => Some(after_bullet != "[ ]"),
+            _ => None,
+        };
+
+        let marker_len = indent.chars().count()
+            + bullet_len
+            + 1
+            + checkbox.map_or(0, |_| 4.min(after_bullet.len()));
+        Some(Self {
+            indent,
+            bullet,
+            checkbox,
+            content_col: marker_len,
+        })
+    }
+
+    /// Marker for the item following this one, numbered lists count up
+    /// and new checkboxes start unchecked
+    fn next(&self) -> String {
+        let bullet = match self.bullet.split_at(self.bullet.len() - 1) {
+            (number, delimiter) if !number.is_empty() => {
+                let number: u64 = number.parse().unwrap_or(0);
+                format!("{}{}", number + 1, delimiter)
+            }
+            _ => self.bullet.clone(),
+        };
+        let checkbox = if self.checkbox.is_some() { "[ ] " } else { "" };
+        format!("{}{} {}", self.indent, bullet, checkbox)
+    }
+}
+
+/// Characters closed automatically when typed, with their closing counterpart
+const AUTO_PAIRS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}'), ('`', '`')];
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    lines: Vec<String>,
+    cursor: Cursor,
+}
+
+/// Kind of the last edit, consecutive typing is merged into a single undo step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Typing,
+    Other,
+}
+
+/// Text being edited inside the TUI, with cursor handling and undo/redo
+#[derive(Debug, Clone)]
+pub struct TextBuffer {
+    lines: Vec<String>,
+    cursor: Cursor,
+    /// Other end of the selection, the cursor being the end that moves
+    anchor: Option<Cursor>,
+    auto_pairs: bool,
+    /// Snippet templates by trigger word
+    snippets: BTreeMap<String, String>,
+    /// Remaining tab stops of the snippet being filled in
+    tab_stops: Vec<(Cursor, Cursor)>,
+    /// End of the tab stop being filled in, as it was when the stop was entered
+    active_stop_end: Option<Cursor>,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+    dirty: bool,
+}
+
+impl TextBuffer {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        Self {
+            lines,
+            cursor: Cursor::default(),
+            anchor: None,
+            auto_pairs: false,
+            snippets: BTreeMap::new(),
+            tab_stops: Vec::new(),
+            active_stop_end: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_edit: None,
+            dirty: false,
+        }
+    }
+
+    /// Close brackets, backticks and `**` automatically while typing
+    pub fn with_auto_pairs(mut self, auto_pairs: bool) -> Self {
+        self.auto_pairs = auto_pairs;
+        self
+    }
+
+    pub fn with_snippets(mut self, snippets: BTreeMap<String, String>) -> Self {
+        self.snippets = snippets;
+        self
+    }
+
+    /// Nest the list item under the cursor one level deeper,
+    /// returns false if the line is not a list item
+    pub fn indent_list_item(&mut self) -> bool {
+        if ListMarker::parse(&self.lines[self.cursor.row]).is_none() {
+            return false;
+        }
+
+        self.begin_edit(EditKind::Other);
+        self.lines[self.cursor.row].insert_str(0, LIST_INDENT);
+        self.cursor.col += LIST_INDENT.len();
+        true
+    }
+
+    /// Move the list item under the cursor one level up,
+    /// returns false if the line is not an indented list item
+    pub fn outdent_list_item(&mut self) -> bool {
+        let Some(marker) = ListMarker::parse(&self.lines[self.cursor.row]) else {
+            return false;
+        };
+        let removed = if marker.indent.starts_with('\t') {
+            1
+        } else {
+            marker.indent.len().min(LIST_INDENT.len())
+        };
+        if removed == 0 {
+            return false;
+        }
+
+        self.begin_edit(EditKind::Other);
+        self.lines[self.cursor.row].drain(..removed);
+        self.cursor.col = self.cursor.col.saturating_sub(removed);
+        true
+    }
+
+    /// Check or uncheck the `- [ ]` checkbox on the current line,
+    /// adding one if the line doesn't have it yet
+    pub fn toggle_checkbox(&mut self) {
+        self.begin_edit(EditKind::Other);
+        let line = &mut self.lines[self.cursor.row];
+
+        let (insert_at, inserted) = match ListMarker::parse(line) {
+            Some(marker) => match marker.checkbox {
+                Some(checked) => {
+                    // Flip the mark in place, the line length doesn't change
+                    let mark_col = marker.indent.chars().count() + marker.bullet.len() + 2;
+                    let (idx, _) = line.char_indices().nth(mark_col).unwrap_or_default();
+                    line.replace_range(idx..idx + 1, if checked { " " } else { "x" });
+                    return;
+                }
+                None => (marker.content_col, "[ ] "),
+            },
+            None => {
+                let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                (indent, "- [ ] ")
+            }
+        };
+
+        let idx = line
+            .char_indices()
+            .nth(insert_at)
+            .map_or(line.len(), |(idx, _)| idx);
+        line.insert_str(idx, inserted);
+        if self.cursor.col >= insert_at {
+            self.cursor.col += inserted.len();
+        }
+    }
+
+    /// Replace the snippet trigger right before the cursor with its template and
+    /// select the first tab stop, returns false if the word isn't a trigger
+    pub fn expand_snippet(&mut self) -> bool {
+        let before: Vec<char> = self.lines[self.cursor.row]
+            .chars()
+            .take(self.cursor.col)
+            .collect();
+        let trigger_len = before
+            .iter()
+            .rev()
+            .take_while(|c| !c.is_whitespace())
+            .count();
+        let trigger: String = before[before.len() - trigger_len..].iter().collect();
+        let Some(template) = self.snippets.get(&trigger).filter(|_| trigger_len > 0) else {
+            return false;
+        };
+        let snippet = Snippet::parse(template);
+
+        self.begin_edit(EditKind::Other);
+        let start = Cursor {
+            row: self.cursor.row,
+            col: self.cursor.col - trigger_len,
+        };
+        let start_idx = self.byte_index(start);
+        let end_idx = self.byte_index(self.cursor);
+        let line = &mut self.lines[start.row];
+        let tail = line.split_off(end_idx);
+        line.truncate(start_idx);
+
+        // Following lines of the snippet line up with the line it was expanded on
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        let indent_len = indent.chars().count();
+        line.push_str(&snippet.lines[0]);
+        for (offset, snippet_line) in snippet.lines[1..].iter().enumerate() {
+            self.lines.insert(
+                start.row + 1 + offset,
+                format!("{}{}", indent, snippet_line),
+            );
+        }
+        self.lines[start.row + snippet.lines.len() - 1].push_str(&tail);
+
+        let to_buffer = |at: Cursor| match at.row {
+            0 => Cursor {
+                row: start.row,
+                col: start.col + at.col,
+            },
+            row => Cursor {
+                row: start.row + row,
+                col: indent_len + at.col,
+            },
+        };
+        self.tab_stops = snippet
+            .stops
+            .iter()
+            .map(|(from, to)| (to_buffer(*from), to_buffer(*to)))
+            .collect();
+        self.active_stop_end = None;
+        self.next_tab_stop()
+    }
+
+    /// Move to the next tab stop of the snippet being filled in, selecting its
+    /// placeholder so typing replaces it. Returns false if no snippet is active.
+    pub fn next_tab_stop(&mut self) -> bool {
+        if self.tab_stops.is_empty() {
+            return false;
+        }
+
+        // Text typed into the previous stop moves the stops after it
+        if let Some(old_end) = self.active_stop_end.take() {
+            let new_end = self.cursor;
+            let shift = |at: &mut Cursor| {
+                if at.row == old_end.row && at.col >= old_end.col {
+                    at.col = at.col - old_end.col + new_end.col;
+                }
+                if at.row >= old_end.row {
+                    at.row = (at.row + new_end.row).saturating_sub(old_end.row);
+                }
+            };
+            for (start, end) in &mut self.tab_stops {
+                shift(start);
+                shift(end);
+            }
+        }
+
+        let (start, end) = self.tab_stops.remove(0);
+        let in_bounds = |at: Cursor| at.row < self.lines.len() && at.col <= self.line_len(at.row);
+        if !in_bounds(start) || !in_bounds(end) {
+            // The text was edited outside of the stops, give up on the snippet
+            self.tab_stops.clear();
+            return false;
+        }
+
+        self.anchor = (start != end).then_some(start);
+        self.cursor = end;
+        self.active_stop_end = (!self.tab_stops.is_empty()).then_some(end);
+        self.last_edit = None;
+        true
+    }
+
+    /// Toggle `marker` around the selection, e.g. `**` for bold or `*` for italic.
+    /// Without a selection an empty pair is inserted with the cursor in between.
+    pub fn toggle_wrap(&mut self, marker: &str) {
+        let Some((start, end)) = self.selection() else {
+            self.begin_edit(EditKind::Other);
+            let idx = self.byte_index(self.cursor);
+            self.lines[self.cursor.row].insert_str(idx, &marker.repeat(2));
+            self.cursor.col += marker.chars().count();
+            return;
+        };
+
+        if self.is_wrapped(start, end, marker) {
+            self.begin_edit(EditKind::Other);
+            let len = marker.chars().count();
+            let end_idx = self.byte_index(end);
+            self.lines[end.row].replace_range(end_idx..end_idx + marker.len(), "");
+            let start = Cursor {
+                row: start.row,
+                col: start.col - len,
+            };
+            let start_idx = self.byte_index(start);
+            self.lines[start.row].replace_range(start_idx..start_idx + marker.len(), "");
+
+            let end_col = if end.row == start.row {
+                end.col - len
+            } else {
+                end.col
+            };
+            self.anchor = Some(start);
+            self.cursor = Cursor {
+                row: end.row,
+                col: end_col,
+            };
+        } else {
+            self.wrap_selection(marker, marker);
+        }
+    }
+
+    /// Record an undo step unless this edit continues the previous one
+    fn begin_edit(&mut self, kind: EditKind) {
+        if kind != EditKind::Typing || self.last_edit != Some(EditKind::Typing) {
+            self.undo_stack.push(self.snapshot());
+        }
+        self.redo_stack.clear();
+        self.last_edit = Some(kind);
+        self.dirty = true;
+    }
+
+    /// Surround the selection with `open` and `close`, keeping the inner text selected
+    fn wrap_selection(&mut self, open: &str, close: &str) {
+        let Some((start, end)) = self.selection() else {
+            return;
+        };
+        self.begin_edit(EditKind::Other);
+
+        let end_idx = self.byte_index(end);
+        self.lines[end.row].insert_str(end_idx, close);
+        let start_idx = self.byte_index(start);
+        self.lines[start.row].insert_str(start_idx, open);
+
+        let len = open.chars().count();
+        let end_col = if end.row == start.row {
+            end.col + len
+        } else {
+            end.col
+        };
+        self.anchor = Some(Cursor {
+            row: start.row,
+            col: start.col + len,
+        });
+        self.cursor = Cursor {
+            row: end.row,
+            col: end_col,
+        };
+    }
+
+    /// Whether the selection is directly surrounded by `marker`. Runs of the
+    /// same character count, so bold `**x**` is not mistaken for italic `*x*`.
+    fn is_wrapped(&self, start: Cursor, end: Cursor, marker: &str) -> bool {
+        let Some(ch) = marker.chars().next() else {
+            return false;
+        };
+        let len = marker.chars().count();
+
+        let before = self.lines[start.row]
+            .chars()
+            .take(start.col)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .take_while(|c| *c == ch)
+            .count();
+        let after = self.lines[end.row]
+            .chars()
+            .skip(end.col)
+            .take_while(|c| *c == ch)
+            .count();
+
+        let run = before.min(after);
+        run >= len && (len > 1 || run % 2 == 1)
+    }
+
+    /// Remove the selected text, leaving the cursor where it started
+    fn delete_selection(&mut self) {
+        let Some((start, end)) = self.selection() else {
+            self.anchor = None;
+            return;
+        };
+
+        let end_idx = self.byte_index(end);
+        let tail = self.lines[end.row].split_off(end_idx);
+        let start_idx = self.byte_index(start);
+        self.lines[start.row].truncate(start_idx);
+        self.lines[start.row].push_str(&tail);
+        self.lines.drain(start.row + 1..=end.row);
+
+        self.cursor = start;
+        self.anchor = None;
+    }
+
+    fn char_at(&self, cursor: Cursor) -> Option<char> {
+        self.lines[cursor.row].chars().nth(cursor.col)
+    }
+
+    fn char_before(&self, cursor: Cursor) -> Option<char> {
+        let col = cursor.col.checked_sub(1)?;
+        self.lines[cursor.row].chars().nth(col)
+    }
+
+    fn is_closing(&self, ch: char) -> bool {
+        ch == '*' || AUTO_PAIRS.iter().any(|(_, close)| *close == ch)
+    }
+
+    /// Text to insert after the cursor once `ch` was typed, if it opens a pair.
+    /// Called with the cursor already after `ch`.
+    fn closing_for(&self, ch: char) -> Option<String> {
+        let typed_at = Cursor {
+            row: self.cursor.row,
+            col: self.cursor.col - 1,
+        };
+        let before = self.char_before(typed_at);
+        let after = self.char_at(self.cursor);
+
+        // Only pair in front of whitespace or closing characters, so typing
+        // inside a word doesn't leave stray closers behind
+        if after.is_some_and(|c| c.is_alphanumeric()) {
+            return None;
+        }
+
+        match ch {
+            // The second `*` of `**` closes bold
+            '*' => (before == Some('*')).then(|| "**".to_string()),
+            // Repeated backticks start a code fence, which is never closed inline
+            '`' => (before != Some('`')).then(|| "`".to_string()),
+            _ => AUTO_PAIRS
+                .iter()
+                .find(|(open, _)| *open == ch)
+                .map(|(_, close)| close.to_string()),
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            lines: self.lines.clone(),
+            cursor: self.cursor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.lines = snapshot.lines;
+        self.cursor = snapshot.cursor;
+        self.anchor = None;
+        self.tab_stops.clear();
+        self.last_edit = None;
+        self.dirty = true;
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Convert a cursor position to a byte index into its line
+    fn byte_index(&self, cursor: Cursor) -> usize {
+        let line = &self.lines[cursor.row];
+        line.char_indices()
+            .nth(cursor.col)
+            .map_or(line.len(), |(idx, _)| idx)
+    }
+}
+
+impl EditorBuffer for TextBuffer {
+    fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+
+    fn set_cursor(&mut self, cursor: Cursor) {
+        let row = cursor.row.min(self.lines.len() - 1);
+        self.cursor = Cursor {
+            row,
+            col: cursor.col.min(self.line_len(row)),
+        };
+        self.last_edit = None;
+    }
+
+    fn selection(&self) -> Option<(Cursor, Cursor)> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.cursor)?;
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    fn extend_selection(&mut self) {
+        self.anchor.get_or_insert(self.cursor);
+    }
+
+    fn clear_selection(&mut self) {
+        self.anchor = None;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Runs of typing are undone together. Opening auto-pair characters wrap the
+    /// selection instead of replacing it.
+    fn insert_char(&mut self, ch: char) {
+        if ch == '\n' {
+            self.insert_newline();
+            return;
+        }
+
+        if self.auto_pairs {
+            let pair = AUTO_PAIRS.iter().find(|(open, _)| *open == ch);
+            if let Some((open, close)) = pair
+                && self.selection().is_some()
+            {
+                self.wrap_selection(&open.to_string(), &close.to_string());
+                return;
+            }
+            if self.char_at(self.cursor) == Some(ch) && self.is_closing(ch) {
+                // Type over the closing character instead of doubling it
+                self.cursor.col += 1;
+                return;
+            }
+        }
+
+        // Replacing a selection is an undo step of its own
+        let kind = if self.selection().is_some() {
+            EditKind::Other
+        } else {
+            EditKind::Typing
+        };
+        self.begin_edit(kind);
+        self.delete_selection();
+        let idx = self.byte_index(self.cursor);
+        self.lines[self.cursor.row].insert(idx, ch);
+        self.cursor.col += 1;
+
+        if self.auto_pairs
+            && let Some(close) = self.closing_for(ch)
+        {
+            let idx = self.byte_index(self.cursor);
+            self.lines[self.cursor.row].insert_str(idx, &close);
+        }
+
+        // A word boundary ends the current undo step
+        if ch.is_whitespace() {
+            self.last_edit = None;
+        }
+    }
+
+    fn insert_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.begin_edit(EditKind::Other);
+        self.delete_selection();
+
+        let idx = self.byte_index(self.cursor);
+        let tail = self.lines[self.cursor.row].split_off(idx);
+
+        let mut pasted = text.split('\n');
+        if let Some(first) = pasted.next() {
+            self.lines[self.cursor.row].push_str(first);
+            self.cursor.col += first.chars().count();
+        }
+        for line in pasted {
+            self.cursor.row += 1;
+            self.lines.insert(self.cursor.row, line.to_string());
+            self.cursor.col = line.chars().count();
+        }
+
+        self.lines[self.cursor.row].push_str(&tail);
+    }
+
+    /// Inside a list item the new line continues the list. Pressing Enter on an
+    /// empty item ends the list instead, leaving a blank line.
+    fn insert_newline(&mut self) {
+        let marker = ListMarker::parse(&self.lines[self.cursor.row])
+            .filter(|marker| self.cursor.col >= marker.content_col);
+
+        self.begin_edit(EditKind::Other);
+        if let Some(marker) = &marker
+            && self.line_len(self.cursor.row) == marker.content_col
+        {
+            self.lines[self.cursor.row].clear();
+            self.cursor.col = 0;
+            return;
+        }
+
+        let idx = self.byte_index(self.cursor);
+        let tail = self.lines[self.cursor.row].split_off(idx);
+        let prefix = marker.map(|marker| marker.next()).unwrap_or_default();
+        self.cursor = Cursor {
+            row: self.cursor.row + 1,
+            col: prefix.chars().count(),
+        };
+        self.lines.insert(self.cursor.row, prefix + &tail);
+    }
+
+    /// An empty auto-pair like `()` is removed as a whole.
+    fn backspace(&mut self) {
+        if self.selection().is_some() {
+            self.begin_edit(EditKind::Other);
+            self.delete_selection();
+        } else if self.cursor.col > 0 {
+            self.begin_edit(EditKind::Other);
+            self.cursor.col -= 1;
+            let idx = self.byte_index(self.cursor);
+            let removed = self.lines[self.cursor.row].remove(idx);
+
+            let next = self.char_at(self.cursor);
+            if self.auto_pairs && AUTO_PAIRS.contains(&(removed, next.unwrap_or_default())) {
+                self.lines[self.cursor.row].remove(idx);
+            }
+        } else if self.cursor.row > 0 {
+            self.begin_edit(EditKind::Other);
+            let line = self.lines.remove(self.cursor.row);
+            self.cursor.row -= 1;
+            self.cursor.col = self.line_len(self.cursor.row);
+            self.lines[self.cursor.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.selection().is_some() {
+            self.begin_edit(EditKind::Other);
+            self.delete_selection();
+        } else if self.cursor.col < self.line_len(self.cursor.row) {
+            self.begin_edit(EditKind::Other);
+            let idx = self.byte_index(self.cursor);
+            self.lines[self.cursor.row].remove(idx);
+        } else if self.cursor.row + 1 < self.lines.len() {
+            self.begin_edit(EditKind::Other);
+            let next = self.lines.remove(self.cursor.row + 1);
+            self.lines[self.cursor.row].push_str(&next);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if self.cursor.col > 0 {
+            self.cursor.col -= 1;
+        } else if self.cursor.row > 0 {
+            self.cursor.row -= 1;
+            self.cursor.col = self.line_len(self.cursor.row);
+        }
+        self.last_edit = None;
+    }
+
+    fn move_right(&mut self) {
+        if self.cursor.col < self.line_len(self.cursor.row) {
+            self.cursor.col += 1;
+        } else if self.cursor.row + 1 < self.lines.len() {
+            self.cursor = Cursor {
+                row: self.cursor.row + 1,
+                col: 0,
+            };
+        }
+        self.last_edit = None;
+    }
+
+    fn move_up(&mut self) {
+        if self.cursor.row > 0 {
+            self.cursor.row -= 1;
+            self.cursor.col = self.cursor.col.min(self.line_len(self.cursor.row));
+        }
+        self.last_edit = None;
+    }
+
+    fn move_down(&mut self) {
+        if self.cursor.row + 1 < self.lines.len() {
+            self.cursor.row += 1;
+            self.cursor.col = self.cursor.col.min(self.line_len(self.cursor.row));
+        }
+        self.last_edit = None;
+    }
+
+    fn move_home(&mut self) {
+        self.cursor.col = 0;
+        self.last_edit = None;
+    }
+
+    fn move_end(&mut self) {
+        self.cursor.col = self.line_len(self.cursor.row);
+        self.last_edit = None;
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typing_and_newlines() {
+        let mut buffer = TextBuffer::new("");
+        for ch in "héllo\nwörld".chars() {
+            buffer.insert_char(ch);
+        }
+
+        assert_eq!(buffer.text(), "héllo\nwörld");
+        assert_eq!(buffer.cursor(), Cursor { row: 1, col: 5 });
+        assert!(buffer.is_dirty());
+    }
+
+    #[test]
+    fn test_backspace_and_delete_join_lines() {
+        let mut buffer = TextBuffer::new("ab\ncd");
+        buffer.move_down();
+        buffer.backspace();
+        assert_eq!(buffer.text(), "abcd");
+        assert_eq!(buffer.cursor(), Cursor { row: 0, col: 2 });
+
+        buffer.insert_newline();
+        buffer.move_up();
+        buffer.move_end();
+        buffer.delete();
+        assert_eq!(buffer.text(), "abcd");
+    }
+
+    #[test]
+    fn test_insert_text_is_a_single_undo_step() {
+        let mut buffer = TextBuffer::new("start end");
+        for _ in 0.."start ".len() {
+            buffer.move_right();
+        }
+
+        buffer.insert_text("one\ntwo\nthree ");
+        assert_eq!(buffer.text(), "start one\ntwo\nthree end");
+        assert_eq!(buffer.cursor(), Cursor { row: 2, col: 6 });
+
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "start end");
+        assert_eq!(buffer.cursor(), Cursor { row: 0, col: 6 });
+
+        assert!(buffer.redo());
+        assert_eq!(buffer.text(), "start one\ntwo\nthree end");
+    }
+
+    #[test]
+    fn test_enter_continues_lists() {
+        let mut buffer = TextBuffer::new("- first");
+        buffer.move_end();
+        buffer.insert_newline();
+        buffer.insert_text("second");
+        assert_eq!(buffer.text(), "- first\n- second");
+
+        let mut buffer = TextBuffer::new("  9. ninth\n");
+        buffer.move_end();
+        buffer.insert_newline();
+        assert_eq!(buffer.text(), "  9. ninth\n  10. \n");
+        assert_eq!(buffer.cursor(), Cursor { row: 1, col: 6 });
+
+        // Enter on an empty item ends the list
+        buffer.insert_newline();
+        assert_eq!(buffer.text(), "  9. ninth\n\n");
+        assert_eq!(buffer.cursor(), Cursor { row: 1, col: 0 });
+    }
+
+    #[test]
+    fn test_enter_continues_checkboxes_unchecked() {
+        let mut buffer = TextBuffer::new("- [x] done");
+        buffer.move_end();
+        buffer.insert_newline();
+
+        assert_eq!(buffer.text(), "- [x] done\n- [ ] ");
+        assert_eq!(buffer.cursor(), Cursor { row: 1, col: 6 });
+    }
+
+    #[test]
+    fn test_indent_and_outdent_list_items() {
+        let mut buffer = TextBuffer::new("- item\nplain");
+        buffer.move_end();
+
+        assert!(buffer.indent_list_item());
+        assert_eq!(buffer.lines()[0], "  - item");
+        assert_eq!(buffer.cursor(), Cursor { row: 0, col: 8 });
+
+        assert!(buffer.outdent_list_item());
+        assert!(!buffer.outdent_list_item());
+        assert_eq!(buffer.lines()[0], "- item");
+
+        buffer.move_down();
+        assert!(!buffer.indent_list_item());
+    }
+
+    #[test]
+    fn test_toggle_checkbox() {
+        let mut buffer = TextBuffer::new("  buy milk");
+        buffer.move_end();
+
+        buffer.toggle_checkbox();
+        assert_eq!(buffer.text(), "  - [ ] buy milk");
+        assert_eq!(buffer.cursor(), Cursor { row: 0, col: 16 });
+
+        buffer.toggle_checkbox();
+        assert_eq!(buffer.text(), "  - [x] buy milk");
+        buffer.toggle_checkbox();
+        assert_eq!(buffer.text(), "  - [ ] buy milk");
+
+        let mut buffer = TextBuffer::new("- [ ]");
+        buffer.toggle_checkbox();
+        assert_eq!(buffer.text(), "- [x]");
+
+        let mut buffer = TextBuffer::new("1. step");
+        buffer.toggle_checkbox();
+        assert_eq!(buffer.text(), "1. [ ] step");
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "1. step");
+    }
+
+    #[test]
+    fn test_auto_pairs() {
+        let mut buffer = TextBuffer::new("").with_auto_pairs(true);
+        for ch in "see (docs) and **bold** `code`".chars() {
+            buffer.insert_char(ch);
+        }
+        assert_eq!(buffer.text(), "see (docs) and **bold** `code`");
+
+        let mut buffer = TextBuffer::new("").with_auto_pairs(true);
+        buffer.insert_char('[');
+        assert_eq!(buffer.text(), "[]");
+        buffer.backspace();
+        assert_eq!(buffer.text(), "");
+
+        // No closers when typing in front of a word
+        let mut buffer = TextBuffer::new("word").with_auto_pairs(true);
+        buffer.insert_char('(');
+        assert_eq!(buffer.text(), "(word");
+    }
+
+    #[test]
+    fn test_toggle_wrap_on_selection() {
+        let mut buffer = TextBuffer::new("make it bold");
+        for _ in 0.."make it ".len() {
+            buffer.move_right();
+        }
+        buffer.extend_selection();
+        buffer.move_end();
+
+        buffer.toggle_wrap("**");
+        assert_eq!(buffer.text(), "make it **bold**");
+        assert_eq!(
+            buffer.selection(),
+            Some((Cursor { row: 0, col: 10 }, Cursor { row: 0, col: 14 }))
+        );
+
+        // Italic doesn't unwrap bold, it nests
+        buffer.toggle_wrap("*");
+        assert_eq!(buffer.text(), "make it ***bold***");
+        buffer.toggle_wrap("*");
+        buffer.toggle_wrap("**");
+        assert_eq!(buffer.text(), "make it bold");
+    }
+
+    #[test]
+    fn test_typing_replaces_selection() {
+        let mut buffer = TextBuffer::new("one\ntwo\nthree").with_auto_pairs(true);
+        buffer.move_right();
+        buffer.extend_selection();
+        buffer.move_down();
+        buffer.move_down();
+
+        buffer.insert_char('x');
+        assert_eq!(buffer.text(), "oxhree");
+
+        buffer.move_home();
+        buffer.extend_selection();
+        buffer.move_end();
+        buffer.insert_char('(');
+        assert_eq!(buffer.text(), "(oxhree)");
+    }
+
+    #[test]
+    fn test_snippet_expansion_with_tab_stops() {
+        let snippets = BTreeMap::from([(
+            "mtg".to_string(),
+            "## ${1:Topic}\nWith: ${2:@person}\n$0".to_string(),
+        )]);
+        let mut buffer = TextBuffer::new("  notes").with_snippets(snippets);
+        buffer.move_end();
+        buffer.insert_char(' ');
+        assert!(!buffer.expand_snippet());
+
+        for ch in "mtg".chars() {
+            buffer.insert_char(ch);
+        }
+        assert!(buffer.expand_snippet());
+        assert_eq!(buffer.text(), "  notes ## Topic\n  With: @person\n  ");
+        assert_eq!(
+            buffer.selection(),
+            Some((Cursor { row: 0, col: 11 }, Cursor { row: 0, col: 16 }))
+        );
+
+        for ch in "Standup".chars() {
+            buffer.insert_char(ch);
+        }
+        assert!(buffer.next_tab_stop());
+        buffer.insert_text("@alice");
+        assert!(buffer.next_tab_stop());
+        assert!(!buffer.next_tab_stop());
+
+        assert_eq!(buffer.text(), "  notes ## Standup\n  With: @alice\n  ");
+        assert_eq!(buffer.cursor(), Cursor { row: 2, col: 2 });
+    }
+
+    #[test]
+    fn test_typing_is_undone_word_by_word() {
+        let mut buffer = TextBuffer::new("");
+        for ch in "hello world".chars() {
+            buffer.insert_char(ch);
+        }
+
+        buffer.undo();
+        assert_eq!(buffer.text(), "hello ");
+        buffer.undo();
+        assert_eq!(buffer.text(), "");
+        assert!(!buffer.undo());
+    }
+}
@@ -0,0 +1,138 @@
+//! Text editing shared by every frontend: the TUI editor, scripted edits with
+//! `devlog edit --apply` and whatever comes next, e.g. a GUI.
+
+pub mod buffer;
+pub mod patch;
+pub mod snippet;
+
+use buffer::Cursor;
+
+/// The editing operations of a text buffer. Frontends translate their input into
+/// these calls, markdown helpers like list continuation live on `TextBuffer`.
+pub trait EditorBuffer {
+    fn text(&self) -> String;
+
+    fn lines(&self) -> &[String];
+
+    fn cursor(&self) -> Cursor;
+
+    /// Move the cursor, clamped to the text. An active selection follows it.
+    fn set_cursor(&mut self, cursor: Cursor);
+
+    /// Selected range as `(start, end)`, `None` if nothing is selected
+    fn selection(&self) -> Option<(Cursor, Cursor)>;
+
+    /// Start selecting at the cursor, following movements extend the selection
+    fn extend_selection(&mut self);
+
+    fn clear_selection(&mut self);
+
+    /// Insert a typed character, replacing the selection
+    fn insert_char(&mut self, ch: char);
+
+    /// Insert a block of text, e.g. a paste, as a single undo step
+    fn insert_text(&mut self, text: &str);
+
+    /// Split the current line at the cursor
+    fn insert_newline(&mut self);
+
+    /// Delete the selection or the character before the cursor, joining lines at
+    /// the line start
+    fn backspace(&mut self);
+
+    /// Delete the selection or the character under the cursor, joining lines at
+    /// the line end
+    fn delete(&mut self);
+
+    fn move_left(&mut self);
+
+    fn move_right(&mut self);
+
+    fn move_up(&mut self);
+
+    fn move_down(&mut self);
+
+    fn move_home(&mut self);
+
+    fn move_end(&mut self);
+
+    /// Revert the last undo step, returns false if there is nothing to undo
+    fn undo(&mut self) -> bool;
+
+    /// Reapply the last undone step, returns false if there is nothing to redo
+    fn redo(&mut self) -> bool;
+
+    /// Whether the buffer changed since it was loaded or last saved
+    fn is_dirty(&self) -> bool;
+
+    fn mark_saved(&mut self);
+
+    /// Select from `start` to `end`, leaving the cursor at `end`
+    fn select(&mut self, start: Cursor, end: Cursor) {
+        self.clear_selection();
+        self.set_cursor(start);
+        self.extend_selection();
+        self.set_cursor(end);
+    }
+
+    /// Replace `count` whole lines from line `start` with `lines`. A `count` of 0
+    /// inserts before line `start`, or after the last line when `start` is past it.
+    fn replace_lines(&mut self, start: usize, count: usize, lines: &[String]) {
+        let total = self.lines().len();
+        let line_end = |buffer: &Self, row: usize| Cursor {
+            row,
+            col: buffer.lines()[row].chars().count(),
+        };
+        let mut text = lines.join("\n");
+        let (from, to) = if start + count < total {
+            // Take the line break after the last replaced line along
+            if !lines.is_empty() {
+                text.push('\n');
+            }
+            (
+                Cursor { row: start, col: 0 },
+                Cursor {
+                    row: start + count,
+                    col: 0,
+                },
+            )
+        } else if start > 0 {
+            // Up to the end of the text there is no line break after, take the one before
+            if !lines.is_empty() {
+                text.insert(0, '\n');
+            }
+            (line_end(self, start - 1), line_end(self, total - 1))
+        } else {
+            (Cursor::default(), line_end(self, total - 1))
+        };
+
+        self.select(from, to);
+        if self.selection().is_some() {
+            self.delete();
+        }
+        self.insert_text(&text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::TextBuffer;
+
+    fn replaced(text: &str, start: usize, count: usize, lines: &[&str]) -> String {
+        let mut buffer = TextBuffer::new(text);
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        buffer.replace_lines(start, count, &lines);
+        buffer.text()
+    }
+
+    #[test]
+    fn test_replace_lines() {
+        assert_eq!(replaced("a\nb\nc", 1, 1, &["B", "B2"]), "a\nB\nB2\nc");
+        assert_eq!(replaced("a\nb\nc", 1, 0, &["x"]), "a\nx\nb\nc");
+        assert_eq!(replaced("a\nb\nc", 1, 2, &[]), "a");
+        assert_eq!(replaced("a\nb\nc", 3, 0, &["d"]), "a\nb\nc\nd");
+        assert_eq!(replaced("a\nb", 0, 2, &["z"]), "z");
+        assert_eq!(replaced("a\nb\nc", 0, 1, &[]), "b\nc");
+    }
+}
@@ -0,0 +1,174 @@
+use color_eyre::eyre::{Result, bail};
+
+use crate::editing::EditorBuffer;
+use crate::error::{DevlogError, ErrorKind};
+
+/// One `@@ -start,count +start,count @@` hunk of a unified diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// Line the hunk starts at in the original text, counting from 1. With no old
+    /// lines it's the line the new ones go after.
+    pub old_start: usize,
+    /// Context and removed lines, as they must be in the text
+    pub old: Vec<String>,
+    /// Context and added lines, as they replace `old`
+    pub new: Vec<String>,
+}
+
+/// The hunks of a unified diff, e.g. from `diff -u` or `git diff`. File headers
+/// and anything outside of hunks is skipped.
+pub fn parse(diff: &str) -> Result<Vec<Hunk>> {
+    let mut hunks = Vec::new();
+    let mut lines = diff.lines().enumerate();
+    while let Some((number, line)) = lines.next() {
+        let Some(header) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        let Some((old_start, old_count, new_count)) = parse_header(header) else {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!(
+                    "Line {} of the patch is not a valid hunk header",
+                    number + 1
+                )
+            ));
+        };
+
+        let mut hunk = Hunk {
+            old_start,
+            old: Vec::new(),
+            new: Vec::new(),
+        };
+        while hunk.old.len() < old_count || hunk.new.len() < new_count {
+            let Some((_, line)) = lines.next() else {
+                bail!(DevlogError::new(
+                    ErrorKind::Validation,
+                    format!("The hunk at line {} of the patch is cut short", number + 1)
+                ));
+            };
+            // Some tools strip the space of empty context lines
+            let (marker, text) = line.split_at(line.len().min(1));
+            match marker {
+                " " | "" => {
+                    hunk.old.push(text.to_string());
+                    hunk.new.push(text.to_string());
+                }
+                "-" => hunk.old.push(text.to_string()),
+                "+" => hunk.new.push(text.to_string()),
+                // `\ No newline at end of file`
+                "\\" => {}
+                _ => bail!(DevlogError::new(
+                    ErrorKind::Validation,
+                    format!(
+                        "The hunk at line {} of the patch holds an unexpected line: {}",
+                        number + 1,
+                        line
+                    )
+                )),
+            }
+        }
+        hunks.push(hunk);
+    }
+
+    if hunks.is_empty() {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "The patch holds no hunks, expected a unified diff"
+        ));
+    }
+    Ok(hunks)
+}
+
+/// `-12,3 +12,4 @@` into the old start, old count and new count
+fn parse_header(header: &str) -> Option<(usize, usize, usize)> {
+    let mut ranges = header.split_whitespace();
+    let old = ranges.next()?.strip_prefix('-')?;
+    let new = ranges.next()?.strip_prefix('+')?;
+    let range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = range(old)?;
+    let (_, new_count) = range(new)?;
+    Some((old_start, old_count, new_count))
+}
+
+/// Apply the hunks, whose line numbers refer to the text before any of them. Every
+/// hunk's old lines have to match the text, a patch that doesn't apply changes nothing.
+pub fn apply(buffer: &mut impl EditorBuffer, hunks: &[Hunk]) -> Result<()> {
+    let mut starts = Vec::new();
+    for (number, hunk) in hunks.iter().enumerate() {
+        let start = if hunk.old.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        if buffer.lines().get(start..start + hunk.old.len()) != Some(&hunk.old[..]) {
+            bail!(DevlogError::new(
+                ErrorKind::Conflict,
+                format!(
+                    "Hunk {} of the patch doesn't match the text at line {}",
+                    number + 1,
+                    start + 1
+                )
+            ));
+        }
+        if starts.last().is_some_and(|&previous| start < previous) {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!("Hunk {} of the patch is out of order", number + 1)
+            ));
+        }
+        starts.push(start);
+    }
+
+    // Last first, so the line numbers of the hunks before stay valid
+    for (start, hunk) in starts.into_iter().zip(hunks).rev() {
+        buffer.replace_lines(start, hunk.old.len(), &hunk.new);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editing::buffer::TextBuffer;
+
+    const PATCH: &str = "\
+--- a/20250920.md
++++ b/20250920.md
+@@ -1,3 +1,3 @@
+ Standup
+-- [ ] ship the fix
++- [x] ship the fix
+ 
+@@ -5,2 +5,3 @@
+ ## Notes
+ Paired with @alice
++Follow up with @bob
+";
+
+    #[test]
+    fn test_apply_patch() {
+        let mut buffer = TextBuffer::new(
+            "Standup\n- [ ] ship the fix\n\nReview\n## Notes\nPaired with @alice\n",
+        );
+        apply(&mut buffer, &parse(PATCH).unwrap()).unwrap();
+        assert_eq!(
+            buffer.text(),
+            "Standup\n- [x] ship the fix\n\nReview\n## Notes\nPaired with @alice\nFollow up with @bob\n"
+        );
+    }
+
+    #[test]
+    fn test_patch_that_does_not_match_changes_nothing() {
+        let text = "Standup\n- [x] shipped\n\nReview\n## Notes\nPaired with @alice\n";
+        let mut buffer = TextBuffer::new(text);
+        let err = apply(&mut buffer, &parse(PATCH).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("Hunk 1"));
+        assert_eq!(buffer.text(), text);
+        assert!(parse("not a diff").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::editing::buffer::Cursor;
+
+/// A parsed snippet template. Tab stops are written `$1` or `${1:placeholder}`,
+/// `$0` marks where the cursor ends up once every stop was visited, `\$` is a literal `$`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub lines: Vec<String>,
+    /// Tab stop ranges relative to the start of the snippet, in visiting order
+    pub stops: Vec<(Cursor, Cursor)>,
+}
+
+impl Snippet {
+    pub fn parse(template: &str) -> Self {
+        let mut lines = vec![String::new()];
+        let mut numbered: Vec<(u32, Cursor, Cursor)> = Vec::new();
+        let mut chars = template.chars().peekable();
+
+        let position = |lines: &Vec<String>| Cursor {
+            row: lines.len() - 1,
+            col: lines.last().map_or(0, |line| line.chars().count()),
+        };
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\n' => lines.push(String::new()),
+                '\\' if chars.peek() == Some(&'$') => {
+                    chars.next();
+                    if let Some(line) = lines.last_mut() {
+                        line.push('$');
+                    }
+                }
+                '$' if chars.peek().is_some_and(char::is_ascii_digit) => {
+                    let mut number = String::new();
+                    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                        number.push(digit);
+                    }
+                    let at = position(&lines);
+                    numbered.push((number.parse().unwrap_or(0), at, at));
+                }
+                '$' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    let mut number = String::new();
+                    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                        number.push(digit);
+                    }
+                    chars.next_if_eq(&':');
+
+                    let start = position(&lines);
+                    for placeholder in chars.by_ref() {
+                        if placeholder == '}' {
+                            break;
+                        }
+                        if let Some(line) = lines.last_mut() {
+                            line.push(placeholder);
+                        }
+                    }
+                    numbered.push((number.parse().unwrap_or(0), start, position(&lines)));
+                }
+                _ => {
+                    if let Some(line) = lines.last_mut() {
+                        line.push(ch);
+                    }
+                }
+            }
+        }
+
+        // `$0` is visited last, and the snippet end acts as `$0` if there is none
+        if !numbered.iter().any(|(number, _, _)| *number == 0) {
+            let end = position(&lines);
+            numbered.push((0, end, end));
+        }
+        numbered.sort_by_key(|(number, _, _)| if *number == 0 { u32::MAX } else { *number });
+
+        Self {
+            lines,
+            stops: numbered
+                .into_iter()
+                .map(|(_, start, end)| (start, end))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tab_stops() {
+        let snippet = Snippet::parse("## ${1:Topic}\nWith: ${2:@person}\n\n$0");
+
+        assert_eq!(snippet.lines, vec!["## Topic", "With: @person", "", ""]);
+        assert_eq!(
+            snippet.stops,
+            vec![
+                (Cursor { row: 0, col: 3 }, Cursor { row: 0, col: 8 }),
+                (Cursor { row: 1, col: 6 }, Cursor { row: 1, col: 13 }),
+                (Cursor { row: 3, col: 0 }, Cursor { row: 3, col: 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_without_final_stop_ends_at_snippet_end() {
+        let snippet = Snippet::parse("costs \\$5 for $1");
+
+        assert_eq!(snippet.lines, vec!["costs $5 for "]);
+        assert_eq!(
+            snippet.stops,
+            vec![
+                (Cursor { row: 0, col: 13 }, Cursor { row: 0, col: 13 }),
+                (Cursor { row: 0, col: 13 }, Cursor { row: 0, col: 13 }),
+            ]
+        );
+    }
+}
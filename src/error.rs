@@ -0,0 +1,96 @@
+use std::fmt;
+
+use color_eyre::Report;
+
+/// Exit code for errors without a more specific kind
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Failure categories with their own exit code, so scripts can branch on them.
+/// The codes are part of the CLI contract, see `docs/exit-codes.md`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The requested entry or config key does not exist
+    NotFound,
+    /// Input was rejected, e.g. a malformed entry id or config value
+    Validation,
+    /// Data changed underneath us, e.g. another process or a sync modified it
+    Conflict,
+    /// Another devlog process holds the lock
+    LockHeld,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::NotFound => 3,
+            ErrorKind::Validation => 4,
+            ErrorKind::Conflict => 5,
+            ErrorKind::LockHeld => 6,
+        }
+    }
+}
+
+/// An error carrying an `ErrorKind`, raised with e.g.
+/// `bail!(DevlogError::new(ErrorKind::NotFound, "..."))`
+#[derive(Debug)]
+pub struct DevlogError {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl DevlogError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DevlogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for DevlogError {}
+
+/// Exit code for a failed command, based on the first `DevlogError` in the chain
+pub fn exit_code(report: &Report) -> i32 {
+    report
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<DevlogError>())
+        .map_or(EXIT_FAILURE, |e| e.kind().exit_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_eyre::eyre::{Context, Result, bail};
+
+    fn not_found() -> Result<()> {
+        bail!(DevlogError::new(
+            ErrorKind::NotFound,
+            "Entry 20250920 not found"
+        ))
+    }
+
+    #[test]
+    fn test_exit_code_survives_context() {
+        let report = not_found().wrap_err("Failed to show entry").unwrap_err();
+
+        assert_eq!(exit_code(&report), 3);
+        assert_eq!(report.to_string(), "Failed to show entry");
+    }
+
+    #[test]
+    fn test_untyped_errors_exit_with_failure() {
+        let report = color_eyre::eyre::eyre!("boom");
+
+        assert_eq!(exit_code(&report), EXIT_FAILURE);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use color_eyre::eyre::{Context, Result};
+use serde_json::json;
+
+use crate::export::{ExportFormat, ExportedEntry, html};
+use crate::models::annotation::AnnotationKind;
+use crate::models::project::ProjectProfile;
+use crate::models::time_log::parse_time_log;
+
+/// Everything needed to hand a project over: its summary and impact, who was
+/// involved, the time logged on it and every entry mentioning it, oldest first
+pub fn render(
+    profile: &ProjectProfile,
+    entries: &[ExportedEntry],
+    format: ExportFormat,
+) -> Result<String> {
+    match format {
+        ExportFormat::Markdown => Ok(to_markdown(profile, entries)),
+        ExportFormat::Json => serde_json::to_string_pretty(&json!({
+            "project": profile.name,
+            "summary": profile.summary(),
+            "impact": profile.impact(),
+            "entries": entries,
+        }))
+        .map(|json| json + "\n")
+        .wrap_err("Failed to serialize the handoff"),
+        ExportFormat::Html => Ok(html::page(
+            &title(profile),
+            &html::markdown_to_html(&body(profile, entries)),
+            "",
+        )),
+    }
+}
+
+fn title(profile: &ProjectProfile) -> String {
+    format!(
+        "Handoff of {}{}",
+        AnnotationKind::Project.prefix(),
+        profile.name
+    )
+}
+
+fn to_markdown(profile: &ProjectProfile, entries: &[ExportedEntry]) -> String {
+    let mut markdown = format!("---\ntitle: \"{}\"\n", title(profile));
+    if let (Some(first), Some(last)) = (entries.first(), entries.last()) {
+        markdown.push_str(&format!("date: {} to {}\n", first.date, last.date));
+    }
+    markdown.push_str(&format!("entries: {}\n---\n\n", entries.len()));
+    markdown.push_str(&body(profile, entries));
+    markdown
+}
+
+fn body(profile: &ProjectProfile, entries: &[ExportedEntry]) -> String {
+    let mut markdown = format!("# {}\n\n", title(profile));
+    for (heading, text) in [
+        ("Summary", profile.summary()),
+        ("Impact", profile.impact()),
+        ("Retrospective", profile.retrospective()),
+    ] {
+        if !text.is_empty() {
+            markdown.push_str(&format!("## {}\n\n{}\n\n", heading, text));
+        }
+    }
+
+    let mut people: BTreeMap<&str, usize> = BTreeMap::new();
+    for person in entries.iter().flat_map(|entry| &entry.annotations.people) {
+        *people.entry(person).or_default() += 1;
+    }
+    if !people.is_empty() {
+        let mut people: Vec<(&str, usize)> = people.into_iter().collect();
+        people.sort_by_key(|(_, count)| Reverse(*count));
+        markdown.push_str("## People\n\n");
+        for (person, count) in people {
+            let prefix = AnnotationKind::Person.prefix();
+            markdown.push_str(&format!("- {}{} ({})\n", prefix, person, count));
+        }
+        markdown.push('\n');
+    }
+
+    let minutes: u32 = entries
+        .iter()
+        .filter_map(|entry| parse_time_log(&entry.content).get(&profile.name).copied())
+        .sum();
+    if minutes > 0 {
+        markdown.push_str(&format!(
+            "## Time logged\n\n{:.1}h\n\n",
+            minutes as f64 / 60.0
+        ));
+    }
+
+    markdown.push_str("## Timeline\n");
+    for entry in entries {
+        markdown.push_str(&format!("\n### {}\n\n", entry.date));
+        // Entry headings are nested below the day
+        for line in entry.content.trim_end().lines() {
+            if line.starts_with('#') {
+                markdown.push_str("##");
+            }
+            markdown.push_str(line);
+            markdown.push('\n');
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+
+    #[test]
+    fn test_handoff_markdown() {
+        let mut profile = ProjectProfile::new("search");
+        profile.body = "## Summary\n\nFull text search.\n\n## Impact\n\n".to_string();
+        let entries: Vec<ExportedEntry> = [
+            ("20250915", "# Kickoff\nWith @alice on ::search ~1h30m"),
+            ("20250916", "::search review with @alice and @bob"),
+        ]
+        .into_iter()
+        .map(|(id, content)| Entry::new(id.parse().unwrap(), content.to_string()).into())
+        .collect();
+
+        let markdown = render(&profile, &entries, ExportFormat::Markdown).unwrap();
+
+        assert!(markdown.starts_with("---\ntitle: \"Handoff of ::search\"\ndate: 2025-09-15 to"));
+        assert!(markdown.contains("## Summary\n\nFull text search.\n\n## People"));
+        assert!(!markdown.contains("## Impact"));
+        assert!(markdown.contains("- @alice (2)\n- @bob (1)\n"));
+        assert!(markdown.contains("## Time logged\n\n1.5h"));
+        assert!(markdown.contains("### 2025-09-15\n\n### Kickoff\n"));
+        assert!(markdown.find("2025-09-15").unwrap() < markdown.rfind("2025-09-16").unwrap());
+    }
+}
@@ -0,0 +1,259 @@
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd, html};
+
+use crate::export::{ExportedEntry, mermaid};
+use crate::models::display::table_of_contents;
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;\
+line-height:1.5;color:#222}article{margin-bottom:3rem}.meta{color:#777;font-size:.9em}\
+.annotation{background:#eef;border-radius:4px;padding:0 .3em;margin-right:.3em}\
+pre{background:#f6f6f6;padding:.8em;overflow-x:auto}blockquote{color:#555;\
+border-left:3px solid #ccc;margin-left:0;padding-left:1em}dt{font-weight:bold}\
+.footnotes{border-top:1px solid #ddd;margin-top:2em;font-size:.9em;color:#555}\
+.diagram{margin:1em 0;text-align:center}.diagram svg{max-width:100%;height:auto}\
+.toc{border-left:3px solid #eef;padding-left:1em;font-size:.9em}";
+
+/// Typesets the `\(..\)` and `\[..\]` math of pages that have any
+const MATHJAX: &str = "<script defer src=\"https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-chtml.js\">\
+</script>\n";
+
+/// Draws the diagrams mermaid-cli couldn't render in the browser instead
+const MERMAID: &str = "<script type=\"module\">import mermaid from \
+\"https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs\";\
+mermaid.initialize({startOnLoad:true});</script>\n";
+
+/// Render entry Markdown to HTML, with tables, task lists, strikethrough, definition
+/// lists, footnotes, `$` math and mermaid diagrams. Footnotes are collected in a section
+/// at the end, in the order they are referenced. Math is left to MathJax, see [`page`].
+pub fn markdown_to_html(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_DEFINITION_LIST
+        | Options::ENABLE_MATH;
+
+    let mut body = Vec::new();
+    let mut footnotes: Vec<(CowStr, Vec<Event>)> = Vec::new();
+    let mut in_footnote = false;
+    let mut diagram: Option<String> = None;
+    for event in Parser::new_ext(markdown, options) {
+        let event = match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang)))
+                if lang.as_ref() == mermaid::LANG =>
+            {
+                diagram = Some(String::new());
+                continue;
+            }
+            Event::Text(text) if diagram.is_some() => {
+                diagram.get_or_insert_default().push_str(&text);
+                continue;
+            }
+            Event::End(TagEnd::CodeBlock) if diagram.is_some() => {
+                Event::Html(diagram_html(&diagram.take().unwrap_or_default()).into())
+            }
+            Event::InlineMath(latex) => Event::InlineHtml(
+                format!("<span class=\"math\">\\({}\\)</span>", escape(&latex)).into(),
+            ),
+            Event::DisplayMath(latex) => Event::InlineHtml(
+                format!("<span class=\"math\">\\[{}\\]</span>", escape(&latex)).into(),
+            ),
+            event => event,
+        };
+        if let Event::Start(Tag::FootnoteDefinition(name)) = &event {
+            footnotes.push((name.clone(), Vec::new()));
+            in_footnote = true;
+        }
+        let ends_footnote = matches!(event, Event::End(TagEnd::FootnoteDefinition));
+        match footnotes.last_mut() {
+            Some((_, events)) if in_footnote => events.push(event),
+            _ => body.push(event),
+        }
+        in_footnote &= !ends_footnote;
+    }
+
+    // The numbers of the references count up in the text, the footnotes follow them
+    let first_reference = |name: &CowStr| {
+        body.iter()
+            .position(
+                |event| matches!(event, Event::FootnoteReference(reference) if reference == name),
+            )
+            .unwrap_or(usize::MAX)
+    };
+    footnotes.sort_by_key(|(name, _)| first_reference(name));
+
+    let mut events = body;
+    if !footnotes.is_empty() {
+        events.push(Event::Html("<section class=\"footnotes\">\n".into()));
+        events.extend(footnotes.into_iter().flat_map(|(_, events)| events));
+        events.push(Event::Html("</section>\n".into()));
+    }
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, events.into_iter());
+    rendered
+}
+
+/// A diagram as the SVG mermaid-cli renders, or as its source for mermaid.js to draw
+fn diagram_html(source: &str) -> String {
+    match mermaid::render_svg(source) {
+        Some(svg) => format!("<figure class=\"diagram\">{}</figure>\n", svg),
+        None => format!("<pre class=\"mermaid\">{}</pre>\n", escape(source)),
+    }
+}
+
+/// A standalone page around `body`. `head` is extra markup for `<head>`,
+/// e.g. a live reload script. Pages with math load MathJax to typeset it, and
+/// pages with diagrams mermaid-cli couldn't render load mermaid.js.
+pub fn page(title: &str, body: &str, head: &str) -> String {
+    let mut scripts = String::new();
+    if body.contains("<span class=\"math\">") {
+        scripts.push_str(MATHJAX);
+    }
+    if body.contains("<pre class=\"mermaid\">") {
+        scripts.push_str(MERMAID);
+    }
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>{}</style>\n{}{}</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        STYLE,
+        scripts,
+        head,
+        body
+    )
+}
+
+pub fn document(title: &str, entries: &[ExportedEntry]) -> String {
+    let body: String = entries.iter().map(article).collect();
+    page(title, &format!("<h1>{}</h1>\n{}", escape(title), body), "")
+}
+
+/// One entry with its dates and annotations above the rendered content, and the
+/// table of contents and language its frontmatter asks for
+pub fn article(entry: &ExportedEntry) -> String {
+    let annotations: String = [
+        ("@", &entry.annotations.people),
+        ("::", &entry.annotations.projects),
+        ("+", &entry.annotations.tags),
+    ]
+    .iter()
+    .flat_map(|(prefix, values)| {
+        let mut values: Vec<&String> = values.iter().collect();
+        values.dedup();
+        values.into_iter().map(move |value| {
+            format!(
+                "<span class=\"annotation\">{}{}</span>",
+                prefix,
+                escape(value)
+            )
+        })
+    })
+    .collect();
+
+    let lang = entry
+        .display
+        .lang
+        .as_ref()
+        .map(|lang| format!(" lang=\"{}\"", escape(lang)))
+        .unwrap_or_default();
+    let toc = table_of_contents(&entry.content)
+        .filter(|_| entry.display.has_toc())
+        .map(|toc| format!("<nav class=\"toc\">\n{}</nav>\n", markdown_to_html(&toc)))
+        .unwrap_or_default();
+
+    format!(
+        "<article id=\"{}\"{}>\n<h2>{}</h2>\n<p class=\"meta\">Created {} · Updated {}</p>\n\
+         <p>{}</p>\n{}{}</article>\n",
+        entry.id,
+        lang,
+        entry.date.format("%A, %e %B %Y"),
+        entry.created_at.format("%Y-%m-%d %H:%M UTC"),
+        entry.updated_at.format("%Y-%m-%d %H:%M UTC"),
+        annotations,
+        toc,
+        markdown_to_html(&entry.content)
+    )
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+
+    #[test]
+    fn test_html_document() {
+        let entry = Entry::new(
+            "20250920".parse().unwrap(),
+            "# Done\n- [x] shipped ::devlog\n\n<script>".to_string(),
+        );
+        let html = document("Devlog", &[ExportedEntry::from(entry)]);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<article id=\"20250920\">"));
+        assert!(html.contains("<span class=\"annotation\">::devlog</span>"));
+        assert!(html.contains("<h1>Done</h1>"));
+        assert!(html.contains("checked=\"\""));
+    }
+
+    #[test]
+    fn test_display_options_of_an_entry() {
+        let mut entry = Entry::new(
+            "20250920".parse().unwrap(),
+            "## Morgens\n\n## Abends\n".to_string(),
+        );
+        entry.display.toc = Some(true);
+        entry.display.lang = Some("de".to_string());
+        let html = article(&ExportedEntry::from(entry));
+
+        assert!(html.starts_with("<article id=\"20250920\" lang=\"de\">"));
+        let toc = &html[html.find("<nav class=\"toc\">").unwrap()..];
+        assert!(toc.starts_with("<nav class=\"toc\">\n<ul>\n<li>Morgens</li>"));
+        assert!(toc.find("</nav>").unwrap() < toc.find("<h2>Morgens</h2>").unwrap());
+    }
+
+    #[test]
+    fn test_footnotes_and_definition_lists() {
+        let html = markdown_to_html(
+            "[^b]: second\n\nFirst[^a] then[^b].\n\n[^a]: first\n\nCrate\n: A Rust package\n",
+        );
+
+        assert!(html.contains("<sup class=\"footnote-reference\"><a href=\"#a\">1</a></sup>"));
+        let section = &html[html.find("<section class=\"footnotes\">").unwrap()..];
+        assert!(section.find("first").unwrap() < section.find("second").unwrap());
+        assert!(html.find("<dl>").unwrap() < html.find("<section").unwrap());
+        assert!(html.contains("<dt>Crate</dt>"));
+        assert!(html.contains("<dd>A Rust package</dd>"));
+    }
+
+    #[test]
+    fn test_math_is_typeset_by_mathjax() {
+        let html = markdown_to_html("Loss $x<y$ costs $5 and $$\\sum_i x_i$$");
+        assert!(html.contains(r#"<span class="math">\(x&lt;y\)</span>"#));
+        assert!(html.contains(r#"<span class="math">\[\sum_i x_i\]</span>"#));
+        assert!(html.contains("costs $5 and"));
+
+        assert!(page("Devlog", &html, "").contains("mathjax@3"));
+        assert!(!page("Devlog", "<p>No math</p>", "").contains("mathjax"));
+    }
+
+    #[test]
+    fn test_mermaid_diagrams() {
+        let html =
+            markdown_to_html("```mermaid\ngraph TD\n  A --> B\n```\n\n```rust\nlet x;\n```\n");
+
+        // Drawn by mermaid-cli where it is installed, by mermaid.js otherwise
+        assert!(
+            html.contains("<figure class=\"diagram\"><svg")
+                || html.contains("<pre class=\"mermaid\">graph TD\n  A --&gt; B\n</pre>")
+        );
+        assert!(!html.contains("language-mermaid"));
+        assert!(html.contains("language-rust"));
+        assert!(page("Devlog", "<pre class=\"mermaid\">graph TD</pre>", "").contains("mermaid@11"));
+    }
+}
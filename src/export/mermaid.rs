@@ -0,0 +1,162 @@
+use std::{
+    collections::{HashMap, hash_map::DefaultHasher},
+    env, fs,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    process::{self, Command, Stdio},
+    sync::{Mutex, OnceLock},
+};
+
+/// Language of the fenced code blocks holding a diagram
+pub const LANG: &str = "mermaid";
+
+/// Render a diagram to SVG with mermaid-cli (`mmdc`), `None` when it isn't installed or
+/// the diagram doesn't render. Results are kept for the rest of the process, the preview
+/// server renders the same diagrams again on every change.
+pub fn render_svg(source: &str) -> Option<String> {
+    static RENDERED: OnceLock<Mutex<HashMap<u64, Option<String>>>> = OnceLock::new();
+
+    let key = source_hash(source);
+    let rendered = RENDERED.get_or_init(Mutex::default);
+    if let Some(svg) = rendered.lock().ok()?.get(&key) {
+        return svg.clone();
+    }
+    let svg = render(source, "svg").and_then(|path| {
+        let svg = fs::read_to_string(&path).ok();
+        let _ = fs::remove_file(path);
+        svg
+    });
+    rendered.lock().ok()?.insert(key, svg.clone());
+    svg
+}
+
+/// Render a diagram into an image file in the temp directory, `format` is its extension
+/// like `svg` or `png`
+pub fn render(source: &str, format: &str) -> Option<PathBuf> {
+    let base = env::temp_dir().join(format!(
+        "devlog-mermaid-{}-{:x}",
+        process::id(),
+        source_hash(source)
+    ));
+    let input = base.with_extension("mmd");
+    let output = base.with_extension(format);
+    fs::write(&input, source).ok()?;
+
+    let status = Command::new("mmdc")
+        .arg("--quiet")
+        .arg("--input")
+        .arg(&input)
+        .arg("--output")
+        .arg(&output)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = fs::remove_file(&input);
+    match status {
+        Ok(status) if status.success() && output.exists() => Some(output),
+        _ => None,
+    }
+}
+
+/// Replace the diagrams of a Markdown document with images rendered by mermaid-cli, for
+/// pandoc to embed. Diagrams that don't render stay code blocks. Returns the document and
+/// the image files, to be removed after the conversion.
+pub fn with_images(markdown: &str) -> (String, Vec<PathBuf>) {
+    let mut document = String::with_capacity(markdown.len());
+    let mut images = Vec::new();
+    let mut lines = markdown.split_inclusive('\n');
+    while let Some(line) = lines.next() {
+        if !is_opening_fence(line) {
+            document.push_str(line);
+            continue;
+        }
+        let block: Vec<&str> = lines
+            .by_ref()
+            .take_while(|line| !line.trim_start().starts_with("```"))
+            .collect();
+        let source = block.concat();
+        match render(&source, "png") {
+            Some(image) => {
+                document.push_str(&format!(
+                    "![{}]({})\n",
+                    title(&source).unwrap_or_default(),
+                    image.display()
+                ));
+                images.push(image);
+            }
+            None => {
+                document.push_str(line);
+                document.push_str(&source);
+                document.push_str("```\n");
+            }
+        }
+    }
+    (document, images)
+}
+
+/// Whether a line opens a fenced diagram, as in ```` ```mermaid ````
+pub fn is_opening_fence(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix("```")
+        .and_then(|info| info.split_whitespace().next())
+        == Some(LANG)
+}
+
+/// Title of a diagram, from its `title:` frontmatter or the `title` line of charts
+/// like gantt and pie charts
+pub fn title(source: &str) -> Option<&str> {
+    source.lines().find_map(|line| {
+        let line = line.trim();
+        let title = line
+            .strip_prefix("title:")
+            .or_else(|| line.strip_prefix("title "))
+            .or_else(|| line.strip_prefix("pie title "))?
+            .trim()
+            .trim_matches('"');
+        (!title.is_empty()).then_some(title)
+    })
+}
+
+/// Type of a diagram like `sequenceDiagram` or `flowchart`, its first word after the
+/// frontmatter and comments
+pub fn kind(source: &str) -> Option<&str> {
+    let mut in_frontmatter = false;
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            if *line == "---" {
+                in_frontmatter = !in_frontmatter;
+                return false;
+            }
+            !in_frontmatter && !line.is_empty() && !line.starts_with("%%")
+        })
+        .find_map(|line| line.split_whitespace().next())
+}
+
+fn source_hash(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_and_kind() {
+        let source =
+            "---\ntitle: Checkout flow\n---\n%% the happy path\nsequenceDiagram\n  A->>B: pay\n";
+        assert_eq!(title(source), Some("Checkout flow"));
+        assert_eq!(kind(source), Some("sequenceDiagram"));
+
+        assert_eq!(title("pie title Pets\n  \"Dogs\": 3"), Some("Pets"));
+        assert_eq!(title("graph TD\n  A --> B"), None);
+        assert_eq!(kind("graph TD\n  A --> B"), Some("graph"));
+
+        assert!(is_opening_fence("```mermaid\n"));
+        assert!(!is_opening_fence("```mermaidjs\n"));
+        assert!(!is_opening_fence("mermaid\n"));
+    }
+}
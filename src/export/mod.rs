@@ -0,0 +1,172 @@
+pub mod handoff;
+pub mod html;
+pub mod mermaid;
+pub mod pandoc;
+pub mod search;
+pub mod terminal;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+
+use crate::models::annotation::Annotations;
+use crate::models::display::{DisplayOptions, table_of_contents};
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+/// Title of exported documents
+pub const EXPORT_TITLE: &str = "Devlog";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// One Markdown document with a section per entry
+    Markdown,
+    /// A JSON array of entries
+    Json,
+    /// A standalone HTML page
+    Html,
+}
+
+/// An entry as it appears in exports, with its annotations parsed
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedEntry {
+    pub id: EntryId,
+    pub date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub annotations: Annotations,
+    #[serde(skip_serializing_if = "DisplayOptions::is_default")]
+    pub display: DisplayOptions,
+    pub content: String,
+}
+
+impl From<Entry> for ExportedEntry {
+    fn from(entry: Entry) -> Self {
+        Self {
+            id: entry.id,
+            date: entry.id.date(),
+            created_at: entry.created_at,
+            updated_at: entry.updated_at,
+            annotations: Annotations::parse(&entry.content),
+            display: entry.display,
+            content: entry.content,
+        }
+    }
+}
+
+/// Load the entries between `from` and `to` (both included), oldest first
+pub fn collect(
+    storage: &Storage,
+    from: Option<EntryId>,
+    to: Option<EntryId>,
+) -> Result<Vec<ExportedEntry>> {
+    let mut ids: Vec<EntryId> = storage
+        .list_entries()?
+        .into_iter()
+        .filter(|id| from.is_none_or(|from| *id >= from) && to.is_none_or(|to| *id <= to))
+        .collect();
+    ids.sort();
+
+    ids.iter()
+        .map(|id| storage.load_entry(id).map(ExportedEntry::from))
+        .collect()
+}
+
+pub fn render(entries: &[ExportedEntry], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Markdown => Ok(to_markdown(entries)),
+        ExportFormat::Json => serde_json::to_string_pretty(entries)
+            .map(|json| json + "\n")
+            .wrap_err("Failed to serialize entries"),
+        ExportFormat::Html => Ok(html::document(EXPORT_TITLE, entries)),
+    }
+}
+
+/// Combined Markdown document. The document frontmatter describes the export, each
+/// entry's own metadata is kept in an HTML comment under its heading.
+pub fn to_markdown(entries: &[ExportedEntry]) -> String {
+    let mut markdown = format!("---\ntitle: {}\n", EXPORT_TITLE);
+    if let (Some(first), Some(last)) = (entries.first(), entries.last()) {
+        markdown.push_str(&format!("date: {} to {}\n", first.date, last.date));
+    }
+    markdown.push_str(&format!("entries: {}\n---\n", entries.len()));
+
+    for entry in entries {
+        markdown.push_str(&format!("\n## {}\n\n", entry.date));
+        markdown.push_str(&format!(
+            "<!-- id: {}, created_at: {}, updated_at: {}",
+            entry.id,
+            entry.created_at.to_rfc3339(),
+            entry.updated_at.to_rfc3339()
+        ));
+        for (name, values) in [
+            ("people", &entry.annotations.people),
+            ("projects", &entry.annotations.projects),
+            ("tags", &entry.annotations.tags),
+        ] {
+            if !values.is_empty() {
+                markdown.push_str(&format!(", {}: {}", name, values.join(" ")));
+            }
+        }
+        markdown.push_str(" -->\n\n");
+        if entry.display.has_toc()
+            && let Some(toc) = table_of_contents(&entry.content)
+        {
+            markdown.push_str(&toc);
+            markdown.push('\n');
+        }
+        markdown.push_str(entry.content.trim_end());
+        markdown.push('\n');
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn save(storage: &Storage, id: &str, content: &str) {
+        let entry = Entry::new(id.parse().unwrap(), content.to_string());
+        storage.save_entry(&entry).unwrap();
+    }
+
+    #[test]
+    fn test_collect_date_range_oldest_first() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(&storage, "20250919", "one");
+        save(&storage, "20250921", "three");
+        save(&storage, "20250920", "two");
+
+        let entries = collect(&storage, Some("20250920".parse().unwrap()), None).unwrap();
+        let ids: Vec<String> = entries.iter().map(|e| e.id.to_string()).collect();
+        assert_eq!(ids, vec!["20250920", "20250921"]);
+    }
+
+    #[test]
+    fn test_markdown_and_json_export() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(&storage, "20250920", "Paired with @bob on ::devlog +rust");
+        let entries = collect(&storage, None, None).unwrap();
+
+        let markdown = render(&entries, ExportFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("---\ntitle: Devlog\ndate: 2025-09-20 to 2025-09-20\n"));
+        assert!(markdown.contains("\n## 2025-09-20\n"));
+        assert!(markdown.contains(", people: bob, projects: devlog, tags: rust -->"));
+        assert!(markdown.ends_with("Paired with @bob on ::devlog +rust\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&entries, ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["id"], "20250920");
+        assert_eq!(json[0]["date"], "2025-09-20");
+        assert_eq!(json[0]["annotations"]["people"][0], "bob");
+    }
+}
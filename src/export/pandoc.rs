@@ -0,0 +1,66 @@
+use std::{
+    io::{ErrorKind, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{self, DevlogError};
+
+/// Convert the combined Markdown export with pandoc, into `output` or to stdout.
+/// The export's frontmatter is a pandoc metadata block, so its title and date
+/// become the document's; the per entry metadata comments are dropped.
+pub fn convert(markdown: &str, to: &str, output: Option<&Path>) -> Result<()> {
+    let mut command = Command::new("pandoc");
+    command.args(args(to, output)).stdin(Stdio::piped());
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) if e.kind() == ErrorKind::NotFound => bail!(DevlogError::new(
+            error::ErrorKind::NotFound,
+            "pandoc is not installed, see https://pandoc.org/installing.html"
+        )),
+        Err(e) => return Err(e).wrap_err("Failed to run pandoc"),
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(markdown.as_bytes())
+            .wrap_err("Failed to send the export to pandoc")?;
+    }
+    let status = child.wait().wrap_err("Failed to run pandoc")?;
+    if !status.success() {
+        bail!("pandoc exited with {}", status);
+    }
+    Ok(())
+}
+
+fn args(to: &str, output: Option<&Path>) -> Vec<String> {
+    let mut args = vec![
+        "--from=markdown".to_string(),
+        format!("--to={}", to),
+        "--standalone".to_string(),
+    ];
+    if let Some(output) = output {
+        args.push(format!("--output={}", output.display()));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pandoc_args() {
+        assert_eq!(
+            args("docx", Some(Path::new("week.docx"))),
+            vec![
+                "--from=markdown",
+                "--to=docx",
+                "--standalone",
+                "--output=week.docx"
+            ]
+        );
+        assert_eq!(args("odt", None).len(), 3);
+    }
+}
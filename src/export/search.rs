@@ -0,0 +1,88 @@
+use chrono::NaiveDate;
+
+use crate::models::note::AnyId;
+use crate::search::EntryMatches;
+
+/// The day of a dated entry, `None` for a note
+fn date(id: &AnyId) -> Option<NaiveDate> {
+    match id {
+        AnyId::Entry(entry_ref) => Some(entry_ref.id.date()),
+        AnyId::Note(_) => None,
+    }
+}
+
+/// Search results as one Markdown document, a section per entry or note with
+/// the matching lines and `context` lines around them
+pub fn to_markdown(query: &str, results: &[EntryMatches], context: usize) -> String {
+    let title = format!("Search results for \"{}\"", query.replace('"', "'"));
+    let hits: usize = results.iter().map(|matches| matches.hits.len()).sum();
+
+    let mut markdown = format!("---\ntitle: '{}'\n", title.replace('\'', "''"));
+    let dates: Vec<NaiveDate> = results
+        .iter()
+        .filter_map(|matches| date(&matches.id))
+        .collect();
+    if let (Some(first), Some(last)) = (dates.first(), dates.last()) {
+        markdown.push_str(&format!("date: {} to {}\n", first, last));
+    }
+    markdown.push_str(&format!(
+        "entries: {}\nmatches: {}\n---\n\n# {}\n",
+        results.len(),
+        hits,
+        title
+    ));
+
+    for matches in results {
+        let heading = date(&matches.id).map_or_else(|| matches.id.to_string(), |d| d.to_string());
+        markdown.push_str(&format!("\n## {}\n\n", heading));
+        for (index, excerpt) in matches.excerpts(context).into_iter().enumerate() {
+            if index > 0 {
+                markdown.push_str("\n…\n\n");
+            }
+            // Entry headings are nested below the day or note name
+            for line in &matches.lines[excerpt] {
+                if line.starts_with('#') {
+                    markdown.push_str("##");
+                }
+                markdown.push_str(line);
+                markdown.push('\n');
+            }
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_grouped_by_entry() {
+        let results = vec![
+            EntryMatches {
+                id: "20250901".parse().unwrap(),
+                lines: vec![
+                    "# Incident".into(),
+                    "a".into(),
+                    "b".into(),
+                    "c".into(),
+                    "incident closed".into(),
+                ],
+                hits: vec![0, 4],
+            },
+            EntryMatches {
+                id: "20250920".parse().unwrap(),
+                lines: vec!["paged for an incident".into()],
+                hits: vec![0],
+            },
+        ];
+
+        assert_eq!(
+            to_markdown("incident", &results, 1),
+            "---\ntitle: 'Search results for \"incident\"'\ndate: 2025-09-01 to 2025-09-20\n\
+             entries: 2\nmatches: 3\n---\n\n# Search results for \"incident\"\n\
+             \n## 2025-09-01\n\n### Incident\na\n\n…\n\nc\nincident closed\n\
+             \n## 2025-09-20\n\npaged for an incident\n"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+use crate::utils::math;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "1";
+const ITALIC: &str = "3";
+const CROSSED_OUT: &str = "9";
+const HEADING: &str = "1;36";
+const CODE: &str = "32";
+const LINK: &str = "4;34";
+const DIM: &str = "90";
+const MATH: &str = "3;96";
+
+/// Render entry Markdown for the terminal with ANSI styles: headings, emphasis, code,
+/// links with their URL, lists, task lists, quotes, definition lists, footnotes and math
+/// as its Unicode approximation
+pub fn markdown_to_ansi(markdown: &str) -> String {
+    let options = Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_DEFINITION_LIST
+        | Options::ENABLE_MATH;
+    let mut renderer = Renderer::default();
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.end_block();
+    renderer.out
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// SGR codes of the open inline styles, applied on top of each other
+    styles: Vec<&'static str>,
+    /// Number of the next item of each open list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    /// Marker of a list item, written in front of its first line
+    marker: Option<String>,
+    quote_depth: usize,
+    /// URLs of the open links, printed after their text
+    links: Vec<String>,
+    in_code_block: bool,
+    in_definition: bool,
+    at_line_start: bool,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.in_code_block => {
+                self.styles.push(CODE);
+                self.write(text.trim_end_matches('\n'));
+                self.styles.pop();
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => self.write(&text),
+            Event::Code(code) => self.styled(CODE, &code),
+            Event::InlineMath(latex) | Event::DisplayMath(latex) => {
+                self.styled(MATH, &math::to_unicode(&latex))
+            }
+            Event::FootnoteReference(name) => self.styled(DIM, &format!("[^{}]", name)),
+            Event::TaskListMarker(done) => self.write(if done { "☑ " } else { "☐ " }),
+            Event::SoftBreak | Event::HardBreak => self.newline(),
+            Event::Rule => {
+                self.start_block();
+                self.styled(DIM, &"─".repeat(40));
+                self.end_block();
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.start_block(),
+            Tag::Heading { .. } => {
+                self.start_block();
+                self.styles.push(HEADING);
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                if let CodeBlockKind::Fenced(lang) = kind
+                    && !lang.is_empty()
+                {
+                    self.styled(DIM, &lang);
+                    self.newline();
+                }
+                self.in_code_block = true;
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.quote_depth += 1;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else if !self.at_line_start {
+                    self.newline();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                if !self.at_line_start {
+                    self.newline();
+                }
+                self.marker = Some(match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                });
+            }
+            Tag::FootnoteDefinition(name) => {
+                self.start_block();
+                self.styled(DIM, &format!("[^{}]: ", name));
+            }
+            Tag::DefinitionList => self.start_block(),
+            Tag::DefinitionListTitle => {
+                if !self.at_line_start {
+                    self.newline();
+                }
+                self.styles.push(BOLD);
+            }
+            Tag::DefinitionListDefinition => {
+                if !self.at_line_start {
+                    self.newline();
+                }
+                self.in_definition = true;
+            }
+            Tag::Emphasis => self.styles.push(ITALIC),
+            Tag::Strong => self.styles.push(BOLD),
+            Tag::Strikethrough => self.styles.push(CROSSED_OUT),
+            Tag::Link { dest_url, .. } => {
+                self.styles.push(LINK);
+                self.links.push(dest_url.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::FootnoteDefinition | TagEnd::DefinitionList => {
+                self.end_block()
+            }
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.end_block();
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.end_block();
+                self.quote_depth -= 1;
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.end_block();
+            }
+            TagEnd::Item => self.end_block(),
+            TagEnd::DefinitionListTitle => {
+                self.styles.pop();
+                self.end_block();
+            }
+            TagEnd::DefinitionListDefinition => {
+                self.end_block();
+                self.in_definition = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+                if let Some(url) = self.links.pop() {
+                    self.styled(DIM, &format!(" ({})", url));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Blocks outside of lists are separated by an empty line
+    fn start_block(&mut self) {
+        if !self.at_line_start && !self.out.is_empty() {
+            self.newline();
+        }
+        if self.lists.is_empty() && !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn end_block(&mut self) {
+        if !self.at_line_start && !self.out.is_empty() {
+            self.newline();
+        }
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        self.at_line_start = true;
+    }
+
+    fn styled(&mut self, style: &'static str, text: &str) {
+        self.styles.push(style);
+        self.write(text);
+        self.styles.pop();
+    }
+
+    /// Write text in the open styles, starting each line with the quote bars and
+    /// indentation of the blocks it is in
+    fn write(&mut self, text: &str) {
+        for (n, line) in text.split('\n').enumerate() {
+            if n > 0 {
+                self.newline();
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.at_line_start || self.out.is_empty() {
+                self.line_prefix();
+            }
+            if self.styles.is_empty() {
+                self.out.push_str(line);
+            } else {
+                self.out
+                    .push_str(&format!("\x1b[{}m{}{}", self.styles.join(";"), line, RESET));
+            }
+        }
+    }
+
+    fn line_prefix(&mut self) {
+        for _ in 0..self.quote_depth {
+            self.out.push_str(&format!("\x1b[{}m│{} ", DIM, RESET));
+        }
+        let depth = self.lists.len();
+        match self.marker.take() {
+            Some(marker) => {
+                self.out.push_str(&"  ".repeat(depth.saturating_sub(1)));
+                self.out.push_str(&marker);
+            }
+            None => self.out.push_str(&"  ".repeat(depth)),
+        }
+        if self.in_code_block || self.in_definition {
+            self.out.push_str("    ");
+        }
+        self.at_line_start = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_ansi() {
+        let rendered = markdown_to_ansi(
+            "# Done\n\nShipped **the fix**, see [PR](https://example.com).\n\n\
+             - one\n- [x] two $x^2$\n  1. nested\n\n> quoted\n\n```rust\nlet x = 1;\n```\n\nCrate\n: A package\n",
+        );
+
+        assert_eq!(
+            rendered,
+            "\x1b[1;36mDone\x1b[0m\n\
+             \n\
+             Shipped \x1b[1mthe fix\x1b[0m, see \x1b[4;34mPR\x1b[0m\x1b[90m (https://example.com)\x1b[0m.\n\
+             \n\
+             • one\n\
+             • ☑ two \x1b[3;96mx²\x1b[0m\n\
+             \x20 1. nested\n\
+             \n\
+             \x1b[90m│\x1b[0m quoted\n\
+             \n\
+             \x1b[90mrust\x1b[0m\n\
+             \x20   \x1b[32mlet x = 1;\x1b[0m\n\
+             \n\
+             \x1b[1mCrate\x1b[0m\n\
+             \x20   A package\n"
+        );
+    }
+}
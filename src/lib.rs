@@ -0,0 +1,17 @@
+pub mod ai;
+pub mod capture;
+pub mod commands;
+pub mod config;
+pub mod digest;
+pub mod editing;
+pub mod error;
+pub mod export;
+pub mod models;
+pub mod publish;
+pub mod search;
+pub mod serve;
+pub mod stats;
+pub mod storage;
+pub mod sync;
+pub mod tui;
+pub mod utils;
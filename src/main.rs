@@ -1,19 +1,83 @@
-use clap::{Parser, Subcommand};
+use std::{env, path::PathBuf, process};
 
-use crate::{commands::config::ConfigSubcommand, storage::Storage};
+use chrono::{DateTime, FixedOffset, Local};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::CompleteEnv;
+use color_eyre::eyre::Result;
 
-mod commands;
-mod config;
-mod models;
-mod storage;
-mod tui;
-mod utils;
+use devlog::{
+    commands::{
+        self,
+        ai::AiSubcommand,
+        annotate::AnnotateSubcommand,
+        backup::BackupSubcommand,
+        bench::BenchSubcommand,
+        capture::CaptureSubcommand,
+        completions::{Shell, COMPLETE_VAR, annotation_candidates},
+        config::ConfigSubcommand,
+        demo::DemoSubcommand,
+        digest::DigestSubcommand,
+        edit::ScriptedEdit,
+        focus::FocusSubcommand,
+        hook::HookSubcommand,
+        index::IndexSubcommand,
+        list::ListFilter,
+        meeting::MeetingSubcommand,
+        migrate::MigrateSubcommand,
+        project::ProjectSubcommand,
+        prompt_hook::PromptShell,
+        scratch::ScratchSubcommand,
+        serve::ServeSubcommand,
+        show::ShowFormat,
+        stats::StatsSubcommand,
+        todo::TodoSubcommand,
+        trash::TrashSubcommand,
+    },
+    config::{
+        Config, ConfigStore, PROFILE_ENV,
+        secrets::{self, SecretStore},
+    },
+    error::{self, DevlogError, ErrorKind},
+    export::ExportFormat,
+    models::{
+        annotation::AnnotationKind,
+        entry_id::{EntryId, EntryRef},
+        frontmatter::FieldMatch,
+        note::AnyId,
+    },
+    publish::PublishTarget,
+    search, status,
+    storage::{CONFIG_DIR_ENV, DATA_DIR_ENV, Storage, section::SectionTarget},
+    utils::{deprecation, output, workspace::Workspace},
+};
 
 #[derive(Parser)]
 #[command(name = env!("CARGO_PKG_NAME"))]
 #[command(about = env!("CARGO_PKG_DESCRIPTION"))]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 struct Cli {
+    /// Show which files would be written or deleted without changing anything
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Only print requested data and errors, no progress or success messages
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Journal to use, one of `devlog config profile list`; also read from DEVLOG_PROFILE
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Store entries in this directory instead of the default one; also read from
+    /// DEVLOG_DATA_DIR
+    #[arg(long, global = true, value_name = "PATH")]
+    data_dir: Option<PathBuf>,
+
+    /// Scope list, search and new to the `::project` of the git repository you are in,
+    /// see `workspaces` in the config
+    #[arg(long, global = true)]
+    here: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -22,50 +86,744 @@ struct Cli {
 enum Commands {
     /// Create a new entry
     New {
-        /// Entry ID to create (format: YYYYMMDD)
-        #[arg(long, value_name = "YYYYMMDD")]
-        id: Option<String>,
+        /// Entry ID to create (format: YYYYMMDD) or note name, defaults to today's entry
+        #[arg(value_name = "ID")]
+        id: Option<AnyId>,
+        /// Deprecated spelling of the entry id argument
+        #[arg(
+            long = "id",
+            value_name = "YYYYMMDD",
+            hide = true,
+            conflicts_with = "id"
+        )]
+        legacy_id: Option<EntryId>,
+        /// Append the message as a timestamped `## HH:MM` sub-entry instead of opening the
+        /// editor, notes get it as a paragraph
+        #[arg(short, long)]
+        message: Option<String>,
+        /// Read the content from stdin like --message, e.g. `git log -1 | devlog new --stdin`
+        #[arg(long, conflicts_with = "message")]
+        stdin: bool,
+        /// Add the content to the end of the existing entry as it is, without a `## HH:MM`
+        /// heading
+        #[arg(long)]
+        append: bool,
+        /// Replace an existing entry or note instead of opening it for editing, with the
+        /// editor or the --message/--stdin content
+        #[arg(long, conflicts_with = "append")]
+        force: bool,
+        /// Ask for the custom frontmatter fields of the config, e.g. mood, afterwards
+        #[arg(long)]
+        prompt_fields: bool,
     },
     /// Edit an existing entry
     Edit {
-        /// Entry ID to edit (format: YYYYMMDD)
-        #[arg(long, value_name = "YYYYMMDD")]
-        id: String,
+        /// Entry ID to edit (format: YYYYMMDD) or note name
+        #[arg(value_name = "ID", required_unless_present = "legacy_id")]
+        id: Option<AnyId>,
+        /// Deprecated spelling of the entry id argument
+        #[arg(
+            long = "id",
+            value_name = "YYYYMMDD",
+            hide = true,
+            conflicts_with = "id"
+        )]
+        legacy_id: Option<EntryId>,
+        /// Apply a unified diff to the entry instead of opening the editor
+        #[arg(long, value_name = "PATCH")]
+        apply: Option<PathBuf>,
+        /// Replace every match of a regex instead of opening the editor, `$1` refers to a group
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["REGEX", "REPLACEMENT"],
+            conflicts_with = "apply"
+        )]
+        replace: Option<Vec<String>>,
     },
     /// Show an entry
     Show {
-        /// Entry ID to display (format: YYYYMMDD)
+        /// Entry ID to display (format: YYYYMMDD), YYYYMMDD-HHMM for a sub-entry or a note name
+        #[arg(value_name = "ID", required_unless_present = "legacy_id")]
+        id: Option<AnyId>,
+        /// Deprecated spelling of the entry id argument
+        #[arg(
+            long = "id",
+            value_name = "YYYYMMDD",
+            hide = true,
+            conflicts_with = "id"
+        )]
+        legacy_id: Option<EntryId>,
+        /// How to print the entry: text, markdown, rendered, json or yaml
+        #[arg(long, value_enum, default_value_t = ShowFormat::Text)]
+        format: ShowFormat,
+        /// Show the entry as it was at this time, e.g. "2025-09-20 15:00"
+        #[arg(long, value_name = "TIME", value_parser = commands::show::parse_as_of)]
+        as_of: Option<DateTime<FixedOffset>>,
+    },
+    /// Render an entry to HTML and serve it on localhost, today's by default
+    Preview {
+        /// Entry to preview
+        #[arg(value_name = "YYYYMMDD")]
+        id: Option<EntryId>,
+        /// Reload the page whenever the entry is saved
+        #[arg(long)]
+        watch: bool,
+        /// Port to listen on, on 127.0.0.1 only
+        #[arg(long, default_value_t = 7879)]
+        port: u16,
+    },
+    /// Move an entry, its events and attachments to the trash
+    Delete {
+        /// Entry ID to delete (format: YYYYMMDD)
+        #[arg(value_name = "YYYYMMDD", required_unless_present = "legacy_id")]
+        id: Option<EntryId>,
+        /// Deprecated spelling of the entry id argument
+        #[arg(
+            long = "id",
+            value_name = "YYYYMMDD",
+            hide = true,
+            conflicts_with = "id"
+        )]
+        legacy_id: Option<EntryId>,
+    },
+    /// Attach a file or the image on the clipboard to an entry
+    Attach {
+        /// File to attach
+        #[arg(required_unless_present = "clipboard", conflicts_with = "clipboard")]
+        file: Option<PathBuf>,
+        /// Attach the image on the clipboard
+        #[arg(long)]
+        clipboard: bool,
+        /// Entry to attach to (format: YYYYMMDD), defaults to today
         #[arg(long, value_name = "YYYYMMDD")]
-        id: String,
+        entry: Option<EntryId>,
+    },
+    /// Move a heading's section to another entry or a `::project` ledger
+    MoveSection {
+        /// Entry the section is in (format: YYYYMMDD), or a sub-entry like 20250920-1432
+        #[arg(value_name = "ID")]
+        from: EntryRef,
+        /// Heading of the section, e.g. "Design review", not needed for a sub-entry
+        heading: Option<String>,
+        /// Entry (format: YYYYMMDD) or `::project` to move the section to
+        #[arg(long, value_name = "TARGET")]
+        to: SectionTarget,
+    },
+    /// Show the saves of an entry and the lines each one changed
+    History {
+        /// Entry ID (format: YYYYMMDD)
+        #[arg(value_name = "YYYYMMDD")]
+        id: EntryId,
+        /// Include the events archived when the log was compacted
+        #[arg(long)]
+        deep: bool,
+    },
+    /// Put an entry back to its content after an earlier save
+    Revert {
+        /// Entry ID (format: YYYYMMDD)
+        #[arg(value_name = "YYYYMMDD")]
+        id: EntryId,
+        /// Number of the save in `devlog history`
+        #[arg(long, value_name = "N")]
+        to: usize,
+        /// Count saves like `devlog history --deep`
+        #[arg(long)]
+        deep: bool,
+    },
+    /// List or restore deleted entries
+    Trash {
+        #[command(subcommand)]
+        subcmd: TrashSubcommand,
     },
     /// List entries
     List {
         /// Launch interactive TUI mode
         #[arg(short, long)]
         interactive: bool,
+        #[command(flatten)]
+        filter: ListFilter,
+    },
+    /// List `+tags` by how often they're used
+    Tags {
+        /// Also print the entries each tag appears in
+        #[arg(long)]
+        dates: bool,
+    },
+    /// List `@people` by how often they're mentioned
+    People {
+        /// Also print the entries each person appears in
+        #[arg(long)]
+        dates: bool,
+    },
+    /// List `::projects` by how often they're referenced
+    Projects {
+        /// Also print the entries each project appears in
+        #[arg(long)]
+        dates: bool,
+    },
+    /// Add or remove an annotation across many entries at once, after a preview
+    Annotate {
+        #[command(subcommand)]
+        subcmd: AnnotateSubcommand,
+    },
+    /// Find the entries mentioning a word or phrase, ignoring case
+    Search {
+        query: String,
+        /// Lines of context around each match in the export
+        #[arg(short = 'C', long, default_value_t = search::DEFAULT_CONTEXT)]
+        context: usize,
+        /// Write the matches, grouped by entry with context, to a Markdown file
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+        /// Only entries with this custom frontmatter field, e.g. mood=tired, may be repeated
+        #[arg(long = "where", value_name = "FIELD=VALUE")]
+        fields: Vec<FieldMatch>,
+    },
+    /// Print the lines of entries matching a regex, for scripts and editor plugins
+    Grep {
+        pattern: String,
+        /// One JSON object per matching line, with the byte ranges of the matches
+        #[arg(long)]
+        json: bool,
+        /// Only print the paths of the matching entry files
+        #[arg(short = 'l', long)]
+        files_with_matches: bool,
+    },
+    /// Export entries as one Markdown, JSON or HTML document
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// First entry to include, e.g. 20250901
+        #[arg(long, value_name = "YYYYMMDD")]
+        since: Option<EntryId>,
+        /// Last entry to include
+        #[arg(long, value_name = "YYYYMMDD")]
+        until: Option<EntryId>,
+        /// Convert the Markdown export with pandoc, e.g. `--via-pandoc --to docx`
+        #[arg(long, requires = "to", conflicts_with = "format")]
+        via_pandoc: bool,
+        /// Pandoc output format, e.g. docx, odt or pdf
+        #[arg(long, value_name = "FORMAT", requires = "via_pandoc")]
+        to: Option<String>,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Project profiles built from `::project` mentions
+    Project {
+        #[command(subcommand)]
+        subcmd: ProjectSubcommand,
+    },
+    /// Maintain the entry index behind listings, annotations and status lines
+    Index {
+        #[command(subcommand)]
+        subcmd: IndexSubcommand,
     },
     /// Configure Devlog settings
     Config {
         #[command(subcommand)]
         subcmd: Option<ConfigSubcommand>,
     },
+    /// Capture entries from other sources, e.g. email from a phone
+    Capture {
+        #[command(subcommand)]
+        subcmd: CaptureSubcommand,
+    },
+    /// Notes that don't belong to a day yet, shown with F2 in the TUI
+    Scratch {
+        #[command(subcommand)]
+        subcmd: Option<ScratchSubcommand>,
+    },
+    /// Archive the vault, manually or weekly in the background
+    Backup {
+        #[command(subcommand)]
+        subcmd: BackupSubcommand,
+    },
+    /// Publish entries, or the weekly digest, as Confluence or Notion pages
+    Publish {
+        /// Where to publish to, configured under `publish` in the config
+        target: PublishTarget,
+        /// Entries to publish, today's if none are selected
+        #[arg(value_name = "YYYYMMDD")]
+        ids: Vec<EntryId>,
+        /// Publish the entries from this day on
+        #[arg(long, value_name = "YYYYMMDD", conflicts_with = "ids")]
+        since: Option<EntryId>,
+        /// Publish the entries up to this day, or the digest of the week ending on it
+        #[arg(long, value_name = "YYYYMMDD", conflicts_with = "ids")]
+        until: Option<EntryId>,
+        /// Publish the weekly digest instead of entries
+        #[arg(long, conflicts_with_all = ["ids", "since"])]
+        digest: bool,
+    },
+    /// Sync the vault with the folder set in `sync.remote`, keeping both versions on conflicts
+    Sync {
+        /// Only list what would be pulled, pushed, merged or deleted
+        #[arg(long)]
+        status: bool,
+        /// Leave files deleted on one side alone on the other, until a sync without this flag
+        #[arg(long)]
+        no_delete: bool,
+    },
+    /// List open `->` action items, overdue ones first
+    Actions {
+        /// Only actions assigned to this person, e.g. `alice`
+        #[arg(long, value_name = "PERSON", add = annotation_candidates(AnnotationKind::Person))]
+        waiting_on: Option<String>,
+        /// Only actions owed by this person, `me` for your own
+        #[arg(long, value_name = "PERSON", add = annotation_candidates(AnnotationKind::Person))]
+        owed_by: Option<String>,
+    },
+    /// Take structured meeting notes with action items
+    Meeting {
+        #[command(subcommand)]
+        subcmd: MeetingSubcommand,
+    },
+    /// Focus timer, optionally locking every entry but today's while it runs
+    Focus {
+        #[command(subcommand)]
+        subcmd: FocusSubcommand,
+    },
+    /// List open todos across entries and check them off
+    Todo {
+        #[command(subcommand)]
+        subcmd: TodoSubcommand,
+    },
+    /// Log git commits in today's entry with a post-commit hook
+    Hook {
+        #[command(subcommand)]
+        subcmd: HookSubcommand,
+    },
+    /// Summaries written by a language model, see `ai` in the config
+    Ai {
+        #[command(subcommand)]
+        subcmd: AiSubcommand,
+    },
+    /// Weekly digest of your entries, compiled on a schedule
+    Digest {
+        #[command(subcommand)]
+        subcmd: DigestSubcommand,
+    },
+    /// Statistics about your writing habits: streaks, word counts, entries per month
+    /// and the most used annotations
+    Stats {
+        /// Number of months to count entries for, ending with the current one
+        #[arg(long, default_value_t = 12)]
+        months: u32,
+        /// Number of tags, people and projects to list
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+        #[command(subcommand)]
+        subcmd: Option<StatsSubcommand>,
+    },
+    /// Run a local endpoint the browser extension sends clipped text to
+    Serve {
+        /// Port to listen on, on 127.0.0.1 only
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+        #[command(subcommand)]
+        subcmd: Option<ServeSubcommand>,
+    },
+    /// Print streak, today's word count and open todos for a tmux or prompt status line
+    TmuxStatus {
+        /// Recompute the stats from every entry, e.g. after editing files by hand
+        #[arg(long)]
+        rebuild: bool,
+    },
+    /// Print a prompt indicator while today's entry is missing or short,
+    /// or with a shell, the script that adds it: `eval "$(devlog prompt-hook zsh)"`
+    PromptHook {
+        /// Shell to print the init script for
+        shell: Option<PromptShell>,
+    },
+    /// Benchmarking and profiling helpers
+    Bench {
+        #[command(subcommand)]
+        subcmd: BenchSubcommand,
+    },
+    /// Explore devlog with a generated vault
+    Demo {
+        #[command(subcommand)]
+        subcmd: DemoSubcommand,
+    },
+    /// Bring data from older devlog layouts into the vault
+    Migrate {
+        #[command(subcommand)]
+        subcmd: MigrateSubcommand,
+    },
+    /// Rewrite deprecated devlog invocations in shell startup files and scripts
+    MigrateCli {
+        /// Additional scripts to check
+        scripts: Vec<PathBuf>,
+        /// Apply the changes instead of only showing them
+        #[arg(long)]
+        write: bool,
+    },
+    /// Suggest improvements to the vault, the report holds no entry content
+    Analyze,
+    /// Show version, paths and environment details for bug reports
+    Info,
+    /// Print the shell completion script, e.g. `source <(devlog completions bash)`
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
 }
 
 fn main() {
-    let cli = Cli::parse();
-    let storage = Storage::new().unwrap_or_else(|e| {
-        eprintln!("Failed to initialize storage: {}", e);
-        std::process::exit(1);
+    // Answers completion requests from the registered shell script and exits
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    let cli = Cli::try_parse().unwrap_or_else(|e| {
+        // Malformed values like a bad entry id get the validation exit code,
+        // everything else keeps clap's own (0 for --help, 2 for usage errors)
+        let code = match e.kind() {
+            clap::error::ErrorKind::InvalidValue | clap::error::ErrorKind::ValueValidation => {
+                ErrorKind::Validation.exit_code()
+            }
+            _ => e.exit_code(),
+        };
+        let _ = e.print();
+        process::exit(code);
+    });
+    output::set_quiet(cli.quiet);
+
+    let storage = Storage::new()
+        .map(|storage| storage.with_dry_run(cli.dry_run))
+        .and_then(|storage| match env_path(CONFIG_DIR_ENV) {
+            Some(config_dir) => storage.with_config_dir(&config_dir),
+            None => Ok(storage),
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to initialize storage: {}", e);
+            process::exit(error::exit_code(&e));
+        });
+
+    let config_store = ConfigStore::new(storage.config_path());
+    if !cli.dry_run {
+        // Keep plaintext credentials in place if the keychain is unavailable
+        match secrets::migrate_plaintext_secrets(&config_store, |name, value| {
+            SecretStore.set(name, value)
+        }) {
+            Ok(0) => {}
+            Ok(count) => status!(
+                "Moved {} credential(s) from config.toml to the OS keychain",
+                count
+            ),
+            Err(e) => eprintln!("Warning: {}", e),
+        }
+    }
+
+    // A broken config must not lock users out, `devlog config` is how they fix it
+    let mut config = config_store.load().unwrap_or_else(|e| {
+        eprintln!("Warning: {}, using default settings", e);
+        Config::default()
     });
 
+    let requested_profile = cli
+        .profile
+        .clone()
+        .or_else(|| env::var(PROFILE_ENV).ok().filter(|name| !name.is_empty()));
+    let profile = config
+        .active_profile(requested_profile.as_deref())
+        .map(|profile| profile.map(|(name, profile)| (name.to_string(), profile.clone())))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(error::exit_code(&e));
+        });
+    let storage = match &profile {
+        Some((name, profile)) => storage
+            .with_profile(name, &profile.data_path)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to initialize storage: {}", e);
+                process::exit(error::exit_code(&e));
+            }),
+        None => storage,
+    };
+    if let Some((_, profile)) = &profile {
+        config.sync = profile.sync.clone();
+    }
+    // An explicit data directory wins over the XDG default and the profile's
+    let storage = match cli.data_dir.clone().or_else(|| env_path(DATA_DIR_ENV)) {
+        Some(data_dir) => storage.with_data_dir(&data_dir).unwrap_or_else(|e| {
+            eprintln!("Failed to initialize storage: {}", e);
+            process::exit(error::exit_code(&e));
+        }),
+        None => storage,
+    };
+    let storage = storage
+        .with_line_ending(config.storage.line_ending)
+        .with_kept_versions(config.storage.keep_versions)
+        .with_event_snapshots(config.storage.snapshot_events);
+
+    let workspace = if cli.here {
+        Some(Workspace::detect(&config.workspaces).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(error::exit_code(&e));
+        }))
+    } else {
+        None
+    };
+
+    // Status line and completion helpers run constantly and must stay fast
+    let run_background_jobs = !cli.dry_run
+        && !matches!(
+            cli.command,
+            Commands::TmuxStatus { .. }
+                | Commands::PromptHook { .. }
+                | Commands::Completions { .. }
+        );
+
     if let Err(e) = match cli.command {
-        Commands::New { id } => commands::new::execute(&storage, id),
-        Commands::Edit { id } => commands::edit::execute(&storage, id),
-        Commands::Show { id } => commands::show::execute(&storage, id),
-        Commands::List { interactive } => commands::list::execute(&storage, interactive),
-        Commands::Config { subcmd } => commands::config::execute(subcmd),
+        Commands::New {
+            id,
+            legacy_id,
+            message,
+            stdin,
+            append,
+            force,
+            prompt_fields,
+        } => {
+            let id = resolve_any_id(id, legacy_id);
+            let content = match message {
+                Some(message) => commands::new::Content::Message(message),
+                None if stdin => commands::new::Content::Stdin,
+                None => commands::new::Content::Editor,
+            };
+            // Without `--here` the project of a known repository is still mentioned
+            let workspace = match workspace {
+                Some(workspace) => Ok(Some(workspace)),
+                None => commands::new::detect_workspace(&storage, &config),
+            };
+            workspace
+                .and_then(|workspace| {
+                    commands::new::execute(
+                        &storage,
+                        id.clone(),
+                        content,
+                        append,
+                        force,
+                        config.todo.carry_forward,
+                        workspace.as_ref(),
+                    )
+                })
+                .and_then(|()| {
+                    if prompt_fields {
+                        commands::new::prompt_fields(&storage, id.as_ref(), &config.fields)
+                    } else {
+                        Ok(())
+                    }
+                })
+        }
+        Commands::Edit {
+            id,
+            legacy_id,
+            apply,
+            replace,
+        } => {
+            let script = match (&apply, &replace) {
+                (Some(path), _) => Some(ScriptedEdit::Patch(path)),
+                (None, Some(replace)) => Some(ScriptedEdit::Replace {
+                    pattern: &replace[0],
+                    replacement: &replace[1],
+                }),
+                (None, None) => None,
+            };
+            required_any_id(id, legacy_id)
+                .and_then(|id| commands::edit::execute(&storage, id, script))
+        }
+        Commands::Show {
+            id,
+            legacy_id,
+            format,
+            as_of,
+        } => required_any_id(id, legacy_id)
+            .and_then(|id| commands::show::execute(&storage, id, format, as_of)),
+        Commands::Preview { id, watch, port } => {
+            commands::preview::execute(&storage, id.unwrap_or_else(EntryId::today), port, watch)
+        }
+        Commands::Delete { id, legacy_id } => {
+            required_id(id, legacy_id).and_then(|id| commands::delete::execute(&storage, id))
+        }
+        Commands::Attach {
+            file,
+            clipboard,
+            entry,
+        } => commands::attach::execute(
+            &storage,
+            entry.unwrap_or_else(EntryId::today),
+            file.as_deref(),
+            clipboard,
+        ),
+        Commands::MoveSection { from, heading, to } => {
+            commands::move_section::execute(&storage, from, heading, to)
+        }
+        Commands::History { id, deep } => commands::history::execute(&storage, id, deep),
+        Commands::Revert { id, to, deep } => commands::history::revert(&storage, id, to, deep),
+        Commands::Trash { subcmd } => commands::trash::execute(&storage, subcmd),
+        Commands::List {
+            interactive,
+            filter,
+        } => commands::list::execute(
+            &storage,
+            interactive,
+            match &workspace {
+                Some(workspace) => filter.with_project(&workspace.project),
+                None => filter,
+            },
+            &config.list.columns,
+        ),
+        Commands::Tags { dates } => {
+            commands::annotations::execute(&storage, AnnotationKind::Tag, dates)
+        }
+        Commands::People { dates } => {
+            commands::annotations::execute(&storage, AnnotationKind::Person, dates)
+        }
+        Commands::Projects { dates } => {
+            commands::annotations::execute(&storage, AnnotationKind::Project, dates)
+        }
+        Commands::Annotate { subcmd } => commands::annotate::execute(&storage, subcmd),
+        Commands::Search {
+            query,
+            context,
+            export,
+            fields,
+        } => commands::search::execute(
+            &storage,
+            &query,
+            context,
+            export,
+            workspace
+                .as_ref()
+                .map(|workspace| workspace.project.as_str()),
+            &fields,
+        ),
+        Commands::Grep {
+            pattern,
+            json,
+            files_with_matches,
+        } => commands::grep::execute(&storage, &pattern, json, files_with_matches),
+        Commands::Export {
+            format,
+            since,
+            until,
+            via_pandoc: _,
+            to,
+            output,
+        } => commands::export::execute(&storage, format, since, until, to, output),
+        Commands::Project { subcmd } => commands::project::execute(&storage, subcmd),
+        Commands::Index { subcmd } => commands::index::execute(&storage, subcmd),
+        Commands::Config { subcmd } => commands::config::execute(
+            &storage,
+            subcmd,
+            profile.as_ref().map(|(name, _)| name.as_str()),
+        ),
+        Commands::Capture { subcmd } => commands::capture::execute(&storage, subcmd),
+        Commands::Scratch { subcmd } => commands::scratch::execute(&storage, subcmd),
+        Commands::Backup { subcmd } => commands::backup::execute(&storage, subcmd),
+        Commands::Publish {
+            target,
+            ids,
+            since,
+            until,
+            digest,
+        } => commands::publish::execute(
+            &storage,
+            target,
+            commands::publish::Selection {
+                ids,
+                since,
+                until,
+                digest,
+            },
+        ),
+        Commands::Sync { status, no_delete } => {
+            commands::sync::execute(&storage, &config, status, !no_delete)
+        }
+        Commands::Actions {
+            waiting_on,
+            owed_by,
+        } => commands::actions::execute(&storage, waiting_on, owed_by),
+        Commands::Meeting { subcmd } => commands::meeting::execute(&storage, subcmd),
+        Commands::Focus { subcmd } => commands::focus::execute(&storage, &config.focus, subcmd),
+        Commands::Todo { subcmd } => commands::todo::execute(&storage, subcmd),
+        Commands::Hook { subcmd } => commands::hook::execute(&storage, &config.hook, subcmd),
+        Commands::Ai { subcmd } => commands::ai::execute(&storage, subcmd),
+        Commands::Digest { subcmd } => commands::digest::execute(&storage, subcmd),
+        Commands::Stats {
+            months,
+            top,
+            subcmd,
+        } => commands::stats::execute(&storage, &config, months, top, subcmd),
+        Commands::Serve { port, subcmd } => commands::serve::execute(&storage, port, subcmd),
+        Commands::TmuxStatus { rebuild } => commands::tmux_status::execute(&storage, rebuild),
+        Commands::PromptHook { shell } => {
+            commands::prompt_hook::execute(&storage, &config.prompt, shell)
+        }
+        Commands::Bench { subcmd } => commands::bench::execute(&storage, subcmd),
+        Commands::Demo { subcmd } => commands::demo::execute(&storage, subcmd),
+        Commands::Migrate { subcmd } => commands::migrate::execute(&storage, subcmd),
+        Commands::MigrateCli { scripts, write } => {
+            commands::migrate_cli::execute(&storage, scripts, write)
+        }
+        Commands::Analyze => commands::analyze::execute(&storage, &config),
+        Commands::Info => commands::info::execute(&storage),
+        Commands::Completions { shell } => commands::completions::execute(shell),
     } {
         eprintln!("Error: {}", e);
-        std::process::exit(1);
+        process::exit(error::exit_code(&e));
+    }
+
+    if run_background_jobs {
+        match storage.backup_if_due(&config.backup, Local::now().naive_local()) {
+            Ok(Some(path)) => status!("Weekly backup written to {}", path.display()),
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: Weekly backup failed: {}", e),
+        }
+        match storage.digest_if_due(&config.digest, Local::now()) {
+            Ok(Some(run)) => status!("Weekly digest of {} to {} compiled", run.start, run.end),
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: Weekly digest failed: {}", e),
+        }
     }
 }
+
+/// Entry id given either as an argument or through the deprecated `--id` flag
+fn resolve_id(id: Option<EntryId>, legacy_id: Option<EntryId>) -> Option<EntryId> {
+    if legacy_id.is_some() {
+        deprecation::ID_FLAG.warn();
+    }
+    id.or(legacy_id)
+}
+
+fn required_id(id: Option<EntryId>, legacy_id: Option<EntryId>) -> Result<EntryId> {
+    resolve_id(id, legacy_id).ok_or_else(|| {
+        DevlogError::new(ErrorKind::Validation, "An entry id (YYYYMMDD) is required").into()
+    })
+}
+
+/// Like `resolve_id` for commands that also take note names, the legacy flag only takes dates
+fn resolve_any_id(id: Option<AnyId>, legacy_id: Option<EntryId>) -> Option<AnyId> {
+    id.or_else(|| resolve_id(None, legacy_id).map(|id| AnyId::Entry(id.into())))
+}
+
+fn required_any_id(id: Option<AnyId>, legacy_id: Option<EntryId>) -> Result<AnyId> {
+    resolve_any_id(id, legacy_id).ok_or_else(|| {
+        DevlogError::new(
+            ErrorKind::Validation,
+            "An entry id (YYYYMMDD) or note name is required",
+        )
+        .into()
+    })
+}
+
+/// A path from an environment variable, unset and empty values are ignored
+fn env_path(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::models::meeting::ACTION_MARKER;
+
+/// Marker of an action item's due date, e.g. `-> @alice: send the doc due:2025-10-20`
+pub const DUE_MARKER: &str = "due:";
+
+/// Who an action item is assigned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Owner {
+    /// Unassigned actions and `-> @me:` ones
+    Me,
+    Person(String),
+}
+
+impl Owner {
+    /// Parse a name given on the command line, `me`, `alice` or `@alice`
+    pub fn parse(name: &str) -> Self {
+        match name.trim().trim_start_matches('@') {
+            "me" => Owner::Me,
+            name => Owner::Person(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Owner::Me => write!(f, "me"),
+            Owner::Person(name) => write!(f, "@{}", name),
+        }
+    }
+}
+
+/// An open `->` todo, e.g. `- [ ] -> @alice: send the doc due:2025-10-20`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Action {
+    pub owner: Owner,
+    pub task: String,
+    pub due: Option<NaiveDate>,
+}
+
+impl Action {
+    /// Parse the text of an open todo, todos not starting with `->` aren't actions
+    pub fn parse(todo: &str) -> Option<Self> {
+        let rest = todo.trim().strip_prefix(ACTION_MARKER)?.trim_start();
+
+        let (owner, task) = match rest
+            .strip_prefix('@')
+            .and_then(|assigned| assigned.split_once(':'))
+            .filter(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+        {
+            Some((name, task)) => (Owner::parse(name), task),
+            None => (Owner::Me, rest),
+        };
+
+        let mut due = None;
+        let task = task
+            .split_whitespace()
+            .filter(|word| {
+                match word
+                    .strip_prefix(DUE_MARKER)
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                {
+                    Some(date) => {
+                        due = Some(date);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        (!task.is_empty()).then_some(Action { owner, task, due })
+    }
+
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.due.is_some_and(|due| due < today)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_assigned_action() {
+        let action = Action::parse("-> @alice: send the doc due:2025-10-20 (14:58)").unwrap();
+        assert_eq!(action.owner, Owner::Person("alice".to_string()));
+        assert_eq!(action.task, "send the doc (14:58)");
+        assert_eq!(action.due, NaiveDate::from_ymd_opt(2025, 10, 20));
+        assert!(action.is_overdue(NaiveDate::from_ymd_opt(2025, 10, 21).unwrap()));
+        assert!(!action.is_overdue(NaiveDate::from_ymd_opt(2025, 10, 20).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_own_actions() {
+        assert_eq!(Action::parse("-> follow up").unwrap().owner, Owner::Me);
+        assert_eq!(Action::parse("-> @me: book room").unwrap().owner, Owner::Me);
+        // Only a name followed by a colon assigns the action
+        let action = Action::parse("-> ask @bob about it: soon").unwrap();
+        assert_eq!(action.owner, Owner::Me);
+        assert_eq!(action.task, "ask @bob about it: soon");
+
+        assert_eq!(Action::parse("write docs"), None);
+        assert_eq!(Action::parse("->  "), None);
+    }
+}
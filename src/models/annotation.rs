@@ -0,0 +1,361 @@
+use std::{fmt, ops::Range, str::FromStr};
+
+use color_eyre::eyre::{Report, Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// The kinds of annotations that can appear in an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnnotationKind {
+    /// `@alice` - people mentions
+    Person,
+    /// `::search-service` - project references
+    Project,
+    /// `+rust` - tags/themes
+    Tag,
+}
+
+impl AnnotationKind {
+    pub const ALL: [AnnotationKind; 3] = [
+        AnnotationKind::Person,
+        AnnotationKind::Project,
+        AnnotationKind::Tag,
+    ];
+
+    /// The marker that starts this kind of annotation
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            AnnotationKind::Person => "@",
+            AnnotationKind::Project => "::",
+            AnnotationKind::Tag => "+",
+        }
+    }
+
+    /// Human readable name, used in help texts and cache files
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnnotationKind::Person => "person",
+            AnnotationKind::Project => "project",
+            AnnotationKind::Tag => "tag",
+        }
+    }
+}
+
+/// A single annotation like `+migration`, as given on the command line
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    pub value: String,
+}
+
+impl Annotation {
+    pub fn parse(value: &str) -> Result<Self> {
+        let Some(kind) = AnnotationKind::ALL
+            .into_iter()
+            .find(|kind| value.starts_with(kind.prefix()))
+        else {
+            bail!(
+                "Invalid annotation '{}': expected +tag, @person or ::project",
+                value
+            );
+        };
+        let name = &value[kind.prefix().len()..];
+        if name.is_empty() || annotation_name_len(name) != name.len() {
+            bail!(
+                "Invalid annotation '{}': names are letters, digits and _, with - or . inside",
+                value
+            );
+        }
+        Ok(Self {
+            kind,
+            value: name.to_string(),
+        })
+    }
+
+    /// Whether `content` mentions this annotation
+    pub fn is_in(&self, content: &str) -> bool {
+        Annotations::parse(content)
+            .values(self.kind)
+            .contains(&self.value)
+    }
+
+    /// `content` with the annotation added, on the trailing line of annotations if it
+    /// ends with one, None when it is already there
+    pub fn add_to(&self, content: &str) -> Option<String> {
+        if self.is_in(content) {
+            return None;
+        }
+        let content = content.trim_end();
+        let last = content.lines().last().unwrap_or_default();
+        let separator = if content.is_empty() {
+            ""
+        } else if is_annotation_line(last) {
+            " "
+        } else {
+            "\n\n"
+        };
+        Some(format!("{}{}{}\n", content, separator, self))
+    }
+
+    /// `content` with every mention of the annotation removed, None when there is none.
+    /// Lines left with nothing but whitespace are dropped.
+    pub fn remove_from(&self, content: &str) -> Option<String> {
+        let mut removed = false;
+        let mut lines = Vec::new();
+        for line in content.split('\n') {
+            let spans: Vec<Range<usize>> = if line.trim_start().starts_with("//") {
+                Vec::new()
+            } else {
+                scan_line(line)
+                    .into_iter()
+                    .filter(|(kind, name)| *kind == self.kind && line[name.clone()] == self.value)
+                    .map(|(kind, name)| name.start - kind.prefix().len()..name.end)
+                    .collect()
+            };
+            if spans.is_empty() {
+                lines.push(line.to_string());
+                continue;
+            }
+
+            removed = true;
+            let mut kept = String::new();
+            let mut from = 0;
+            for span in spans {
+                kept.push_str(&line[from..span.start]);
+                // Drop the space that separated the annotation from the text before it
+                kept.truncate(kept.trim_end_matches([' ', '\t']).len());
+                from = span.end;
+            }
+            kept.push_str(&line[from..]);
+            if !kept.trim().is_empty() {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                lines.push(format!("{}{}", indent, kept.trim()));
+            }
+        }
+        removed.then(|| lines.join("\n"))
+    }
+}
+
+impl FromStr for Annotation {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.kind.prefix(), self.value)
+    }
+}
+
+/// Whether a line holds nothing but annotations, like `+rust +cli`
+fn is_annotation_line(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty()
+        && line
+            .split_whitespace()
+            .all(|word| Annotation::parse(word).is_ok())
+}
+
+/// Annotations extracted from an entry's content
+///
+/// Vec preserves order and frequency, e.g. "Met @alice then @bob then @alice"
+/// yields `["alice", "bob", "alice"]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotations {
+    pub people: Vec<String>,
+    pub projects: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl Annotations {
+    /// Parse all annotations from markdown content
+    pub fn parse(content: &str) -> Self {
+        let mut annotations = Annotations::default();
+
+        for line in content.lines() {
+            // Skip comment lines, the same way the editor strips them
+            if line.trim_start().starts_with("//") {
+                continue;
+            }
+
+            for (kind, name) in scan_line(line) {
+                annotations.values_mut(kind).push(line[name].to_string());
+            }
+        }
+
+        annotations
+    }
+
+    /// Get the values of the given annotation kind
+    pub fn values(&self, kind: AnnotationKind) -> &[String] {
+        match kind {
+            AnnotationKind::Person => &self.people,
+            AnnotationKind::Project => &self.projects,
+            AnnotationKind::Tag => &self.tags,
+        }
+    }
+
+    fn values_mut(&mut self, kind: AnnotationKind) -> &mut Vec<String> {
+        match kind {
+            AnnotationKind::Person => &mut self.people,
+            AnnotationKind::Project => &mut self.projects,
+            AnnotationKind::Tag => &mut self.tags,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.people.is_empty() && self.projects.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// Find all annotations in a single line, with the byte range of each name
+/// (without its prefix)
+fn scan_line(line: &str) -> Vec<(AnnotationKind, Range<usize>)> {
+    let mut found = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut rest = line;
+
+    while let Some(ch) = rest.chars().next() {
+        // An annotation must start a word, so `alice@example.com`, `1+1` and
+        // `std::fs` are not mistaken for annotations
+        let at_word_start = prev.is_none_or(|p| !is_name_char(p) && p != ':');
+
+        if at_word_start
+            && let Some(kind) = AnnotationKind::ALL
+                .into_iter()
+                .find(|kind| rest.starts_with(kind.prefix()))
+        {
+            let after_prefix = &rest[kind.prefix().len()..];
+            let name_len = annotation_name_len(after_prefix);
+
+            if name_len > 0 {
+                let start = line.len() - after_prefix.len();
+                found.push((kind, start..start + name_len));
+                prev = after_prefix[..name_len].chars().last();
+                rest = &after_prefix[name_len..];
+                continue;
+            }
+        }
+
+        prev = Some(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    found
+}
+
+/// Length in bytes of the annotation name at the start of `s`.
+/// Names may contain `-` and `.` in the middle, but not at the end,
+/// so "Talked to @alice." yields `alice`.
+fn annotation_name_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut end = 0;
+
+    for ch in s.chars() {
+        if is_name_char(ch) {
+            len += ch.len_utf8();
+            end = len;
+        } else if (ch == '-' || ch == '.') && end > 0 {
+            len += ch.len_utf8();
+        } else {
+            break;
+        }
+    }
+
+    end
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all_kinds() {
+        let annotations =
+            Annotations::parse("Met with @alice to discuss ::devlog-mvp.\nAdded +rust +cli");
+
+        assert_eq!(annotations.people, vec!["alice"]);
+        assert_eq!(annotations.projects, vec!["devlog-mvp"]);
+        assert_eq!(annotations.tags, vec!["rust", "cli"]);
+    }
+
+    #[test]
+    fn test_parse_preserves_order_and_frequency() {
+        let annotations = Annotations::parse("Met @alice then @bob then @alice");
+        assert_eq!(annotations.people, vec!["alice", "bob", "alice"]);
+    }
+
+    #[test]
+    fn test_parse_underscore_names() {
+        let annotations = Annotations::parse("Paired with @huijing_huang, @bob");
+        assert_eq!(annotations.people, vec!["huijing_huang", "bob"]);
+    }
+
+    #[test]
+    fn test_parse_ignores_non_annotations() {
+        let annotations = Annotations::parse(
+            "Mail alice@example.com, 1+1 = 2, use std::fs and C++ or a lone @ and + here",
+        );
+        assert!(annotations.is_empty());
+    }
+
+    #[test]
+    fn test_parse_skips_comment_lines() {
+        let annotations = Annotations::parse("+real\n// +template @comment ::lines");
+        assert_eq!(annotations.tags, vec!["real"]);
+        assert!(annotations.people.is_empty());
+        assert!(annotations.projects.is_empty());
+    }
+
+    #[test]
+    fn test_parse_annotations_in_markdown() {
+        let annotations = Annotations::parse("- (@alice) worked on **::search**, see [+notes]");
+        assert_eq!(annotations.people, vec!["alice"]);
+        assert_eq!(annotations.projects, vec!["search"]);
+        assert_eq!(annotations.tags, vec!["notes"]);
+    }
+
+    #[test]
+    fn test_parse_annotation() {
+        let tag: Annotation = "+migration".parse().unwrap();
+        assert_eq!(tag.kind, AnnotationKind::Tag);
+        assert_eq!(tag.value, "migration");
+        assert_eq!(tag.to_string(), "+migration");
+
+        for value in ["migration", "+", "@alice.", "::two words"] {
+            assert!(Annotation::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_add_annotation() {
+        let tag: Annotation = "+migration".parse().unwrap();
+        assert_eq!(
+            tag.add_to("Cut over the db\n").unwrap(),
+            "Cut over the db\n\n+migration\n"
+        );
+        assert_eq!(
+            tag.add_to("Cut over the db\n\n+db +ops").unwrap(),
+            "Cut over the db\n\n+db +ops +migration\n"
+        );
+        assert_eq!(tag.add_to("Started the +migration"), None);
+    }
+
+    #[test]
+    fn test_remove_annotation() {
+        let tag: Annotation = "+migration".parse().unwrap();
+        assert_eq!(
+            tag.remove_from("Started the +migration with @alice\n\n+migration\n- +migration done")
+                .unwrap(),
+            "Started the with @alice\n\n- done"
+        );
+        assert_eq!(tag.remove_from("+migrations\n// +migration"), None);
+    }
+}
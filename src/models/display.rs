@@ -0,0 +1,134 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::models::frontmatter::lenient;
+
+/// How an entry wants to be shown, from optional keys of its frontmatter:
+///
+/// ```yaml
+/// toc: true    # start with a table of contents of its headings
+/// wrap: false  # keep long lines on one row in the TUI
+/// lang: de     # language of the text, for spellcheckers and hyphenation
+/// ```
+///
+/// Values of the wrong type are ignored like missing ones, a typo must not make the
+/// entry unreadable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayOptions {
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub toc: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub wrap: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_lang",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub lang: Option<String>,
+}
+
+fn lenient_lang<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let lang: Option<String> = lenient(deserializer)?;
+    Ok(lang
+        .map(|lang| lang.trim().to_string())
+        .filter(|lang| !lang.is_empty()))
+}
+
+impl DisplayOptions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn has_toc(&self) -> bool {
+        self.toc == Some(true)
+    }
+
+    /// Whether long lines are soft-wrapped, the default
+    pub fn wraps(&self) -> bool {
+        self.wrap != Some(false)
+    }
+
+    /// `content` with a table of contents in front when the entry asks for one
+    pub fn apply_toc(&self, content: &str) -> String {
+        match table_of_contents(content).filter(|_| self.has_toc()) {
+            Some(toc) => format!("{}\n{}", toc, content),
+            None => content.to_string(),
+        }
+    }
+}
+
+/// A Markdown list of the headings in `markdown`, nested by level, `None` without
+/// headings. Lines in fenced code blocks aren't headings.
+pub fn table_of_contents(markdown: &str) -> Option<String> {
+    let mut in_code_block = false;
+    let mut headings = Vec::new();
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if let Some(title) = line[level..].strip_prefix(' ')
+            && (1..=6).contains(&level)
+            && !title.trim().is_empty()
+        {
+            headings.push((level, title.trim()));
+        }
+    }
+
+    let top = headings.iter().map(|(level, _)| *level).min()?;
+    let mut toc = String::new();
+    for (level, title) in headings {
+        toc.push_str(&format!("{}- {}\n", "  ".repeat(level - top), title));
+    }
+    Some(toc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_roundtrip_through_frontmatter() {
+        let options: DisplayOptions =
+            serde_yaml::from_str("id: 20250920\ntoc: true\nwrap: false\nlang: de\n").unwrap();
+        assert!(options.has_toc());
+        assert!(!options.wraps());
+        assert_eq!(options.lang.as_deref(), Some("de"));
+        assert_eq!(
+            serde_yaml::to_string(&options).unwrap(),
+            "toc: true\nwrap: false\nlang: de\n"
+        );
+
+        let options: DisplayOptions = serde_yaml::from_str("toc: yes please\nlang: ' '\n").unwrap();
+        assert!(options.is_default());
+        assert!(options.wraps());
+    }
+
+    #[test]
+    fn test_table_of_contents() {
+        let markdown = "## 09:00 Standup\n\nNotes\n\n### Blockers\n\n```sh\n# not a heading\n```\n\n## 14:00 Review\n#hashtag\n";
+        assert_eq!(
+            table_of_contents(markdown).unwrap(),
+            "- 09:00 Standup\n  - Blockers\n- 14:00 Review\n"
+        );
+        assert_eq!(table_of_contents("No headings here"), None);
+
+        let options = DisplayOptions {
+            toc: Some(true),
+            ..DisplayOptions::default()
+        };
+        assert_eq!(options.apply_toc("# One\n"), "- One\n\n# One\n");
+        assert_eq!(DisplayOptions::default().apply_toc("# One\n"), "# One\n");
+    }
+}
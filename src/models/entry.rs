@@ -1,17 +1,46 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveTime, Utc};
 use std::fmt;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use crate::models::display::DisplayOptions;
+use crate::models::entry_id::EntryId;
+use crate::models::frontmatter;
+use crate::storage::events::{Event, EventKind};
+
+/// A timestamped `## HH:MM` section of an entry, added with `devlog new -m`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubEntry {
+    pub time: NaiveTime,
+    /// The section including its heading
+    pub content: String,
+}
+
+/// Time of a `## HH:MM` sub-entry heading, which may be followed by a title
+/// as in `## 14:32 Meeting: Design review`
+pub fn sub_entry_time(line: &str) -> Option<NaiveTime> {
+    let heading = line.trim_end().strip_prefix("## ")?;
+    let (time, title) = heading.split_at_checked(5)?;
+    if !title.is_empty() && !title.starts_with(' ') {
+        return None;
+    }
+    NaiveTime::parse_from_str(time, "%H:%M").ok()
+}
+
+#[derive(Debug, Clone)]
 pub struct Entry {
-    pub id: String, // YYYYMMDD format
+    pub id: EntryId,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub content: String, // Markdown content
+    /// Display hints from the frontmatter, like `toc: true`
+    pub display: DisplayOptions,
+    /// Frontmatter keys devlog doesn't know, written back as they were
+    pub extra: serde_yaml::Mapping,
 }
 
 impl Entry {
     /// Create a new entry with id and content
-    pub fn new(id: String, content: String) -> Self {
+    pub fn new(id: EntryId, content: String) -> Self {
         let now = Utc::now();
 
         Self {
@@ -19,6 +48,8 @@ impl Entry {
             created_at: now,
             updated_at: now,
             content,
+            display: DisplayOptions::default(),
+            extra: serde_yaml::Mapping::new(),
         }
     }
 
@@ -28,48 +59,184 @@ impl Entry {
         self.updated_at = Utc::now();
     }
 
+    /// The `## HH:MM` sections of the entry in the order they were written. A
+    /// section ends at the next heading of the same or a higher level.
+    pub fn sub_entries(&self) -> Vec<SubEntry> {
+        let mut sub_entries = Vec::new();
+        let mut current: Option<SubEntry> = None;
+        for line in self.content.lines() {
+            let is_section = line.starts_with("# ") || line.starts_with("## ");
+            if is_section && let Some(done) = current.take() {
+                sub_entries.push(done);
+            }
+            if let Some(time) = sub_entry_time(line) {
+                current = Some(SubEntry {
+                    time,
+                    content: String::new(),
+                });
+            }
+            if let Some(sub_entry) = &mut current {
+                sub_entry.content.push_str(line);
+                sub_entry.content.push('\n');
+            }
+        }
+        sub_entries.extend(current);
+        for sub_entry in &mut sub_entries {
+            sub_entry
+                .content
+                .truncate(sub_entry.content.trim_end().len());
+        }
+        sub_entries
+    }
+
+    /// The first sub-entry written at `time`
+    pub fn sub_entry(&self, time: NaiveTime) -> Option<SubEntry> {
+        self.sub_entries()
+            .into_iter()
+            .find(|sub_entry| sub_entry.time == time)
+    }
+
+    /// Remove the section under the first heading titled `heading` and return it.
+    /// `14:32` also finds `## 14:32 Meeting: Design review`. The section ends at the
+    /// next heading of the same or a higher level.
+    pub fn cut_section(&mut self, heading: &str) -> Option<String> {
+        let wanted = heading.trim_start_matches('#').trim();
+        let lines: Vec<&str> = self.content.lines().collect();
+        let headings = headings(&lines);
+        let &(start, level) = headings.iter().find(|&&(index, level)| {
+            let title = lines[index][level..].trim();
+            !wanted.is_empty()
+                && (title.eq_ignore_ascii_case(wanted)
+                    || title
+                        .strip_prefix(wanted)
+                        .is_some_and(|rest| rest.starts_with(' ')))
+        })?;
+        let end = headings
+            .iter()
+            .find(|&&(index, other)| index > start && other <= level)
+            .map_or(lines.len(), |&(index, _)| index);
+
+        let section = lines[start..end].join("\n").trim_end().to_string();
+        let before = lines[..start].join("\n");
+        let after = lines[end..].join("\n");
+        let rest = [before.trim_end(), after.trim()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        self.update_content(rest);
+        Some(section)
+    }
+
     /// Get a preview of the entry content
     pub fn preview(&self) -> String {
-        // Get the first line of content
-        let first_line = self.content.lines().next().unwrap_or("").trim();
+        preview(&self.content)
+    }
 
-        // Target visual width of 60 characters
-        const MAX_WIDTH: usize = 60;
-        const ELIPSIS_WIDTH: usize = 3; // "..." width
+    /// Fold one event into the entry as replayed so far. Events recording content
+    /// replace it and move `updated_at` to when they happened, the others explain a
+    /// change and leave the entry as it was. `None` if the content changed without
+    /// being recorded, so it isn't known from then on.
+    pub fn apply_event(mut self, event: &Event) -> Option<Entry> {
+        match event.kind {
+            EventKind::Created
+            | EventKind::Updated
+            | EventKind::Replaced
+            | EventKind::Reverted
+            | EventKind::Snapshot => {
+                self.content = event.content.clone()?;
+                self.updated_at = event.at.with_timezone(&Utc);
+            }
+            EventKind::TagAdded
+            | EventKind::TagRemoved
+            | EventKind::AttachmentAdded
+            | EventKind::SyncConflictResolved
+            | EventKind::Renamed
+            | EventKind::Locked
+            | EventKind::Unlocked => {}
+        }
+        Some(self)
+    }
 
-        // If the line fits within the limit, return it as is
-        if first_line.width() <= MAX_WIDTH {
-            return first_line.to_string();
+    /// The entry as it was at `at`, folding its `events` up to then with `apply_event`.
+    /// `None` if the entry didn't exist yet or its last change by then was recorded
+    /// without its content.
+    pub fn state_at(&self, events: &[Event], at: DateTime<FixedOffset>) -> Option<Entry> {
+        let mut state: Option<Entry> = None;
+        for event in events.iter().filter(|event| event.at <= at) {
+            // New content tells everything, also after a change recorded without it
+            let replayed = if event.kind.changes_content() {
+                state.take().or_else(|| Some(self.clone()))
+            } else {
+                state.take()
+            };
+            state = replayed.and_then(|entry| entry.apply_event(event));
         }
+        state
+    }
+}
 
-        let mut current_width = 0;
-        let mut truncate_at = 0;
+/// Line numbers and levels of the markdown headings, skipping code blocks
+fn headings(lines: &[&str]) -> Vec<(usize, usize)> {
+    let mut in_code = false;
+    let mut headings = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        let level = line.chars().take_while(|&ch| ch == '#').count();
+        if !in_code && (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            headings.push((index, level));
+        }
+    }
+    headings
+}
 
-        for (idx, ch) in first_line.char_indices() {
-            let char_width = ch.width().unwrap_or(0);
+/// The first line of a document, shortened to 60 columns
+pub fn preview(content: &str) -> String {
+    // Get the first line of content
+    let first_line = content.lines().next().unwrap_or("").trim();
 
-            if current_width + char_width + ELIPSIS_WIDTH > MAX_WIDTH {
-                break;
-            }
+    // Target visual width of 60 characters
+    const MAX_WIDTH: usize = 60;
+    const ELIPSIS_WIDTH: usize = 3; // "..." width
+
+    // If the line fits within the limit, return it as is
+    if first_line.width() <= MAX_WIDTH {
+        return first_line.to_string();
+    }
 
-            current_width += char_width;
-            truncate_at = idx + ch.len_utf8();
+    let mut current_width = 0;
+    let mut truncate_at = 0;
+
+    for (idx, ch) in first_line.char_indices() {
+        let char_width = ch.width().unwrap_or(0);
+
+        if current_width + char_width + ELIPSIS_WIDTH > MAX_WIDTH {
+            break;
         }
 
-        format!("{}...", &first_line[..truncate_at])
+        current_width += char_width;
+        truncate_at = idx + ch.len_utf8();
     }
+
+    format!("{}...", &first_line[..truncate_at])
 }
 
 impl fmt::Display for Entry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
+        writeln!(
             f,
-            "Id: {}\nCreated: {}\nUpdated: {}\n---\n\n{}",
+            "Id: {}\nCreated: {}\nUpdated: {}",
             self.id,
             self.created_at.to_rfc3339(),
-            self.updated_at.to_rfc3339(),
-            self.content
-        )
+            self.updated_at.to_rfc3339()
+        )?;
+        for (field, value) in frontmatter::fields(&self.extra) {
+            writeln!(f, "{}: {}", field, value)?;
+        }
+        write!(f, "---\n\n{}", self.content)
     }
 }
 
@@ -80,9 +247,9 @@ mod tests {
     #[test]
     fn test_new_entry_with_id() {
         let content = "Test content".to_string();
-        let id = "20250910".to_string();
+        let id: EntryId = "20250910".parse().unwrap();
 
-        let entry = Entry::new(id.clone(), content.clone());
+        let entry = Entry::new(id, content.clone());
 
         assert_eq!(entry.id, id);
         assert_eq!(entry.content, content);
@@ -90,7 +257,7 @@ mod tests {
 
     #[test]
     fn test_update_content() {
-        let id = "20250920".to_string();
+        let id: EntryId = "20250920".parse().unwrap();
         let content = "Test content".to_string();
         let mut entry = Entry::new(id, content);
         let original_created = entry.created_at;
@@ -106,11 +273,29 @@ mod tests {
         assert!(entry.updated_at > entry.created_at);
     }
 
+    #[test]
+    fn test_cut_section() {
+        let id: EntryId = "20250920".parse().unwrap();
+        let mut entry = Entry::new(
+            id,
+            "Intro\n\n## 14:32 Meeting: Review\n\n### Notes\n\n```sh\n# not a heading\n```\n\n## 16:00\n\nLater".to_string(),
+        );
+
+        assert_eq!(entry.cut_section("Missing"), None);
+        assert_eq!(
+            entry.cut_section("14:32").unwrap(),
+            "## 14:32 Meeting: Review\n\n### Notes\n\n```sh\n# not a heading\n```"
+        );
+        assert_eq!(entry.content, "Intro\n\n## 16:00\n\nLater");
+        assert_eq!(entry.cut_section("## 16:00").unwrap(), "## 16:00\n\nLater");
+        assert_eq!(entry.content, "Intro");
+    }
+
     #[test]
     fn test_display_format() {
-        let id = "20250921".to_string();
+        let id: EntryId = "20250921".parse().unwrap();
         let content = "# Test Entry\n\nThis is a test entry.".to_string();
-        let entry = Entry::new(id.clone(), content.clone());
+        let entry = Entry::new(id, content.clone());
 
         let display_output = format!("{}", entry);
 
@@ -124,7 +309,7 @@ mod tests {
 
     #[test]
     fn test_preview_short_content() {
-        let id = "20250925".to_string();
+        let id: EntryId = "20250925".parse().unwrap();
         let content = "Short content".to_string();
         let entry = Entry::new(id, content.clone());
 
@@ -134,7 +319,7 @@ mod tests {
 
     #[test]
     fn test_preview_long_ascii_content() {
-        let id = "20250925".to_string();
+        let id: EntryId = "20250925".parse().unwrap();
         let content = "This is a very long line that exceeds sixty characters and should be truncated properly".to_string();
         let entry = Entry::new(id, content);
 
@@ -145,7 +330,7 @@ mod tests {
 
     #[test]
     fn test_preview_multiline_content() {
-        let id = "20250925".to_string();
+        let id: EntryId = "20250925".parse().unwrap();
         let content = "First line\nSecond line\nThird line".to_string();
         let entry = Entry::new(id, content);
 
@@ -155,7 +340,7 @@ mod tests {
 
     #[test]
     fn test_preview_chinese_characters() {
-        let id = "20250925".to_string();
+        let id: EntryId = "20250925".parse().unwrap();
         // Chinese characters typically have width of 2 each
         let content =
             "这是一个包含中文字符的测试内容，用来测试预览功能是否能正确处理Unicode字符".to_string();
@@ -165,4 +350,84 @@ mod tests {
         assert!(preview.width() <= 60);
         assert!(preview.ends_with("..."));
     }
+
+    #[test]
+    fn test_sub_entries() {
+        let id: EntryId = "20250920".parse().unwrap();
+        let content = "# Plan\n\n## 09:15\n\nStandup\n\n### Notes\n\n- one\n\n## Done\n\n## 14:32 Review\n\nLGTM\n\n## 16:000\n";
+        let entry = Entry::new(id, content.to_string());
+
+        let sub_entries = entry.sub_entries();
+        assert_eq!(sub_entries.len(), 2);
+        assert_eq!(
+            sub_entries[0].content,
+            "## 09:15\n\nStandup\n\n### Notes\n\n- one"
+        );
+        assert_eq!(sub_entries[1].content, "## 14:32 Review\n\nLGTM");
+
+        let time = NaiveTime::from_hms_opt(14, 32, 0).unwrap();
+        assert_eq!(
+            entry.sub_entry(time).unwrap().content,
+            "## 14:32 Review\n\nLGTM"
+        );
+        assert!(entry.sub_entry(NaiveTime::MIN).is_none());
+    }
+
+    #[test]
+    fn test_state_at_replays_events_up_to_an_instant() {
+        let event = |at: &str, kind: EventKind, content: Option<&str>| Event {
+            at: DateTime::parse_from_rfc3339(at).unwrap(),
+            kind,
+            content: content.map(str::to_string),
+            detail: None,
+        };
+        let events = [
+            event(
+                "2025-09-20T09:00:00+02:00",
+                EventKind::Created,
+                Some("Morning"),
+            ),
+            event(
+                "2025-09-20T16:00:00+02:00",
+                EventKind::Updated,
+                Some("Afternoon"),
+            ),
+            event("2025-09-20T16:01:00+02:00", EventKind::TagAdded, None),
+            event("2025-09-20T16:02:00+02:00", EventKind::Locked, None),
+            event("2025-09-20T17:00:00+02:00", EventKind::Updated, None),
+            event(
+                "2025-09-20T17:30:00+02:00",
+                EventKind::Snapshot,
+                Some("Evening"),
+            ),
+        ];
+        let entry = Entry::new("20250920".parse().unwrap(), "Afternoon".to_string());
+        let at = |at: &str| DateTime::parse_from_rfc3339(at).unwrap();
+
+        let before = entry
+            .state_at(&events, at("2025-09-20T15:00:00+02:00"))
+            .unwrap();
+        assert_eq!(before.content, "Morning");
+        assert_eq!(before.updated_at, at("2025-09-20T07:00:00Z"));
+        let after = entry
+            .state_at(&events, at("2025-09-20T16:30:00+02:00"))
+            .unwrap();
+        assert_eq!(after.content, "Afternoon");
+        assert_eq!(after.updated_at, at("2025-09-20T14:00:00Z"));
+        // Unknown after a change recorded without content, until the next one with it
+        assert!(
+            entry
+                .state_at(&events, at("2025-09-20T17:10:00+02:00"))
+                .is_none()
+        );
+        let evening = entry
+            .state_at(&events, at("2025-09-20T18:00:00+02:00"))
+            .unwrap();
+        assert_eq!(evening.content, "Evening");
+        assert!(
+            entry
+                .state_at(&events, at("2025-09-20T08:00:00+02:00"))
+                .is_none()
+        );
+    }
 }
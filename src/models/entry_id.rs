@@ -0,0 +1,283 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{Datelike, Local, NaiveDate, NaiveTime};
+use color_eyre::eyre::{Report, Result, bail, eyre};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+const FORMAT: &str = "%Y%m%d";
+const TIME_FORMAT: &str = "%H%M";
+
+/// A validated entry id, the `YYYYMMDD` date of the entry.
+///
+/// Ids are parsed once at the boundary (CLI arguments, file names) so the rest
+/// of the code never has to deal with malformed ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryId(NaiveDate);
+
+impl EntryId {
+    pub fn from_date(date: NaiveDate) -> Self {
+        Self(date)
+    }
+
+    /// The id of today's entry in the local timezone
+    pub fn today() -> Self {
+        Self(Local::now().date_naive())
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.0
+    }
+
+    /// Four digit year, e.g. `2025`
+    pub fn year(&self) -> String {
+        format!("{:04}", self.0.year())
+    }
+
+    /// Two digit month, e.g. `09`
+    pub fn month(&self) -> String {
+        format!("{:02}", self.0.month())
+    }
+
+    /// Parse an id, explaining exactly what is wrong with a malformed one
+    pub fn parse(value: &str) -> Result<Self> {
+        if value.len() != 8 || !value.bytes().all(|b| b.is_ascii_digit()) {
+            bail!(
+                "Invalid entry id '{}': expected 8 digits in YYYYMMDD format, e.g. 20250920",
+                value
+            );
+        }
+
+        let year: i32 = value[0..4].parse()?;
+        let month: u32 = value[4..6].parse()?;
+        let day: u32 = value[6..8].parse()?;
+
+        if !(1..=12).contains(&month) {
+            bail!(
+                "Invalid entry id '{}': month {:02} does not exist",
+                value,
+                month
+            );
+        }
+
+        NaiveDate::from_ymd_opt(year, month, day)
+            .map(Self)
+            .ok_or_else(|| {
+                eyre!(
+                    "Invalid entry id '{}': day {:02} does not exist in {}-{:02}",
+                    value,
+                    day,
+                    year,
+                    month
+                )
+            })
+    }
+}
+
+impl FromStr for EntryId {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for EntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format(FORMAT))
+    }
+}
+
+/// Serialized as its `YYYYMMDD` string, e.g. in cache files
+impl Serialize for EntryId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntryId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).map_err(de::Error::custom)
+    }
+}
+
+impl PartialEq<&str> for EntryId {
+    fn eq(&self, other: &&str) -> bool {
+        Self::parse(other).is_ok_and(|other| *self == other)
+    }
+}
+
+/// An entry or one of its timestamped sub-entries, `YYYYMMDD` or `YYYYMMDD-HHMM`.
+///
+/// Sub-entries are the `## HH:MM` sections of the day's entry, see `Entry::sub_entries`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryRef {
+    pub id: EntryId,
+    pub time: Option<NaiveTime>,
+}
+
+impl EntryRef {
+    pub fn sub_entry(id: EntryId, time: NaiveTime) -> Self {
+        Self {
+            id,
+            time: Some(time),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        let Some((id, time)) = value.split_once('-') else {
+            return EntryId::parse(value).map(Self::from);
+        };
+        let id = EntryId::parse(id)?;
+        if time.len() != 4 || !time.bytes().all(|b| b.is_ascii_digit()) {
+            bail!(
+                "Invalid sub-entry id '{}': expected YYYYMMDD-HHMM, e.g. 20250920-1432",
+                value
+            );
+        }
+        let time = NaiveTime::parse_from_str(time, TIME_FORMAT)
+            .map_err(|_| eyre!("Invalid sub-entry id '{}': {} is not a time", value, time))?;
+        Ok(Self::sub_entry(id, time))
+    }
+}
+
+impl From<EntryId> for EntryRef {
+    fn from(id: EntryId) -> Self {
+        Self { id, time: None }
+    }
+}
+
+impl FromStr for EntryRef {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for EntryRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.time {
+            Some(time) => write!(f, "{}-{}", self.id, time.format(TIME_FORMAT)),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// An inclusive range of entries, `YYYYMMDD..YYYYMMDD`, either end may be left open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EntryRange {
+    pub start: Option<EntryId>,
+    pub end: Option<EntryId>,
+}
+
+impl EntryRange {
+    pub fn parse(value: &str) -> Result<Self> {
+        let Some((start, end)) = value.split_once("..") else {
+            bail!(
+                "Invalid range '{}': expected YYYYMMDD..YYYYMMDD, e.g. 20250901..20250915",
+                value
+            );
+        };
+        let bound = |id: &str| (!id.is_empty()).then(|| EntryId::parse(id)).transpose();
+        let range = Self {
+            start: bound(start)?,
+            end: bound(end)?,
+        };
+        if let (Some(start), Some(end)) = (range.start, range.end)
+            && start > end
+        {
+            bail!("Invalid range '{}': {} comes after {}", value, start, end);
+        }
+        Ok(range)
+    }
+
+    pub fn contains(&self, id: &EntryId) -> bool {
+        self.start.is_none_or(|start| start <= *id) && self.end.is_none_or(|end| *id <= end)
+    }
+}
+
+impl FromStr for EntryRange {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_id() {
+        let id: EntryId = "20250920".parse().unwrap();
+        assert_eq!(id.date(), NaiveDate::from_ymd_opt(2025, 9, 20).unwrap());
+        assert_eq!(id.year(), "2025");
+        assert_eq!(id.month(), "09");
+        assert_eq!(id.to_string(), "20250920");
+    }
+
+    #[test]
+    fn test_parse_rejects_wrong_format() {
+        for value in ["2025092", "2025-09-20", "202509200", "notes", ""] {
+            let err = EntryId::parse(value).unwrap_err().to_string();
+            assert!(err.contains("expected 8 digits"), "{}: {}", value, err);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_impossible_dates() {
+        let err = EntryId::parse("20251320").unwrap_err().to_string();
+        assert!(err.contains("month 13 does not exist"));
+
+        let err = EntryId::parse("20250230").unwrap_err().to_string();
+        assert!(err.contains("day 30 does not exist in 2025-02"));
+
+        assert!(EntryId::parse("20240229").is_ok());
+    }
+
+    #[test]
+    fn test_ordering_follows_dates() {
+        let older = EntryId::parse("20241231").unwrap();
+        let newer = EntryId::parse("20250101").unwrap();
+        assert!(older < newer);
+    }
+
+    #[test]
+    fn test_parse_entry_ref() {
+        let day: EntryRef = "20250920".parse().unwrap();
+        assert_eq!(day.time, None);
+        assert_eq!(day.to_string(), "20250920");
+
+        let sub: EntryRef = "20250920-1432".parse().unwrap();
+        assert_eq!(sub.id, EntryId::parse("20250920").unwrap());
+        assert_eq!(sub.time, NaiveTime::from_hms_opt(14, 32, 0));
+        assert_eq!(sub.to_string(), "20250920-1432");
+
+        for value in [
+            "20250920-",
+            "20250920-14:32",
+            "20250920-2460",
+            "2025092-1432",
+        ] {
+            assert!(EntryRef::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_parse_entry_range() {
+        let range: EntryRange = "20250901..20250915".parse().unwrap();
+        assert!(range.contains(&EntryId::parse("20250901").unwrap()));
+        assert!(range.contains(&EntryId::parse("20250915").unwrap()));
+        assert!(!range.contains(&EntryId::parse("20250916").unwrap()));
+
+        let open: EntryRange = "20250910..".parse().unwrap();
+        assert_eq!(open.end, None);
+        assert!(open.contains(&EntryId::parse("20261231").unwrap()));
+
+        for value in ["20250901", "20250915..20250901", "2025091..20250915"] {
+            assert!(EntryRange::parse(value).is_err(), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{Report, Result, bail};
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
+
+use crate::models::annotation::Annotations;
+use crate::models::display::DisplayOptions;
+
+/// The YAML frontmatter of entries and notes. Keys devlog doesn't know are kept in
+/// `extra` and written back as they were, so other tools can store data there too.
+///
+/// ```yaml
+/// id: '20250920'
+/// created_at: 2025-09-20T08:12:00Z
+/// updated_at: 2025-09-20T17:40:00Z
+/// toc: true
+/// tags: [rust]
+/// people: [alice]
+/// projects: [devlog]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Frontmatter {
+    #[serde(
+        default,
+        deserialize_with = "lenient_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub display: DisplayOptions,
+    /// Annotations of the content, written for tools reading the files. The content
+    /// stays the source of truth, these are ignored when reading.
+    #[serde(
+        default,
+        deserialize_with = "ignored",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub tags: Vec<String>,
+    #[serde(
+        default,
+        deserialize_with = "ignored",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub people: Vec<String>,
+    #[serde(
+        default,
+        deserialize_with = "ignored",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub projects: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_yaml::Mapping,
+}
+
+impl Frontmatter {
+    /// The frontmatter of a document with `content`, with its annotations
+    pub fn of(
+        id: &str,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+        content: &str,
+    ) -> Self {
+        let annotations = Annotations::parse(content);
+        Self {
+            id: Some(id.to_string()),
+            created_at: Some(created_at),
+            updated_at: Some(updated_at),
+            tags: dedup(annotations.tags),
+            people: dedup(annotations.people),
+            projects: dedup(annotations.projects),
+            ..Self::default()
+        }
+    }
+
+    /// Split a document into its frontmatter and body. `None` for documents without a
+    /// complete `---` block at the start, a `---` rule in the body doesn't end it.
+    pub fn split(document: &str) -> Option<(&str, &str)> {
+        let rest = document.strip_prefix("---\n")?;
+        if let Some(body) = rest.strip_prefix("---\n") {
+            return Some(("", body));
+        }
+        match rest.split_once("\n---\n") {
+            Some((yaml, body)) => Some((yaml, body)),
+            None => rest.strip_suffix("\n---").map(|yaml| (yaml, "")),
+        }
+    }
+}
+
+/// The custom fields of a frontmatter like `mood: tired` or `hours: 7.5`, as text.
+/// Only scalar values are fields, lists and maps are left to the tools writing them.
+pub fn fields(extra: &serde_yaml::Mapping) -> BTreeMap<String, String> {
+    extra
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                serde_yaml::Value::String(value) => value.clone(),
+                serde_yaml::Value::Number(value) => value.to_string(),
+                serde_yaml::Value::Bool(value) => value.to_string(),
+                _ => return None,
+            };
+            Some((key.as_str()?.to_string(), value))
+        })
+        .collect()
+}
+
+/// A `field=value` condition of `--where`, the value is compared ignoring case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMatch {
+    pub field: String,
+    pub value: String,
+}
+
+impl FieldMatch {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.split_once('=') {
+            Some((field, expected)) if !field.trim().is_empty() => Ok(Self {
+                field: field.trim().to_string(),
+                value: expected.trim().to_string(),
+            }),
+            _ => bail!("Invalid condition '{}': expected e.g. mood=tired", value),
+        }
+    }
+
+    pub fn matches(&self, fields: &BTreeMap<String, String>) -> bool {
+        fields
+            .get(&self.field)
+            .is_some_and(|value| value.eq_ignore_ascii_case(&self.value))
+    }
+}
+
+impl FromStr for FieldMatch {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Values in the order they are first mentioned
+fn dedup(values: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(values.len());
+    for value in values {
+        if !unique.contains(&value) {
+            unique.push(value);
+        }
+    }
+    unique
+}
+
+/// A value of the wrong type is taken as missing, a typo in a hand-edited frontmatter
+/// must not make the entry unreadable
+pub(crate) fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_yaml::Value::deserialize(deserializer)?;
+    Ok(serde_yaml::from_value(value).ok())
+}
+
+/// Ids are written quoted but a hand-written `id: 20250920` is a number
+fn lenient_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::String(id) => Some(id),
+        serde_yaml::Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    })
+}
+
+fn ignored<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    serde_yaml::Value::deserialize(deserializer)?;
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys_roundtrip() {
+        let yaml = "id: 20250920\nmood: tired\ncreated_at: 2025-09-20T08:12:00Z\n\
+                    tags: [stale]\ntoc: maybe\nlocation:\n  city: Berlin\n";
+        let frontmatter: Frontmatter = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(frontmatter.id.as_deref(), Some("20250920"));
+        assert!(frontmatter.created_at.is_some());
+        assert!(frontmatter.display.is_default());
+        assert!(frontmatter.tags.is_empty());
+        let keys: Vec<&str> = frontmatter
+            .extra
+            .keys()
+            .filter_map(|key| key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["mood", "location"]);
+
+        let written = serde_yaml::to_string(&frontmatter).unwrap();
+        assert!(written.ends_with("mood: tired\nlocation:\n  city: Berlin\n"));
+        let reread: Frontmatter = serde_yaml::from_str(&written).unwrap();
+        assert_eq!(reread, frontmatter);
+    }
+
+    #[test]
+    fn test_fields_and_conditions() {
+        let frontmatter: Frontmatter =
+            serde_yaml::from_str("mood: Tired\nhours: 7.5\nremote: true\nlinks: [a]\n").unwrap();
+        let fields = fields(&frontmatter.extra);
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields["hours"], "7.5");
+
+        assert!(FieldMatch::parse("mood=tired").unwrap().matches(&fields));
+        assert!(
+            FieldMatch::parse(" remote = true ")
+                .unwrap()
+                .matches(&fields)
+        );
+        assert!(!FieldMatch::parse("mood=happy").unwrap().matches(&fields));
+        assert!(!FieldMatch::parse("location=home").unwrap().matches(&fields));
+        for condition in ["mood", "=tired", ""] {
+            assert!(FieldMatch::parse(condition).is_err(), "{}", condition);
+        }
+    }
+
+    #[test]
+    fn test_annotations_are_written() {
+        let now = Utc::now();
+        let frontmatter = Frontmatter::of("20250920", now, now, "+rust with @alice, +rust again");
+        assert_eq!(frontmatter.tags, vec!["rust"]);
+        assert_eq!(frontmatter.people, vec!["alice"]);
+        let written = serde_yaml::to_string(&frontmatter).unwrap();
+        assert!(written.starts_with("id: '20250920'\n"));
+        assert!(written.contains("\ntags:\n- rust\npeople:\n- alice\n"));
+        assert!(!written.contains("projects"));
+    }
+
+    #[test]
+    fn test_split_keeps_rules_in_the_body() {
+        let document = "---\nid: 20250920\n---\n\nAbove\n\n---\n\nBelow\n";
+        assert_eq!(
+            Frontmatter::split(document),
+            Some(("id: 20250920", "\nAbove\n\n---\n\nBelow\n"))
+        );
+        assert_eq!(Frontmatter::split("---\n---\nBody"), Some(("", "Body")));
+        assert_eq!(Frontmatter::split("---\nid: 1\n---"), Some(("id: 1", "")));
+        assert_eq!(Frontmatter::split("No frontmatter\n---\n"), None);
+        assert_eq!(Frontmatter::split("---\nunterminated"), None);
+    }
+}
@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use crate::models::entry_id::EntryRef;
+
+/// What a `[[...]]` link points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// `[[20250920]]`, or a sub-entry like `[[20250920-1432]]`
+    Entry(EntryRef),
+    /// `[[oncall-runbook]]`, the note of that name or else the project
+    Name(String),
+}
+
+impl LinkTarget {
+    /// `None` for text that is neither an entry id nor a name, e.g. `[[see above]]`.
+    /// Names may be written as projects, `[[::devlog]]`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.starts_with(|c: char| c.is_ascii_digit()) {
+            return EntryRef::parse(value).ok().map(Self::Entry);
+        }
+        let name = value.strip_prefix("::").unwrap_or(value);
+        let valid = name.starts_with(char::is_alphabetic)
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        valid.then(|| Self::Name(name.to_string()))
+    }
+
+    /// What backlinks are kept under: the day for entries, so a link to a sub-entry
+    /// counts for its day, or the name
+    pub fn key(&self) -> String {
+        match self {
+            Self::Entry(entry_ref) => entry_ref.id.to_string(),
+            Self::Name(name) => name.clone(),
+        }
+    }
+}
+
+/// A `[[target]]` link with the byte range of the whole link in the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub target: LinkTarget,
+    pub range: Range<usize>,
+}
+
+/// The links of a Markdown text in order, outside of fenced code blocks
+pub fn links(text: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut in_code_block = false;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        links.extend(line_links(line).into_iter().map(|link| Link {
+            range: start + link.range.start..start + link.range.end,
+            ..link
+        }));
+    }
+    links
+}
+
+/// The links of a single line
+pub fn line_links(line: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut from = 0;
+    while let Some(open) = line[from..].find("[[").map(|open| from + open) {
+        let inner = open + 2;
+        let Some(close) = line[inner..].find("]]").map(|close| inner + close) else {
+            break;
+        };
+        match LinkTarget::parse(&line[inner..close]).filter(|_| !line[inner..close].contains('[')) {
+            Some(target) => {
+                links.push(Link {
+                    target,
+                    range: open..close + 2,
+                });
+                from = close + 2;
+            }
+            None => from = inner,
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_of_entries_and_names() {
+        let text = "See [[20250920]] and [[20250921-1432]], the [[oncall-runbook]] and \
+                    [[::devlog]]. Not [[see above]] or [[]].\n\n```\n[[20250101]]\n```\n";
+        let links = links(text);
+        let keys: Vec<String> = links.iter().map(|link| link.target.key()).collect();
+        assert_eq!(
+            keys,
+            vec!["20250920", "20250921", "oncall-runbook", "devlog"]
+        );
+        assert_eq!(&text[links[0].range.clone()], "[[20250920]]");
+        assert!(matches!(
+            &links[1].target,
+            LinkTarget::Entry(entry_ref) if entry_ref.time.is_some()
+        ));
+    }
+
+    #[test]
+    fn test_nested_brackets_find_the_inner_link() {
+        let links = line_links("[[not [[20250920]] ]] [[20250932]]");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].range, 6..18);
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::NaiveTime;
+
+/// Marker of an action item in meeting notes, e.g. `-> @alice: send the doc`
+pub const ACTION_MARKER: &str = "->";
+
+/// The section a meeting starts with: a timestamped heading, so the meeting is a
+/// sub-entry of the day, followed by its attendees and projects
+pub fn template(title: &str, annotations: &[String], start: NaiveTime) -> String {
+    let mut section = format!("## {} Meeting: {}\n", start.format("%H:%M"), title);
+
+    let mentions = |prefix: &str| {
+        annotations
+            .iter()
+            .filter(|annotation| annotation.starts_with(prefix))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    for (label, prefix) in [("Attendees", "@"), ("Projects", "::"), ("Tags", "+")] {
+        let mentions = mentions(prefix);
+        if !mentions.is_empty() {
+            section.push_str(&format!("\n{}: {}", label, mentions));
+        }
+    }
+
+    section.push_str(&format!(
+        "\n\n### Notes\n\n\n### Action items\n\n{} ",
+        ACTION_MARKER
+    ));
+    section
+}
+
+/// Turn the `->` action items of meeting notes into todos stamped with the time
+/// they were taken down at, e.g. `- [ ] -> @alice: send the doc (14:58)`. Empty
+/// action items and blank lines left over from the template are dropped.
+pub fn stamp_action_items(notes: &str, time: NaiveTime) -> String {
+    let mut stamped = Vec::new();
+    for line in notes.lines() {
+        // Blank lines the template left for writing in shouldn't pile up
+        if line.trim().is_empty() && stamped.last().is_some_and(|last: &String| last.is_empty()) {
+            continue;
+        }
+        let trimmed = line.trim_start();
+        let Some(action) = trimmed.strip_prefix(ACTION_MARKER) else {
+            stamped.push(line.trim_end().to_string());
+            continue;
+        };
+        let action = action.trim();
+        if action.is_empty() {
+            continue;
+        }
+        let indent = &line[..line.len() - trimmed.len()];
+        stamped.push(format!(
+            "{}- [ ] {} {} ({})",
+            indent,
+            ACTION_MARKER,
+            action,
+            time.format("%H:%M")
+        ));
+    }
+    stamped.join("\n").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_template_prefills_annotations() {
+        let annotations = ["@alice", "::search_engine", "@bob"].map(String::from);
+        let section = template("Design review", &annotations, at(14, 32));
+
+        assert!(section.starts_with(
+            "## 14:32 Meeting: Design review\n\nAttendees: @alice, @bob\nProjects: ::search_engine\n\n"
+        ));
+        assert!(!section.contains("Tags:"));
+        assert!(section.ends_with("### Action items\n\n-> "));
+    }
+
+    #[test]
+    fn test_stamp_action_items() {
+        let notes = "### Notes\n\n- decided on v2\n  -> @alice: send the doc\n\n\n### Action items\n\n-> follow up\n->   \n- [ ] -> already a todo";
+
+        assert_eq!(
+            stamp_action_items(notes, at(14, 58)),
+            "### Notes\n\n- decided on v2\n  - [ ] -> @alice: send the doc (14:58)\n\n### Action items\n\n- [ ] -> follow up (14:58)\n- [ ] -> already a todo"
+        );
+    }
+}
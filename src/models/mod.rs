@@ -1 +1,12 @@
+pub mod action;
+pub mod annotation;
+pub mod display;
 pub mod entry;
+pub mod entry_id;
+pub mod frontmatter;
+pub mod link;
+pub mod meeting;
+pub mod note;
+pub mod project;
+pub mod time_log;
+pub mod todo;
@@ -0,0 +1,197 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{Report, Result, bail};
+use serde::{Serialize, Serializer};
+
+use crate::models::entry::preview;
+use crate::models::entry_id::{EntryId, EntryRef};
+use crate::models::frontmatter;
+
+const MAX_NAME_LEN: usize = 64;
+
+/// The name of a note, a slug like `oncall-runbook`.
+///
+/// Names start with a letter, so they never look like the `YYYYMMDD` id of a dated entry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NoteId(String);
+
+impl NoteId {
+    pub fn parse(value: &str) -> Result<Self> {
+        let starts_with_letter = value.chars().next().is_some_and(|c| c.is_ascii_lowercase());
+        let valid_chars = value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !starts_with_letter || !valid_chars {
+            bail!(
+                "Invalid note name '{}': use lowercase letters, digits, '-' and '_', \
+                 starting with a letter, e.g. oncall-runbook",
+                value
+            );
+        }
+        if value.len() > MAX_NAME_LEN {
+            bail!(
+                "Invalid note name '{}': at most {} characters",
+                value,
+                MAX_NAME_LEN
+            );
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for NoteId {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for NoteId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Either kind of id accepted by `new`, `edit` and `show`: a dated entry (or one of its
+/// sub-entries) for anything starting with a digit, a note otherwise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyId {
+    Entry(EntryRef),
+    Note(NoteId),
+}
+
+impl AnyId {
+    pub fn parse(value: &str) -> Result<Self> {
+        if value.starts_with(|c: char| c.is_ascii_digit()) {
+            EntryRef::parse(value).map(Self::Entry)
+        } else {
+            NoteId::parse(value).map(Self::Note)
+        }
+    }
+}
+
+impl FromStr for AnyId {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for AnyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entry(entry_ref) => entry_ref.fmt(f),
+            Self::Note(id) => id.fmt(f),
+        }
+    }
+}
+
+impl From<EntryId> for AnyId {
+    fn from(id: EntryId) -> Self {
+        Self::Entry(id.into())
+    }
+}
+
+/// Serialized as its string, e.g. in the JSON output of `devlog grep`
+impl Serialize for AnyId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A long-lived note kept next to the dated entries, e.g. interview prep or a runbook
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub id: NoteId,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub content: String,
+    /// Frontmatter keys devlog doesn't know, written back as they were
+    pub extra: serde_yaml::Mapping,
+}
+
+impl Note {
+    pub fn new(id: NoteId, content: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            created_at: now,
+            updated_at: now,
+            content,
+            extra: serde_yaml::Mapping::new(),
+        }
+    }
+
+    /// Update the content and timestamp
+    pub fn update_content(&mut self, content: String) {
+        self.content = content;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn preview(&self) -> String {
+        preview(&self.content)
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Note: {}\nCreated: {}\nUpdated: {}",
+            self.id,
+            self.created_at.to_rfc3339(),
+            self.updated_at.to_rfc3339()
+        )?;
+        for (field, value) in frontmatter::fields(&self.extra) {
+            writeln!(f, "{}: {}", field, value)?;
+        }
+        write!(f, "---\n\n{}", self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_note_id() {
+        assert_eq!(
+            NoteId::parse("oncall-runbook").unwrap().as_str(),
+            "oncall-runbook"
+        );
+        assert!(NoteId::parse("interview_prep2").is_ok());
+
+        for value in [
+            "",
+            "Runbook",
+            "2fa-setup",
+            "on call",
+            "../secrets",
+            "notes.md",
+        ] {
+            assert!(NoteId::parse(value).is_err(), "{}", value);
+        }
+        assert!(NoteId::parse(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_parse_any_id() {
+        assert!(matches!(
+            AnyId::parse("20250920-1432").unwrap(),
+            AnyId::Entry(EntryRef { time: Some(_), .. })
+        ));
+        assert_eq!(
+            AnyId::parse("oncall-runbook").unwrap().to_string(),
+            "oncall-runbook"
+        );
+        // Anything starting with a digit must be a valid date
+        assert!(AnyId::parse("2025-notes").is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+use color_eyre::eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::entry_id::EntryId;
+
+const SUMMARY_HEADING: &str = "## Summary";
+const IMPACT_HEADING: &str = "## Impact";
+const RETROSPECTIVE_HEADING: &str = "## Retrospective";
+
+/// What devlog knows about a `::project`: the entries mentioning it, kept up to date
+/// on every save, and a summary and impact written by hand below the frontmatter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectProfile {
+    pub name: String,
+    pub references: BTreeSet<EntryId>,
+    /// Day the project was closed with `devlog project close`, closed projects are left
+    /// out of shell completions and stale projects
+    pub closed: Option<NaiveDate>,
+    pub body: String,
+}
+
+/// Answers to the questions `devlog project close` asks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Retrospective {
+    pub outcome: String,
+    pub impact: String,
+    pub lessons: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Frontmatter {
+    name: String,
+    #[serde(default)]
+    references: Vec<EntryId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    closed: Option<NaiveDate>,
+}
+
+impl ProjectProfile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            references: BTreeSet::new(),
+            closed: None,
+            body: format!("{}\n\n\n{}\n\n", SUMMARY_HEADING, IMPACT_HEADING),
+        }
+    }
+
+    /// Parse a profile file, markdown with YAML frontmatter like entries
+    pub fn parse(content: &str) -> Result<Self> {
+        let (yaml, body) = content
+            .strip_prefix("---\n")
+            .and_then(|rest| rest.split_once("\n---\n"))
+            .unwrap_or(("", content));
+        let frontmatter: Frontmatter =
+            serde_yaml::from_str(yaml).wrap_err("Failed to parse project frontmatter")?;
+        Ok(Self {
+            name: frontmatter.name,
+            references: frontmatter.references.into_iter().collect(),
+            closed: frontmatter.closed,
+            body: body.trim_start_matches('\n').to_string(),
+        })
+    }
+
+    pub fn to_markdown(&self) -> Result<String> {
+        let frontmatter = Frontmatter {
+            name: self.name.clone(),
+            references: self.references.iter().copied().collect(),
+            closed: self.closed,
+        };
+        let yaml = serde_yaml::to_string(&frontmatter).wrap_err("Failed to serialize project")?;
+        Ok(format!("---\n{}---\n\n{}", yaml, self.body))
+    }
+
+    pub fn summary(&self) -> String {
+        self.section(SUMMARY_HEADING)
+    }
+
+    pub fn impact(&self) -> String {
+        self.section(IMPACT_HEADING)
+    }
+
+    pub fn retrospective(&self) -> String {
+        self.section(RETROSPECTIVE_HEADING)
+    }
+
+    /// Mark the project closed on `day` and append the retrospective to its ledger,
+    /// leaving out questions that weren't answered
+    pub fn close(&mut self, day: NaiveDate, retrospective: &Retrospective) {
+        let mut section = format!("{}\n\nClosed on {}.\n", RETROSPECTIVE_HEADING, day);
+        for (heading, answer) in [
+            ("Outcome", &retrospective.outcome),
+            ("Impact", &retrospective.impact),
+            ("Lessons", &retrospective.lessons),
+        ] {
+            if !answer.trim().is_empty() {
+                section.push_str(&format!("\n### {}\n\n{}\n", heading, answer.trim()));
+            }
+        }
+        self.body = format!("{}\n\n{}", self.body.trim_end(), section);
+        self.closed = Some(day);
+    }
+
+    /// Text under `heading` up to the next heading of the same level
+    fn section(&self, heading: &str) -> String {
+        self.body
+            .lines()
+            .skip_while(|line| line.trim_end() != heading)
+            .skip(1)
+            .take_while(|line| !line.starts_with("## "))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_keeps_hand_written_sections() {
+        let mut profile = ProjectProfile::new("search-engine");
+        profile.body = "## Summary\n\nFull text search.\n\n## Impact\n\nFaster support.\n".into();
+        profile.references.insert("20250915".parse().unwrap());
+
+        let markdown = profile.to_markdown().unwrap();
+        assert!(markdown.starts_with("---\nname: search-engine\nreferences:\n- '20250915'\n---\n"));
+
+        let parsed = ProjectProfile::parse(&markdown).unwrap();
+        assert_eq!(parsed, profile);
+        assert_eq!(parsed.summary(), "Full text search.");
+        assert_eq!(parsed.impact(), "Faster support.");
+    }
+
+    #[test]
+    fn test_close_appends_retrospective() {
+        let mut profile = ProjectProfile::new("search");
+        let day = NaiveDate::from_ymd_opt(2025, 9, 30).unwrap();
+        profile.close(
+            day,
+            &Retrospective {
+                outcome: "Shipped to everyone".to_string(),
+                impact: String::new(),
+                lessons: "Index early\n".to_string(),
+            },
+        );
+
+        assert_eq!(
+            profile.retrospective(),
+            "Closed on 2025-09-30.\n\n### Outcome\n\nShipped to everyone\n\n### Lessons\n\nIndex early"
+        );
+        let parsed = ProjectProfile::parse(&profile.to_markdown().unwrap()).unwrap();
+        assert_eq!(parsed.closed, Some(day));
+        assert_eq!(parsed, profile);
+    }
+
+    #[test]
+    fn test_empty_sections() {
+        let profile = ProjectProfile::new("devlog");
+        assert_eq!(profile.summary(), "");
+        assert_eq!(profile.impact(), "");
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::models::annotation::{AnnotationKind, Annotations};
+
+/// Marker of a duration annotation, e.g. `~1h30m`
+pub const DURATION_PREFIX: char = '~';
+
+/// Minutes logged per project, parsed from duration annotations.
+///
+/// A duration counts for the `::projects` on its own line, split evenly if there
+/// are several. Durations on lines without a project are kept under `""`.
+pub fn parse_time_log(content: &str) -> BTreeMap<String, u32> {
+    let mut minutes: BTreeMap<String, u32> = BTreeMap::new();
+
+    for line in content.lines() {
+        // Skip comment lines, the same way annotations do
+        if line.trim_start().starts_with("//") {
+            continue;
+        }
+        let logged: u32 = line.split_whitespace().filter_map(parse_duration).sum();
+        if logged == 0 {
+            continue;
+        }
+
+        let annotations = Annotations::parse(line);
+        let mut projects = annotations.values(AnnotationKind::Project).to_vec();
+        projects.sort();
+        projects.dedup();
+        if projects.is_empty() {
+            *minutes.entry(String::new()).or_default() += logged;
+            continue;
+        }
+        let share = logged / projects.len() as u32;
+        for project in projects {
+            *minutes.entry(project).or_default() += share;
+        }
+    }
+
+    minutes
+}
+
+/// Parse a duration annotation like `~2h`, `~45m` or `~1h30m` into minutes
+pub fn parse_duration(word: &str) -> Option<u32> {
+    let spec = word
+        .strip_prefix(DURATION_PREFIX)?
+        .trim_end_matches(['.', ',', ';', ')']);
+
+    let mut total = 0;
+    let mut number = String::new();
+    let mut seen_unit = false;
+    for ch in spec.chars() {
+        match ch {
+            '0'..='9' => number.push(ch),
+            'h' | 'm' if !number.is_empty() => {
+                let value: u32 = number.parse().ok()?;
+                total += if ch == 'h' { value * 60 } else { value };
+                number.clear();
+                seen_unit = true;
+            }
+            _ => return None,
+        }
+    }
+
+    (seen_unit && number.is_empty() && total > 0).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("~2h"), Some(120));
+        assert_eq!(parse_duration("~45m"), Some(45));
+        assert_eq!(parse_duration("~1h30m."), Some(90));
+        assert_eq!(parse_duration("~90"), None);
+        assert_eq!(parse_duration("~h"), None);
+        assert_eq!(parse_duration("2h"), None);
+    }
+
+    #[test]
+    fn test_parse_time_log_by_project() {
+        let log = parse_time_log(
+            "Fixed sync ::devlog ~2h\nPaired on ::web and ::api ~1h\nEmails ~30m\n// ::devlog ~5h",
+        );
+
+        assert_eq!(log.get("devlog"), Some(&120));
+        assert_eq!(log.get("web"), Some(&30));
+        assert_eq!(log.get("api"), Some(&30));
+        assert_eq!(log.get(""), Some(&30));
+    }
+}
@@ -0,0 +1,68 @@
+/// Box of a todo checked off with `devlog todo done`
+pub const DONE: char = 'x';
+/// Box of a todo carried forward to a later entry, which no longer counts as open
+pub const CARRIED: char = '>';
+
+/// Text of the open `- [ ]` and `* [ ]` items of markdown content
+pub fn open_todos(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(open_todo)
+        .map(|todo| todo.trim().to_string())
+        .collect()
+}
+
+/// Put `mark` in the box of the `n`th open todo, counting from 0. None if there
+/// are fewer open todos.
+pub fn mark_todo(content: &str, n: usize, mark: char) -> Option<String> {
+    let mut open = 0;
+    let mut marked = false;
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if open_todo(line).is_some() {
+                open += 1;
+                if open == n + 1 {
+                    marked = true;
+                    return line.replacen("[ ]", &format!("[{}]", mark), 1);
+                }
+            }
+            line.to_string()
+        })
+        .collect();
+
+    marked.then(|| {
+        let mut content_marked = lines.join("\n");
+        if content.ends_with('\n') {
+            content_marked.push('\n');
+        }
+        content_marked
+    })
+}
+
+fn open_todo(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    line.strip_prefix("- [ ]")
+        .or_else(|| line.strip_prefix("* [ ]"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_todos() {
+        let content = "Did things\n- [ ] open\n  * [ ] nested\n- [x] done\n- [>] carried";
+        assert_eq!(open_todos(content), vec!["open", "nested"]);
+    }
+
+    #[test]
+    fn test_mark_todo() {
+        let content = "- [x] done\n- [ ] first\n  - [ ] second [ ]\n";
+        assert_eq!(
+            mark_todo(content, 1, DONE).unwrap(),
+            "- [x] done\n- [ ] first\n  - [x] second [ ]\n"
+        );
+        assert_eq!(mark_todo(content, 2, DONE), None);
+    }
+}
@@ -0,0 +1,188 @@
+use color_eyre::eyre::Result;
+use serde_json::{Value, json};
+
+use crate::export::html::markdown_to_html;
+use crate::publish::{Api, Document, Outcome, expect_success};
+
+/// Publishes documents as pages of a Confluence space through the REST API
+pub struct Confluence<'a, A: Api> {
+    api: &'a A,
+    /// e.g. `https://team.atlassian.net/wiki`
+    base_url: String,
+    space: String,
+    parent_id: Option<String>,
+}
+
+impl<'a, A: Api> Confluence<'a, A> {
+    pub fn new(api: &'a A, base_url: &str, space: &str, parent_id: Option<String>) -> Self {
+        Self {
+            api,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            space: space.to_string(),
+            parent_id,
+        }
+    }
+
+    /// Create the page of `document` or update it in place. A page published before,
+    /// or one with the same title in the space, is updated.
+    pub fn publish(&self, document: &Document, known_page: Option<&str>) -> Result<Outcome> {
+        let page = match known_page {
+            Some(page) => Some(page.to_string()),
+            None => self.find_by_title(&document.title)?,
+        };
+        let current_version = match &page {
+            Some(page) => self.version(page)?,
+            None => None,
+        };
+
+        let outcome = match (page, current_version) {
+            (Some(page), Some(version)) => {
+                let url = format!("{}/rest/api/content/{}", self.base_url, page);
+                let mut body = self.content(document);
+                body["id"] = json!(page);
+                body["version"] = json!({ "number": version + 1 });
+                let (status, response) = self.api.send("PUT", &url, Some(&body))?;
+                expect_success(status, response, "update the Confluence page")?;
+                Outcome::Updated(page)
+            }
+            // Never published, or the page was deleted since
+            _ => {
+                let url = format!("{}/rest/api/content", self.base_url);
+                let mut body = self.content(document);
+                if let Some(parent) = &self.parent_id {
+                    body["ancestors"] = json!([{ "id": parent }]);
+                }
+                let (status, response) = self.api.send("POST", &url, Some(&body))?;
+                let response = expect_success(status, response, "create the Confluence page")?;
+                Outcome::Created(response["id"].as_str().unwrap_or_default().to_string())
+            }
+        };
+
+        if !document.labels.is_empty() {
+            // Adding a label the page already has is a no-op
+            let url = format!(
+                "{}/rest/api/content/{}/label",
+                self.base_url,
+                outcome.page_id()
+            );
+            let labels: Vec<Value> = document
+                .labels
+                .iter()
+                .map(|label| json!({ "prefix": "global", "name": label }))
+                .collect();
+            let (status, response) = self.api.send("POST", &url, Some(&json!(labels)))?;
+            expect_success(status, response, "label the Confluence page")?;
+        }
+        Ok(outcome)
+    }
+
+    fn content(&self, document: &Document) -> Value {
+        json!({
+            "type": "page",
+            "title": document.title,
+            "space": { "key": self.space },
+            "body": {
+                "storage": {
+                    "value": markdown_to_html(&document.markdown),
+                    "representation": "storage",
+                },
+            },
+        })
+    }
+
+    fn find_by_title(&self, title: &str) -> Result<Option<String>> {
+        let url = format!(
+            "{}/rest/api/content?spaceKey={}&title={}",
+            self.base_url,
+            encode(&self.space),
+            encode(title)
+        );
+        let (status, response) = self.api.send("GET", &url, None)?;
+        let response = expect_success(status, response, "search Confluence")?;
+        Ok(response["results"][0]["id"].as_str().map(str::to_string))
+    }
+
+    /// Current version of the page, none if it doesn't exist anymore
+    fn version(&self, page: &str) -> Result<Option<u64>> {
+        let url = format!("{}/rest/api/content/{}?expand=version", self.base_url, page);
+        let (status, response) = self.api.send("GET", &url, None)?;
+        if status == 404 {
+            return Ok(None);
+        }
+        let response = expect_success(status, response, "read the Confluence page")?;
+        Ok(response["version"]["number"].as_u64())
+    }
+}
+
+/// Percent-encode a query parameter value
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::publish::tests::FakeApi;
+    use chrono::NaiveDate;
+
+    fn document() -> Document {
+        Document {
+            key: "20250915".to_string(),
+            title: "Devlog 2025-09-15".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 9, 15).unwrap(),
+            labels: vec!["rust".to_string()],
+            markdown: "Shipped **search**".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_publish_updates_a_known_page() {
+        let api = FakeApi::new(vec![(200, json!({ "version": { "number": 3 } }))]);
+        let confluence = Confluence::new(&api, "https://team.atlassian.net/wiki/", "DEV", None);
+
+        let outcome = confluence.publish(&document(), Some("42")).unwrap();
+
+        assert_eq!(outcome, Outcome::Updated("42".to_string()));
+        assert_eq!(
+            api.requests(),
+            vec![
+                "GET https://team.atlassian.net/wiki/rest/api/content/42?expand=version",
+                "PUT https://team.atlassian.net/wiki/rest/api/content/42",
+                "POST https://team.atlassian.net/wiki/rest/api/content/42/label",
+            ]
+        );
+        let put = api.requests.borrow()[1].2.clone().unwrap();
+        assert_eq!(put["version"]["number"], 4);
+        assert_eq!(
+            put["body"]["storage"]["value"],
+            "<p>Shipped <strong>search</strong></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_publish_creates_a_missing_page() {
+        let api = FakeApi::new(vec![
+            (200, json!({ "results": [] })),
+            (200, json!({ "id": "7" })),
+        ]);
+        let confluence = Confluence::new(&api, "https://wiki", "DEV", Some("1".to_string()));
+
+        let outcome = confluence.publish(&document(), None).unwrap();
+
+        assert_eq!(outcome, Outcome::Created("7".to_string()));
+        assert_eq!(
+            api.requests()[0],
+            "GET https://wiki/rest/api/content?spaceKey=DEV&title=Devlog%202025-09-15"
+        );
+        let post = api.requests.borrow()[1].2.clone().unwrap();
+        assert_eq!(post["ancestors"][0]["id"], "1");
+    }
+}
@@ -0,0 +1,248 @@
+pub mod confluence;
+pub mod notion;
+use std::{collections::BTreeMap, fs, io::ErrorKind, sync::Arc};
+
+use chrono::NaiveDate;
+use color_eyre::eyre::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::digest::Digest;
+use crate::export::ExportedEntry;
+use crate::storage::Storage;
+
+const PUBLISHED_FILE_NAME: &str = "published.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PublishTarget {
+    Confluence,
+    Notion,
+}
+
+impl PublishTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PublishTarget::Confluence => "confluence",
+            PublishTarget::Notion => "notion",
+        }
+    }
+}
+
+/// One page to publish. The key identifies it across runs: the entry id,
+/// or `digest-<first day>` for a weekly digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub key: String,
+    pub title: String,
+    pub date: NaiveDate,
+    /// `+tags`, published as labels
+    pub labels: Vec<String>,
+    pub markdown: String,
+}
+
+impl Document {
+    pub fn entry(entry: &ExportedEntry) -> Self {
+        Self {
+            key: entry.id.to_string(),
+            title: format!("Devlog {}", entry.date),
+            date: entry.date,
+            labels: labels(&entry.annotations.tags),
+            markdown: entry.content.clone(),
+        }
+    }
+
+    /// The digest is published as is, its tags are the ones of the whole week
+    pub fn digest(digest: &Digest, entries: &[ExportedEntry]) -> Self {
+        let tags: Vec<String> = entries
+            .iter()
+            .flat_map(|entry| entry.annotations.tags.iter().cloned())
+            .collect();
+        Self {
+            key: format!("digest-{}", digest.start),
+            title: format!("Devlog week of {}", digest.start),
+            date: digest.start,
+            labels: labels(&tags),
+            markdown: digest.markdown.clone(),
+        }
+    }
+}
+
+/// Tags as labels: lowercase, each once, sorted
+fn labels(tags: &[String]) -> Vec<String> {
+    let mut labels: Vec<String> = tags.iter().map(|tag| tag.to_lowercase()).collect();
+    labels.sort();
+    labels.dedup();
+    labels
+}
+
+/// What publishing a document did, with the id of its page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Created(String),
+    Updated(String),
+}
+
+impl Outcome {
+    pub fn page_id(&self) -> &str {
+        match self {
+            Outcome::Created(page) | Outcome::Updated(page) => page,
+        }
+    }
+}
+
+/// Remote page ids of published documents by target and document key, so
+/// publishing again updates the page instead of creating another one
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Published {
+    #[serde(default)]
+    pub pages: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Published {
+    pub fn load(storage: &Storage) -> Result<Self> {
+        let path = storage.state_path().join(PUBLISHED_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, storage: &Storage) -> Result<()> {
+        let content = toml::to_string(self).wrap_err("Failed to serialize published pages")?;
+        storage
+            .writer()
+            .write_atomic(&storage.state_path().join(PUBLISHED_FILE_NAME), &content)
+            .wrap_err("Failed to save published pages")
+    }
+
+    pub fn page(&self, target: PublishTarget, key: &str) -> Option<&str> {
+        self.pages.get(target.name())?.get(key).map(String::as_str)
+    }
+
+    pub fn set_page(&mut self, target: PublishTarget, key: &str, page_id: String) {
+        self.pages
+            .entry(target.name().to_string())
+            .or_default()
+            .insert(key.to_string(), page_id);
+    }
+}
+
+/// A JSON HTTP API. Responses are returned whatever their status, so callers
+/// can tell a missing page from a failure.
+pub trait Api {
+    fn send(&self, method: &str, url: &str, body: Option<&Value>) -> Result<(u16, Value)>;
+}
+
+/// Fail unless `status` is a success, with the API's own error message
+pub fn expect_success(status: u16, body: Value, what: &str) -> Result<Value> {
+    if (200..300).contains(&status) {
+        return Ok(body);
+    }
+    let message = body["message"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| body.to_string());
+    bail!("Failed to {}: HTTP {}: {}", what, status, message)
+}
+
+/// `Api` over HTTPS, sending the same headers with every request
+pub struct HttpApi {
+    agent: ureq::Agent,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl HttpApi {
+    pub fn new(headers: Vec<(&'static str, String)>) -> Result<Self> {
+        let tls = native_tls::TlsConnector::new().wrap_err("Failed to set up TLS")?;
+        Ok(Self {
+            agent: ureq::AgentBuilder::new()
+                .tls_connector(Arc::new(tls))
+                .build(),
+            headers,
+        })
+    }
+}
+
+impl Api for HttpApi {
+    fn send(&self, method: &str, url: &str, body: Option<&Value>) -> Result<(u16, Value)> {
+        let mut request = self
+            .agent
+            .request(method, url)
+            .set("Accept", "application/json");
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+        let response = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to reach {}", url)),
+        };
+        let status = response.status();
+        let text = response
+            .into_string()
+            .wrap_err_with(|| format!("Failed to read the response of {}", url))?;
+        // Deletes and some errors have empty or non JSON bodies
+        let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+        Ok((status, body))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{cell::RefCell, collections::VecDeque};
+
+    use super::*;
+
+    /// Replays canned responses and records the requests made
+    #[derive(Default)]
+    pub struct FakeApi {
+        pub responses: RefCell<VecDeque<(u16, Value)>>,
+        pub requests: RefCell<Vec<(String, String, Option<Value>)>>,
+    }
+
+    impl FakeApi {
+        pub fn new(responses: Vec<(u16, Value)>) -> Self {
+            Self {
+                responses: RefCell::new(responses.into()),
+                requests: RefCell::default(),
+            }
+        }
+
+        pub fn requests(&self) -> Vec<String> {
+            self.requests
+                .borrow()
+                .iter()
+                .map(|(method, url, _)| format!("{} {}", method, url))
+                .collect()
+        }
+    }
+
+    impl Api for FakeApi {
+        fn send(&self, method: &str, url: &str, body: Option<&Value>) -> Result<(u16, Value)> {
+            self.requests
+                .borrow_mut()
+                .push((method.to_string(), url.to_string(), body.cloned()));
+            Ok(self
+                .responses
+                .borrow_mut()
+                .pop_front()
+                .unwrap_or((200, Value::Null)))
+        }
+    }
+
+    #[test]
+    fn test_published_pages_roundtrip() {
+        let mut published = Published::default();
+        published.set_page(PublishTarget::Notion, "20250915", "abc".to_string());
+
+        let parsed: Published = toml::from_str(&toml::to_string(&published).unwrap()).unwrap();
+        assert_eq!(parsed.page(PublishTarget::Notion, "20250915"), Some("abc"));
+        assert_eq!(parsed.page(PublishTarget::Confluence, "20250915"), None);
+    }
+}
@@ -0,0 +1,314 @@
+use color_eyre::eyre::Result;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
+use serde_json::{Value, json};
+
+use crate::publish::{Api, Document, Outcome, expect_success};
+
+pub const API_URL: &str = "https://api.notion.com/v1";
+/// API version sent in the `Notion-Version` header
+pub const API_VERSION: &str = "2022-06-28";
+
+/// Notion accepts at most this many blocks per request and characters per text
+const MAX_BLOCKS: usize = 100;
+const MAX_TEXT_LEN: usize = 2000;
+
+/// Code languages Notion knows, others are published as plain text
+const LANGUAGES: [&str; 12] = [
+    "bash",
+    "c",
+    "go",
+    "java",
+    "javascript",
+    "json",
+    "python",
+    "rust",
+    "shell",
+    "sql",
+    "typescript",
+    "yaml",
+];
+
+/// Publishes documents as pages of a Notion database. The database needs a `Name`
+/// title, a `Date` date, a `Tags` multi-select and an `Entry` text property.
+pub struct Notion<'a, A: Api> {
+    api: &'a A,
+    database_id: String,
+}
+
+impl<'a, A: Api> Notion<'a, A> {
+    pub fn new(api: &'a A, database_id: &str) -> Self {
+        Self {
+            api,
+            database_id: database_id.to_string(),
+        }
+    }
+
+    /// Create the page of `document` or replace the properties and content of the
+    /// page published before, found by its `Entry` property if it isn't known
+    pub fn publish(&self, document: &Document, known_page: Option<&str>) -> Result<Outcome> {
+        let page = match known_page {
+            Some(page) => Some(page.to_string()),
+            None => self.find_by_key(&document.key)?,
+        };
+        let blocks = blocks(&document.markdown);
+
+        if let Some(page) = page {
+            let url = format!("{}/pages/{}", API_URL, page);
+            let body = json!({ "properties": self.properties(document) });
+            let (status, response) = self.api.send("PATCH", &url, Some(&body))?;
+            // A page deleted in Notion is published again below
+            if status != 404 {
+                expect_success(status, response, "update the Notion page")?;
+                self.clear(&page)?;
+                self.append(&page, &blocks)?;
+                return Ok(Outcome::Updated(page));
+            }
+        }
+
+        let first = blocks.len().min(MAX_BLOCKS);
+        let body = json!({
+            "parent": { "database_id": self.database_id },
+            "properties": self.properties(document),
+            "children": blocks[..first],
+        });
+        let (status, response) =
+            self.api
+                .send("POST", &format!("{}/pages", API_URL), Some(&body))?;
+        let response = expect_success(status, response, "create the Notion page")?;
+        let page = response["id"].as_str().unwrap_or_default().to_string();
+        self.append(&page, &blocks[first..])?;
+        Ok(Outcome::Created(page))
+    }
+
+    fn properties(&self, document: &Document) -> Value {
+        let tags: Vec<Value> = document
+            .labels
+            .iter()
+            .map(|label| json!({ "name": label }))
+            .collect();
+        json!({
+            "Name": { "title": rich_text(&document.title) },
+            "Date": { "date": { "start": document.date.to_string() } },
+            "Tags": { "multi_select": tags },
+            "Entry": { "rich_text": rich_text(&document.key) },
+        })
+    }
+
+    fn find_by_key(&self, key: &str) -> Result<Option<String>> {
+        let url = format!("{}/databases/{}/query", API_URL, self.database_id);
+        let body = json!({ "filter": { "property": "Entry", "rich_text": { "equals": key } } });
+        let (status, response) = self.api.send("POST", &url, Some(&body))?;
+        let response = expect_success(status, response, "search the Notion database")?;
+        Ok(response["results"][0]["id"].as_str().map(str::to_string))
+    }
+
+    /// Delete every block of the page
+    fn clear(&self, page: &str) -> Result<()> {
+        let mut children = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut url = format!("{}/blocks/{}/children?page_size=100", API_URL, page);
+            if let Some(cursor) = &cursor {
+                url.push_str(&format!("&start_cursor={}", cursor));
+            }
+            let (status, response) = self.api.send("GET", &url, None)?;
+            let response = expect_success(status, response, "read the Notion page")?;
+            children.extend(
+                response["results"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|block| block["id"].as_str().map(str::to_string)),
+            );
+            cursor = response["next_cursor"].as_str().map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        for child in children {
+            let url = format!("{}/blocks/{}", API_URL, child);
+            let (status, response) = self.api.send("DELETE", &url, None)?;
+            expect_success(status, response, "clear the Notion page")?;
+        }
+        Ok(())
+    }
+
+    fn append(&self, page: &str, blocks: &[Value]) -> Result<()> {
+        for chunk in blocks.chunks(MAX_BLOCKS) {
+            let url = format!("{}/blocks/{}/children", API_URL, page);
+            let body = json!({ "children": chunk });
+            let (status, response) = self.api.send("PATCH", &url, Some(&body))?;
+            expect_success(status, response, "write the Notion page")?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert markdown into Notion blocks, as plain text without inline formatting.
+/// Nested lists are flattened.
+pub fn blocks(markdown: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    // Kind of the block being collected and its extra fields
+    let mut current: Option<(&str, Value)> = None;
+    let mut text = String::new();
+    // Per open list, whether it is numbered
+    let mut lists: Vec<bool> = Vec::new();
+    let mut in_quote = false;
+
+    for event in Parser::new_ext(markdown, pulldown_cmark::Options::ENABLE_TASKLISTS) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                let kind = match level {
+                    HeadingLevel::H1 => "heading_1",
+                    HeadingLevel::H2 => "heading_2",
+                    _ => "heading_3",
+                };
+                current = Some((kind, json!({})));
+            }
+            Event::Start(Tag::Paragraph) if current.is_none() => {
+                let kind = if in_quote { "quote" } else { "paragraph" };
+                current = Some((kind, json!({})));
+            }
+            Event::Start(Tag::List(first)) => {
+                // Text of the item holding the nested list comes first
+                flush(&mut blocks, &mut current, &mut text);
+                lists.push(first.is_some());
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                let kind = if lists.last() == Some(&true) {
+                    "numbered_list_item"
+                } else {
+                    "bulleted_list_item"
+                };
+                current = Some((kind, json!({})));
+            }
+            Event::TaskListMarker(checked) => {
+                current = Some(("to_do", json!({ "checked": checked })));
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().map(str::to_lowercase)
+                    }
+                    CodeBlockKind::Indented => None,
+                }
+                .filter(|language| LANGUAGES.contains(&language.as_str()))
+                .unwrap_or_else(|| "plain text".to_string());
+                current = Some(("code", json!({ "language": language })));
+            }
+            Event::Start(Tag::BlockQuote(_)) => in_quote = true,
+            Event::End(TagEnd::BlockQuote(_)) => in_quote = false,
+            Event::Text(value) | Event::Code(value) => text.push_str(&value),
+            Event::SoftBreak => text.push(' '),
+            Event::HardBreak => text.push('\n'),
+            Event::Rule => {
+                flush(&mut blocks, &mut current, &mut text);
+                blocks.push(json!({ "object": "block", "type": "divider", "divider": {} }));
+            }
+            // Paragraphs of a list item make up the item
+            Event::End(TagEnd::Paragraph) if !lists.is_empty() => {}
+            Event::End(
+                TagEnd::Heading(_) | TagEnd::Paragraph | TagEnd::Item | TagEnd::CodeBlock,
+            ) => flush(&mut blocks, &mut current, &mut text),
+            _ => {}
+        }
+    }
+    flush(&mut blocks, &mut current, &mut text);
+    blocks
+}
+
+/// Add the block being collected, if any
+fn flush(blocks: &mut Vec<Value>, current: &mut Option<(&str, Value)>, text: &mut String) {
+    if let Some((kind, mut fields)) = current.take() {
+        fields["rich_text"] = json!(rich_text(text.trim_end()));
+        blocks.push(json!({ "object": "block", "type": kind, kind: fields }));
+    }
+    text.clear();
+}
+
+/// Notion rich text, split into pieces of the maximum length
+fn rich_text(text: &str) -> Vec<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(MAX_TEXT_LEN)
+        .map(|chunk| json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::publish::tests::FakeApi;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_markdown_to_blocks() {
+        let blocks = blocks(
+            "# Week\n\nShipped `search`\nto prod\n\n- [x] done\n- open\n\n1. first\n\n\
+             ```rust\nfn main() {}\n```\n\n> quoted\n\n---",
+        );
+        let kinds: Vec<&str> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                "heading_1",
+                "paragraph",
+                "to_do",
+                "bulleted_list_item",
+                "numbered_list_item",
+                "code",
+                "quote",
+                "divider"
+            ]
+        );
+        assert_eq!(
+            blocks[1]["paragraph"]["rich_text"][0]["text"]["content"],
+            "Shipped search to prod"
+        );
+        assert_eq!(blocks[2]["to_do"]["checked"], true);
+        assert_eq!(blocks[5]["code"]["language"], "rust");
+    }
+
+    #[test]
+    fn test_publish_replaces_known_page() {
+        let api = FakeApi::new(vec![
+            (200, json!({})),
+            (
+                200,
+                json!({ "results": [{ "id": "b1" }], "next_cursor": null }),
+            ),
+        ]);
+        let document = Document {
+            key: "20250915".to_string(),
+            title: "Devlog 2025-09-15".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 9, 15).unwrap(),
+            labels: vec!["rust".to_string()],
+            markdown: "Shipped".to_string(),
+        };
+
+        let outcome = Notion::new(&api, "db")
+            .publish(&document, Some("p1"))
+            .unwrap();
+
+        assert_eq!(outcome, Outcome::Updated("p1".to_string()));
+        assert_eq!(
+            api.requests(),
+            vec![
+                format!("PATCH {}/pages/p1", API_URL),
+                format!("GET {}/blocks/p1/children?page_size=100", API_URL),
+                format!("DELETE {}/blocks/b1", API_URL),
+                format!("PATCH {}/blocks/p1/children", API_URL),
+            ]
+        );
+        let properties = api.requests.borrow()[0].2.clone().unwrap();
+        assert_eq!(
+            properties["properties"]["Tags"]["multi_select"][0]["name"],
+            "rust"
+        );
+    }
+}
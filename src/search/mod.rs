@@ -0,0 +1,306 @@
+use std::fmt;
+use std::ops::Range;
+
+use color_eyre::eyre::Result;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::models::note::AnyId;
+use crate::storage::Storage;
+
+/// Lines shown before and after each match unless asked otherwise
+pub const DEFAULT_CONTEXT: usize = 2;
+
+/// An entry or note containing the query, with the numbers of its matching lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryMatches {
+    pub id: AnyId,
+    pub lines: Vec<String>,
+    /// Indices into `lines`, in order
+    pub hits: Vec<usize>,
+}
+
+impl EntryMatches {
+    /// Ranges of lines around the hits, `context` lines on either side, with
+    /// overlapping and adjacent ranges joined
+    pub fn excerpts(&self, context: usize) -> Vec<Range<usize>> {
+        let mut excerpts: Vec<Range<usize>> = Vec::new();
+        for &hit in &self.hits {
+            let range = hit.saturating_sub(context)..(hit + context + 1).min(self.lines.len());
+            match excerpts.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => excerpts.push(range),
+            }
+        }
+        excerpts
+    }
+}
+
+/// Whether `line` contains `query`, ignoring case
+pub fn is_match(line: &str, query: &str) -> bool {
+    line.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// An entry or note left out of the results because it couldn't be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreadable {
+    pub id: AnyId,
+    pub error: String,
+}
+
+impl fmt::Display for Unreadable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Skipped unreadable entry {}: {}", self.id, self.error)
+    }
+}
+
+/// The matches of a search, and the entries it had to skip
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<T> {
+    pub matches: Vec<T>,
+    pub unreadable: Vec<Unreadable>,
+}
+
+/// An entry or note with its content, without the frontmatter
+type Contents = (AnyId, String);
+
+/// The content of every entry that can be loaded, oldest first, then of every
+/// note by name. One broken file shouldn't hide the matches in all the others,
+/// so those are reported instead.
+fn load_contents(storage: &Storage) -> Result<(Vec<Contents>, Vec<Unreadable>)> {
+    let mut ids = storage.list_entries()?;
+    ids.sort();
+    ids.dedup();
+
+    let mut contents = Vec::new();
+    let mut unreadable = Vec::new();
+    let entries = ids.into_iter().map(|id| {
+        let content = storage.load_entry(&id).map(|entry| entry.content);
+        (AnyId::from(id), content)
+    });
+    let notes = storage.list_notes()?.into_iter().map(|id| {
+        let content = storage.load_note(&id).map(|note| note.content);
+        (AnyId::Note(id), content)
+    });
+    for (id, content) in entries.chain(notes) {
+        match content {
+            Ok(content) => contents.push((id, content)),
+            Err(e) => unreadable.push(Unreadable {
+                id,
+                error: e.to_string(),
+            }),
+        }
+    }
+    Ok((contents, unreadable))
+}
+
+/// Every entry with a line containing `query`, ignoring case, oldest first,
+/// then every such note
+pub fn search(storage: &Storage, query: &str) -> Result<Found<EntryMatches>> {
+    let (contents, unreadable) = load_contents(storage)?;
+
+    let mut matches = Vec::new();
+    for (id, content) in contents {
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let hits: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| is_match(line, query))
+            .map(|(index, _)| index)
+            .collect();
+        if !hits.is_empty() {
+            matches.push(EntryMatches { id, lines, hits });
+        }
+    }
+    Ok(Found {
+        matches,
+        unreadable,
+    })
+}
+
+/// A line of an entry or note matching the pattern given to `devlog grep`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LineMatch {
+    pub entry: AnyId,
+    /// Counted from 1 in the content, without its frontmatter
+    pub line: usize,
+    pub text: String,
+    /// Byte ranges of every match in `text`
+    pub spans: Vec<Range<usize>>,
+}
+
+/// Every line matching `pattern`, oldest entry first, then the notes
+pub fn grep(storage: &Storage, pattern: &Regex) -> Result<Found<LineMatch>> {
+    let (contents, unreadable) = load_contents(storage)?;
+
+    let mut matches = Vec::new();
+    for (id, content) in contents {
+        for (index, line) in content.lines().enumerate() {
+            let spans: Vec<Range<usize>> = pattern.find_iter(line).map(|m| m.range()).collect();
+            if !spans.is_empty() {
+                matches.push(LineMatch {
+                    entry: id.clone(),
+                    line: index + 1,
+                    text: line.to_string(),
+                    spans,
+                });
+            }
+        }
+    }
+    Ok(Found {
+        matches,
+        unreadable,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_search_ignores_case() {
+        let (storage, _temp_dir) = create_test_storage();
+        for (id, content) in [
+            ("20250920", "Paged for an Incident\nrolled back"),
+            ("20250901", "incident review\nquiet day\nincident closed"),
+            ("20250910", "nothing"),
+        ] {
+            storage
+                .save_entry(&Entry::new(id.parse().unwrap(), content.to_string()))
+                .unwrap();
+        }
+
+        let results = search(&storage, "INCIDENT").unwrap();
+        let found: Vec<(String, Vec<usize>)> = results
+            .matches
+            .iter()
+            .map(|matches| (matches.id.to_string(), matches.hits.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("20250901".to_string(), vec![0, 2]),
+                ("20250920".to_string(), vec![0])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_skips_unreadable_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage
+            .save_entry(&Entry::new(
+                "20250920".parse().unwrap(),
+                "incident review".to_string(),
+            ))
+            .unwrap();
+        let broken = storage.entry_path(&"20250921".parse().unwrap()).unwrap();
+        std::fs::write(&broken, "---\nid: 20250101\n---\n\nincident notes").unwrap();
+
+        let results = search(&storage, "incident").unwrap();
+        let found: Vec<String> = results.matches.iter().map(|m| m.id.to_string()).collect();
+        assert_eq!(found, vec!["20250920"]);
+        assert_eq!(results.unreadable.len(), 1);
+        assert_eq!(results.unreadable[0].id.to_string(), "20250921");
+    }
+
+    #[test]
+    fn test_grep_reports_lines_and_spans() {
+        let (storage, _temp_dir) = create_test_storage();
+        for (id, content) in [
+            ("20250920", "Fixed PR-12\nreviewed PR-7 and PR-9"),
+            ("20250901", "no pull requests"),
+        ] {
+            storage
+                .save_entry(&Entry::new(id.parse().unwrap(), content.to_string()))
+                .unwrap();
+        }
+
+        // A copy in a subdirectory is not a second entry
+        let nested = storage.data_path().join("entries").join("2025");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("20250920.md"), "PR-1").unwrap();
+
+        let results = grep(&storage, &Regex::new(r"PR-\d+").unwrap())
+            .unwrap()
+            .matches;
+        assert_eq!(
+            results,
+            vec![
+                LineMatch {
+                    entry: "20250920".parse().unwrap(),
+                    line: 1,
+                    text: "Fixed PR-12".to_string(),
+                    spans: std::iter::once(6..11).collect(),
+                },
+                LineMatch {
+                    entry: "20250920".parse().unwrap(),
+                    line: 2,
+                    text: "reviewed PR-7 and PR-9".to_string(),
+                    spans: vec![9..13, 18..22],
+                },
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&results[0]).unwrap(),
+            serde_json::json!({
+                "entry": "20250920",
+                "line": 1,
+                "text": "Fixed PR-12",
+                "spans": [{ "start": 6, "end": 11 }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_notes_are_searched_after_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage
+            .save_entry(&Entry::new(
+                "20250920".parse().unwrap(),
+                "Restarted the pods".to_string(),
+            ))
+            .unwrap();
+        storage
+            .append_to_note(
+                &"oncall-runbook".parse().unwrap(),
+                "# Runbook\nRestart the pods",
+            )
+            .unwrap();
+
+        let found: Vec<String> = search(&storage, "restart")
+            .unwrap()
+            .matches
+            .iter()
+            .map(|m| m.id.to_string())
+            .collect();
+        assert_eq!(found, vec!["20250920", "oncall-runbook"]);
+
+        let lines = grep(&storage, &Regex::new("pods").unwrap())
+            .unwrap()
+            .matches;
+        assert_eq!(lines[1].entry.to_string(), "oncall-runbook");
+        assert_eq!(lines[1].line, 2);
+    }
+
+    #[test]
+    fn test_excerpts_join_nearby_hits() {
+        let matches = EntryMatches {
+            id: "20250920".parse().unwrap(),
+            lines: (0..20).map(|n| n.to_string()).collect(),
+            hits: vec![1, 4, 15],
+        };
+        assert_eq!(matches.excerpts(1), vec![0..6, 14..17]);
+        assert_eq!(matches.excerpts(2), vec![0..7, 13..18]);
+        assert_eq!(matches.excerpts(0), vec![1..2, 4..5, 15..16]);
+    }
+}
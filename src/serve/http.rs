@@ -0,0 +1,246 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use color_eyre::eyre::{Context, Result, bail};
+
+/// Largest request body accepted, clips are a selection and a URL
+const MAX_BODY_LEN: usize = 1024 * 1024;
+/// Longest request line or header accepted
+const MAX_LINE_LEN: usize = 8 * 1024;
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 64;
+/// How long a client may take to send its request or read the response. Requests
+/// are served one at a time, so a client that stalls blocks everyone else.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The parts of an HTTP/1.1 request the serve mode looks at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Read one request, the body is only read up to its `Content-Length`
+    pub fn read(reader: &mut impl BufRead) -> Result<Self> {
+        let mut line = String::new();
+        read_line(reader, &mut line).wrap_err("Failed to read request")?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+            bail!("Malformed request line '{}'", line.trim_end());
+        };
+        let method = method.to_string();
+        let path = path.split('?').next().unwrap_or_default().to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            read_line(reader, &mut line).wrap_err("Failed to read request headers")?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if headers.len() == MAX_HEADERS {
+                bail!("More than {} request headers", MAX_HEADERS);
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        let mut request = Self {
+            method,
+            path,
+            headers,
+            body: Vec::new(),
+        };
+        let len: usize = request
+            .header("content-length")
+            .and_then(|len| len.parse().ok())
+            .unwrap_or(0);
+        if len > MAX_BODY_LEN {
+            bail!("Request body of {} bytes is too large", len);
+        }
+        request.body.resize(len, 0);
+        reader
+            .read_exact(&mut request.body)
+            .wrap_err("Failed to read request body")?;
+        Ok(request)
+    }
+
+    /// Value of a header, names are case-insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Token from an `Authorization: Bearer <token>` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.header("authorization")?
+            .strip_prefix("Bearer ")
+            .map(str::trim)
+    }
+}
+
+/// Read one line of the request head into `line`, refusing lines over `MAX_LINE_LEN`
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> Result<()> {
+    line.clear();
+    let len = reader.by_ref().take(MAX_LINE_LEN as u64).read_line(line)?;
+    if len == MAX_LINE_LEN && !line.ends_with('\n') {
+        bail!("Line is longer than {} bytes", MAX_LINE_LEN);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub fn empty(status: u16) -> Self {
+        Self {
+            status,
+            content_type: "text/plain",
+            body: String::new(),
+        }
+    }
+
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: body.to_string(),
+        }
+    }
+
+    pub fn html(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, serde_json::json!({ "error": message }))
+    }
+
+    /// Write the response with CORS headers, so extension pages on any origin can call us.
+    /// Every endpoint but `/pair` needs a token, which is what actually guards the vault,
+    /// and `/pair` turns down web pages by their `Origin`.
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: {}\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Authorization, Content-Type\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+        .and_then(|_| writer.flush())
+        .wrap_err("Failed to write response")
+    }
+}
+
+/// Serve requests one at a time until the process is stopped
+pub fn serve(listener: TcpListener, handle: impl Fn(&Request) -> Response) -> Result<()> {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = serve_connection(stream, &handle) {
+                    eprintln!("warning: {:#}", e);
+                }
+            }
+            Err(e) => eprintln!("warning: Failed to accept connection: {}", e),
+        }
+    }
+    Ok(())
+}
+
+fn serve_connection(stream: TcpStream, handle: impl Fn(&Request) -> Response) -> Result<()> {
+    stream
+        .set_read_timeout(Some(IO_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(IO_TIMEOUT)))
+        .wrap_err("Failed to set socket timeouts")?;
+    let mut reader = BufReader::new(stream.try_clone().wrap_err("Failed to read socket")?);
+    let response = match Request::read(&mut reader) {
+        Ok(request) => handle(&request),
+        Err(e) => Response::error(400, &format!("{:#}", e)),
+    };
+    let mut stream = stream;
+    response.write_to(&mut stream)
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /clip?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc\r\n\
+                   Content-Length: 4\r\n\r\n{}{}trailing";
+        let request = Request::read(&mut raw.as_bytes()).unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/clip");
+        assert_eq!(request.header("HOST"), Some("localhost"));
+        assert_eq!(request.bearer_token(), Some("abc"));
+        assert_eq!(request.body, b"{}{}");
+    }
+
+    #[test]
+    fn test_read_request_limits_head_size() {
+        let long_header = format!(
+            "GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "a".repeat(MAX_LINE_LEN)
+        );
+        assert!(Request::read(&mut long_header.as_bytes()).is_err());
+
+        let many_headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X-A: b\r\n".repeat(MAX_HEADERS + 1)
+        );
+        assert!(Request::read(&mut many_headers.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_response_has_cors_headers() {
+        let mut out = Vec::new();
+        Response::error(404, "nope").write_to(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(out.contains("Access-Control-Allow-Origin: *\r\n"));
+        assert!(out.ends_with("{\"error\":\"nope\"}"));
+    }
+}
@@ -0,0 +1,210 @@
+pub mod http;
+pub mod pairing;
+pub mod preview;
+
+use std::net::TcpListener;
+
+use chrono::Utc;
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::models::entry_id::EntryId;
+use crate::serve::{
+    http::{Request, Response},
+    pairing::Pairing,
+};
+use crate::storage::Storage;
+
+/// Tag added to everything clipped from the browser
+pub const CLIP_TAG: &str = "+clip";
+
+/// Origins of extension pages, browsers send one of these with every extension request
+const EXTENSION_SCHEMES: [&str; 3] = [
+    "chrome-extension://",
+    "moz-extension://",
+    "safari-web-extension://",
+];
+
+/// Selected text sent by the browser extension
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Clip {
+    pub text: String,
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl Clip {
+    /// Markdown appended to the entry: the selection as a quote and a link to its page
+    pub fn to_markdown(&self) -> String {
+        let quote = self
+            .text
+            .trim()
+            .lines()
+            .map(|line| format!("> {}", line).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let title = self
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+            .unwrap_or(&self.url);
+        format!("{}\n\n[{}]({}) {}", quote, title, self.url, CLIP_TAG)
+    }
+}
+
+#[derive(Deserialize)]
+struct PairRequest {
+    code: String,
+}
+
+/// Local HTTP endpoint for the browser extension companion
+pub struct Server<'a> {
+    storage: &'a Storage,
+}
+
+impl<'a> Server<'a> {
+    pub fn new(storage: &'a Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Serve requests one at a time until the process is stopped
+    pub fn run(&self, listener: TcpListener) -> Result<()> {
+        http::serve(listener, |request| self.handle(request))
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            // CORS preflight, the headers are on every response
+            ("OPTIONS", _) => Ok(Response::empty(204)),
+            ("POST", "/pair") => self.pair(request),
+            ("POST", "/clip") => self.clip(request),
+            (_, "/pair" | "/clip") => Ok(Response::error(405, "Use POST")),
+            _ => Ok(Response::error(404, "Unknown endpoint")),
+        };
+        response.unwrap_or_else(|e| Response::error(500, &format!("{:#}", e)))
+    }
+
+    fn pair(&self, request: &Request) -> Result<Response> {
+        // A web page could otherwise burn the pairing code with wrong guesses
+        if request.header("origin").is_some_and(|origin| {
+            !EXTENSION_SCHEMES
+                .iter()
+                .any(|scheme| origin.starts_with(scheme))
+        }) {
+            return Ok(Response::error(403, "Pair from the browser extension"));
+        }
+        let Ok(body) = serde_json::from_slice::<PairRequest>(&request.body) else {
+            return Ok(Response::error(400, "Expected {\"code\": \"...\"}"));
+        };
+        let mut pairing = Pairing::load(self.storage)?;
+        let token = pairing.redeem(&body.code, Utc::now())?;
+        pairing.save(self.storage)?;
+        Ok(match token {
+            Some(token) => Response::json(201, json!({ "token": token })),
+            None => Response::error(403, "Invalid or expired pairing code"),
+        })
+    }
+
+    fn clip(&self, request: &Request) -> Result<Response> {
+        let pairing = Pairing::load(self.storage)?;
+        if !request
+            .bearer_token()
+            .is_some_and(|token| pairing.is_authorized(token))
+        {
+            return Ok(Response::error(401, "Pair with `devlog serve pair` first"));
+        }
+        let clip = match serde_json::from_slice::<Clip>(&request.body) {
+            Ok(clip) if !clip.text.trim().is_empty() => clip,
+            _ => {
+                return Ok(Response::error(
+                    400,
+                    "Expected {\"text\": \"...\", \"url\": \"...\"}",
+                ));
+            }
+        };
+
+        let id = EntryId::today();
+        self.storage.append_to_entry(&id, &clip.to_markdown())?;
+        Ok(Response::json(201, json!({ "entry": id.to_string() })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn post(path: &str, token: Option<&str>, body: serde_json::Value) -> Request {
+        let mut headers = vec![("content-type".to_string(), "application/json".to_string())];
+        if let Some(token) = token {
+            headers.push(("authorization".to_string(), format!("Bearer {}", token)));
+        }
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            headers,
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    #[test]
+    fn test_clip_to_markdown() {
+        let clip = Clip {
+            text: "first line\n\nsecond line\n".to_string(),
+            url: "https://example.com/post".to_string(),
+            title: Some("A post".to_string()),
+        };
+        assert_eq!(
+            clip.to_markdown(),
+            "> first line\n>\n> second line\n\n[A post](https://example.com/post) +clip"
+        );
+    }
+
+    #[test]
+    fn test_pair_then_clip_into_today() {
+        let (storage, _temp_dir) = create_test_storage();
+        let server = Server::new(&storage);
+
+        let clip = json!({ "text": "quote", "url": "https://example.com" });
+        assert_eq!(
+            server.handle(&post("/clip", None, clip.clone())).status,
+            401
+        );
+
+        let mut pairing = Pairing::default();
+        let code = pairing.new_code(Utc::now()).unwrap().code.clone();
+        pairing.save(&storage).unwrap();
+
+        let mut from_page = post("/pair", None, json!({ "code": "000000" }));
+        from_page
+            .headers
+            .push(("origin".to_string(), "https://example.com".to_string()));
+        assert_eq!(server.handle(&from_page).status, 403);
+
+        let mut request = post("/pair", None, json!({ "code": code }));
+        request.headers.push((
+            "origin".to_string(),
+            "chrome-extension://abcdefghijklmnop".to_string(),
+        ));
+        let response = server.handle(&request);
+        assert_eq!(response.status, 201);
+        let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        let token = body["token"].as_str().unwrap();
+
+        assert_eq!(server.handle(&post("/clip", Some(token), clip)).status, 201);
+        assert_eq!(
+            storage.load_entry(&EntryId::today()).unwrap().content,
+            "> quote\n\n[https://example.com](https://example.com) +clip"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::{fs, io::ErrorKind};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::eyre::{Context, Result, eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+const PAIRING_FILE_NAME: &str = "serve.toml";
+
+/// How long a pairing code printed by `devlog serve pair` can be used
+const CODE_LIFETIME_MINUTES: i64 = 10;
+
+/// Wrong guesses allowed before a pairing code is thrown away
+const MAX_FAILED_ATTEMPTS: u32 = 3;
+
+/// Pairing state of the serve mode: at most one pending code, and the tokens
+/// handed out to paired extensions
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pairing {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<PairingCode>,
+    #[serde(default)]
+    pub tokens: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairingCode {
+    pub code: String,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub failed_attempts: u32,
+}
+
+impl Pairing {
+    pub fn load(storage: &Storage) -> Result<Self> {
+        let path = storage.state_path().join(PAIRING_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, storage: &Storage) -> Result<()> {
+        let content = toml::to_string(self).wrap_err("Failed to serialize pairing state")?;
+        storage
+            .writer()
+            .write_atomic(&storage.state_path().join(PAIRING_FILE_NAME), &content)
+            .wrap_err("Failed to save pairing state")
+    }
+
+    /// Start pairing, replacing any code that wasn't used yet
+    pub fn new_code(&mut self, now: DateTime<Utc>) -> Result<&PairingCode> {
+        // Rejection sampling keeps every code equally likely
+        let code = loop {
+            let value = u32::from_le_bytes(random_bytes()?);
+            if value < u32::MAX - u32::MAX % 1_000_000 {
+                break value % 1_000_000;
+            }
+        };
+        Ok(self.code.insert(PairingCode {
+            code: format!("{:06}", code),
+            expires_at: now + Duration::minutes(CODE_LIFETIME_MINUTES),
+            failed_attempts: 0,
+        }))
+    }
+
+    /// Trade a pairing code for a new token. Codes work once, and are thrown away
+    /// after a few wrong guesses so they can't be brute forced.
+    pub fn redeem(&mut self, code: &str, now: DateTime<Utc>) -> Result<Option<String>> {
+        let Some(mut pending) = self.code.take() else {
+            return Ok(None);
+        };
+        if pending.expires_at < now {
+            return Ok(None);
+        }
+        if pending.code != code.trim() {
+            pending.failed_attempts += 1;
+            if pending.failed_attempts < MAX_FAILED_ATTEMPTS {
+                self.code = Some(pending);
+            }
+            return Ok(None);
+        }
+        let token = URL_SAFE_NO_PAD.encode(random_bytes::<24>()?);
+        self.tokens.push(token.clone());
+        Ok(Some(token))
+    }
+
+    pub fn is_authorized(&self, token: &str) -> bool {
+        self.tokens.iter().any(|known| known == token)
+    }
+}
+
+/// Bytes from the OS random number generator, codes and tokens must not be guessable
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    getrandom::fill(&mut bytes).map_err(|e| eyre!("Failed to generate random bytes: {}", e))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redeem_code_once() {
+        let now = Utc::now();
+        let mut pairing = Pairing::default();
+        let code = pairing.new_code(now).unwrap().code.clone();
+
+        let token = pairing.redeem(&code, now).unwrap().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(pairing.is_authorized(&token));
+        assert_eq!(pairing.redeem(&code, now).unwrap(), None);
+    }
+
+    #[test]
+    fn test_wrong_or_expired_code_is_rejected() {
+        let now = Utc::now();
+        let mut pairing = Pairing::default();
+        let code = pairing.new_code(now).unwrap().code.clone();
+        for _ in 1..MAX_FAILED_ATTEMPTS {
+            assert_eq!(pairing.redeem("not-it", now).unwrap(), None);
+        }
+        // A typo doesn't cost the code, running out of guesses does
+        assert!(pairing.code.is_some());
+        assert_eq!(pairing.redeem("not-it", now).unwrap(), None);
+        assert_eq!(pairing.redeem(&code, now).unwrap(), None);
+
+        let code = pairing.new_code(now).unwrap().code.clone();
+        assert_eq!(code.len(), 6);
+        assert_eq!(
+            pairing.redeem(&code, now + Duration::minutes(11)).unwrap(),
+            None
+        );
+        assert!(pairing.tokens.is_empty());
+    }
+}
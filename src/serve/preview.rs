@@ -0,0 +1,143 @@
+use std::{fs, net::TcpListener, time::UNIX_EPOCH};
+
+use color_eyre::eyre::Result;
+use serde_json::json;
+
+use crate::export::{ExportedEntry, html};
+use crate::models::entry_id::EntryId;
+use crate::serve::http::{self, Request, Response};
+use crate::storage::Storage;
+
+/// How often a watching page asks whether the entry changed
+const POLL_INTERVAL_MS: u32 = 500;
+
+/// Serves an entry rendered to HTML, read from disk on every request. With `watch`,
+/// the page polls `/version` and reloads once the entry file changes.
+pub struct PreviewServer<'a> {
+    storage: &'a Storage,
+    id: EntryId,
+    watch: bool,
+}
+
+impl<'a> PreviewServer<'a> {
+    pub fn new(storage: &'a Storage, id: EntryId, watch: bool) -> Self {
+        Self { storage, id, watch }
+    }
+
+    pub fn run(&self, listener: TcpListener) -> Result<()> {
+        http::serve(listener, |request| self.handle(request))
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/") => self.page(),
+            ("GET", "/version") => self
+                .version()
+                .map(|version| Response::json(200, json!({ "version": version }))),
+            (_, "/" | "/version") => Ok(Response::error(405, "Use GET")),
+            _ => Ok(Response::error(404, "Unknown endpoint")),
+        };
+        response.unwrap_or_else(|e| Response::error(500, &format!("{:#}", e)))
+    }
+
+    fn page(&self) -> Result<Response> {
+        let body = if self.storage.entry_path(&self.id)?.exists() {
+            html::article(&ExportedEntry::from(self.storage.load_entry(&self.id)?))
+        } else {
+            format!(
+                "<p class=\"meta\">Entry {} doesn't exist yet.</p>\n",
+                self.id
+            )
+        };
+        let head = if self.watch {
+            reload_script(&self.version()?)
+        } else {
+            String::new()
+        };
+        Ok(Response::html(
+            200,
+            html::page(&self.id.date().to_string(), &body, &head),
+        ))
+    }
+
+    /// Changes whenever the entry file is written, created or removed
+    fn version(&self) -> Result<String> {
+        let path = self.storage.entry_path(&self.id)?;
+        Ok(match fs::metadata(path) {
+            Ok(metadata) => {
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .unwrap_or_default();
+                format!("{}-{}", modified.as_nanos(), metadata.len())
+            }
+            Err(_) => "missing".to_string(),
+        })
+    }
+}
+
+fn reload_script(version: &str) -> String {
+    format!(
+        "<script>\nsetInterval(async () => {{\n  try {{\n    \
+         const response = await fetch('/version');\n    \
+         const {{ version }} = await response.json();\n    \
+         if (version !== {}) location.reload();\n  }} catch (e) {{}}\n}}, {});\n</script>\n",
+        json!(version),
+        POLL_INTERVAL_MS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn get(path: &str) -> Request {
+        Request {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_preview_renders_and_tracks_changes() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let server = PreviewServer::new(&storage, id, true);
+
+        let missing = server.handle(&get("/"));
+        assert_eq!(missing.status, 200);
+        assert!(missing.body.contains("doesn't exist yet"));
+        assert!(missing.body.contains("if (version !== \"missing\")"));
+
+        storage
+            .save_entry(&Entry::new(id, "# Shipped\n\n**preview**".to_string()))
+            .unwrap();
+        let page = server.handle(&get("/"));
+        assert_eq!(page.content_type, "text/html; charset=utf-8");
+        assert!(page.body.contains("<strong>preview</strong>"));
+        assert_ne!(
+            server.handle(&get("/version")).body,
+            "{\"version\":\"missing\"}"
+        );
+        assert_eq!(server.handle(&get("/nope")).status, 404);
+    }
+
+    #[test]
+    fn test_preview_without_watch_has_no_script() {
+        let (storage, _temp_dir) = create_test_storage();
+        let server = PreviewServer::new(&storage, "20250920".parse().unwrap(), false);
+        assert!(!server.handle(&get("/")).body.contains("<script>"));
+    }
+}
@@ -0,0 +1,160 @@
+use color_eyre::eyre::{Result, bail};
+use regex::Regex;
+
+use crate::config::{ColumnConfig, ColumnSource};
+use crate::error::{DevlogError, ErrorKind};
+use crate::storage::index::IndexedEntry;
+
+/// Project name of `hours = "*"`, counting the time of every project
+const ALL_PROJECTS: &str = "*";
+
+/// A computed column of the `devlog list` table, evaluated per entry from what the
+/// index knows about it
+pub trait Column {
+    /// Header of the column
+    fn name(&self) -> &str;
+
+    /// Whether `value` needs the entry's content, which the index doesn't keep.
+    /// Content is only read for entries when a column asks for it.
+    fn needs_content(&self) -> bool {
+        false
+    }
+
+    /// The cell of an entry, `content` is given if `needs_content`
+    fn value(&self, entry: &IndexedEntry, content: Option<&str>) -> String;
+}
+
+/// Number of matches of a regex in the entry, e.g. merge requests as `MR!123`
+pub struct MatchCount {
+    name: String,
+    regex: Regex,
+}
+
+impl Column for MatchCount {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn needs_content(&self) -> bool {
+        true
+    }
+
+    fn value(&self, _entry: &IndexedEntry, content: Option<&str>) -> String {
+        content
+            .map_or(0, |content| self.regex.find_iter(content).count())
+            .to_string()
+    }
+}
+
+/// Hours logged with duration annotations, for one project or all of them
+pub struct Hours {
+    name: String,
+    /// `None` for every project
+    project: Option<String>,
+}
+
+impl Column for Hours {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn value(&self, entry: &IndexedEntry, _content: Option<&str>) -> String {
+        let minutes: u32 = match &self.project {
+            Some(project) => entry.minutes.get(project).copied().unwrap_or_default(),
+            None => entry.minutes.values().sum(),
+        };
+        match minutes {
+            0 => "-".to_string(),
+            minutes => format!("{:.1}", f64::from(minutes) / 60.0),
+        }
+    }
+}
+
+/// The columns defined under `[[list.columns]]`, in the order they are configured
+pub fn from_config(columns: &[ColumnConfig]) -> Result<Vec<Box<dyn Column>>> {
+    columns.iter().map(column).collect()
+}
+
+fn column(config: &ColumnConfig) -> Result<Box<dyn Column>> {
+    let name = config.name.trim();
+    if name.is_empty() {
+        bail!(DevlogError::new(
+            ErrorKind::Validation,
+            "List columns need a name"
+        ));
+    }
+    let name = name.to_string();
+    Ok(match &config.source {
+        ColumnSource::Count(pattern) => {
+            let regex = Regex::new(pattern).map_err(|e| {
+                DevlogError::new(
+                    ErrorKind::Validation,
+                    format!("Invalid regex of list column '{}': {}", name, e),
+                )
+            })?;
+            Box::new(MatchCount { name, regex })
+        }
+        ColumnSource::Hours(project) => {
+            let project = project.trim().trim_start_matches("::");
+            Box::new(Hours {
+                name,
+                project: (project != ALL_PROJECTS).then(|| project.to_string()),
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+
+    fn config(toml: &str) -> Vec<ColumnConfig> {
+        #[derive(serde::Deserialize)]
+        struct List {
+            columns: Vec<ColumnConfig>,
+        }
+        toml::from_str::<List>(toml).unwrap().columns
+    }
+
+    #[test]
+    fn test_columns_from_config() {
+        let columns = from_config(&config(
+            r#"
+            [[columns]]
+            name = "MRS"
+            count = "MR!\\d+"
+
+            [[columns]]
+            name = "HOURS"
+            hours = "*"
+
+            [[columns]]
+            name = "DEVLOG"
+            hours = "::devlog"
+            "#,
+        ))
+        .unwrap();
+
+        let content = "Reviewed MR!12 and MR!15 ~1h30m ::devlog\nMR! draft ~30m\n";
+        let entry = IndexedEntry::of(
+            &Entry::new("20250920".parse().unwrap(), content.to_string()),
+            None,
+        );
+        let row: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                let content = column.needs_content().then_some(content);
+                column.value(&entry, content)
+            })
+            .collect();
+        assert_eq!(row, vec!["2", "2.0", "1.5"]);
+        assert_eq!(columns[0].name(), "MRS");
+    }
+
+    #[test]
+    fn test_invalid_columns_are_rejected() {
+        assert!(from_config(&config("[[columns]]\nname = \"X\"\ncount = \"(\"\n")).is_err());
+        assert!(from_config(&config("[[columns]]\nname = \" \"\nhours = \"*\"\n")).is_err());
+    }
+}
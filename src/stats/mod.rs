@@ -0,0 +1,291 @@
+pub mod columns;
+pub mod svg;
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::config::GoalConfig;
+use crate::models::{annotation::AnnotationKind, entry_id::EntryId};
+use crate::storage::index::{EntryIndex, IndexedEntry};
+
+/// Label of time logged on lines without a `::project`
+pub const NO_PROJECT: &str = "(no project)";
+
+/// Writing habits over all entries, shown by `devlog stats` and on the dashboard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overview {
+    /// First day of each of the last months and its number of entries, oldest first
+    pub entries_per_month: Vec<(NaiveDate, usize)>,
+    pub entries: usize,
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    pub total_words: usize,
+    /// Words per entry, rounded down
+    pub average_words: usize,
+    /// Most mentioned `+tags`, `@people` and `::projects` with the number of entries
+    /// mentioning them, most first
+    pub top_tags: Vec<(String, usize)>,
+    pub top_people: Vec<(String, usize)>,
+    pub top_projects: Vec<(String, usize)>,
+}
+
+impl Overview {
+    /// Aggregate the entry index, no entry is read. `months` is the number of months
+    /// counted per month, `top` the number of annotations listed per kind.
+    pub fn from_index(index: &EntryIndex, today: NaiveDate, months: u32, top: usize) -> Self {
+        let entries = index.entries.len();
+        let total_words = index.entries.values().map(|entry| entry.words).sum();
+        let top_of = |kind| top_annotations(index.entries.values(), kind, top);
+        Self {
+            entries_per_month: entries_per_month(index, today, months),
+            entries,
+            current_streak: index.streak(today),
+            longest_streak: index.longest_streak(),
+            total_words,
+            average_words: total_words.checked_div(entries).unwrap_or(0),
+            top_tags: top_of(AnnotationKind::Tag),
+            top_people: top_of(AnnotationKind::Person),
+            top_projects: top_of(AnnotationKind::Project),
+        }
+    }
+}
+
+/// Number of entries in each of the last `months` months, ending with the one of `today`
+pub fn entries_per_month(
+    index: &EntryIndex,
+    today: NaiveDate,
+    months: u32,
+) -> Vec<(NaiveDate, usize)> {
+    let this_month = today.with_day(1).unwrap_or(today);
+    (0..months)
+        .rev()
+        .filter_map(|ago| this_month.checked_sub_months(Months::new(ago)))
+        .map(|month| {
+            let end = month
+                .checked_add_months(Months::new(1))
+                .unwrap_or(NaiveDate::MAX);
+            let count = index
+                .entries
+                .range(EntryId::from_date(month)..EntryId::from_date(end))
+                .count();
+            (month, count)
+        })
+        .collect()
+}
+
+/// The `top` annotations of `kind` mentioned by the most entries, with their number of
+/// entries. An entry mentioning one several times counts once.
+pub fn top_annotations<'a>(
+    entries: impl IntoIterator<Item = &'a IndexedEntry>,
+    kind: AnnotationKind,
+    top: usize,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in entries {
+        let mut seen: Vec<&str> = entry
+            .annotations
+            .values(kind)
+            .iter()
+            .map(String::as_str)
+            .collect();
+        seen.sort_unstable();
+        seen.dedup();
+        for value in seen {
+            *counts.entry(value).or_default() += 1;
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(value, count)| (value.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(top);
+    counts
+}
+
+/// Hours logged per project in each of the last weeks
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyHours {
+    /// Monday of every week, oldest first
+    pub weeks: Vec<NaiveDate>,
+    /// Project and its hours in each week, most hours first
+    pub projects: Vec<(String, Vec<f64>)>,
+}
+
+impl WeeklyHours {
+    pub fn from_index(index: &EntryIndex, today: NaiveDate, weeks: usize) -> Self {
+        let this_week = today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+        let weeks: Vec<NaiveDate> = (0..weeks as u64)
+            .rev()
+            .filter_map(|ago| this_week.checked_sub_days(Days::new(ago * 7)))
+            .collect();
+
+        let mut hours: HashMap<String, Vec<f64>> = HashMap::new();
+        for (week_idx, week) in weeks.iter().enumerate() {
+            let end = *week + Days::new(6);
+            for (_, entry) in index
+                .entries
+                .range(EntryId::from_date(*week)..=EntryId::from_date(end))
+            {
+                for (project, minutes) in &entry.minutes {
+                    let name = if project.is_empty() {
+                        NO_PROJECT
+                    } else {
+                        project
+                    };
+                    hours
+                        .entry(name.to_string())
+                        .or_insert_with(|| vec![0.0; weeks.len()])[week_idx] +=
+                        f64::from(*minutes) / 60.0;
+                }
+            }
+        }
+
+        let mut projects: Vec<(String, Vec<f64>)> = hours.into_iter().collect();
+        let total = |hours: &[f64]| hours.iter().sum::<f64>();
+        projects.sort_by(|a, b| {
+            total(&b.1)
+                .total_cmp(&total(&a.1))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        Self { weeks, projects }
+    }
+}
+
+/// One week of a goal's burndown
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurndownPoint {
+    pub day: NaiveDate,
+    /// Hours still to log on this day if the goal progressed evenly
+    pub ideal: f64,
+    /// Hours actually still to log, `None` for days in the future
+    pub remaining: Option<f64>,
+}
+
+/// Weekly points from the goal's start to its due date, plus the due date and today
+pub fn burndown(
+    index: &EntryIndex,
+    project: &str,
+    goal: &GoalConfig,
+    today: NaiveDate,
+) -> Vec<BurndownPoint> {
+    let target = f64::from(goal.hours);
+    let span = (goal.due - goal.start).num_days().max(1) as f64;
+
+    let mut days: Vec<NaiveDate> = goal
+        .start
+        .iter_weeks()
+        .take_while(|day| *day < goal.due)
+        .collect();
+    days.push(goal.due);
+    // Today is where the actual line ends, so it always gets a point
+    if goal.start < today && today < goal.due && !days.contains(&today) {
+        days.push(today);
+        days.sort();
+    }
+
+    days.into_iter()
+        .map(|day| {
+            let elapsed = (day - goal.start).num_days() as f64;
+            let remaining = (day <= today).then(|| {
+                let logged: u32 = index
+                    .entries
+                    .range(EntryId::from_date(goal.start)..=EntryId::from_date(day))
+                    .filter_map(|(_, entry)| entry.minutes.get(project))
+                    .sum();
+                (target - f64::from(logged) / 60.0).max(0.0)
+            });
+            BurndownPoint {
+                day,
+                ideal: target * (1.0 - elapsed / span),
+                remaining,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use crate::storage::index::IndexedEntry;
+
+    fn index(entries: &[(&str, &str)]) -> EntryIndex {
+        let mut index = EntryIndex::default();
+        for (id, content) in entries {
+            let entry = Entry::new(id.parse().unwrap(), content.to_string());
+            index
+                .entries
+                .insert(entry.id, IndexedEntry::of(&entry, None));
+        }
+        index
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_overview() {
+        let index = index(&[
+            ("20250801", "+rust with @alice"),
+            ("20250917", "+rust +rust ::devlog"),
+            ("20250918", "+cli on ::devlog with @alice"),
+            ("20250920", "three words here"),
+        ]);
+        let overview = Overview::from_index(&index, date(9, 20), 3, 1);
+
+        assert_eq!(
+            overview.entries_per_month,
+            vec![(date(7, 1), 0), (date(8, 1), 1), (date(9, 1), 3)]
+        );
+        assert_eq!(overview.entries, 4);
+        assert_eq!(overview.current_streak, 1);
+        assert_eq!(overview.longest_streak, 2);
+        assert_eq!(overview.total_words, 14);
+        assert_eq!(overview.average_words, 3);
+        assert_eq!(overview.top_tags, vec![("rust".to_string(), 2)]);
+        assert_eq!(overview.top_people, vec![("alice".to_string(), 2)]);
+        assert_eq!(overview.top_projects, vec![("devlog".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_weekly_hours_per_project() {
+        let index = index(&[
+            ("20250909", "::devlog ~2h"),
+            ("20250915", "::devlog ~1h\n::web ~4h"),
+            ("20250921", "reading ~30m"),
+        ]);
+        let hours = WeeklyHours::from_index(&index, date(9, 20), 2);
+
+        assert_eq!(hours.weeks, vec![date(9, 8), date(9, 15)]);
+        assert_eq!(
+            hours.projects,
+            vec![
+                ("web".to_string(), vec![0.0, 4.0]),
+                ("devlog".to_string(), vec![2.0, 1.0]),
+                (NO_PROJECT.to_string(), vec![0.0, 0.5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_burndown() {
+        let index = index(&[("20250902", "::devlog ~4h"), ("20250910", "::devlog ~6h")]);
+        let goal = GoalConfig {
+            hours: 20,
+            start: date(9, 1),
+            due: date(9, 15),
+        };
+        let points = burndown(&index, "devlog", &goal, date(9, 9));
+
+        let days: Vec<NaiveDate> = points.iter().map(|p| p.day).collect();
+        assert_eq!(days, vec![date(9, 1), date(9, 8), date(9, 9), date(9, 15)]);
+        assert_eq!(points[1].remaining, Some(16.0));
+        assert_eq!(points[1].ideal, 10.0);
+        assert_eq!(points[3].remaining, None);
+        assert_eq!(points[3].ideal, 0.0);
+    }
+}
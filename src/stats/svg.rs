@@ -0,0 +1,187 @@
+use std::fmt::Write;
+
+use crate::stats::{BurndownPoint, WeeklyHours};
+
+const WIDTH: f64 = 720.0;
+const HEIGHT: f64 = 360.0;
+const MARGIN: f64 = 48.0;
+const IDEAL_COLOR: &str = "#999";
+const COLORS: [&str; 6] = [
+    "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#edc948",
+];
+
+/// Grouped bar chart of hours per project, one group per week
+pub fn weekly_hours(hours: &WeeklyHours) -> String {
+    let max = hours
+        .projects
+        .iter()
+        .flat_map(|(_, weeks)| weeks.iter().copied())
+        .fold(0.0, f64::max)
+        .max(1.0);
+    let mut svg = header("Hours per project per week");
+    axes(&mut svg, max, "h");
+
+    let group_width = (WIDTH - 2.0 * MARGIN) / hours.weeks.len().max(1) as f64;
+    let bar_width = group_width * 0.8 / hours.projects.len().max(1) as f64;
+    for (week_idx, week) in hours.weeks.iter().enumerate() {
+        let group_x = MARGIN + group_width * week_idx as f64 + group_width * 0.1;
+        for (project_idx, (_, weeks)) in hours.projects.iter().enumerate() {
+            let bar_height = weeks[week_idx] / max * plot_height();
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+                group_x + bar_width * project_idx as f64,
+                HEIGHT - MARGIN - bar_height,
+                bar_width,
+                bar_height,
+                COLORS[project_idx % COLORS.len()]
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="middle">{}</text>"#,
+            group_x + group_width * 0.4,
+            HEIGHT - MARGIN + 16.0,
+            week.format("%m-%d")
+        );
+    }
+
+    let entries: Vec<(&str, &str)> = hours
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, _))| (name.as_str(), COLORS[idx % COLORS.len()]))
+        .collect();
+    legend(&mut svg, &entries);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Line chart of the hours still to log against the ideal, evenly paced line
+pub fn burndown(project: &str, points: &[BurndownPoint]) -> String {
+    let max = points
+        .iter()
+        .map(|point| point.ideal.max(point.remaining.unwrap_or(0.0)))
+        .fold(0.0, f64::max)
+        .max(1.0);
+    let mut svg = header(&format!("Burndown of ::{}", escape(project)));
+    axes(&mut svg, max, "h");
+
+    let step = (WIDTH - 2.0 * MARGIN) / (points.len().max(2) - 1) as f64;
+    let position = |idx: usize, hours: f64| {
+        format!(
+            "{:.1},{:.1}",
+            MARGIN + step * idx as f64,
+            HEIGHT - MARGIN - hours / max * plot_height()
+        )
+    };
+    let ideal: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(idx, point)| position(idx, point.ideal))
+        .collect();
+    let remaining: Vec<String> = points
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, point)| point.remaining.map(|hours| position(idx, hours)))
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{}" stroke-dasharray="6 4"/>"#,
+        ideal.join(" "),
+        IDEAL_COLOR
+    );
+    let _ = writeln!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+        remaining.join(" "),
+        COLORS[0]
+    );
+    for (idx, point) in points.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="middle">{}</text>"#,
+            MARGIN + step * idx as f64,
+            HEIGHT - MARGIN + 16.0,
+            point.day.format("%m-%d")
+        );
+    }
+
+    legend(
+        &mut svg,
+        &[("remaining", COLORS[0]), ("ideal", IDEAL_COLOR)],
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn plot_height() -> f64 {
+    HEIGHT - 2.0 * MARGIN
+}
+
+fn header(title: &str) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif">
+<rect width="100%" height="100%" fill="white"/>
+<text x="{MARGIN}" y="24" font-size="16">{title}</text>
+"#
+    )
+}
+
+/// Axis lines with the maximum value labelled on the y axis
+fn axes(svg: &mut String, max: f64, unit: &str) {
+    let bottom = HEIGHT - MARGIN;
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN}" y1="{MARGIN}" x2="{MARGIN}" y2="{bottom}" stroke="#333"/>
+<line x1="{MARGIN}" y1="{bottom}" x2="{}" y2="{bottom}" stroke="#333"/>
+<text x="{}" y="{}" font-size="11" text-anchor="end">{:.0}{unit}</text>"##,
+        WIDTH - MARGIN,
+        MARGIN - 4.0,
+        MARGIN + 4.0,
+        max
+    );
+}
+
+/// Colored squares with the name of each series, top right
+fn legend(svg: &mut String, entries: &[(&str, &str)]) {
+    for (idx, (name, color)) in entries.iter().enumerate() {
+        let y = MARGIN + 16.0 * idx as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="10" height="10" fill="{}"/><text x="{:.1}" y="{:.1}" font-size="11">{}</text>"#,
+            WIDTH - MARGIN - 110.0,
+            y,
+            color,
+            WIDTH - MARGIN - 96.0,
+            y + 9.0,
+            escape(name)
+        );
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_weekly_hours_svg() {
+        let hours = WeeklyHours {
+            weeks: vec![NaiveDate::from_ymd_opt(2025, 9, 15).unwrap()],
+            projects: vec![("a<b".to_string(), vec![2.0])],
+        };
+        let svg = weekly_hours(&hours);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains("a&lt;b"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDateTime;
+use color_eyre::eyre::{Context, Result};
+
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+use crate::storage::events::EventKind;
+
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "webp", "svg"];
+
+/// A file saved next to an entry in `attachments/<id>/`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub path: PathBuf,
+    /// The link to the file relative to the entry, entries live in `entries/`
+    pub link: String,
+}
+
+impl Attachment {
+    /// Markdown linking to the attachment, images are embedded
+    pub fn markdown(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let is_image = self
+            .path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        if is_image {
+            format!("![{}]({})", name, self.link)
+        } else {
+            format!("[{}]({})", name, self.link)
+        }
+    }
+}
+
+/// File name of an image pasted at `now`, e.g. `pasted-20250920-143205.png`
+pub fn pasted_image_name(now: NaiveDateTime, extension: &str) -> String {
+    format!("pasted-{}.{}", now.format("%Y%m%d-%H%M%S"), extension)
+}
+
+impl Storage {
+    /// Get the directory holding the attachments of entry `id`
+    pub fn attachments_path(&self, id: &EntryId) -> PathBuf {
+        self.data_path.join("attachments").join(id.to_string())
+    }
+
+    /// Save `contents` as an attachment of entry `id`. A number is added to `name`
+    /// if the entry already has an attachment with that name.
+    pub fn save_attachment(
+        &self,
+        id: &EntryId,
+        name: &str,
+        contents: Vec<u8>,
+    ) -> Result<Attachment> {
+        let dir = self.attachments_path(id);
+        self.writer.create_dir_all(&dir)?;
+
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+            _ => (name, None),
+        };
+        let mut file_name = name.to_string();
+        let mut counter = 1;
+        while dir.join(&file_name).exists() {
+            counter += 1;
+            file_name = match extension {
+                Some(extension) => format!("{}-{}.{}", stem, counter, extension),
+                None => format!("{}-{}", stem, counter),
+            };
+        }
+
+        let path = dir.join(&file_name);
+        self.writer
+            .write(&path, contents)
+            .wrap_err_with(|| format!("Failed to save attachment {}", path.display()))?;
+        self.record_detail(id, EventKind::AttachmentAdded, &file_name)?;
+        Ok(Attachment {
+            link: format!("../attachments/{}/{}", id, file_name.replace(' ', "%20")),
+            path,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_save_attachment_keeps_existing_files() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+
+        let first = storage
+            .save_attachment(&id, "screen shot.png", b"one".to_vec())
+            .unwrap();
+        let second = storage
+            .save_attachment(&id, "screen shot.png", b"two".to_vec())
+            .unwrap();
+        let notes = storage.save_attachment(&id, "notes", Vec::new()).unwrap();
+
+        assert_eq!(
+            first.markdown(),
+            "![screen shot.png](../attachments/20250920/screen%20shot.png)"
+        );
+        assert!(
+            second
+                .path
+                .ends_with("attachments/20250920/screen shot-2.png")
+        );
+        assert_eq!(std::fs::read(&first.path).unwrap(), b"one");
+        assert_eq!(notes.markdown(), "[notes](../attachments/20250920/notes)");
+
+        let details: Vec<Option<String>> = storage
+            .events(&id)
+            .unwrap()
+            .into_iter()
+            .filter(|event| event.kind == EventKind::AttachmentAdded)
+            .map(|event| event.detail)
+            .collect();
+        assert_eq!(details[1].as_deref(), Some("screen shot-2.png"));
+    }
+}
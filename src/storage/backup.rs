@@ -0,0 +1,302 @@
+use std::{
+    fs,
+    io::{ErrorKind, Read},
+    path::{Path, PathBuf},
+};
+
+use chrono::{Days, NaiveDateTime};
+use color_eyre::eyre::{Context, Result, bail};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use walkdir::WalkDir;
+
+use crate::config::BackupConfig;
+use crate::error::{self, DevlogError};
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+const ARCHIVE_PREFIX: &str = "devlog-";
+const ARCHIVE_SUFFIX: &str = ".tar.gz";
+const STAMP_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Automatic backups are taken once the newest archive is this old
+const AUTO_INTERVAL: Days = Days::new(7);
+
+/// A backup archive, named after the local time it was created at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created: NaiveDateTime,
+}
+
+impl Storage {
+    /// Get the directory archives are written to
+    pub fn backup_dir(&self, config: &BackupConfig) -> PathBuf {
+        config
+            .location
+            .clone()
+            .unwrap_or_else(|| self.state_path.join("backups"))
+    }
+
+    /// Archive the whole data directory (entries, events, ...) into `dir`
+    pub fn create_backup(&self, dir: &Path, now: NaiveDateTime) -> Result<PathBuf> {
+        let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let files = WalkDir::new(&self.data_path)
+            .into_iter()
+            // Backups stored inside the data directory must not end up in the next backup
+            .filter_entry(|e| e.path() != dir)
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file());
+        for file in files {
+            let name = file
+                .path()
+                .strip_prefix(&self.data_path)
+                .unwrap_or(file.path());
+            archive
+                .append_path_with_name(file.path(), name)
+                .wrap_err_with(|| format!("Failed to archive {}", file.path().display()))?;
+        }
+        let contents = archive
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .wrap_err("Failed to compress backup")?;
+
+        let path = dir.join(format!(
+            "{}{}{}",
+            ARCHIVE_PREFIX,
+            now.format(STAMP_FORMAT),
+            ARCHIVE_SUFFIX
+        ));
+        self.writer.create_dir_all(dir)?;
+        self.writer.write_atomic(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Write the files of entry `id` from an archive back into the data directory,
+    /// returning their paths. Fails if the entry doesn't exist in the archive.
+    pub fn restore_from_backup(&self, archive: &Path, id: &EntryId) -> Result<Vec<PathBuf>> {
+        let files = read_backup_entry(archive, id)?;
+        if !files.iter().any(|(path, _)| path.starts_with("entries")) {
+            bail!(DevlogError::new(
+                error::ErrorKind::NotFound,
+                format!("Entry '{}' is not in {}", id, archive.display())
+            ));
+        }
+
+        let mut restored = Vec::new();
+        for (name, contents) in files {
+            let path = self.data_path.join(&name);
+            if let Some(parent) = path.parent() {
+                self.writer.create_dir_all(parent)?;
+            }
+            self.writer.write_atomic(&path, contents)?;
+            restored.push(path);
+        }
+        if !self.writer.is_dry_run()
+            && let Ok(entry) = self.load_entry(id)
+        {
+            self.entry_written(&entry)?;
+        }
+        Ok(restored)
+    }
+
+    /// Delete all but the `keep` newest archives in `dir`, returning the deleted ones
+    pub fn rotate_backups(&self, dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+        let backups = list_backups(dir)?;
+        let excess = backups.len().saturating_sub(keep);
+        let mut removed = Vec::new();
+        for backup in backups.into_iter().take(excess) {
+            self.writer.remove_file(&backup.path)?;
+            removed.push(backup.path);
+        }
+        Ok(removed)
+    }
+
+    /// Take a backup and rotate old ones if automatic backups are enabled and the
+    /// newest archive is at least a week old
+    pub fn backup_if_due(
+        &self,
+        config: &BackupConfig,
+        now: NaiveDateTime,
+    ) -> Result<Option<PathBuf>> {
+        if !config.auto {
+            return Ok(None);
+        }
+        let dir = self.backup_dir(config);
+        let due = list_backups(&dir)?
+            .last()
+            .and_then(|newest| newest.created.checked_add_days(AUTO_INTERVAL))
+            .is_none_or(|next| next <= now);
+        if !due {
+            return Ok(None);
+        }
+
+        let path = self.create_backup(&dir, now)?;
+        self.rotate_backups(&dir, config.keep.max(1))?;
+        Ok(Some(path))
+    }
+}
+
+/// Files of one entry read from an archive, paths relative to the data directory
+pub type ArchivedFiles = Vec<(PathBuf, Vec<u8>)>;
+
+/// Read the files belonging to entry `id` from an archive: the entry itself, its
+/// event log and anything under `attachments/<id>/`. The archive is streamed, so
+/// only these files are decompressed into memory.
+pub fn read_backup_entry(archive: &Path, id: &EntryId) -> Result<ArchivedFiles> {
+    let file = fs::File::open(archive)
+        .wrap_err_with(|| format!("Failed to open {}", archive.display()))?;
+    let wanted = [
+        PathBuf::from(format!("entries/{}.md", id)),
+        PathBuf::from(format!("events/{}.jsonl", id)),
+    ];
+    let attachments = PathBuf::from("attachments").join(id.to_string());
+
+    let mut files = Vec::new();
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    for item in archive.entries().wrap_err("Failed to read backup")? {
+        let mut item = item.wrap_err("Failed to read backup")?;
+        let path = item.path().wrap_err("Invalid path in backup")?.into_owned();
+        if !wanted.contains(&path) && !path.starts_with(&attachments) {
+            continue;
+        }
+        let mut contents = Vec::new();
+        item.read_to_end(&mut contents)
+            .wrap_err_with(|| format!("Failed to read {} from backup", path.display()))?;
+        files.push((path, contents));
+    }
+    Ok(files)
+}
+
+/// Archives in `dir`, oldest first. Other files are ignored.
+pub fn list_backups(dir: &Path) -> Result<Vec<Backup>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", dir.display())),
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let stamp = name
+                .strip_prefix(ARCHIVE_PREFIX)?
+                .strip_suffix(ARCHIVE_SUFFIX)?;
+            let created = NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).ok()?;
+            Some(Backup {
+                path: e.path(),
+                created,
+            })
+        })
+        .collect();
+    backups.sort_by_key(|backup| backup.created);
+    Ok(backups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 9, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backup_contains_entries_and_events() {
+        let (storage, temp_dir) = create_test_storage();
+        let entry = Entry::new("20250920".parse().unwrap(), "content".to_string());
+        storage.save_entry(&entry).unwrap();
+
+        let path = storage
+            .create_backup(&temp_dir.path().join("backups"), at(20))
+            .unwrap();
+        assert!(path.ends_with("devlog-20250920T120000.tar.gz"));
+
+        let file = fs::File::open(&path).unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        let mut names: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().display().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["entries/20250920.md", "events/20250920.jsonl"]);
+    }
+
+    #[test]
+    fn test_restore_single_entry() {
+        let (storage, temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let mut entry = Entry::new(id, "original".to_string());
+        storage.save_entry(&entry).unwrap();
+        let other = Entry::new("20250921".parse().unwrap(), "other".to_string());
+        storage.save_entry(&other).unwrap();
+        let archive = storage
+            .create_backup(&temp_dir.path().join("backups"), at(21))
+            .unwrap();
+
+        let mut names: Vec<PathBuf> = read_backup_entry(&archive, &id)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("entries/20250920.md"),
+                PathBuf::from("events/20250920.jsonl")
+            ]
+        );
+
+        entry.update_content("changed".to_string());
+        storage.save_entry(&entry).unwrap();
+        storage.restore_from_backup(&archive, &id).unwrap();
+        assert_eq!(storage.load_entry(&id).unwrap().content, "original");
+        assert_eq!(storage.events(&id).unwrap().len(), 1);
+
+        let missing: EntryId = "20250101".parse().unwrap();
+        assert!(storage.restore_from_backup(&archive, &missing).is_err());
+    }
+
+    #[test]
+    fn test_auto_backup_is_weekly_and_rotates() {
+        let (storage, temp_dir) = create_test_storage();
+        let config = BackupConfig {
+            auto: true,
+            location: Some(temp_dir.path().join("backups")),
+            keep: 2,
+        };
+
+        assert!(storage.backup_if_due(&config, at(1)).unwrap().is_some());
+        assert!(storage.backup_if_due(&config, at(7)).unwrap().is_none());
+        assert!(storage.backup_if_due(&config, at(8)).unwrap().is_some());
+        assert!(storage.backup_if_due(&config, at(15)).unwrap().is_some());
+
+        let created: Vec<NaiveDateTime> = list_backups(&temp_dir.path().join("backups"))
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.created)
+            .collect();
+        assert_eq!(created, vec![at(8), at(15)]);
+
+        let disabled = BackupConfig {
+            auto: false,
+            ..config
+        };
+        assert!(storage.backup_if_due(&disabled, at(30)).unwrap().is_none());
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate};
+use color_eyre::eyre::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::config::DigestConfig;
+use crate::digest::{self, Digest, schedule::WeeklySchedule};
+use crate::storage::Storage;
+
+const RUN_LOG_FILE_NAME: &str = "digest.jsonl";
+
+/// One line of the digest run log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DigestRun {
+    pub at: DateTime<FixedOffset>,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub path: Option<PathBuf>,
+    /// Why the run failed, none if it succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Storage {
+    /// Get the directory digests are written to
+    pub fn digest_dir(&self, config: &DigestConfig) -> PathBuf {
+        config
+            .location
+            .clone()
+            .unwrap_or_else(|| self.state_path.join("digests"))
+    }
+
+    /// Read the run log, oldest first
+    pub fn digest_runs(&self) -> Result<Vec<DigestRun>> {
+        let path = self.state_path.join(RUN_LOG_FILE_NAME);
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        };
+        Ok(raw
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Compile the digest of the week ending with `end`, write it to the digest
+    /// directory, pipe it to the configured command and log the run
+    pub fn run_digest(&self, config: &DigestConfig, end: NaiveDate) -> Result<DigestRun> {
+        let digest = digest::compile(self, end)?;
+        let path = self
+            .digest_dir(config)
+            .join(format!("digest-{}.md", digest.start));
+
+        let result = self
+            .writer
+            .create_dir_all(path.parent().unwrap_or(&self.state_path))
+            .and_then(|()| self.writer.write(&path, &digest.markdown))
+            .and_then(|()| match &config.command {
+                Some(command) if !self.writer.is_dry_run() => send(command, &digest),
+                _ => Ok(()),
+            });
+
+        let run = DigestRun {
+            at: Local::now().fixed_offset(),
+            start: digest.start,
+            end: digest.end,
+            path: Some(path),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        self.log_digest_run(&run)?;
+        result.map(|()| run)
+    }
+
+    /// Run the scheduled digest if its time has passed since the last run. A failed
+    /// run counts as a run, so it is reported once instead of after every command.
+    pub fn digest_if_due(
+        &self,
+        config: &DigestConfig,
+        now: DateTime<Local>,
+    ) -> Result<Option<DigestRun>> {
+        let Some(schedule) = config.schedule else {
+            return Ok(None);
+        };
+        let last_ran = self
+            .digest_runs()?
+            .last()
+            .map(|run| run.at.with_timezone(&Local));
+        if !schedule.is_due(&now, last_ran) {
+            return Ok(None);
+        }
+        let end = WeeklySchedule::week_end(&schedule.last_run(&now));
+        self.run_digest(config, end).map(Some)
+    }
+
+    fn log_digest_run(&self, run: &DigestRun) -> Result<()> {
+        let mut line = serde_json::to_string(run).wrap_err("Failed to serialize digest run")?;
+        line.push('\n');
+        self.writer.create_dir_all(&self.state_path)?;
+        self.writer
+            .append(&self.state_path.join(RUN_LOG_FILE_NAME), line)
+    }
+}
+
+/// Pipe the digest to `command` run by the shell
+fn send(command: &str, digest: &Digest) -> Result<()> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .spawn()
+        .wrap_err_with(|| format!("Failed to run '{}'", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(digest.markdown.as_bytes())
+            .wrap_err_with(|| format!("Failed to send the digest to '{}'", command))?;
+    }
+    let status = child
+        .wait()
+        .wrap_err_with(|| format!("Failed to run '{}'", command))?;
+    if !status.success() {
+        bail!("'{}' exited with {}", command, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_digest_runs_once_per_week() {
+        let (storage, temp_dir) = create_test_storage();
+        let entry = Entry::new("20250915".parse().unwrap(), "Shipped ::devlog".to_string());
+        storage.save_entry(&entry).unwrap();
+
+        let at = |value: &str| Local.from_local_datetime(&value.parse().unwrap()).unwrap();
+        let sent = temp_dir.path().join("sent.md");
+        let config = DigestConfig {
+            schedule: Some(
+                WeeklySchedule::parse("monday", "9:00", at("2025-09-01T00:00:00").fixed_offset())
+                    .unwrap(),
+            ),
+            location: None,
+            command: Some(format!("cat > '{}'", sent.display())),
+        };
+
+        let run = storage
+            .digest_if_due(&config, at("2025-09-24T12:00:00"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.start, NaiveDate::from_ymd_opt(2025, 9, 15).unwrap());
+        assert!(run.error.is_none());
+        assert!(
+            fs::read_to_string(run.path.unwrap())
+                .unwrap()
+                .contains("Shipped")
+        );
+        assert!(fs::read_to_string(&sent).unwrap().contains("Shipped"));
+        assert_eq!(storage.digest_runs().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_send_is_logged() {
+        let (storage, _temp_dir) = create_test_storage();
+        let config = DigestConfig {
+            command: Some("exit 3".to_string()),
+            ..DigestConfig::default()
+        };
+
+        assert!(
+            storage
+                .run_digest(&config, NaiveDate::from_ymd_opt(2025, 9, 21).unwrap())
+                .is_err()
+        );
+        let runs = storage.digest_runs().unwrap();
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].error.as_ref().unwrap().contains("exit"));
+    }
+}
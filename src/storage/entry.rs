@@ -1,47 +1,181 @@
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::annotation::Annotations;
 use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::models::frontmatter::Frontmatter;
 use crate::storage::Storage;
-use chrono::Utc;
-use color_eyre::eyre::{Context, Result};
+use crate::storage::events::{self, EventKind};
+use crate::utils::output;
+use crate::utils::text::normalize_line_endings;
+use chrono::{NaiveTime, Timelike, Utc};
+use color_eyre::eyre::{Context, Result, bail};
 use std::fs;
+use std::path::Path;
 use walkdir::WalkDir;
 
 impl Storage {
     /// Save an entry to disk
     pub fn save_entry(&self, entry: &Entry) -> Result<()> {
+        let kind = if self.entry_path(&entry.id)?.exists() {
+            EventKind::Updated
+        } else {
+            EventKind::Created
+        };
+        self.write_entry(entry, kind)
+    }
+
+    /// Overwrite an entry with unrelated content, recorded as a `Replaced` event
+    pub fn replace_entry(&self, entry: &Entry) -> Result<()> {
+        self.write_entry(entry, EventKind::Replaced)
+    }
+
+    /// Save several entries as one change: if one fails, the entries already saved are put
+    /// back the way they were
+    pub fn save_entries(&self, entries: &[Entry]) -> Result<()> {
+        let mut previous = Vec::with_capacity(entries.len());
+        for entry in entries {
+            previous.push(if self.entry_path(&entry.id)?.exists() {
+                Some(self.load_entry(&entry.id)?)
+            } else {
+                None
+            });
+        }
+
+        for (saved, entry) in entries.iter().enumerate() {
+            if let Err(e) = self.save_entry(entry) {
+                for (entry, previous) in entries[..saved].iter().zip(&previous) {
+                    match previous {
+                        Some(previous) => self.save_entry(previous)?,
+                        None => self.writer.remove_file(&self.entry_path(&entry.id)?)?,
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn write_entry(&self, entry: &Entry, kind: EventKind) -> Result<()> {
         let entries_path = self.get_entries_path()?;
         let file_path = entries_path.join(format!("{}.md", entry.id));
-        let content = self.serialize_entry(entry)?;
-
-        fs::write(&file_path, content)
+        let content = self.line_ending.apply(&self.serialize_entry(entry)?);
+        // A previous version that can't be read must not keep the new one from being saved
+        let previous = self.load_entry(&entry.id).ok();
+
+        // Editors and crashes must never leave a half-written entry behind
+        self.keep_version(&entry.id)?;
+        self.writer
+            .write_atomic(&file_path, content)
             .wrap_err_with(|| format!("Failed to save entry to {}", file_path.display()))?;
-        Ok(())
+        self.record_event(&entry.id, kind, &entry.content)?;
+        if let Some(previous) = previous {
+            for (kind, detail) in events::detail_events(&previous.content, &entry.content) {
+                self.record_detail(&entry.id, kind, &detail)?;
+            }
+        }
+        self.entry_written(entry)
+    }
+
+    /// Bring the index and project profiles up to date with an entry written to disk
+    pub(crate) fn entry_written(&self, entry: &Entry) -> Result<()> {
+        let previous = self.update_index(entry)?;
+        self.update_project_references(
+            &entry.id,
+            previous
+                .as_ref()
+                .map_or(&[], |previous| &previous.annotations.projects),
+            &Annotations::parse(&entry.content).projects,
+        )
     }
 
     /// Load an entry from disk
-    pub fn load_entry(&self, id: &str) -> Result<Entry> {
-        let entries_path = self.get_entries_path()?;
-        let file_path = entries_path.join(format!("{}.md", id));
-        let content = fs::read_to_string(&file_path)
-            .wrap_err_with(|| format!("Failed to read entry from {}", file_path.display()))?;
+    pub fn load_entry(&self, id: &EntryId) -> Result<Entry> {
+        let file_path = self.entry_path(id)?;
+        if !file_path.exists() {
+            bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!("Entry '{}' not found", id)
+            ));
+        }
+        let content = read_entry_file(&file_path)?;
 
         self.deserialize_entry(id, &content)
     }
 
-    /// List all entries from disk
-    pub fn list_entries(&self) -> Result<Vec<String>> {
+    /// Append text to an entry as a new paragraph, creating the entry if needed
+    pub fn append_to_entry(&self, id: &EntryId, text: &str) -> Result<()> {
+        self.append(id, text, |_| "\n\n")
+    }
+
+    /// Append a `- ` list item to an entry, continuing the list the entry ends with if any
+    pub fn append_list_item(&self, id: &EntryId, item: &str) -> Result<()> {
+        self.append(id, item, |content| {
+            let last = content.lines().last().unwrap_or_default().trim_start();
+            if last.starts_with("- ") || last.starts_with("* ") {
+                "\n"
+            } else {
+                "\n\n"
+            }
+        })
+    }
+
+    fn append(&self, id: &EntryId, text: &str, separator: impl Fn(&str) -> &str) -> Result<()> {
+        let entry = if self.entry_path(id)?.exists() {
+            let mut entry = self.load_entry(id)?;
+            let existing = entry.content.trim_end();
+            let content = format!("{}{}{}", existing, separator(existing), text);
+            entry.update_content(content.trim_start().to_string());
+            entry
+        } else {
+            Entry::new(*id, text.to_string())
+        };
+        self.save_entry(&entry)
+    }
+
+    /// Append a timestamped `## HH:MM` sub-entry to an entry, creating the entry if needed.
+    /// Text written in the same minute as the entry's last section joins that section.
+    pub fn append_sub_entry(&self, id: &EntryId, time: NaiveTime, text: &str) -> Result<()> {
+        let time = time
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(time);
+        let same_minute = self.entry_path(id)?.exists() && {
+            let entry = self.load_entry(id)?;
+            entry.sub_entries().last().is_some_and(|last| {
+                last.time == time && entry.content.trim_end().ends_with(&last.content)
+            })
+        };
+        if same_minute {
+            return self.append_to_entry(id, text.trim());
+        }
+        self.append_to_entry(
+            id,
+            &format!("## {}\n\n{}", time.format("%H:%M"), text.trim()),
+        )
+    }
+
+    /// List all entries from disk.
+    ///
+    /// Markdown files whose name isn't a valid `YYYYMMDD` id are not entries and are skipped,
+    /// as is everything matched by `.devlogignore`. Only files directly in the entries
+    /// directory are entries, see `misplaced_entries` for the ones in subdirectories.
+    pub fn list_entries(&self) -> Result<Vec<EntryId>> {
         let entries_path = self.get_entries_path()?;
-        let mut entries = Vec::new();
+        let ignore_rules = self.ignore_rules()?;
+        let mut entries: Vec<EntryId> = Vec::new();
 
         let md_files = WalkDir::new(&entries_path)
+            .max_depth(1)
             .into_iter()
+            .filter_entry(|e| !ignore_rules.is_ignored(e.path(), e.file_type().is_dir()))
             .filter_map(|e| e.ok())
             .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"));
 
         for entry in md_files {
-            if let Some(stem) = entry.path().file_stem() {
-                if let Some(id) = stem.to_str() {
-                    entries.push(id.to_string());
-                }
+            if let Some(stem) = entry.path().file_stem()
+                && let Some(id) = stem.to_str().and_then(|s| s.parse().ok())
+            {
+                entries.push(id);
             }
         }
 
@@ -50,96 +184,209 @@ impl Storage {
         Ok(entries)
     }
 
+    /// Entry files in subdirectories of the entries directory, e.g. from a year/month
+    /// layout made by hand. devlog doesn't list or open them until they are moved up.
+    pub fn misplaced_entries(&self) -> Result<Vec<std::path::PathBuf>> {
+        let entries_path = self.get_entries_path()?;
+        let ignore_rules = self.ignore_rules()?;
+        Ok(WalkDir::new(&entries_path)
+            .into_iter()
+            .filter_entry(|e| !ignore_rules.is_ignored(e.path(), e.file_type().is_dir()))
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.depth() >= 2
+                    && e.path()
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| stem.parse::<EntryId>().is_ok())
+                    && e.path().extension().is_some_and(|ext| ext == "md")
+            })
+            .map(|e| e.into_path())
+            .collect())
+    }
+
+    /// Move the files of `misplaced_entries` up into the entries directory. A file whose
+    /// day already has an entry is left where it is, those are returned after the moved ids.
+    pub fn flatten_entries(&self) -> Result<(Vec<EntryId>, Vec<std::path::PathBuf>)> {
+        let mut moved = Vec::new();
+        let mut kept = Vec::new();
+        for path in self.misplaced_entries()? {
+            let Some(id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<EntryId>().ok())
+            else {
+                continue;
+            };
+            let target = self.entry_path(&id)?;
+            if target.exists() || moved.contains(&id) {
+                kept.push(path);
+            } else {
+                self.writer.rename(&path, &target)?;
+                moved.push(id);
+            }
+        }
+        Ok((moved, kept))
+    }
+
+    /// Get the path of an entry's file, whether or not it exists
+    pub fn entry_path(&self, id: &EntryId) -> Result<std::path::PathBuf> {
+        Ok(self.get_entries_path()?.join(format!("{}.md", id)))
+    }
+
     /// Get the entries directory path, creating it if it doesn't exist
-    fn get_entries_path(&self) -> Result<std::path::PathBuf> {
+    pub(super) fn get_entries_path(&self) -> Result<std::path::PathBuf> {
         let entries_path = self.data_path.join("entries");
-        
+
         // Create entries directory if it doesn't exist
-        fs::create_dir_all(&entries_path).wrap_err_with(|| {
-            format!(
-                "Failed to create entries directory: {}",
-                entries_path.display()
-            )
-        })?;
+        self.writer
+            .create_dir_all(&entries_path)
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to create entries directory: {}",
+                    entries_path.display()
+                )
+            })?;
 
         Ok(entries_path)
     }
 
     /// Serialize entry to markdown with YAML frontmatter
-    fn serialize_entry(&self, entry: &Entry) -> Result<String> {
-        let frontmatter = format!(
-            r#"---
-id: {}
-created_at: {}
-updated_at: {}
----
-
-{}"#,
-            entry.id, entry.created_at, entry.updated_at, entry.content
-        );
-        Ok(frontmatter)
+    pub fn serialize_entry(&self, entry: &Entry) -> Result<String> {
+        let frontmatter = Frontmatter {
+            display: entry.display.clone(),
+            extra: entry.extra.clone(),
+            ..Frontmatter::of(
+                &entry.id.to_string(),
+                entry.created_at,
+                entry.updated_at,
+                &entry.content,
+            )
+        };
+        to_document(&frontmatter, &entry.content)
     }
 
     /// Deserialize entry from markdown with YAML frontmatter
-    fn deserialize_entry(&self, id: &str, content: &str) -> Result<Entry> {
+    pub fn deserialize_entry(&self, id: &EntryId, content: &str) -> Result<Entry> {
+        let (frontmatter, content) = parse_document(&id.to_string(), content)?;
         let now = Utc::now();
-
-        // Simple frontmatter parsing
-        if content.starts_with("---\n") {
-            let parts: Vec<&str> = content.split("---").collect();
-            if parts.len() >= 3 {
-                let yaml_content = parts[1];
-                let md_content = parts[2].trim_start().to_string();
-
-                // Parse YAML frontmatter
-                let frontmatter: serde_yaml::Value = serde_yaml::from_str(yaml_content)
-                    .wrap_err("Failed to parse YAML frontmatter")?;
-
-                let created_at = frontmatter["created_at"]
-                    .as_str()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(now);
-
-                let updated_at = frontmatter["updated_at"]
-                    .as_str()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(now);
-
-                return Ok(Entry {
-                    id: id.to_string(),
-                    created_at,
-                    updated_at,
-                    content: md_content,
-                });
-            }
-        }
-
-        // Fallback: treat entire content as markdown
         Ok(Entry {
-            id: id.to_string(),
-            created_at: now,
-            updated_at: now,
-            content: content.to_string(),
+            id: *id,
+            created_at: frontmatter.created_at.unwrap_or(now),
+            updated_at: frontmatter.updated_at.unwrap_or(now),
+            content,
+            display: frontmatter.display,
+            extra: frontmatter.extra,
         })
     }
 }
 
+/// Markdown with a YAML frontmatter, the format entries and notes are stored in
+pub(super) fn to_document(frontmatter: &Frontmatter, content: &str) -> Result<String> {
+    let yaml = serde_yaml::to_string(frontmatter).wrap_err("Failed to serialize frontmatter")?;
+    Ok(format!("---\n{}---\n\n{}", yaml, content))
+}
+
+/// The frontmatter and content of a document written by `to_document`. Files without
+/// frontmatter are taken as content with an empty one, the caller fills in the time.
+pub(super) fn parse_document(id: &str, content: &str) -> Result<(Frontmatter, String)> {
+    let Some((yaml, body)) = Frontmatter::split(content) else {
+        return Ok((Frontmatter::default(), content.to_string()));
+    };
+    let frontmatter: Frontmatter = if yaml.trim().is_empty() {
+        Frontmatter::default()
+    } else {
+        serde_yaml::from_str(yaml).wrap_err("Failed to parse YAML frontmatter")?
+    };
+
+    // The file name is the source of truth, a different frontmatter id means
+    // the file was copied or renamed by hand and can't be trusted silently
+    if let Some(frontmatter_id) = &frontmatter.id
+        && frontmatter_id != id
+    {
+        bail!(
+            "Entry {} has mismatching id '{}' in its frontmatter",
+            id,
+            frontmatter_id
+        );
+    }
+
+    Ok((frontmatter, body.trim_start_matches('\n').to_string()))
+}
+
+/// Read an entry file written by any tool: a BOM is dropped, line endings are
+/// normalized to LF and invalid UTF-8 is replaced instead of failing the read
+pub(super) fn read_entry_file(path: &Path) -> Result<String> {
+    let bytes =
+        fs::read(path).wrap_err_with(|| format!("Failed to read entry from {}", path.display()))?;
+
+    let content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(err) => {
+            output::warn(format!(
+                "{} is not valid UTF-8, unreadable characters were replaced",
+                path.display()
+            ));
+            String::from_utf8_lossy(err.as_bytes()).into_owned()
+        }
+    };
+
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    Ok(normalize_line_endings(content))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::text::LineEnding;
     use tempfile::TempDir;
 
     fn create_test_storage() -> (Storage, TempDir) {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
-        let storage = Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
         (storage, temp_dir)
     }
 
+    #[test]
+    fn test_append_list_items() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+
+        storage.append_to_entry(&id, "Shipped it").unwrap();
+        storage.append_list_item(&id, "- first").unwrap();
+        storage.append_list_item(&id, "- second").unwrap();
+
+        let entry = storage.load_entry(&id).unwrap();
+        assert_eq!(entry.content, "Shipped it\n\n- first\n- second");
+    }
+
+    #[test]
+    fn test_append_sub_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+
+        let morning = NaiveTime::from_hms_opt(9, 5, 0).unwrap();
+        let afternoon = NaiveTime::from_hms_opt(14, 32, 0).unwrap();
+        storage.append_sub_entry(&id, morning, "Standup\n").unwrap();
+        storage.append_sub_entry(&id, afternoon, "Review").unwrap();
+        let later = NaiveTime::from_hms_opt(14, 32, 40).unwrap();
+        storage.append_sub_entry(&id, later, "Merged").unwrap();
+
+        let entry = storage.load_entry(&id).unwrap();
+        assert_eq!(
+            entry.content,
+            "## 09:05\n\nStandup\n\n## 14:32\n\nReview\n\nMerged"
+        );
+        let times: Vec<NaiveTime> = entry.sub_entries().iter().map(|s| s.time).collect();
+        assert_eq!(times, vec![morning, afternoon]);
+    }
+
     #[test]
     fn test_save_and_load_entry() {
         let (storage, _temp_dir) = create_test_storage();
 
-        let id = "20250920".to_string();
+        let id: EntryId = "20250920".parse().unwrap();
         let content = "#Test entry\n\nThis is a test.".to_string();
         let entry = Entry::new(id, content);
 
@@ -158,9 +405,9 @@ mod tests {
         let (storage, _temp_dir) = create_test_storage();
 
         // Create some test entries
-        let entry1 = Entry::new("20250920".to_string(), "First entry".to_string());
-        let entry2 = Entry::new("20250921".to_string(), "Second entry".to_string());
-        let entry3 = Entry::new("20250919".to_string(), "Third entry".to_string());
+        let entry1 = Entry::new("20250920".parse().unwrap(), "First entry".to_string());
+        let entry2 = Entry::new("20250921".parse().unwrap(), "Second entry".to_string());
+        let entry3 = Entry::new("20250919".parse().unwrap(), "Third entry".to_string());
 
         // Save entries
         storage.save_entry(&entry1).expect("Failed to save entry1");
@@ -184,7 +431,7 @@ mod tests {
         let (storage, _temp_dir) = create_test_storage();
 
         let original_entry = Entry::new(
-            "20250920".to_string(),
+            "20250920".parse().unwrap(),
             "# Original\n\nSome content.".to_string(),
         );
 
@@ -199,4 +446,156 @@ mod tests {
         assert_eq!(deserialized.id, original_entry.id);
         assert_eq!(deserialized.content, original_entry.content);
     }
+
+    #[test]
+    fn test_display_options_survive_a_save() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id = "20250920".parse().unwrap();
+        let loaded = storage
+            .deserialize_entry(
+                &id,
+                "---\nid: 20250920\ntoc: true\nlang: de\n---\n\n# Notizen\n",
+            )
+            .expect("Failed to deserialize the entry.");
+        assert!(loaded.display.has_toc());
+
+        let serialized = storage.serialize_entry(&loaded).unwrap();
+        assert!(serialized.contains("\ntoc: true\nlang: de\n---\n"));
+        let reloaded = storage.deserialize_entry(&id, &serialized).unwrap();
+        assert_eq!(reloaded.display, loaded.display);
+        assert_eq!(reloaded.content, "# Notizen\n");
+    }
+
+    #[test]
+    fn test_list_entries_skips_invalid_file_names() {
+        let (storage, temp_dir) = create_test_storage();
+        let entry = Entry::new("20250920".parse().unwrap(), "Valid".to_string());
+        storage.save_entry(&entry).expect("Failed to save entry");
+
+        let entries_path = temp_dir.path().join("data").join("entries");
+        fs::write(entries_path.join("README.md"), "Not an entry").unwrap();
+        fs::write(entries_path.join("20251340.md"), "Impossible date").unwrap();
+        fs::create_dir_all(entries_path.join("2025")).unwrap();
+        fs::write(entries_path.join("2025").join("20250921.md"), "Nested").unwrap();
+
+        let entries = storage.list_entries().expect("Failed to list entries");
+        assert_eq!(entries, vec!["20250920"]);
+        assert_eq!(
+            storage.misplaced_entries().unwrap(),
+            vec![entries_path.join("2025").join("20250921.md")]
+        );
+    }
+
+    #[test]
+    fn test_flatten_entries_keeps_files_of_existing_days() {
+        let (storage, temp_dir) = create_test_storage();
+        let entry = Entry::new("20250920".parse().unwrap(), "Kept".to_string());
+        storage.save_entry(&entry).expect("Failed to save entry");
+
+        let month_path = temp_dir
+            .path()
+            .join("data")
+            .join("entries")
+            .join("2025")
+            .join("09");
+        fs::create_dir_all(&month_path).unwrap();
+        fs::write(month_path.join("20250920.md"), "Duplicate").unwrap();
+        fs::write(month_path.join("20250921.md"), "Nested").unwrap();
+
+        let (moved, kept) = storage.flatten_entries().unwrap();
+        assert_eq!(moved, vec!["20250921"]);
+        assert_eq!(kept, vec![month_path.join("20250920.md")]);
+        assert_eq!(
+            storage.list_entries().unwrap(),
+            vec!["20250921", "20250920"]
+        );
+        assert_eq!(
+            storage
+                .load_entry(&"20250921".parse().unwrap())
+                .unwrap()
+                .content,
+            "Nested"
+        );
+    }
+
+    #[test]
+    fn test_list_entries_respects_devlogignore() {
+        let (storage, temp_dir) = create_test_storage();
+        let entry = Entry::new("20250920".parse().unwrap(), "Valid".to_string());
+        storage.save_entry(&entry).expect("Failed to save entry");
+
+        let data_path = temp_dir.path().join("data");
+        let foreign_dir = data_path.join("entries").join(".obsidian");
+        fs::create_dir_all(&foreign_dir).unwrap();
+        fs::write(foreign_dir.join("20250101.md"), "Plugin metadata").unwrap();
+        fs::write(data_path.join("entries").join("20250102.md"), "Draft").unwrap();
+        fs::write(data_path.join(".devlogignore"), ".obsidian/\n20250102.md\n").unwrap();
+
+        let entries = storage.list_entries().expect("Failed to list entries");
+        assert_eq!(entries, vec!["20250920"]);
+    }
+
+    #[test]
+    fn test_load_entry_tolerates_crlf_bom_and_invalid_utf8() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let path = storage.entry_path(&id).unwrap();
+
+        let mut bytes = "\u{feff}---\r\nid: 20250920\r\n---\r\n\r\nLine one\r\nLine two "
+            .as_bytes()
+            .to_vec();
+        bytes.extend_from_slice(&[0xff, 0xfe]);
+        fs::write(&path, bytes).unwrap();
+
+        let entry = storage.load_entry(&id).expect("Failed to load entry");
+        assert_eq!(entry.content, "Line one\nLine two \u{fffd}\u{fffd}");
+    }
+
+    #[test]
+    fn test_save_entry_uses_configured_line_endings() {
+        let (storage, _temp_dir) = create_test_storage();
+        let storage = storage.with_line_ending(LineEnding::Crlf);
+        let entry = Entry::new("20250920".parse().unwrap(), "one\ntwo".to_string());
+        storage.save_entry(&entry).expect("Failed to save entry");
+
+        let raw = fs::read_to_string(storage.entry_path(&entry.id).unwrap()).unwrap();
+        assert!(raw.contains("one\r\ntwo"));
+        assert!(!raw.replace("\r\n", "").contains('\n'));
+
+        let loaded = storage.load_entry(&entry.id).expect("Failed to load entry");
+        assert_eq!(loaded.content, "one\ntwo");
+    }
+
+    #[test]
+    fn test_frontmatter_survives_rules_and_unknown_keys() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let document = "---\nid: 20250920\ncreated_at: 2025-09-20T08:00:00Z\nmood: tired\n---\n\n\
+                        Pairing with @alice on ::devlog\n\n---\n\nAfter the break +rust\n";
+        let entry = storage.deserialize_entry(&id, document).unwrap();
+        assert_eq!(
+            entry.content,
+            "Pairing with @alice on ::devlog\n\n---\n\nAfter the break +rust\n"
+        );
+        assert_eq!(entry.created_at.to_rfc3339(), "2025-09-20T08:00:00+00:00");
+
+        let serialized = storage.serialize_entry(&entry).unwrap();
+        assert!(serialized.contains("\ntags:\n- rust\npeople:\n- alice\nprojects:\n- devlog\n"));
+        assert!(serialized.contains("\nmood: tired\n---\n"));
+        let reloaded = storage.deserialize_entry(&id, &serialized).unwrap();
+        assert_eq!(reloaded.content, entry.content);
+        assert_eq!(reloaded.extra, entry.extra);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_mismatching_frontmatter_id() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let content = "---\nid: 20250921\ncreated_at: 2025-09-20T10:00:00Z\n---\n\nCopied";
+
+        let Err(err) = storage.deserialize_entry(&id, content) else {
+            panic!("Mismatching ids should be rejected");
+        };
+        assert!(err.to_string().contains("mismatching id '20250921'"));
+    }
 }
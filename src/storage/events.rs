@@ -0,0 +1,475 @@
+use std::{
+    collections::BTreeSet,
+    fmt, fs,
+    io::{ErrorKind as IoErrorKind, Read, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, FixedOffset, Local};
+use color_eyre::eyre::{Context, Result, bail};
+use flate2::{Compression, read::MultiGzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::annotation::Annotations;
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+use crate::sync::merge::MarkedConflict;
+
+/// What happened to an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Created,
+    Updated,
+    /// Overwritten on purpose with `devlog new --force`
+    Replaced,
+    /// Put back to the content of an earlier event with `devlog revert`
+    Reverted,
+    /// The entry as it was when the events before it were moved to the archive
+    Snapshot,
+    /// A `+tag` appeared in the entry, the tag is the detail
+    TagAdded,
+    /// The last mention of a `+tag` was removed from the entry
+    TagRemoved,
+    /// A file was attached to the entry, its name is the detail
+    AttachmentAdded,
+    /// The conflict markers a sync left in the entry were all resolved
+    SyncConflictResolved,
+    /// The entry was moved to another day, the id it had before is the detail
+    Renamed,
+    /// The entry was locked against edits
+    Locked,
+    /// A locked entry can be edited again
+    Unlocked,
+}
+
+impl EventKind {
+    /// Whether the event records new content of the entry. Other events explain a change
+    /// recorded next to them, the content after them is the one before.
+    pub fn changes_content(&self) -> bool {
+        matches!(
+            self,
+            EventKind::Created
+                | EventKind::Updated
+                | EventKind::Replaced
+                | EventKind::Reverted
+                | EventKind::Snapshot
+        )
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EventKind::Created => "created",
+            EventKind::Updated => "updated",
+            EventKind::Replaced => "replaced",
+            EventKind::Reverted => "reverted",
+            EventKind::Snapshot => "snapshot",
+            EventKind::TagAdded => "tag added",
+            EventKind::TagRemoved => "tag removed",
+            EventKind::AttachmentAdded => "attachment added",
+            EventKind::SyncConflictResolved => "sync conflict resolved",
+            EventKind::Renamed => "renamed",
+            EventKind::Locked => "locked",
+            EventKind::Unlocked => "unlocked",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One line of an entry's event log. Timestamps keep the local offset they were
+/// recorded with, so the hour of day is the one the user saw on their clock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub at: DateTime<FixedOffset>,
+    pub kind: EventKind,
+    /// Content of the entry after the event, missing in logs written before history was
+    /// kept and in events imported without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// What the event was about, e.g. `+migration` for a tag or the name of an attachment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Storage {
+    /// Get the directory holding one `YYYYMMDD.jsonl` event log per entry
+    pub fn events_path(&self) -> PathBuf {
+        self.data_path.join("events")
+    }
+
+    /// The events moved out of the log of `id` when it was compacted, gzipped JSON Lines
+    /// next to the log
+    pub fn event_archive_path(&self, id: &EntryId) -> PathBuf {
+        self.events_path().join(format!("{}.jsonl.gz", id))
+    }
+
+    /// Append an event happening now to the log of `id`, with the content the entry has
+    /// after it
+    pub fn record_event(&self, id: &EntryId, kind: EventKind, content: &str) -> Result<()> {
+        let event = Event {
+            at: Local::now().fixed_offset(),
+            kind,
+            content: Some(content.to_string()),
+            detail: None,
+        };
+        self.append_event(id, &event)?;
+        self.compact_events(id, content)
+    }
+
+    /// Append an event explaining a change, e.g. `TagAdded` with the tag, without content
+    pub fn record_detail(&self, id: &EntryId, kind: EventKind, detail: &str) -> Result<()> {
+        self.append_event(
+            id,
+            &Event {
+                at: Local::now().fixed_offset(),
+                kind,
+                content: None,
+                detail: Some(detail.to_string()),
+            },
+        )
+    }
+
+    fn append_event(&self, id: &EntryId, event: &Event) -> Result<()> {
+        let events_path = self.events_path();
+        self.writer.create_dir_all(&events_path)?;
+        self.writer.append(
+            &events_path.join(format!("{}.jsonl", id)),
+            event_line(event)?,
+        )
+    }
+
+    /// Once the log of `id` has more than `snapshot_events` events, move them gzipped to
+    /// the end of its archive and start the log over with a snapshot of `content`, so
+    /// reading it stays quick. The archive is written first: a crash in between repeats
+    /// events in the deep history instead of losing them.
+    fn compact_events(&self, id: &EntryId, content: &str) -> Result<()> {
+        if self.snapshot_events == 0 || self.writer.is_dry_run() {
+            return Ok(());
+        }
+        let path = self.events_path().join(format!("{}.jsonl", id));
+        let log = fs::read(&path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        if log.iter().filter(|byte| **byte == b'\n').count() <= self.snapshot_events {
+            return Ok(());
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&log)
+            .wrap_err("Failed to compress events")?;
+        let compressed = encoder.finish().wrap_err("Failed to compress events")?;
+        self.writer
+            .append(&self.event_archive_path(id), compressed)?;
+
+        let snapshot = Event {
+            at: Local::now().fixed_offset(),
+            kind: EventKind::Snapshot,
+            content: Some(content.to_string()),
+            detail: None,
+        };
+        self.writer.write_atomic(&path, event_line(&snapshot)?)
+    }
+
+    /// Read the event log of `id`, oldest first. Compacted logs start with a snapshot,
+    /// `deep_events` has the events before it.
+    pub fn events(&self, id: &EntryId) -> Result<Vec<Event>> {
+        let path = self.events_path().join(format!("{}.jsonl", id));
+        match fs::read_to_string(&path) {
+            Ok(raw) => Ok(parse_events(&raw)),
+            Err(e) if e.kind() == IoErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// The whole history of `id`, oldest first: its archived events followed by its log,
+    /// without the snapshots standing in for the archive
+    pub fn deep_events(&self, id: &EntryId) -> Result<Vec<Event>> {
+        let path = self.event_archive_path(id);
+        let archived = match fs::File::open(&path) {
+            Ok(file) => {
+                // Every compaction appends a gzip member of its own
+                let mut raw = String::new();
+                MultiGzDecoder::new(file)
+                    .read_to_string(&mut raw)
+                    .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+                parse_events(&raw)
+            }
+            Err(e) if e.kind() == IoErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        };
+
+        let mut events: Vec<Event> = Vec::new();
+        for event in archived.into_iter().chain(self.events(id)?) {
+            let archived = event.kind == EventKind::Snapshot
+                && events
+                    .last()
+                    .is_some_and(|last| last.content == event.content);
+            if !archived {
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Put entry `id` back to the content it had after event `number` of its log, or of
+    /// its whole history when `deep`, counting from 1. Reverting is an event itself, so it
+    /// can be reverted too.
+    pub fn revert_entry(&self, id: &EntryId, number: usize, deep: bool) -> Result<Entry> {
+        let events = if deep {
+            self.deep_events(id)?
+        } else {
+            self.events(id)?
+        };
+        if number == 0 || number > events.len() {
+            bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!("Entry '{}' has no event {}", id, number)
+            ));
+        }
+        // A tag or attachment event leaves the content of the change it was recorded with
+        let content = events[..number]
+            .iter()
+            .rev()
+            .find(|event| event.kind.changes_content())
+            .and_then(|event| event.content.as_ref());
+        let Some(content) = content else {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!(
+                    "Event {} of entry '{}' was recorded without its content",
+                    number, id
+                )
+            ));
+        };
+
+        let mut entry = self.load_entry(id)?;
+        entry.update_content(content.clone());
+        self.write_entry(&entry, EventKind::Reverted)?;
+        Ok(entry)
+    }
+
+    /// Read the whole history of every entry
+    pub fn all_events(&self) -> Result<Vec<(EntryId, Event)>> {
+        let entries = match fs::read_dir(self.events_path()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == IoErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).wrap_err("Failed to read event logs"),
+        };
+
+        let mut events = Vec::new();
+        for file in entries.filter_map(|e| e.ok()) {
+            let name = file.file_name();
+            let Some(id) = name
+                .to_str()
+                .and_then(|name| name.strip_suffix(".jsonl"))
+                .and_then(|id| id.parse::<EntryId>().ok())
+            else {
+                continue;
+            };
+            events.extend(self.deep_events(&id)?.into_iter().map(|event| (id, event)));
+        }
+        Ok(events)
+    }
+}
+
+/// Events explaining the change of an entry from `before` to `after`, with their
+/// details: the tags added and removed, and the conflicts a sync left being resolved
+pub fn detail_events(before: &str, after: &str) -> Vec<(EventKind, String)> {
+    let before_tags: BTreeSet<String> = Annotations::parse(before).tags.into_iter().collect();
+    let after_tags: BTreeSet<String> = Annotations::parse(after).tags.into_iter().collect();
+    let mut events: Vec<(EventKind, String)> = after_tags
+        .difference(&before_tags)
+        .map(|tag| (EventKind::TagAdded, format!("+{}", tag)))
+        .collect();
+    events.extend(
+        before_tags
+            .difference(&after_tags)
+            .map(|tag| (EventKind::TagRemoved, format!("+{}", tag))),
+    );
+
+    let conflicts = |text: &str| MarkedConflict::find_all(&text.lines().collect::<Vec<_>>()).len();
+    let resolved = conflicts(before);
+    if resolved > 0 && conflicts(after) == 0 {
+        events.push((
+            EventKind::SyncConflictResolved,
+            format!(
+                "{} conflict{}",
+                resolved,
+                if resolved == 1 { "" } else { "s" }
+            ),
+        ));
+    }
+    events
+}
+
+fn event_line(event: &Event) -> Result<String> {
+    let mut line = serde_json::to_string(event).wrap_err("Failed to serialize event")?;
+    line.push('\n');
+    Ok(line)
+}
+
+/// A line cut short by a crash shouldn't hide the rest of the history
+fn parse_events(raw: &str) -> Vec<Event> {
+    raw.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_saving_entries_records_events() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let mut entry = Entry::new(id, "first".to_string());
+        storage.save_entry(&entry).unwrap();
+        entry.update_content("second".to_string());
+        storage.save_entry(&entry).unwrap();
+
+        let kinds: Vec<EventKind> = storage
+            .events(&id)
+            .unwrap()
+            .iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(kinds, vec![EventKind::Created, EventKind::Updated]);
+        assert_eq!(storage.all_events().unwrap().len(), 2);
+
+        storage
+            .replace_entry(&Entry::new(id, "third".to_string()))
+            .unwrap();
+        let last = storage.events(&id).unwrap().pop().unwrap();
+        assert_eq!(last.kind, EventKind::Replaced);
+        assert_eq!(storage.load_entry(&id).unwrap().content, "third");
+    }
+
+    #[test]
+    fn test_revert_to_an_earlier_event() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let mut entry = Entry::new(id, "first".to_string());
+        storage.save_entry(&entry).unwrap();
+        entry.update_content("second".to_string());
+        storage.save_entry(&entry).unwrap();
+
+        let reverted = storage.revert_entry(&id, 1, false).unwrap();
+        assert_eq!(reverted.content, "first");
+        assert_eq!(storage.load_entry(&id).unwrap().content, "first");
+        let events = storage.events(&id).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].kind, EventKind::Reverted);
+        assert_eq!(events[2].content.as_deref(), Some("first"));
+
+        assert!(storage.revert_entry(&id, 0, false).is_err());
+        assert!(storage.revert_entry(&id, 4, false).is_err());
+    }
+
+    #[test]
+    fn test_long_logs_are_compacted_into_the_archive() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage = Storage::new_with_base_dir(temp_dir.path())
+            .expect("Failed to create storage")
+            .with_event_snapshots(3);
+        let id: EntryId = "20250920".parse().unwrap();
+        let mut entry = Entry::new(id, String::new());
+        for n in 1..=9 {
+            entry.update_content(format!("draft {}", n));
+            storage.save_entry(&entry).unwrap();
+        }
+
+        // Compacted after the 4th and 7th save, each time to a snapshot of the last one
+        let events = storage.events(&id).unwrap();
+        let kinds: Vec<EventKind> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![EventKind::Snapshot, EventKind::Updated, EventKind::Updated]
+        );
+        assert_eq!(events[0].content.as_deref(), Some("draft 7"));
+        assert!(storage.event_archive_path(&id).exists());
+
+        let deep = storage.deep_events(&id).unwrap();
+        let contents: Vec<&str> = deep.iter().filter_map(|e| e.content.as_deref()).collect();
+        let drafts: Vec<String> = (1..=9).map(|n| format!("draft {}", n)).collect();
+        assert_eq!(contents, drafts);
+        assert_eq!(deep[0].kind, EventKind::Created);
+        assert_eq!(storage.all_events().unwrap().len(), 9);
+
+        let reverted = storage.revert_entry(&id, 2, true).unwrap();
+        assert_eq!(reverted.content, "draft 2");
+    }
+
+    #[test]
+    fn test_tags_and_resolved_conflicts_are_recorded() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        let mut entry = Entry::new(id, "Started +migration".to_string());
+        storage.save_entry(&entry).unwrap();
+        entry.update_content(
+            "<<<<<<< yours\nStarted +migration\n=======\nStarted +rollout\n>>>>>>> on disk"
+                .to_string(),
+        );
+        storage.save_entry(&entry).unwrap();
+        entry.update_content("Started +rollout".to_string());
+        storage.save_entry(&entry).unwrap();
+
+        let events: Vec<(EventKind, Option<String>)> = storage
+            .events(&id)
+            .unwrap()
+            .into_iter()
+            .map(|event| (event.kind, event.detail))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (EventKind::Created, None),
+                (EventKind::Updated, None),
+                (EventKind::TagAdded, Some("+rollout".to_string())),
+                (EventKind::Updated, None),
+                (EventKind::TagRemoved, Some("+migration".to_string())),
+                (
+                    EventKind::SyncConflictResolved,
+                    Some("1 conflict".to_string())
+                ),
+            ]
+        );
+
+        // A detail event reverts to the content of the change it explains
+        let reverted = storage.revert_entry(&id, 3, false).unwrap();
+        assert!(reverted.content.starts_with("<<<<<<< yours"));
+    }
+
+    #[test]
+    fn test_events_without_content_still_read() {
+        let event: Event =
+            serde_json::from_str(r#"{"at":"2025-09-20T09:00:00+02:00","kind":"updated"}"#).unwrap();
+        assert_eq!(event.content, None);
+    }
+
+    #[test]
+    fn test_corrupt_event_lines_are_skipped() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        storage.record_event(&id, EventKind::Created, "").unwrap();
+        let path = storage.events_path().join("20250920.jsonl");
+        fs::write(
+            &path,
+            format!("{}{{\"at\": \"2025-09", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(storage.events(&id).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{fs, io::ErrorKind};
+
+use chrono::{DateTime, Duration, FixedOffset};
+use color_eyre::eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+const FOCUS_FILE_NAME: &str = "focus.toml";
+
+/// A running focus timer started with `devlog focus start`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusSession {
+    pub started: DateTime<FixedOffset>,
+    pub until: DateTime<FixedOffset>,
+    /// Only today's entry can be edited while the session runs
+    #[serde(default)]
+    pub lock_entries: bool,
+}
+
+impl FocusSession {
+    pub fn new(now: DateTime<FixedOffset>, minutes: u32, lock_entries: bool) -> Self {
+        Self {
+            started: now,
+            until: now + Duration::minutes(i64::from(minutes)),
+            lock_entries,
+        }
+    }
+
+    /// The session if one is running at `now`, finished sessions read as none
+    pub fn load(storage: &Storage, now: DateTime<FixedOffset>) -> Result<Option<Self>> {
+        let path = storage.state_path().join(FOCUS_FILE_NAME);
+        let session: Self = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        };
+        Ok((session.until > now).then_some(session))
+    }
+
+    pub fn save(&self, storage: &Storage) -> Result<()> {
+        let content = toml::to_string(self).wrap_err("Failed to serialize focus session")?;
+        storage
+            .writer()
+            .write_atomic(&storage.state_path().join(FOCUS_FILE_NAME), &content)
+            .wrap_err("Failed to save focus session")
+    }
+
+    /// End the running session, if any
+    pub fn clear(storage: &Storage) -> Result<()> {
+        let path = storage.state_path().join(FOCUS_FILE_NAME);
+        if path.exists() {
+            storage.writer().remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Minutes left at `now`, rounded up
+    pub fn remaining_minutes(&self, now: DateTime<FixedOffset>) -> u64 {
+        ((self.until - now).num_seconds().max(0) as u64).div_ceil(60)
+    }
+
+    /// Why `id` can't be edited right now, none if it can
+    pub fn blocks(&self, id: &EntryId, now: DateTime<FixedOffset>) -> Option<String> {
+        if !self.lock_entries || *id == EntryId::from_date(now.date_naive()) {
+            return None;
+        }
+        Some(self.lock_message())
+    }
+
+    /// Why notes can't be edited right now, none if they can
+    pub fn blocks_notes(&self) -> Option<String> {
+        self.lock_entries.then(|| self.lock_message())
+    }
+
+    fn lock_message(&self) -> String {
+        format!(
+            "Focusing until {}, old entries can wait. Today's entry is open for notes, \
+             or end the session with `devlog focus stop`.",
+            self.until.format("%H:%M")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn at(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&format!("2025-09-20T{}:00+02:00", time)).unwrap()
+    }
+
+    #[test]
+    fn test_session_ends_after_its_minutes() {
+        let (storage, _temp_dir) = create_test_storage();
+        assert_eq!(FocusSession::load(&storage, at("09:00")).unwrap(), None);
+
+        let session = FocusSession::new(at("09:00"), 25, true);
+        session.save(&storage).unwrap();
+        assert_eq!(
+            FocusSession::load(&storage, at("09:24")).unwrap(),
+            Some(session.clone())
+        );
+        assert_eq!(session.remaining_minutes(at("09:10")), 15);
+        assert_eq!(FocusSession::load(&storage, at("09:25")).unwrap(), None);
+
+        FocusSession::clear(&storage).unwrap();
+        assert_eq!(FocusSession::load(&storage, at("09:10")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_locked_session_only_allows_today() {
+        let now = at("09:10");
+        let today: EntryId = "20250920".parse().unwrap();
+        let yesterday: EntryId = "20250919".parse().unwrap();
+
+        let locked = FocusSession::new(at("09:00"), 25, true);
+        assert_eq!(locked.blocks(&today, now), None);
+        assert!(locked.blocks(&yesterday, now).unwrap().contains("09:25"));
+
+        let unlocked = FocusSession::new(at("09:00"), 25, false);
+        assert_eq!(unlocked.blocks(&yesterday, now), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use color_eyre::eyre::{Context, Result, bail};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::storage::Storage;
+
+/// Name of the ignore file in the vault root
+pub const IGNORE_FILE_NAME: &str = ".devlogignore";
+
+/// Files and directories excluded from the vault by `.devlogignore`,
+/// using the same pattern syntax as `.gitignore`
+pub struct IgnoreRules {
+    matcher: Gitignore,
+}
+
+impl IgnoreRules {
+    /// Load the rules from `root`, a missing ignore file ignores nothing
+    pub fn load(root: &Path) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+        let ignore_file = root.join(IGNORE_FILE_NAME);
+
+        if ignore_file.exists()
+            && let Some(err) = builder.add(&ignore_file)
+        {
+            bail!("Invalid pattern in {}: {}", ignore_file.display(), err);
+        }
+
+        let matcher = builder
+            .build()
+            .wrap_err_with(|| format!("Failed to load {}", ignore_file.display()))?;
+        Ok(Self { matcher })
+    }
+
+    /// Whether `path` (inside the vault root) or any of its parent directories is ignored
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.matcher
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+    }
+}
+
+impl Storage {
+    /// Get the ignore rules of the vault, read from `.devlogignore` in the data directory
+    pub fn ignore_rules(&self) -> Result<IgnoreRules> {
+        IgnoreRules::load(&self.data_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gitignore_syntax() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        fs::write(
+            root.join(IGNORE_FILE_NAME),
+            "# Foreign tools\n.obsidian/\n*.tmp.md\n!keep.tmp.md\n",
+        )
+        .unwrap();
+
+        let rules = IgnoreRules::load(root).expect("Failed to load rules");
+
+        assert!(rules.is_ignored(&root.join("entries/.obsidian"), true));
+        assert!(rules.is_ignored(&root.join("entries/.obsidian/workspace.md"), false));
+        assert!(rules.is_ignored(&root.join("entries/20250920.tmp.md"), false));
+        assert!(!rules.is_ignored(&root.join("entries/keep.tmp.md"), false));
+        assert!(!rules.is_ignored(&root.join("entries/20250920.md"), false));
+    }
+
+    #[test]
+    fn test_missing_ignore_file_ignores_nothing() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let rules = IgnoreRules::load(temp_dir.path()).expect("Failed to load rules");
+
+        assert!(!rules.is_ignored(&temp_dir.path().join("entries/20250920.md"), false));
+    }
+}
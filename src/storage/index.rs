@@ -0,0 +1,578 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    path::Path,
+    time::SystemTime,
+};
+
+use chrono::{Days, NaiveDate, NaiveTime};
+use color_eyre::eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::action::Action;
+use crate::models::annotation::{AnnotationKind, Annotations};
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::models::frontmatter;
+use crate::models::link;
+use crate::models::time_log::parse_time_log;
+use crate::models::todo;
+use crate::storage::Storage;
+use crate::utils::lock::FileLock;
+
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// Bumped whenever `IndexedEntry` gains data, so old indexes are rebuilt
+const INDEX_VERSION: u32 = 5;
+
+/// What the index knows about one entry, enough for listings, status lines and
+/// annotation lookups without reading the entry itself
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedEntry {
+    pub preview: String,
+    pub words: usize,
+    /// Unchecked `- [ ]` items
+    pub open_todos: usize,
+    /// Text of the unchecked items, e.g. meeting action items
+    pub todos: Vec<String>,
+    pub annotations: Annotations,
+    /// Minutes logged per project with duration annotations like `~1h30m`
+    pub minutes: BTreeMap<String, u32>,
+    /// Times of the `## HH:MM` sub-entries
+    pub sub_entries: Vec<NaiveTime>,
+    /// Custom frontmatter fields like `mood`, for `--where`
+    pub fields: BTreeMap<String, String>,
+    /// Keys of the `[[...]]` links to other entries, notes and projects
+    pub links: Vec<String>,
+    /// Modification time of the entry file when it was indexed
+    pub modified: Option<SystemTime>,
+}
+
+impl IndexedEntry {
+    pub fn of(entry: &Entry, modified: Option<SystemTime>) -> Self {
+        let todos = todo::open_todos(&entry.content);
+        Self {
+            preview: entry.preview(),
+            // List markers and other punctuation aren't words
+            words: entry
+                .content
+                .split_whitespace()
+                .filter(|word| word.chars().any(char::is_alphanumeric))
+                .count(),
+            open_todos: todos.len(),
+            todos,
+            annotations: Annotations::parse(&entry.content),
+            minutes: parse_time_log(&entry.content),
+            sub_entries: entry.sub_entries().iter().map(|s| s.time).collect(),
+            fields: frontmatter::fields(&entry.extra),
+            links: links(&entry.id, &entry.content),
+            modified,
+        }
+    }
+}
+
+/// The keys an entry links to once each, without links to itself
+fn links(id: &EntryId, content: &str) -> Vec<String> {
+    let own = id.to_string();
+    let mut keys: Vec<String> = Vec::new();
+    for key in link::links(content).iter().map(|link| link.target.key()) {
+        if key != own && !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// How often an annotation value is mentioned and in which entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationUsage {
+    pub value: String,
+    /// Number of mentions, an entry can mention a value several times
+    pub count: usize,
+    /// Entries mentioning the value, oldest first
+    pub entries: Vec<EntryId>,
+}
+
+/// Freshness of the index, reported by `devlog info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexStatus {
+    Missing,
+    /// This many entries were added, removed or changed outside devlog since
+    /// they were indexed, they are picked up on next use
+    Stale(usize),
+    Fresh,
+}
+
+/// Every entry of the vault by id, stored in `cache_path/index.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryIndex {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    pub entries: BTreeMap<EntryId, IndexedEntry>,
+}
+
+impl EntryIndex {
+    pub fn get(&self, id: &EntryId) -> Option<&IndexedEntry> {
+        self.entries.get(id)
+    }
+
+    /// Consecutive days with a non-empty entry up to `today`. A day without an entry
+    /// yet doesn't break the streak until it's over, so this counts from yesterday then.
+    pub fn streak(&self, today: NaiveDate) -> usize {
+        let written = |date: NaiveDate| {
+            self.get(&EntryId::from_date(date))
+                .is_some_and(|entry| entry.words > 0)
+        };
+
+        let mut day = if written(today) {
+            today
+        } else {
+            match today.checked_sub_days(Days::new(1)) {
+                Some(yesterday) => yesterday,
+                None => return 0,
+            }
+        };
+        let mut streak = 0;
+        while written(day) {
+            streak += 1;
+            match day.checked_sub_days(Days::new(1)) {
+                Some(previous) => day = previous,
+                None => break,
+            }
+        }
+        streak
+    }
+
+    /// Most consecutive days with a non-empty entry, ever
+    pub fn longest_streak(&self) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        let mut last: Option<NaiveDate> = None;
+        for (id, _) in self.entries.iter().filter(|(_, entry)| entry.words > 0) {
+            let day = id.date();
+            current = match last {
+                Some(last) if last.succ_opt() == Some(day) => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            last = Some(day);
+        }
+        longest
+    }
+
+    pub fn open_todos(&self) -> usize {
+        self.entries.values().map(|entry| entry.open_todos).sum()
+    }
+
+    /// Open todos of every entry, oldest entry first
+    pub fn todos(&self) -> Vec<(EntryId, &str)> {
+        self.entries
+            .iter()
+            .flat_map(|(id, entry)| entry.todos.iter().map(|todo| (*id, todo.as_str())))
+            .collect()
+    }
+
+    /// Open `->` action items of every entry, oldest entry first
+    pub fn actions(&self) -> Vec<(EntryId, Action)> {
+        self.todos()
+            .into_iter()
+            .filter_map(|(id, todo)| Action::parse(todo).map(|action| (id, action)))
+            .collect()
+    }
+
+    /// Every value of an annotation kind, most frequent first
+    pub fn annotation_usage(&self, kind: AnnotationKind) -> Vec<AnnotationUsage> {
+        let mut usage: HashMap<&str, AnnotationUsage> = HashMap::new();
+        // Entries are ordered by id, so every value's entries end up oldest first
+        for (id, entry) in &self.entries {
+            for value in entry.annotations.values(kind) {
+                let value_usage = usage
+                    .entry(value.as_str())
+                    .or_insert_with(|| AnnotationUsage {
+                        value: value.clone(),
+                        count: 0,
+                        entries: Vec::new(),
+                    });
+                value_usage.count += 1;
+                if value_usage.entries.last() != Some(id) {
+                    value_usage.entries.push(*id);
+                }
+            }
+        }
+
+        let mut values: Vec<AnnotationUsage> = usage.into_values().collect();
+        // Most frequent first, alphabetical among equals
+        values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        values
+    }
+
+    /// Projects no entry of the last `days` days up to `today` mentions, with the entry
+    /// that mentioned them last, the longest quiet first
+    pub fn stale_projects(&self, today: NaiveDate, days: u64) -> Vec<(String, EntryId)> {
+        let Some(cutoff) = today.checked_sub_days(Days::new(days)) else {
+            return Vec::new();
+        };
+        let mut stale: Vec<(String, EntryId)> = self
+            .annotation_usage(AnnotationKind::Project)
+            .into_iter()
+            .filter_map(|usage| Some((usage.value, *usage.entries.last()?)))
+            .filter(|(_, last)| last.date() <= cutoff)
+            .collect();
+        stale.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        stale
+    }
+
+    /// Entries linking to `key`, a day like `20250920` or a note or project name,
+    /// oldest first
+    pub fn backlinks(&self, key: &str) -> Vec<EntryId> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.links.iter().any(|link| link == key))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+impl Storage {
+    /// Read the index as it was last written, without looking at any entry.
+    /// Meant for status lines that must stay fast, everything else uses `index`.
+    pub fn load_index(&self) -> Result<EntryIndex> {
+        let Ok(raw) = fs::read_to_string(self.index_path()) else {
+            return Ok(EntryIndex::default());
+        };
+        // A corrupt or outdated index reads as empty and is rebuilt on next use
+        Ok(serde_json::from_str(&raw)
+            .ok()
+            .filter(|index: &EntryIndex| index.version == INDEX_VERSION)
+            .unwrap_or_default())
+    }
+
+    /// Get the index, first re-reading the entries that were added or changed
+    /// outside devlog and dropping removed ones. Unchanged entries are not read.
+    pub fn index(&self) -> Result<EntryIndex> {
+        let mut index = self.load_index()?;
+        let mut changed = false;
+
+        let ids: BTreeSet<EntryId> = self.list_entries()?.into_iter().collect();
+        let before = index.entries.len();
+        index.entries.retain(|id, _| ids.contains(id));
+        changed |= index.entries.len() != before;
+
+        for id in ids {
+            let modified = modified_time(&self.entry_path(&id)?);
+            if index
+                .get(&id)
+                .is_some_and(|indexed| indexed.modified.is_some() && indexed.modified == modified)
+            {
+                continue;
+            }
+            // An unreadable entry shouldn't break everything else using the index
+            if let Ok(entry) = self.load_entry(&id) {
+                index.entries.insert(id, IndexedEntry::of(&entry, modified));
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.save_index(&mut index)?;
+        }
+        Ok(index)
+    }
+
+    /// Recompute the index from every entry
+    pub fn rebuild_index(&self) -> Result<EntryIndex> {
+        let mut index = EntryIndex::default();
+        for id in self.list_entries()? {
+            let Ok(entry) = self.load_entry(&id) else {
+                continue;
+            };
+            let modified = modified_time(&self.entry_path(&id)?);
+            index.entries.insert(id, IndexedEntry::of(&entry, modified));
+        }
+        self.save_index(&mut index)?;
+        Ok(index)
+    }
+
+    pub fn index_status(&self) -> Result<IndexStatus> {
+        if !self.index_path().exists() {
+            return Ok(IndexStatus::Missing);
+        }
+        let index = self.load_index()?;
+        let ids: BTreeSet<EntryId> = self.list_entries()?.into_iter().collect();
+
+        let removed = index.entries.keys().filter(|id| !ids.contains(id)).count();
+        let mut changed = 0;
+        for id in &ids {
+            let modified = modified_time(&self.entry_path(id)?);
+            if index
+                .get(id)
+                .is_none_or(|indexed| indexed.modified != modified)
+            {
+                changed += 1;
+            }
+        }
+
+        Ok(match removed + changed {
+            0 => IndexStatus::Fresh,
+            stale => IndexStatus::Stale(stale),
+        })
+    }
+
+    /// Get every value of an annotation kind with the entries mentioning it
+    pub fn annotation_usage(&self, kind: AnnotationKind) -> Result<Vec<AnnotationUsage>> {
+        Ok(self.index()?.annotation_usage(kind))
+    }
+
+    /// Get all known values of an annotation kind, e.g. every `+tag` ever used
+    pub fn annotation_values(&self, kind: AnnotationKind) -> Result<Vec<String>> {
+        Ok(self
+            .annotation_usage(kind)?
+            .into_iter()
+            .map(|usage| usage.value)
+            .collect())
+    }
+
+    /// Record a saved entry in the index
+    /// Returns what the index had for the entry before
+    pub(crate) fn update_index(&self, entry: &Entry) -> Result<Option<IndexedEntry>> {
+        // Another process saving an entry at the same time, e.g. a capture job next to
+        // the TUI, would otherwise write back an index without this one
+        let _lock = if self.writer.is_dry_run() {
+            None
+        } else {
+            Some(FileLock::acquire(&self.index_path())?)
+        };
+        let modified = modified_time(&self.entry_path(&entry.id)?);
+        let mut index = self.load_index()?;
+        let previous = index
+            .entries
+            .insert(entry.id, IndexedEntry::of(entry, modified));
+        self.save_index(&mut index)?;
+        Ok(previous)
+    }
+
+    fn index_path(&self) -> std::path::PathBuf {
+        self.cache_path.join(INDEX_FILE_NAME)
+    }
+
+    fn save_index(&self, index: &mut EntryIndex) -> Result<()> {
+        index.version = INDEX_VERSION;
+        let serialized = serde_json::to_string(index).wrap_err("Failed to serialize index")?;
+        self.writer.write_atomic(&self.index_path(), serialized)
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn save(storage: &Storage, id: &str, content: &str) {
+        let entry = Entry::new(id.parse().unwrap(), content.to_string());
+        storage.save_entry(&entry).unwrap();
+    }
+
+    #[test]
+    fn test_indexed_entry_stats() {
+        let entry = Entry::new(
+            "20250920".parse().unwrap(),
+            "Did things +rust\n- [ ] open\n  * [ ] nested\n- done".to_string(),
+        );
+        let indexed = IndexedEntry::of(&entry, None);
+        assert_eq!(indexed.preview, "Did things +rust");
+        assert_eq!(indexed.words, 6);
+        assert_eq!(indexed.open_todos, 2);
+        assert_eq!(indexed.todos, vec!["open", "nested"]);
+        assert_eq!(indexed.annotations.tags, vec!["rust"]);
+    }
+
+    #[test]
+    fn test_actions_of_all_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(
+            &storage,
+            "20250919",
+            "- [ ] -> @alice: send the doc\n- [x] -> done",
+        );
+        save(&storage, "20250920", "- [ ] plain todo\n- [ ] -> follow up");
+
+        let actions = storage.index().unwrap().actions();
+        let tasks: Vec<(String, &str)> = actions
+            .iter()
+            .map(|(id, action)| (id.to_string(), action.task.as_str()))
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("20250919".to_string(), "send the doc"),
+                ("20250920".to_string(), "follow up")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_saving_entries_updates_index() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(&storage, "20250918", "one");
+        save(&storage, "20250919", "two - [ ] words");
+        save(&storage, "20250920", "- [ ] three");
+
+        let index = storage.load_index().unwrap();
+        assert_eq!(index.entries.len(), 3);
+        assert_eq!(index.open_todos(), 1);
+        let day = |d| NaiveDate::from_ymd_opt(2025, 9, d).unwrap();
+        assert_eq!(index.streak(day(20)), 3);
+        // Today isn't written yet, the streak still counts
+        assert_eq!(index.streak(day(21)), 3);
+        assert_eq!(index.streak(day(22)), 0);
+        assert_eq!(index.longest_streak(), 3);
+
+        assert_eq!(storage.index_status().unwrap(), IndexStatus::Fresh);
+        assert_eq!(storage.rebuild_index().unwrap(), index);
+    }
+
+    #[test]
+    fn test_annotation_usage_sorted_by_frequency() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(&storage, "20250920", "+rust with @bob");
+        save(&storage, "20250921", "+cli +rust on ::devlog");
+
+        let tags = storage.annotation_values(AnnotationKind::Tag).unwrap();
+        assert_eq!(tags, vec!["rust", "cli"]);
+        let people = storage.annotation_values(AnnotationKind::Person).unwrap();
+        assert_eq!(people, vec!["bob"]);
+        let projects = storage.annotation_values(AnnotationKind::Project).unwrap();
+        assert_eq!(projects, vec!["devlog"]);
+
+        let rust = &storage.annotation_usage(AnnotationKind::Tag).unwrap()[0];
+        assert_eq!(rust.count, 2);
+        assert_eq!(rust.entries, vec!["20250920", "20250921"]);
+    }
+
+    #[test]
+    fn test_stale_projects() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(&storage, "20250701", "::legacy and ::api");
+        save(&storage, "20250801", "::migration");
+        save(&storage, "20250915", "::api again");
+
+        let index = storage.index().unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 9, 20).unwrap();
+        let stale: Vec<(String, String)> = index
+            .stale_projects(today, 30)
+            .into_iter()
+            .map(|(project, last)| (project, last.to_string()))
+            .collect();
+        assert_eq!(
+            stale,
+            vec![
+                ("legacy".to_string(), "20250701".to_string()),
+                ("migration".to_string(), "20250801".to_string())
+            ]
+        );
+        assert!(index.stale_projects(today, 90).is_empty());
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(
+            &storage,
+            "20250920",
+            "Started [[oncall-runbook]], see [[20250920]]",
+        );
+        save(
+            &storage,
+            "20250921",
+            "Follow-up of [[20250920-0930]] and [[20250920]]",
+        );
+        save(
+            &storage,
+            "20250925",
+            "Back to [[20250920]] and [[::devlog]]",
+        );
+
+        let index = storage.index().unwrap();
+        let ids = |key: &str| -> Vec<String> {
+            index
+                .backlinks(key)
+                .iter()
+                .map(|id| id.to_string())
+                .collect()
+        };
+        assert_eq!(ids("20250920"), vec!["20250921", "20250925"]);
+        assert_eq!(ids("oncall-runbook"), vec!["20250920"]);
+        assert_eq!(ids("devlog"), vec!["20250925"]);
+        assert!(ids("20250921").is_empty());
+        assert_eq!(
+            index.get(&"20250921".parse().unwrap()).unwrap().links,
+            vec!["20250920"]
+        );
+    }
+
+    #[test]
+    fn test_index_picks_up_changes_made_outside_devlog() {
+        let (storage, _temp_dir) = create_test_storage();
+        save(&storage, "20250920", "+first");
+        save(&storage, "20250921", "+gone");
+        assert_eq!(storage.index_status().unwrap(), IndexStatus::Fresh);
+
+        // Make sure the edit gets a newer modification time than the save
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let first = storage.entry_path(&"20250920".parse().unwrap()).unwrap();
+        let edited = fs::read_to_string(&first)
+            .unwrap()
+            .replace("+first", "+edited");
+        fs::write(&first, edited).unwrap();
+        fs::remove_file(storage.entry_path(&"20250921".parse().unwrap()).unwrap()).unwrap();
+        assert_eq!(storage.index_status().unwrap(), IndexStatus::Stale(2));
+
+        let tags = storage.annotation_values(AnnotationKind::Tag).unwrap();
+        assert_eq!(tags, vec!["edited"]);
+        assert_eq!(storage.index_status().unwrap(), IndexStatus::Fresh);
+    }
+
+    #[test]
+    fn test_missing_index_is_built_on_use() {
+        let (storage, temp_dir) = create_test_storage();
+        save(&storage, "20250920", "+first");
+        fs::remove_file(temp_dir.path().join("cache").join(INDEX_FILE_NAME)).unwrap();
+
+        assert_eq!(storage.index_status().unwrap(), IndexStatus::Missing);
+        assert!(storage.load_index().unwrap().entries.is_empty());
+        assert_eq!(storage.index().unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_updates_keep_every_entry() {
+        let (storage, _temp_dir) = create_test_storage();
+        let threads: Vec<_> = (1..=8)
+            .map(|day| {
+                let storage = storage.clone();
+                std::thread::spawn(move || {
+                    let id = format!("202509{:02}", day).parse().unwrap();
+                    storage
+                        .update_index(&Entry::new(id, "+busy".to_string()))
+                        .unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(storage.load_index().unwrap().entries.len(), 8);
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::ErrorKind as IoErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use color_eyre::eyre::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::storage::{
+    Storage,
+    entry::{parse_document, read_entry_file},
+    events::{Event, EventKind},
+};
+
+/// One line of `events/YYYYMMDD.jsonl` as written by the event-sourcing prototype.
+/// Annotations are derived from the content now, their events are only read to be dropped.
+#[derive(Debug, Deserialize)]
+enum LegacyEvent {
+    Created {
+        content: String,
+        timestamp: DateTime<FixedOffset>,
+    },
+    ContentUpdated {
+        content: String,
+        timestamp: DateTime<FixedOffset>,
+    },
+    AnnotationParsed {},
+}
+
+/// How the events and markdown of a prototype entry were brought together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyOutcome {
+    /// Events and markdown agreed
+    Imported,
+    /// The markdown was edited after the last event and was kept
+    MarkdownKept,
+    /// The markdown was missing, empty or older than the events, which were replayed instead
+    Replayed,
+    /// The vault already has this entry, it was left alone
+    Skipped,
+}
+
+#[derive(Debug, Default)]
+pub struct LegacyReport {
+    pub entries: Vec<(EntryId, LegacyOutcome)>,
+    /// Event log lines that couldn't be read and were left out
+    pub unreadable_events: usize,
+}
+
+/// An entry of the prototype with its history replayed from the events
+struct Replayed {
+    content: String,
+    history: Vec<Event>,
+}
+
+impl Storage {
+    /// The prototype vault, `~/.devlog` with `events/` and `entries/` inside
+    pub fn legacy_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".devlog"))
+    }
+
+    /// Bring the entries of a prototype vault at `source` into this one, keeping
+    /// their history. Entries already in the vault are never overwritten.
+    pub fn migrate_legacy(&self, source: &Path) -> Result<LegacyReport> {
+        let events_dir = source.join("events");
+        let entries_dir = source.join("entries");
+        if !events_dir.is_dir() && !entries_dir.is_dir() {
+            bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!(
+                    "No prototype vault at {}, expected events/ or entries/ inside",
+                    source.display()
+                )
+            ));
+        }
+
+        let mut ids = BTreeSet::new();
+        ids.extend(ids_in(&events_dir, "jsonl")?);
+        ids.extend(ids_in(&entries_dir, "md")?);
+
+        let mut report = LegacyReport::default();
+        for id in ids {
+            if self.entry_path(&id)?.exists() {
+                report.entries.push((id, LegacyOutcome::Skipped));
+                continue;
+            }
+
+            let events_path = events_dir.join(format!("{}.jsonl", id));
+            let replayed = match fs::read_to_string(&events_path) {
+                Ok(raw) => replay(&raw, &mut report.unreadable_events),
+                Err(e) if e.kind() == IoErrorKind::NotFound => None,
+                Err(e) => {
+                    return Err(e)
+                        .wrap_err_with(|| format!("Failed to read {}", events_path.display()));
+                }
+            };
+            let markdown = read_markdown(&entries_dir.join(format!("{}.md", id)), &id)?;
+
+            let (outcome, content, history) = reconcile(replayed, markdown);
+            if content.trim().is_empty() {
+                continue;
+            }
+            self.import_entry(id, content, history)?;
+            report.entries.push((id, outcome));
+        }
+        Ok(report)
+    }
+
+    /// Write an entry together with its event log, taking both from another vault
+    fn import_entry(&self, id: EntryId, content: String, history: Vec<Event>) -> Result<()> {
+        let mut entry = Entry::new(id, content);
+        if let (Some(first), Some(last)) = (history.first(), history.last()) {
+            entry.created_at = first.at.with_timezone(&Utc);
+            entry.updated_at = last.at.with_timezone(&Utc);
+        }
+
+        let file_path = self.get_entries_path()?.join(format!("{}.md", id));
+        let document = self.line_ending.apply(&self.serialize_entry(&entry)?);
+        self.writer
+            .write(&file_path, document)
+            .wrap_err_with(|| format!("Failed to save entry to {}", file_path.display()))?;
+
+        let mut log = String::new();
+        for event in &history {
+            log.push_str(&serde_json::to_string(event).wrap_err("Failed to serialize event")?);
+            log.push('\n');
+        }
+        let events_path = self.events_path();
+        self.writer.create_dir_all(&events_path)?;
+        self.writer
+            .write(&events_path.join(format!("{}.jsonl", id)), log)?;
+
+        self.entry_written(&entry)
+    }
+}
+
+/// Ids of the `YYYYMMDD.<extension>` files in `dir`, other files are ignored
+fn ids_in(dir: &Path, extension: &str) -> Result<Vec<EntryId>> {
+    let files = match fs::read_dir(dir) {
+        Ok(files) => files,
+        Err(e) if e.kind() == IoErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", dir.display())),
+    };
+    Ok(files
+        .filter_map(|file| file.ok())
+        .filter_map(|file| {
+            let path = file.path();
+            if path.extension()? != extension {
+                return None;
+            }
+            path.file_stem()?.to_str()?.parse().ok()
+        })
+        .collect())
+}
+
+/// Replay a prototype event log into the final content and the history in the
+/// format of this vault. None if no event set any content.
+fn replay(raw: &str, unreadable: &mut usize) -> Option<Replayed> {
+    let mut content = None;
+    let mut history = Vec::new();
+    for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(event) = serde_json::from_str::<LegacyEvent>(line) else {
+            *unreadable += 1;
+            continue;
+        };
+        match event {
+            LegacyEvent::Created {
+                content: text,
+                timestamp,
+            }
+            | LegacyEvent::ContentUpdated {
+                content: text,
+                timestamp,
+            } => {
+                let kind = if content.is_some() {
+                    EventKind::Updated
+                } else {
+                    EventKind::Created
+                };
+                history.push(Event {
+                    at: timestamp,
+                    kind,
+                    content: Some(text.clone()),
+                    detail: None,
+                });
+                content = Some(text);
+            }
+            LegacyEvent::AnnotationParsed {} => {}
+        }
+    }
+    history.sort_by_key(|event| event.at);
+    content.map(|content| Replayed { content, history })
+}
+
+/// Content and last change of a prototype markdown file, None if it's missing
+fn read_markdown(path: &Path, id: &EntryId) -> Result<Option<(String, DateTime<FixedOffset>)>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| DateTime::<Local>::from(modified).fixed_offset())
+        .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+    let (_, content) = parse_document(&id.to_string(), &read_entry_file(path)?)?;
+    Ok(Some((content, modified)))
+}
+
+/// Settle on the content of an entry when the events and the markdown disagree.
+/// The markdown was rewritten after every event, so a newer markdown file holds
+/// edits made outside the prototype, while an older or empty one was never
+/// written after the last events.
+fn reconcile(
+    replayed: Option<Replayed>,
+    markdown: Option<(String, DateTime<FixedOffset>)>,
+) -> (LegacyOutcome, String, Vec<Event>) {
+    let markdown = markdown.filter(|(content, _)| !content.trim().is_empty());
+    match (replayed, markdown) {
+        (Some(replayed), None) => (LegacyOutcome::Replayed, replayed.content, replayed.history),
+        (None, Some((content, modified))) => {
+            let created = Event {
+                at: modified,
+                kind: EventKind::Created,
+                content: Some(content.clone()),
+                detail: None,
+            };
+            (LegacyOutcome::Imported, content, vec![created])
+        }
+        (Some(replayed), Some((content, _))) if content.trim() == replayed.content.trim() => {
+            (LegacyOutcome::Imported, replayed.content, replayed.history)
+        }
+        (Some(mut replayed), Some((content, modified)))
+            if replayed
+                .history
+                .last()
+                .is_none_or(|last| modified > last.at) =>
+        {
+            replayed.history.push(Event {
+                at: modified,
+                kind: EventKind::Updated,
+                content: Some(content.clone()),
+                detail: None,
+            });
+            (LegacyOutcome::MarkdownKept, content, replayed.history)
+        }
+        (Some(replayed), Some(_)) => (LegacyOutcome::Replayed, replayed.content, replayed.history),
+        (None, None) => (LegacyOutcome::Skipped, String::new(), Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn legacy_vault(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (name, contents) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    const EVENTS: &str = concat!(
+        r#"{"Created":{"id":"20250905","content":"First draft","timestamp":"2025-09-05T09:00:00+02:00"}}"#,
+        "\n",
+        r#"{"AnnotationParsed":{"tags":[],"people":[],"projects":[],"timestamp":"2025-09-05T09:00:00+02:00"}}"#,
+        "\n",
+        "{\"ContentUp",
+        "\n",
+        r#"{"ContentUpdated":{"content":"Worked with @alice","timestamp":"2025-09-05T17:30:00+02:00"}}"#,
+        "\n",
+    );
+
+    #[test]
+    fn test_migrate_replays_events() {
+        let (storage, _temp_dir) = create_test_storage();
+        let source = legacy_vault(&[
+            ("events/20250905.jsonl", EVENTS),
+            ("entries/20250906.md", "Only markdown"),
+        ]);
+
+        let report = storage.migrate_legacy(source.path()).unwrap();
+
+        let id: EntryId = "20250905".parse().unwrap();
+        assert_eq!(
+            report.entries,
+            vec![
+                (id, LegacyOutcome::Replayed),
+                ("20250906".parse().unwrap(), LegacyOutcome::Imported)
+            ]
+        );
+        assert_eq!(report.unreadable_events, 1);
+        assert_eq!(
+            storage.load_entry(&id).unwrap().content,
+            "Worked with @alice"
+        );
+        let history = storage.events(&id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].kind, EventKind::Created);
+        assert_eq!(history[1].at.to_rfc3339(), "2025-09-05T17:30:00+02:00");
+
+        let again = storage.migrate_legacy(source.path()).unwrap();
+        assert!(
+            again
+                .entries
+                .iter()
+                .all(|(_, outcome)| *outcome == LegacyOutcome::Skipped)
+        );
+    }
+
+    #[test]
+    fn test_migrate_keeps_markdown_edited_after_events() {
+        let (storage, _temp_dir) = create_test_storage();
+        let source = legacy_vault(&[
+            ("events/20250905.jsonl", EVENTS),
+            ("entries/20250905.md", "Worked with @alice and @bob"),
+        ]);
+
+        let report = storage.migrate_legacy(source.path()).unwrap();
+        let id: EntryId = "20250905".parse().unwrap();
+        assert_eq!(report.entries, vec![(id, LegacyOutcome::MarkdownKept)]);
+        assert_eq!(
+            storage.load_entry(&id).unwrap().content,
+            "Worked with @alice and @bob"
+        );
+        assert_eq!(storage.events(&id).unwrap().len(), 3);
+
+        // A markdown file older than the last event missed that write
+        let markdown = fs::File::options()
+            .write(true)
+            .open(source.path().join("entries/20250905.md"))
+            .unwrap();
+        markdown
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_757_000_000))
+            .unwrap();
+        let (storage, _temp_dir) = create_test_storage();
+        let report = storage.migrate_legacy(source.path()).unwrap();
+        assert_eq!(report.entries, vec![(id, LegacyOutcome::Replayed)]);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{cell::RefCell, collections::BTreeMap};
+
+use chrono::Local;
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::storage::events::{Event, EventKind};
+use crate::storage::repository::EntryRepository;
+
+/// Entries and their event logs kept in memory only, for tests and generated demo
+/// vaults. Nothing is read from or written to disk, everything is gone when it's
+/// dropped.
+#[derive(Debug, Default)]
+pub struct MemoryRepository {
+    entries: RefCell<BTreeMap<EntryId, Entry>>,
+    events: RefCell<BTreeMap<EntryId, Vec<Event>>>,
+}
+
+impl MemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+}
+
+impl EntryRepository for MemoryRepository {
+    fn list_entries(&self) -> Result<Vec<EntryId>> {
+        Ok(self.entries.borrow().keys().rev().copied().collect())
+    }
+
+    fn load_entry(&self, id: &EntryId) -> Result<Entry> {
+        match self.entries.borrow().get(id) {
+            Some(entry) => Ok(entry.clone()),
+            None => bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!("Entry '{}' not found", id)
+            )),
+        }
+    }
+
+    fn save_entry(&self, entry: &Entry) -> Result<()> {
+        let previous = self.entries.borrow_mut().insert(entry.id, entry.clone());
+        let kind = match previous {
+            Some(_) => EventKind::Updated,
+            None => EventKind::Created,
+        };
+        self.events
+            .borrow_mut()
+            .entry(entry.id)
+            .or_default()
+            .push(Event {
+                at: Local::now().fixed_offset(),
+                kind,
+                content: Some(entry.content.clone()),
+                detail: None,
+            });
+        Ok(())
+    }
+
+    fn events(&self, id: &EntryId) -> Result<Vec<Event>> {
+        Ok(self.events.borrow().get(id).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_and_events_stay_in_memory() {
+        let repository = MemoryRepository::new();
+        let id: EntryId = "20250920".parse().unwrap();
+        assert!(repository.load_entry(&id).is_err());
+
+        let mut entry = Entry::new(id, "First draft".to_string());
+        repository.save_entry(&entry).unwrap();
+        entry.update_content("Second draft".to_string());
+        repository.save_entry(&entry).unwrap();
+        repository
+            .save_entry(&Entry::new("20250921".parse().unwrap(), String::new()))
+            .unwrap();
+
+        assert_eq!(
+            repository.list_entries().unwrap(),
+            vec!["20250921", "20250920"]
+        );
+        assert_eq!(repository.load_entry(&id).unwrap().content, "Second draft");
+        let kinds: Vec<EventKind> = repository
+            .events(&id)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(kinds, vec![EventKind::Created, EventKind::Updated]);
+    }
+}
@@ -4,10 +4,34 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub mod attachment;
+pub mod backup;
+pub mod digest;
 pub mod entry;
+pub mod events;
+pub mod focus;
+pub mod ignore_rules;
+pub mod index;
+pub mod legacy;
+pub mod memory;
+pub mod note;
 mod platform;
+pub mod projects;
+pub mod repository;
+pub mod scratch;
+pub mod section;
+pub mod trash;
+pub mod versions;
+pub mod writer;
 
+use crate::utils::text::LineEnding;
 use platform::{get_xdg_directory, XdgDirectoryType};
+use writer::Writer;
+
+/// Environment variable overriding where entries are stored, like `--data-dir`
+pub const DATA_DIR_ENV: &str = "DEVLOG_DATA_DIR";
+/// Environment variable overriding the directory of `config.toml`
+pub const CONFIG_DIR_ENV: &str = "DEVLOG_CONFIG_DIR";
 
 #[derive(Clone)]
 pub struct Storage {
@@ -19,6 +43,14 @@ pub struct Storage {
     cache_path: PathBuf,
     /// Path for state files (logs, history)
     state_path: PathBuf,
+    /// Performs all filesystem mutations, or only reports them in dry-run mode
+    writer: Writer,
+    /// Line endings of saved entries
+    line_ending: LineEnding,
+    /// Earlier versions kept of each entry when it's saved, 0 keeps none
+    keep_versions: usize,
+    /// Events an entry's log may hold before it's compacted, 0 never compacts
+    snapshot_events: usize,
 }
 
 impl Storage {
@@ -34,11 +66,15 @@ impl Storage {
             data_path,
             cache_path,
             state_path,
+            writer: Writer::default(),
+            line_ending: LineEnding::default(),
+            keep_versions: 0,
+            snapshot_events: 0,
         })
     }
 
-    /// Create a new Storage instance for testing with custom base directory
-    #[cfg(test)]
+    /// Create a new Storage instance with a custom base directory,
+    /// used by tests and for generated demo/benchmark vaults
     pub fn new_with_base_dir(base_dir: &Path) -> Result<Self> {
         let config_path = base_dir.join("config");
         let data_path = base_dir.join("data");
@@ -56,9 +92,73 @@ impl Storage {
             data_path,
             cache_path,
             state_path,
+            writer: Writer::default(),
+            line_ending: LineEnding::default(),
+            keep_versions: 0,
+            snapshot_events: 0,
         })
     }
 
+    /// Use the journal of a profile: its entries in `data_path`, and a cache and state
+    /// of its own under `profiles/<name>` so indexes and sync history stay apart
+    pub fn with_profile(mut self, name: &str, data_path: &Path) -> Result<Self> {
+        self.data_path = data_path.to_path_buf();
+        self.cache_path = self.cache_path.join("profiles").join(name);
+        self.state_path = self.state_path.join("profiles").join(name);
+
+        // Like any other write, a dry run doesn't create them
+        for dir in [&self.data_path, &self.cache_path, &self.state_path] {
+            self.writer.create_dir_all(dir)?;
+        }
+        Ok(self)
+    }
+
+    /// Store entries in `data_path` instead of the XDG data directory. Cache and state
+    /// stay in theirs, under a key of the path so indexes of different journals don't mix
+    pub fn with_data_dir(mut self, data_path: &Path) -> Result<Self> {
+        let key = path_key(data_path);
+        self.data_path = data_path.to_path_buf();
+        self.cache_path = self.cache_path.join("data-dirs").join(&key);
+        self.state_path = self.state_path.join("data-dirs").join(&key);
+
+        for dir in [&self.data_path, &self.cache_path, &self.state_path] {
+            self.writer.create_dir_all(dir)?;
+        }
+        Ok(self)
+    }
+
+    /// Read and write `config.toml` in `config_path` instead of the XDG config directory
+    pub fn with_config_dir(mut self, config_path: &Path) -> Result<Self> {
+        self.writer.create_dir_all(config_path)?;
+        self.config_path = config_path.to_path_buf();
+        Ok(self)
+    }
+
+    /// Save entries with the given line endings
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Keep the last `versions` versions of an entry file whenever it's overwritten
+    pub fn with_kept_versions(mut self, versions: usize) -> Self {
+        self.keep_versions = versions;
+        self
+    }
+
+    /// Move the events of a log to its gzipped archive once there are more than `events`,
+    /// leaving a snapshot of the entry in their place
+    pub fn with_event_snapshots(mut self, events: usize) -> Self {
+        self.snapshot_events = events;
+        self
+    }
+
+    /// Only report intended writes and deletes instead of performing them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.writer = Writer::new(dry_run);
+        self
+    }
+
     /// Get XDG config directory with platform-specific fallbacks
     fn get_config_dir() -> Result<PathBuf> {
         get_xdg_directory(XdgDirectoryType::Config, "devlog", dirs::config_dir)
@@ -98,6 +198,23 @@ impl Storage {
     pub fn state_path(&self) -> &Path {
         &self.state_path
     }
+
+    /// Get the writer every filesystem mutation should go through
+    pub fn writer(&self) -> &Writer {
+        &self.writer
+    }
+}
+
+/// FNV-1a of the absolute path, stable across Rust versions unlike `DefaultHasher`
+fn path_key(path: &Path) -> String {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let hash = path
+        .to_string_lossy()
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
 }
 
 #[cfg(test)]
@@ -130,13 +247,53 @@ mod tests {
         assert_eq!(storage.state_path(), temp_dir.path().join("state"));
     }
 
+    #[test]
+    fn test_profile_has_its_own_directories() {
+        let (storage, temp_dir) = create_test_storage();
+        let journal = temp_dir.path().join("work");
+        let profile = storage
+            .clone()
+            .with_profile("work", &journal)
+            .expect("Failed to switch profile");
+
+        assert_eq!(profile.data_path(), journal);
+        assert_eq!(profile.config_path(), storage.config_path());
+        assert_eq!(
+            profile.state_path(),
+            temp_dir.path().join("state/profiles/work")
+        );
+        assert!(profile.cache_path().exists());
+    }
+
+    #[test]
+    fn test_data_dir_override() {
+        let (storage, temp_dir) = create_test_storage();
+        let journal = temp_dir.path().join("journal");
+        let overridden = storage
+            .clone()
+            .with_data_dir(&journal)
+            .expect("Failed to override data dir")
+            .with_config_dir(&temp_dir.path().join("dotfiles"))
+            .expect("Failed to override config dir");
+
+        assert_eq!(overridden.data_path(), journal);
+        assert_eq!(overridden.config_path(), temp_dir.path().join("dotfiles"));
+        assert!(overridden.cache_path().starts_with(storage.cache_path()));
+        assert_ne!(overridden.cache_path(), storage.cache_path());
+
+        let other = storage
+            .with_data_dir(&temp_dir.path().join("other"))
+            .expect("Failed to override data dir");
+        assert_ne!(other.state_path(), overridden.state_path());
+    }
+
     #[test]
     fn test_entry_operations() {
         use crate::models::entry::Entry;
         let (storage, temp_dir) = create_test_storage();
 
         // Test that entry operations create the entries directory
-        let entry = Entry::new("20250920".to_string(), "Test content".to_string());
+        let entry = Entry::new("20250920".parse().unwrap(), "Test content".to_string());
         storage.save_entry(&entry).expect("Failed to save entry");
 
         // Verify that entries directory is created within data directory
@@ -150,9 +307,49 @@ mod tests {
 
         // Test loading the entry
         let loaded_entry = storage
-            .load_entry("20250920")
+            .load_entry(&"20250920".parse().unwrap())
             .expect("Failed to load entry");
         assert_eq!(loaded_entry.id, "20250920");
         assert_eq!(loaded_entry.content, "Test content");
     }
+
+    #[test]
+    fn test_dry_run_does_not_save_entries() {
+        use crate::models::entry::Entry;
+        let (storage, temp_dir) = create_test_storage();
+        let storage = storage.with_dry_run(true);
+
+        let entry = Entry::new("20250920".parse().unwrap(), "Test content".to_string());
+        storage
+            .save_entry(&entry)
+            .expect("Dry-run save should succeed");
+
+        assert!(!temp_dir
+            .path()
+            .join("data")
+            .join("entries")
+            .join("20250920.md")
+            .exists());
+        assert!(storage.load_entry(&"20250920".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_does_not_create_overridden_directories() {
+        let (storage, temp_dir) = create_test_storage();
+        let journal = temp_dir.path().join("journal");
+        let dotfiles = temp_dir.path().join("dotfiles");
+        let overridden = storage
+            .with_dry_run(true)
+            .with_profile("work", &journal)
+            .expect("Failed to use profile")
+            .with_data_dir(&journal)
+            .expect("Failed to override data dir")
+            .with_config_dir(&dotfiles)
+            .expect("Failed to override config dir");
+
+        assert!(!journal.exists());
+        assert!(!dotfiles.exists());
+        assert!(!overridden.cache_path().exists());
+        assert!(!overridden.state_path().exists());
+    }
 }
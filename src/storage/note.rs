@@ -0,0 +1,165 @@
+use std::{fs, io::ErrorKind as IoErrorKind, path::PathBuf};
+
+use chrono::Utc;
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::frontmatter::Frontmatter;
+use crate::models::note::{Note, NoteId};
+use crate::storage::Storage;
+use crate::storage::entry::{parse_document, read_entry_file, to_document};
+
+impl Storage {
+    /// Get the directory notes are kept in, next to the entries
+    pub fn notes_path(&self) -> PathBuf {
+        self.data_path.join("notes")
+    }
+
+    pub fn note_path(&self, id: &NoteId) -> PathBuf {
+        self.notes_path().join(format!("{}.md", id))
+    }
+
+    /// Save a note to disk. Notes have no event log and aren't indexed, they
+    /// don't count towards streaks or stats of the days they were written on.
+    pub fn save_note(&self, note: &Note) -> Result<()> {
+        let path = self.note_path(&note.id);
+        self.writer.create_dir_all(&self.notes_path())?;
+        let frontmatter = Frontmatter {
+            extra: note.extra.clone(),
+            ..Frontmatter::of(
+                note.id.as_str(),
+                note.created_at,
+                note.updated_at,
+                &note.content,
+            )
+        };
+        let content = self
+            .line_ending
+            .apply(&to_document(&frontmatter, &note.content)?);
+        self.writer
+            .write(&path, content)
+            .wrap_err_with(|| format!("Failed to save note to {}", path.display()))
+    }
+
+    pub fn load_note(&self, id: &NoteId) -> Result<Note> {
+        let path = self.note_path(id);
+        if !path.exists() {
+            bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!("Note '{}' not found", id)
+            ));
+        }
+        let (frontmatter, content) = parse_document(id.as_str(), &read_entry_file(&path)?)?;
+        let now = Utc::now();
+        Ok(Note {
+            id: id.clone(),
+            created_at: frontmatter.created_at.unwrap_or(now),
+            updated_at: frontmatter.updated_at.unwrap_or(now),
+            content,
+            extra: frontmatter.extra,
+        })
+    }
+
+    /// Append text to a note as a new paragraph, creating the note if needed
+    pub fn append_to_note(&self, id: &NoteId, text: &str) -> Result<()> {
+        let note = if self.note_path(id).exists() {
+            let mut note = self.load_note(id)?;
+            let content = format!("{}\n\n{}", note.content.trim_end(), text);
+            note.update_content(content.trim_start().to_string());
+            note
+        } else {
+            Note::new(id.clone(), text.to_string())
+        };
+        self.save_note(&note)
+    }
+
+    /// All notes by name. Files whose name isn't a valid note name, and files
+    /// matched by `.devlogignore`, are skipped.
+    pub fn list_notes(&self) -> Result<Vec<NoteId>> {
+        let dir = self.notes_path();
+        let ignore_rules = self.ignore_rules()?;
+        let files = match fs::read_dir(&dir) {
+            Ok(files) => files,
+            Err(e) if e.kind() == IoErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", dir.display())),
+        };
+
+        let mut notes: Vec<NoteId> = files
+            .filter_map(|file| file.ok())
+            .filter(|file| !ignore_rules.is_ignored(&file.path(), false))
+            .filter_map(|file| {
+                let name = file.file_name().into_string().ok()?;
+                name.strip_suffix(".md")?.parse().ok()
+            })
+            .collect();
+        notes.sort();
+        Ok(notes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_notes_are_kept_apart_from_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        let runbook: NoteId = "oncall-runbook".parse().unwrap();
+        storage
+            .save_note(&Note::new(runbook.clone(), "# Runbook".to_string()))
+            .unwrap();
+        storage
+            .append_to_note(&"interview-prep".parse().unwrap(), "Questions")
+            .unwrap();
+        storage
+            .append_to_note(&runbook, "Restart the pods")
+            .unwrap();
+        fs::write(storage.notes_path().join("Not A Note.md"), "").unwrap();
+
+        let names: Vec<String> = storage
+            .list_notes()
+            .unwrap()
+            .iter()
+            .map(NoteId::to_string)
+            .collect();
+        assert_eq!(names, vec!["interview-prep", "oncall-runbook"]);
+        assert_eq!(
+            storage.load_note(&runbook).unwrap().content,
+            "# Runbook\n\nRestart the pods"
+        );
+        assert!(storage.list_entries().unwrap().is_empty());
+        assert!(storage.load_note(&"missing".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_list_notes_respects_devlogignore() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage
+            .append_to_note(&"draft-ideas".parse().unwrap(), "Half-baked")
+            .unwrap();
+        storage
+            .append_to_note(&"oncall-runbook".parse().unwrap(), "Restart the pods")
+            .unwrap();
+        fs::write(
+            storage.data_path.join(".devlogignore"),
+            "notes/draft-*.md\n",
+        )
+        .unwrap();
+
+        let names: Vec<String> = storage
+            .list_notes()
+            .unwrap()
+            .iter()
+            .map(NoteId::to_string)
+            .collect();
+        assert_eq!(names, vec!["oncall-runbook"]);
+    }
+}
@@ -162,66 +162,107 @@ mod tests {
     fn test_windows_directory_paths() {
         // Test get_xdg_directory with devlog app name on Windows
         let test_cases = [
-            (XdgDirectoryType::Config, "AppData\\Roaming\\devlog", "Windows config directory"),
-            (XdgDirectoryType::Data, "AppData\\Roaming\\devlog", "Windows data directory"),
-            (XdgDirectoryType::Cache, "AppData\\Local\\devlog", "Windows cache directory"),
-            (XdgDirectoryType::State, "AppData\\Roaming\\devlog", "Windows state directory"),
+            (
+                XdgDirectoryType::Config,
+                "AppData\\Roaming\\devlog",
+                "Windows config directory",
+            ),
+            (
+                XdgDirectoryType::Data,
+                "AppData\\Roaming\\devlog",
+                "Windows data directory",
+            ),
+            (
+                XdgDirectoryType::Cache,
+                "AppData\\Local\\devlog",
+                "Windows cache directory",
+            ),
+            (
+                XdgDirectoryType::State,
+                "AppData\\Roaming\\devlog",
+                "Windows state directory",
+            ),
         ];
 
         for (dir_type, expected_suffix, description) in test_cases {
             // Test fallback behavior by passing None as dirs_fn
             let actual_path = get_xdg_directory(dir_type, "devlog", || None)
-                .expect(&format!("Should get {} path", description));
-            
+                .unwrap_or_else(|_| panic!("Should get {} path", description));
+
             let path_str = actual_path.to_string_lossy();
-            
+
             assert!(
                 path_str.ends_with(expected_suffix),
                 "{} should end with '{}', got: '{}'",
-                description, expected_suffix, path_str
+                description,
+                expected_suffix,
+                path_str
             );
-            
+
             assert!(
                 actual_path.exists(),
                 "{} should be created and exist",
                 description
             );
-            
+
             // Clean up test directory
             let _ = std::fs::remove_dir_all(&actual_path);
         }
     }
 
     #[test]
-    #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd"))]
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    ))]
     fn test_unix_directory_paths() {
         // Test get_xdg_directory with devlog app name on Unix
         let test_cases = [
-            (XdgDirectoryType::Config, ".config/devlog", "Unix config directory"),
-            (XdgDirectoryType::Data, ".local/share/devlog", "Unix data directory"),
-            (XdgDirectoryType::Cache, ".cache/devlog", "Unix cache directory"),
-            (XdgDirectoryType::State, ".local/state/devlog", "Unix state directory"),
+            (
+                XdgDirectoryType::Config,
+                ".config/devlog",
+                "Unix config directory",
+            ),
+            (
+                XdgDirectoryType::Data,
+                ".local/share/devlog",
+                "Unix data directory",
+            ),
+            (
+                XdgDirectoryType::Cache,
+                ".cache/devlog",
+                "Unix cache directory",
+            ),
+            (
+                XdgDirectoryType::State,
+                ".local/state/devlog",
+                "Unix state directory",
+            ),
         ];
 
         for (dir_type, expected_suffix, description) in test_cases {
             // Test fallback behavior by passing None as dirs_fn
             let actual_path = get_xdg_directory(dir_type, "devlog", || None)
-                .expect(&format!("Should get {} path", description));
-            
+                .unwrap_or_else(|_| panic!("Should get {} path", description));
+
             let path_str = actual_path.to_string_lossy();
-            
+
             assert!(
                 path_str.ends_with(expected_suffix),
                 "{} should end with '{}', got: '{}'",
-                description, expected_suffix, path_str
+                description,
+                expected_suffix,
+                path_str
             );
-            
+
             assert!(
                 actual_path.exists(),
                 "{} should be created and exist",
                 description
             );
-            
+
             // Clean up test directory
             let _ = std::fs::remove_dir_all(&actual_path);
         }
@@ -232,32 +273,50 @@ mod tests {
     fn test_macos_directory_paths() {
         // Test get_xdg_directory with devlog app name on macOS
         let test_cases = [
-            (XdgDirectoryType::Config, "Library/Application Support/devlog", "macOS config directory"),
-            (XdgDirectoryType::Data, "Library/Application Support/devlog", "macOS data directory"),
-            (XdgDirectoryType::Cache, "Library/Caches/devlog", "macOS cache directory"),
-            (XdgDirectoryType::State, "Library/Application Support/devlog", "macOS state directory"),
+            (
+                XdgDirectoryType::Config,
+                "Library/Application Support/devlog",
+                "macOS config directory",
+            ),
+            (
+                XdgDirectoryType::Data,
+                "Library/Application Support/devlog",
+                "macOS data directory",
+            ),
+            (
+                XdgDirectoryType::Cache,
+                "Library/Caches/devlog",
+                "macOS cache directory",
+            ),
+            (
+                XdgDirectoryType::State,
+                "Library/Application Support/devlog",
+                "macOS state directory",
+            ),
         ];
 
         for (dir_type, expected_suffix, description) in test_cases {
             // Test fallback behavior by passing None as dirs_fn
             let actual_path = get_xdg_directory(dir_type, "devlog", || None)
-                .expect(&format!("Should get {} path", description));
-            
+                .unwrap_or_else(|_| panic!("Should get {} path", description));
+
             let path_str = actual_path.to_string_lossy();
-            
+
             assert!(
                 path_str.ends_with(expected_suffix),
                 "{} should end with '{}', got: '{}'",
-                description, expected_suffix, path_str
+                description,
+                expected_suffix,
+                path_str
             );
-            
+
             assert!(
                 actual_path.exists(),
                 "{} should be created and exist",
                 description
             );
-            
-            // Clean up test directory  
+
+            // Clean up test directory
             let _ = std::fs::remove_dir_all(&actual_path);
         }
     }
@@ -0,0 +1,288 @@
+use std::{collections::BTreeSet, fs, io::ErrorKind as IoErrorKind, path::PathBuf};
+
+use chrono::NaiveDate;
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::annotation::AnnotationKind;
+use crate::models::entry_id::EntryId;
+use crate::models::project::{ProjectProfile, Retrospective};
+use crate::storage::Storage;
+use crate::utils::text::normalize_line_endings;
+
+impl Storage {
+    /// Get the directory holding one `<name>.md` profile per `::project`
+    pub fn projects_path(&self) -> PathBuf {
+        self.data_path.join("projects")
+    }
+
+    pub(super) fn project_path(&self, name: &str) -> PathBuf {
+        self.projects_path().join(format!("{}.md", name))
+    }
+
+    /// Get the directory holding generated summaries, like those of closed projects
+    pub fn summaries_path(&self) -> PathBuf {
+        self.data_path.join("summaries")
+    }
+
+    /// Load the profile of `name`, none if the project was never mentioned
+    pub fn load_project(&self, name: &str) -> Result<Option<ProjectProfile>> {
+        let path = self.project_path(name);
+        match fs::read_to_string(&path) {
+            Ok(content) => ProjectProfile::parse(&normalize_line_endings(&content))
+                .wrap_err_with(|| format!("Failed to read {}", path.display()))
+                .map(Some),
+            Err(e) if e.kind() == IoErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save_project(&self, profile: &ProjectProfile) -> Result<()> {
+        self.writer.create_dir_all(&self.projects_path())?;
+        self.writer
+            .write_atomic(&self.project_path(&profile.name), profile.to_markdown()?)
+    }
+
+    /// Load every profile, sorted by name
+    pub fn list_projects(&self) -> Result<Vec<ProjectProfile>> {
+        let dir = match fs::read_dir(self.projects_path()) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == IoErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).wrap_err("Failed to read the projects directory"),
+        };
+        let mut names: Vec<String> = dir
+            .filter_map(|file| file.ok())
+            .filter_map(|file| {
+                file.file_name()
+                    .to_str()
+                    .and_then(|name| name.strip_suffix(".md"))
+                    .map(str::to_string)
+            })
+            .collect();
+        names.sort();
+
+        let mut profiles = Vec::new();
+        for name in names {
+            profiles.extend(self.load_project(&name)?);
+        }
+        Ok(profiles)
+    }
+
+    /// Names of the projects closed with `close_project`
+    pub fn closed_projects(&self) -> Result<BTreeSet<String>> {
+        Ok(self
+            .list_projects()?
+            .into_iter()
+            .filter(|profile| profile.closed.is_some())
+            .map(|profile| profile.name)
+            .collect())
+    }
+
+    /// Append the retrospective to the ledger of `name` and mark the project closed
+    pub fn close_project(
+        &self,
+        name: &str,
+        day: NaiveDate,
+        retrospective: &Retrospective,
+    ) -> Result<ProjectProfile> {
+        let mut profile = self.existing_project(name)?;
+        if let Some(closed) = profile.closed {
+            bail!(DevlogError::new(
+                ErrorKind::Conflict,
+                format!("Project '::{}' was already closed on {}", name, closed)
+            ));
+        }
+        profile.close(day, retrospective);
+        self.save_project(&profile)?;
+        Ok(profile)
+    }
+
+    /// Open a closed project again, its retrospective stays in the ledger
+    pub fn reopen_project(&self, name: &str) -> Result<ProjectProfile> {
+        let mut profile = self.existing_project(name)?;
+        if profile.closed.take().is_none() {
+            bail!(DevlogError::new(
+                ErrorKind::Conflict,
+                format!("Project '::{}' isn't closed", name)
+            ));
+        }
+        self.save_project(&profile)?;
+        Ok(profile)
+    }
+
+    fn existing_project(&self, name: &str) -> Result<ProjectProfile> {
+        match self.load_project(name)? {
+            Some(profile) => Ok(profile),
+            None => bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!("Project '::{}' isn't mentioned in any entry", name)
+            )),
+        }
+    }
+
+    /// Point the profiles of the projects entry `id` mentions at it, and drop it from
+    /// the ones it no longer mentions. Profiles are created on first mention.
+    pub(crate) fn update_project_references(
+        &self,
+        id: &EntryId,
+        before: &[String],
+        after: &[String],
+    ) -> Result<()> {
+        let before: BTreeSet<&String> = before.iter().collect();
+        let after: BTreeSet<&String> = after.iter().collect();
+
+        for name in before.union(&after) {
+            let mentioned = after.contains(name);
+            let mut profile = match self.load_project(name)? {
+                Some(profile) => profile,
+                None if mentioned => ProjectProfile::new(name),
+                None => continue,
+            };
+            let changed = if mentioned {
+                profile.references.insert(*id)
+            } else {
+                profile.references.remove(id)
+            };
+            if changed {
+                self.save_project(&profile)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Recompute the references of every profile from the entry index, creating
+    /// profiles for projects mentioned before they existed. Returns the number of profiles.
+    pub fn rebuild_projects(&self) -> Result<usize> {
+        let mut profiles: Vec<ProjectProfile> = self.list_projects()?;
+        for profile in &mut profiles {
+            profile.references.clear();
+        }
+        for usage in self.annotation_usage(AnnotationKind::Project)? {
+            match profiles.iter_mut().find(|p| p.name == usage.value) {
+                Some(profile) => profile.references.extend(usage.entries),
+                None => {
+                    let mut profile = ProjectProfile::new(&usage.value);
+                    profile.references.extend(usage.entries);
+                    profiles.push(profile);
+                }
+            }
+        }
+        for profile in &profiles {
+            self.save_project(profile)?;
+        }
+        Ok(profiles.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn ids(profile: &ProjectProfile) -> Vec<String> {
+        profile.references.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_save_entry_updates_references() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250915".parse().unwrap();
+        storage
+            .save_entry(&Entry::new(id, "Work on ::search and ::devlog".to_string()))
+            .unwrap();
+        storage
+            .save_entry(&Entry::new(
+                "20250916".parse().unwrap(),
+                "::search".to_string(),
+            ))
+            .unwrap();
+
+        let search = storage.load_project("search").unwrap().unwrap();
+        assert_eq!(ids(&search), vec!["20250915", "20250916"]);
+
+        // Dropping a mention removes the reference but keeps the profile
+        storage
+            .save_entry(&Entry::new(id, "Only ::search".to_string()))
+            .unwrap();
+        let devlog = storage.load_project("devlog").unwrap().unwrap();
+        assert!(devlog.references.is_empty());
+        assert_eq!(storage.list_projects().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_rebuild_keeps_hand_written_text() {
+        let (storage, _temp_dir) = create_test_storage();
+        let mut profile = ProjectProfile::new("search");
+        profile.body = "## Summary\n\nSearch.\n".to_string();
+        profile.references.insert("20200101".parse().unwrap());
+        storage.save_project(&profile).unwrap();
+        storage
+            .save_entry(&Entry::new(
+                "20250915".parse().unwrap(),
+                "::search".to_string(),
+            ))
+            .unwrap();
+
+        assert_eq!(storage.rebuild_projects().unwrap(), 1);
+        let search = storage.load_project("search").unwrap().unwrap();
+        assert_eq!(ids(&search), vec!["20250915"]);
+        assert_eq!(search.summary(), "Search.");
+    }
+
+    #[test]
+    fn test_close_and_reopen_project() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage
+            .save_entry(&Entry::new(
+                "20250915".parse().unwrap(),
+                "::search and ::devlog".to_string(),
+            ))
+            .unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 9, 30).unwrap();
+        let retrospective = Retrospective {
+            outcome: "Shipped".to_string(),
+            ..Retrospective::default()
+        };
+
+        storage
+            .close_project("search", day, &retrospective)
+            .unwrap();
+        assert_eq!(
+            storage.closed_projects().unwrap(),
+            BTreeSet::from(["search".to_string()])
+        );
+        assert!(
+            storage
+                .close_project("search", day, &retrospective)
+                .is_err()
+        );
+        assert!(
+            storage
+                .close_project("nothing", day, &retrospective)
+                .is_err()
+        );
+
+        // Saving an entry keeps the project closed
+        storage
+            .save_entry(&Entry::new(
+                "20250916".parse().unwrap(),
+                "::search".to_string(),
+            ))
+            .unwrap();
+        let search = storage.load_project("search").unwrap().unwrap();
+        assert_eq!(search.closed, Some(day));
+        assert!(search.retrospective().contains("Shipped"));
+
+        storage.reopen_project("search").unwrap();
+        assert!(storage.closed_projects().unwrap().is_empty());
+        assert!(storage.reopen_project("search").is_err());
+    }
+}
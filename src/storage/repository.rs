@@ -0,0 +1,151 @@
+use chrono::{DateTime, FixedOffset};
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::annotation::Annotations;
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+use crate::storage::events::Event;
+use crate::storage::index::{EntryIndex, IndexedEntry};
+
+/// Where entries and their history are kept. There is one entry model, the Markdown
+/// file is the current state and the event log its history, and annotations are always
+/// derived from the content rather than stored next to it.
+pub trait EntryRepository {
+    /// Ids of every entry, newest first
+    fn list_entries(&self) -> Result<Vec<EntryId>>;
+
+    fn load_entry(&self, id: &EntryId) -> Result<Entry>;
+
+    /// Save an entry, recording a `Created` or `Updated` event
+    fn save_entry(&self, entry: &Entry) -> Result<()>;
+
+    /// The whole history of an entry, oldest first
+    fn events(&self, id: &EntryId) -> Result<Vec<Event>>;
+
+    /// The `+tags`, `@people` and `::projects` an entry mentions
+    fn annotations(&self, id: &EntryId) -> Result<Annotations> {
+        Ok(Annotations::parse(&self.load_entry(id)?.content))
+    }
+
+    /// The metadata of every entry, with the annotations derived from its content, as
+    /// the TUI and `list` and `show` read it
+    fn index(&self) -> Result<EntryIndex> {
+        let mut index = EntryIndex::default();
+        for id in self.list_entries()? {
+            // An unreadable entry shouldn't break everything else using the index
+            if let Ok(entry) = self.load_entry(&id) {
+                index.entries.insert(id, IndexedEntry::of(&entry, None));
+            }
+        }
+        Ok(index)
+    }
+
+    /// Entry `id` as it was at `at`, replayed from its events
+    fn entry_at(&self, id: &EntryId, at: DateTime<FixedOffset>) -> Result<Entry> {
+        let entry = self.load_entry(id)?;
+        match entry.state_at(&self.events(id)?, at) {
+            Some(entry) => Ok(entry),
+            None => bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!(
+                    "Entry '{}' has no recorded content as of {}",
+                    id,
+                    at.format("%Y-%m-%d %H:%M")
+                )
+            )),
+        }
+    }
+}
+
+/// The vault on disk: `entries/` with one Markdown file per day and `events/` with
+/// one JSON Lines log per entry, its index cached in `cache_path/index.json`
+impl EntryRepository for Storage {
+    fn list_entries(&self) -> Result<Vec<EntryId>> {
+        Storage::list_entries(self)
+    }
+
+    fn load_entry(&self, id: &EntryId) -> Result<Entry> {
+        Storage::load_entry(self, id)
+    }
+
+    fn save_entry(&self, entry: &Entry) -> Result<()> {
+        Storage::save_entry(self, entry)
+    }
+
+    fn events(&self, id: &EntryId) -> Result<Vec<Event>> {
+        Storage::deep_events(self, id)
+    }
+
+    fn index(&self) -> Result<EntryIndex> {
+        Storage::index(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::memory::MemoryRepository;
+    use tempfile::TempDir;
+
+    /// Goes through the trait only, like code that doesn't know the backend
+    fn mention_count(repository: &impl EntryRepository) -> usize {
+        let ids = repository.list_entries().unwrap();
+        ids.iter()
+            .map(|id| repository.annotations(id).unwrap().projects.len())
+            .sum()
+    }
+
+    #[test]
+    fn test_storage_as_repository() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        let repository: &dyn EntryRepository = &storage;
+        let id: EntryId = "20250920".parse().unwrap();
+        repository
+            .save_entry(&Entry::new(
+                id,
+                "Paired on ::search with @alice".to_string(),
+            ))
+            .unwrap();
+
+        assert_eq!(repository.annotations(&id).unwrap().people, vec!["alice"]);
+        assert_eq!(repository.events(&id).unwrap().len(), 1);
+        assert_eq!(mention_count(&storage), 1);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        let memory = MemoryRepository::new();
+        let repositories: [&dyn EntryRepository; 2] = [&storage, &memory];
+        for repository in repositories {
+            for (id, content) in [("20250920", "::search"), ("20250921", "::search ::api")] {
+                let entry = Entry::new(id.parse().unwrap(), content.to_string());
+                repository.save_entry(&entry).unwrap();
+            }
+        }
+
+        assert_eq!(
+            memory.list_entries().unwrap(),
+            storage.list_entries().unwrap()
+        );
+        assert_eq!(mention_count(&memory), mention_count(&storage));
+
+        let annotations = |index: EntryIndex| -> Vec<Annotations> {
+            index
+                .entries
+                .into_values()
+                .map(|entry| entry.annotations)
+                .collect()
+        };
+        assert_eq!(
+            annotations(memory.index().unwrap()),
+            annotations(EntryRepository::index(&storage).unwrap())
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::{collections::BTreeSet, fs, io::ErrorKind, path::PathBuf};
+
+use color_eyre::eyre::{Context, Result};
+
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+use crate::utils::text::normalize_line_endings;
+
+const SCRATCH_FILE_NAME: &str = "scratch.md";
+
+impl Storage {
+    /// Get the scratch pad file, notes that don't belong to a day yet
+    pub fn scratch_path(&self) -> PathBuf {
+        self.state_path.join(SCRATCH_FILE_NAME)
+    }
+
+    /// Load the scratch pad, empty if nothing was written yet
+    pub fn load_scratch(&self) -> Result<String> {
+        let path = self.scratch_path();
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(normalize_line_endings(&content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save_scratch(&self, content: &str) -> Result<()> {
+        self.writer
+            .write_atomic(&self.scratch_path(), content)
+            .wrap_err("Failed to save scratch pad")
+    }
+
+    /// Move scratch pad lines (0-based) to the end of the entry `id`, creating the
+    /// entry if needed. Returns the number of moved lines.
+    ///
+    /// The lines are only removed from the scratch pad once the entry was saved,
+    /// so a failure never loses them.
+    pub fn move_scratch_lines(&self, lines: &BTreeSet<usize>, id: &EntryId) -> Result<usize> {
+        let scratch = self.load_scratch()?;
+        let (moved, kept): (Vec<_>, Vec<_>) = scratch
+            .lines()
+            .enumerate()
+            .partition(|(idx, _)| lines.contains(idx));
+        if moved.is_empty() {
+            return Ok(0);
+        }
+
+        let moved_text = moved
+            .iter()
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.append_to_entry(id, &moved_text)?;
+
+        let kept: Vec<&str> = kept.into_iter().map(|(_, line)| line).collect();
+        self.save_scratch(&kept.join("\n"))?;
+        Ok(moved.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_scratch_roundtrip() {
+        let (storage, _temp_dir) = create_test_storage();
+        assert_eq!(storage.load_scratch().unwrap(), "");
+
+        storage.save_scratch("idea one\nidea two").unwrap();
+        assert_eq!(storage.load_scratch().unwrap(), "idea one\nidea two");
+    }
+
+    #[test]
+    fn test_move_scratch_lines_into_entry() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        storage.save_scratch("keep\nmove a\nmove b").unwrap();
+
+        let moved = storage
+            .move_scratch_lines(&BTreeSet::from([1, 2]), &id)
+            .unwrap();
+        assert_eq!(moved, 2);
+        assert_eq!(storage.load_entry(&id).unwrap().content, "move a\nmove b");
+        assert_eq!(storage.load_scratch().unwrap(), "keep");
+
+        // Moving into an existing entry appends
+        storage
+            .move_scratch_lines(&BTreeSet::from([0]), &id)
+            .unwrap();
+        assert_eq!(
+            storage.load_entry(&id).unwrap().content,
+            "move a\nmove b\n\nkeep"
+        );
+        assert_eq!(storage.load_scratch().unwrap(), "");
+    }
+}
@@ -0,0 +1,178 @@
+use std::{fmt, str::FromStr};
+
+use color_eyre::eyre::{Report, Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::entry_id::EntryId;
+use crate::models::project::ProjectProfile;
+use crate::storage::Storage;
+
+/// Where `move_section` puts a section: another day or a `::project` ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionTarget {
+    Entry(EntryId),
+    Project(String),
+}
+
+impl SectionTarget {
+    /// Parse `::project` or an entry id like `20250921`
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        match s.strip_prefix("::") {
+            Some(name)
+                if !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|ch| ch.is_alphanumeric() || "_-.".contains(ch)) =>
+            {
+                Ok(SectionTarget::Project(name.to_string()))
+            }
+            Some(_) => bail!(DevlogError::new(
+                ErrorKind::Validation,
+                format!("'{}' is not a project, expected ::name", s)
+            )),
+            None => Ok(SectionTarget::Entry(s.parse()?)),
+        }
+    }
+}
+
+impl FromStr for SectionTarget {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for SectionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionTarget::Entry(id) => write!(f, "{}", id),
+            SectionTarget::Project(name) => write!(f, "::{}", name),
+        }
+    }
+}
+
+impl Storage {
+    /// Cut the section under `heading` out of entry `from` and append it to `to`.
+    /// The destination is written first and put back if the source can't be saved,
+    /// so the section is never lost or left in both places. Returns the heading line.
+    pub fn move_section(
+        &self,
+        from: &EntryId,
+        heading: &str,
+        to: &SectionTarget,
+    ) -> Result<String> {
+        if *to == SectionTarget::Entry(*from) {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                "The section is already in that entry"
+            ));
+        }
+        let mut source = self.load_entry(from)?;
+        let Some(section) = source.cut_section(heading) else {
+            bail!(DevlogError::new(
+                ErrorKind::NotFound,
+                format!("No section '{}' in entry {}", heading, from)
+            ));
+        };
+
+        match to {
+            SectionTarget::Entry(id) => {
+                let path = self.entry_path(id)?;
+                let previous = if path.exists() {
+                    Some(self.load_entry(id)?)
+                } else {
+                    None
+                };
+                self.append_to_entry(id, &section)?;
+                if let Err(e) = self.save_entry(&source) {
+                    match previous {
+                        Some(previous) => self.save_entry(&previous)?,
+                        None => self.writer.remove_file(&path)?,
+                    }
+                    return Err(e);
+                }
+            }
+            SectionTarget::Project(name) => {
+                let previous = self.load_project(name)?;
+                let mut profile = previous
+                    .clone()
+                    .unwrap_or_else(|| ProjectProfile::new(name));
+                profile.body = format!("{}\n\n{}\n", profile.body.trim_end(), section);
+                self.save_project(&profile)?;
+                if let Err(e) = self.save_entry(&source) {
+                    match previous {
+                        Some(previous) => self.save_project(&previous)?,
+                        None => self.writer.remove_file(&self.project_path(name))?,
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(section.lines().next().unwrap_or_default().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use crate::storage::events::EventKind;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_move_section_between_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        let from: EntryId = "20250920".parse().unwrap();
+        let to: EntryId = "20250921".parse().unwrap();
+        let content = "## 09:00\n\nStandup\n\n## 14:32 Review\n\nShip it";
+        storage
+            .save_entry(&Entry::new(from, content.to_string()))
+            .unwrap();
+
+        let heading = storage
+            .move_section(&from, "14:32", &SectionTarget::Entry(to))
+            .unwrap();
+
+        assert_eq!(heading, "## 14:32 Review");
+        assert_eq!(
+            storage.load_entry(&from).unwrap().content,
+            "## 09:00\n\nStandup"
+        );
+        assert_eq!(
+            storage.load_entry(&to).unwrap().content,
+            "## 14:32 Review\n\nShip it"
+        );
+        let kinds =
+            |id| -> Vec<EventKind> { storage.events(id).unwrap().iter().map(|e| e.kind).collect() };
+        assert_eq!(kinds(&from), vec![EventKind::Created, EventKind::Updated]);
+        assert_eq!(kinds(&to), vec![EventKind::Created]);
+    }
+
+    #[test]
+    fn test_move_section_to_project() {
+        let (storage, _temp_dir) = create_test_storage();
+        let from: EntryId = "20250920".parse().unwrap();
+        let content = "Notes\n\n## Decision\n\nUse v2";
+        storage
+            .save_entry(&Entry::new(from, content.to_string()))
+            .unwrap();
+
+        let target: SectionTarget = "::search".parse().unwrap();
+        storage.move_section(&from, "decision", &target).unwrap();
+
+        let profile = storage.load_project("search").unwrap().unwrap();
+        assert!(profile.body.ends_with("## Decision\n\nUse v2\n"));
+        assert_eq!(storage.load_entry(&from).unwrap().content, "Notes");
+        assert!(storage.move_section(&from, "Decision", &target).is_err());
+    }
+}
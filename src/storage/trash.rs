@@ -0,0 +1,233 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDateTime;
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{self, DevlogError};
+use crate::models::annotation::Annotations;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+const STAMP_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// A deleted entry, its files kept in the trash under their paths relative to
+/// the data directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedEntry {
+    pub id: EntryId,
+    pub deleted: NaiveDateTime,
+    pub path: PathBuf,
+}
+
+impl Storage {
+    /// Get the directory deleted entries are moved to
+    pub fn trash_path(&self) -> PathBuf {
+        self.state_path.join("trash")
+    }
+
+    /// Move entry `id` with its event log, archived events and attachments to the trash
+    pub fn trash_entry(&self, id: &EntryId, now: NaiveDateTime) -> Result<TrashedEntry> {
+        let entry = self.load_entry(id)?;
+        let trashed = TrashedEntry {
+            id: *id,
+            deleted: now,
+            path: self
+                .trash_path()
+                .join(format!("{}-{}", id, now.format(STAMP_FORMAT))),
+        };
+
+        for (from, relative) in self.entry_files(id)? {
+            let to = trashed.path.join(relative);
+            if let Some(parent) = to.parent() {
+                self.writer.create_dir_all(parent)?;
+            }
+            self.writer.rename(&from, &to)?;
+        }
+
+        if !self.writer.is_dry_run() {
+            self.update_project_references(id, &Annotations::parse(&entry.content).projects, &[])?;
+            self.index()?;
+        }
+        Ok(trashed)
+    }
+
+    /// Deleted entries, oldest deletion first
+    pub fn list_trash(&self) -> Result<Vec<TrashedEntry>> {
+        let dir = self.trash_path();
+        let items = match fs::read_dir(&dir) {
+            Ok(items) => items,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", dir.display())),
+        };
+
+        let mut trashed: Vec<TrashedEntry> = items
+            .filter_map(|item| item.ok())
+            .filter_map(|item| {
+                let name = item.file_name().into_string().ok()?;
+                let (id, stamp) = name.rsplit_once('-')?;
+                Some(TrashedEntry {
+                    id: id.parse().ok()?,
+                    deleted: NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).ok()?,
+                    path: item.path(),
+                })
+            })
+            .collect();
+        trashed.sort_by_key(|item| (item.deleted, item.id));
+        Ok(trashed)
+    }
+
+    /// Put the most recently deleted copy of entry `id` back. Fails if the entry
+    /// exists again, or was never deleted.
+    pub fn restore_from_trash(&self, id: &EntryId) -> Result<TrashedEntry> {
+        let Some(trashed) = self.list_trash()?.into_iter().rfind(|item| item.id == *id) else {
+            bail!(DevlogError::new(
+                error::ErrorKind::NotFound,
+                format!("Entry '{}' is not in the trash", id)
+            ));
+        };
+        if self.entry_path(id)?.exists() {
+            bail!(DevlogError::new(
+                error::ErrorKind::Validation,
+                format!(
+                    "Entry '{}' exists, delete it before restoring the old one",
+                    id
+                )
+            ));
+        }
+
+        for (from, relative) in trashed_files(&trashed.path)? {
+            let to = self.data_path.join(relative);
+            if let Some(parent) = to.parent() {
+                self.writer.create_dir_all(parent)?;
+            }
+            self.writer.rename(&from, &to)?;
+        }
+        if !self.writer.is_dry_run() {
+            fs::remove_dir_all(&trashed.path)
+                .wrap_err_with(|| format!("Failed to remove {}", trashed.path.display()))?;
+            let entry = self.load_entry(id)?;
+            self.entry_written(&entry)?;
+        }
+        Ok(trashed)
+    }
+
+    /// Existing files of entry `id`, with their paths relative to the data directory
+    fn entry_files(&self, id: &EntryId) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let candidates = [
+            self.entry_path(id)?,
+            self.events_path().join(format!("{}.jsonl", id)),
+            self.event_archive_path(id),
+            self.attachments_path(id),
+        ];
+        Ok(candidates
+            .into_iter()
+            .filter(|path| path.exists())
+            .filter_map(|path| {
+                let relative = path.strip_prefix(&self.data_path).ok()?.to_path_buf();
+                Some((path, relative))
+            })
+            .collect())
+    }
+}
+
+/// Top-level files and directories of a trashed entry, e.g. `entries/20250920.md`
+fn trashed_files(dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files = Vec::new();
+    for kind in fs::read_dir(dir).wrap_err_with(|| format!("Failed to read {}", dir.display()))? {
+        let kind = kind?.path();
+        for item in
+            fs::read_dir(&kind).wrap_err_with(|| format!("Failed to read {}", kind.display()))?
+        {
+            let path = item?.path();
+            let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+            files.push((path, relative));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 9, 20)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_trash_and_restore_entry() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        storage
+            .save_entry(&Entry::new(id, "worked on ::search".to_string()))
+            .unwrap();
+
+        let trashed = storage.trash_entry(&id, at(9)).unwrap();
+        assert!(storage.list_entries().unwrap().is_empty());
+        assert!(!storage.events_path().join("20250920.jsonl").exists());
+        assert!(trashed.path.join("events/20250920.jsonl").exists());
+        assert!(
+            storage
+                .load_project("search")
+                .unwrap()
+                .unwrap()
+                .references
+                .is_empty()
+        );
+        assert_eq!(storage.list_trash().unwrap(), vec![trashed]);
+
+        storage.restore_from_trash(&id).unwrap();
+        assert_eq!(
+            storage.load_entry(&id).unwrap().content,
+            "worked on ::search"
+        );
+        assert_eq!(storage.events(&id).unwrap().len(), 1);
+        assert!(storage.list_trash().unwrap().is_empty());
+        assert!(
+            storage
+                .load_project("search")
+                .unwrap()
+                .unwrap()
+                .references
+                .contains(&id)
+        );
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite() {
+        let (storage, _temp_dir) = create_test_storage();
+        let id: EntryId = "20250920".parse().unwrap();
+        storage
+            .save_entry(&Entry::new(id, "old".to_string()))
+            .unwrap();
+        storage.trash_entry(&id, at(9)).unwrap();
+        storage
+            .save_entry(&Entry::new(id, "new".to_string()))
+            .unwrap();
+
+        assert!(storage.restore_from_trash(&id).is_err());
+        assert!(
+            storage
+                .restore_from_trash(&"20250101".parse().unwrap())
+                .is_err()
+        );
+        assert_eq!(storage.load_entry(&id).unwrap().content, "new");
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Local, NaiveDateTime, Utc};
+use color_eyre::eyre::{Context, Result};
+
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+
+/// Versions of an entry live in `backups/<id>/` of the state directory, next to the
+/// archives when those aren't moved elsewhere
+const VERSIONS_DIR: &str = "backups";
+/// Microseconds keep quick saves in a row apart
+const STAMP_FORMAT: &str = "%Y%m%dT%H%M%S%6f";
+
+/// An earlier version of an entry file, named after the local time it was replaced at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryVersion {
+    pub path: PathBuf,
+    pub saved: NaiveDateTime,
+}
+
+impl Storage {
+    /// Directory the earlier versions of entry `id` are kept in
+    pub fn versions_path(&self, id: &EntryId) -> PathBuf {
+        self.state_path.join(VERSIONS_DIR).join(id.to_string())
+    }
+
+    /// Copy the entry file aside before it's overwritten, keeping the newest
+    /// `keep_versions` copies. Does nothing for new entries, when versions are off, in
+    /// dry-run mode, or when the file is the same as the newest copy.
+    pub(super) fn keep_version(&self, id: &EntryId) -> Result<()> {
+        if self.keep_versions == 0 || self.writer.is_dry_run() {
+            return Ok(());
+        }
+        let current = match fs::read(self.entry_path(id)?) {
+            Ok(current) => current,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read entry {}", id)),
+        };
+
+        let dir = self.versions_path(id);
+        let versions = self.entry_versions(id)?;
+        let unchanged = versions
+            .first()
+            .is_some_and(|newest| fs::read(&newest.path).is_ok_and(|newest| newest == current));
+        if !unchanged {
+            self.writer.create_dir_all(&dir)?;
+            let name = format!("{}.md", Local::now().naive_local().format(STAMP_FORMAT));
+            self.writer.write_atomic(&dir.join(name), current)?;
+        }
+
+        // The copy just written is the newest and isn't in `versions`
+        let keep = if unchanged {
+            self.keep_versions
+        } else {
+            self.keep_versions - 1
+        };
+        for version in versions.iter().skip(keep) {
+            self.writer.remove_file(&version.path)?;
+        }
+        Ok(())
+    }
+
+    /// Earlier versions of entry `id`, newest first
+    pub fn entry_versions(&self, id: &EntryId) -> Result<Vec<EntryVersion>> {
+        list_versions(&self.versions_path(id))
+    }
+
+    /// Put an earlier version of an entry back. The content it replaces is kept as a
+    /// version itself, so restoring can be undone.
+    pub fn restore_version(&self, id: &EntryId, version: &EntryVersion) -> Result<Entry> {
+        let content = fs::read_to_string(&version.path)
+            .wrap_err_with(|| format!("Failed to read {}", version.path.display()))?;
+        let mut entry = self.deserialize_entry(id, &content)?;
+        entry.updated_at = Utc::now();
+        self.save_entry(&entry)?;
+        Ok(entry)
+    }
+}
+
+fn list_versions(dir: &Path) -> Result<Vec<EntryVersion>> {
+    let files = match fs::read_dir(dir) {
+        Ok(files) => files,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", dir.display())),
+    };
+
+    let mut versions: Vec<EntryVersion> = files
+        .filter_map(|file| file.ok())
+        .filter_map(|file| {
+            let name = file.file_name().into_string().ok()?;
+            let stamp = name.strip_suffix(".md")?;
+            let saved = NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).ok()?;
+            Some(EntryVersion {
+                path: file.path(),
+                saved,
+            })
+        })
+        .collect();
+    versions.sort_by_key(|version| std::cmp::Reverse(version.saved));
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_storage(keep_versions: usize) -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage = Storage::new_with_base_dir(temp_dir.path())
+            .expect("Failed to create storage")
+            .with_kept_versions(keep_versions);
+        (storage, temp_dir)
+    }
+
+    fn save(storage: &Storage, id: &EntryId, content: &str) {
+        let mut entry = storage
+            .load_entry(id)
+            .unwrap_or_else(|_| Entry::new(*id, String::new()));
+        entry.update_content(content.to_string());
+        storage.save_entry(&entry).unwrap();
+    }
+
+    #[test]
+    fn test_saves_keep_the_newest_versions() {
+        let (storage, temp_dir) = create_test_storage(2);
+        let id: EntryId = "20250920".parse().unwrap();
+        for content in ["one", "two", "three", "four"] {
+            save(&storage, &id, content);
+        }
+
+        assert!(temp_dir.path().join("state/backups/20250920").is_dir());
+        let versions = storage.entry_versions(&id).unwrap();
+        let contents: Vec<String> = versions
+            .iter()
+            .map(|version| {
+                storage.deserialize_entry(&id, &fs::read_to_string(&version.path).unwrap())
+            })
+            .map(|entry| entry.unwrap().content)
+            .collect();
+        assert_eq!(contents, vec!["three", "two"]);
+
+        let restored = storage.restore_version(&id, &versions[1]).unwrap();
+        assert_eq!(restored.content, "two");
+        assert_eq!(storage.load_entry(&id).unwrap().content, "two");
+        assert_eq!(storage.entry_versions(&id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_versions_are_off_by_default() {
+        let (storage, _temp_dir) = create_test_storage(0);
+        let id: EntryId = "20250920".parse().unwrap();
+        save(&storage, &id, "one");
+        save(&storage, &id, "two");
+
+        assert!(storage.entry_versions(&id).unwrap().is_empty());
+        assert!(!storage.versions_path(&id).exists());
+    }
+}
@@ -0,0 +1,192 @@
+use color_eyre::eyre::{Context, Result};
+use std::{fs, io::Write, path::Path};
+
+/// Single funnel for every filesystem mutation devlog performs.
+///
+/// In dry-run mode the intended write/delete is reported on stderr and skipped,
+/// which lets destructive commands preview their effects without special casing.
+#[derive(Debug, Clone, Default)]
+pub struct Writer {
+    dry_run: bool,
+}
+
+impl Writer {
+    pub fn new(dry_run: bool) -> Self {
+        Self { dry_run }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Write `contents` to `path`, replacing any existing file
+    pub fn write(&self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        let contents = contents.as_ref();
+        if self.dry_run {
+            eprintln!(
+                "[dry-run] Would write {} ({} bytes)",
+                path.display(),
+                contents.len()
+            );
+            return Ok(());
+        }
+
+        fs::write(path, contents).wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Write `contents` to a temporary sibling file and rename it over `path`,
+    /// so readers never observe a half-written file
+    pub fn write_atomic(&self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        if self.dry_run {
+            return self.write(path, contents);
+        }
+
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+
+        self.write(&temp_path, contents)?;
+        self.rename(&temp_path, path)
+    }
+
+    /// Write `contents` to `path` like `write` and make it executable, e.g. for a script
+    /// run by another tool
+    pub fn write_executable(&self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        self.write(path, contents)?;
+        #[cfg(unix)]
+        if !self.dry_run {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+                .wrap_err_with(|| format!("Failed to make {} executable", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Append `contents` to `path`, creating the file if needed
+    pub fn append(&self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        let contents = contents.as_ref();
+        if self.dry_run {
+            eprintln!(
+                "[dry-run] Would append to {} ({} bytes)",
+                path.display(),
+                contents.len()
+            );
+            return Ok(());
+        }
+
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents))
+            .wrap_err_with(|| format!("Failed to append to {}", path.display()))
+    }
+
+    /// Delete the file at `path`
+    pub fn remove_file(&self, path: &Path) -> Result<()> {
+        if self.dry_run {
+            eprintln!("[dry-run] Would delete {}", path.display());
+            return Ok(());
+        }
+
+        fs::remove_file(path).wrap_err_with(|| format!("Failed to delete {}", path.display()))
+    }
+
+    /// Move the file at `from` to `to`
+    pub fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        if self.dry_run {
+            eprintln!(
+                "[dry-run] Would move {} to {}",
+                from.display(),
+                to.display()
+            );
+            return Ok(());
+        }
+
+        fs::rename(from, to)
+            .wrap_err_with(|| format!("Failed to move {} to {}", from.display(), to.display()))
+    }
+
+    /// Create `path` and all of its missing parents
+    pub fn create_dir_all(&self, path: &Path) -> Result<()> {
+        if self.dry_run {
+            // Creating an empty directory is not worth reporting, but is still skipped
+            return Ok(());
+        }
+
+        fs::create_dir_all(path)
+            .wrap_err_with(|| format!("Failed to create directory: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_writer_performs_writes() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let writer = Writer::new(false);
+        let path = temp_dir.path().join("nested").join("file.md");
+
+        writer
+            .create_dir_all(path.parent().unwrap())
+            .expect("Failed to create dir");
+        writer.write(&path, "content").expect("Failed to write");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "content");
+
+        writer
+            .write_atomic(&path, "replaced")
+            .expect("Failed to write atomically");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "replaced");
+        assert!(!temp_dir.path().join("nested").join("file.md.tmp").exists());
+
+        writer
+            .append(&path, "\nappended")
+            .expect("Failed to append");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "replaced\nappended");
+
+        writer.remove_file(&path).expect("Failed to delete");
+        assert!(!path.exists());
+
+        let script = temp_dir.path().join("nested").join("hook");
+        writer
+            .write_executable(&script, "#!/bin/sh\n")
+            .expect("Failed to write script");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&script).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn test_dry_run_writer_touches_nothing() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let writer = Writer::new(true);
+        let existing = temp_dir.path().join("existing.md");
+        fs::write(&existing, "keep me").unwrap();
+
+        writer
+            .write(temp_dir.path().join("new.md").as_path(), "content")
+            .expect("Dry-run write should succeed");
+        writer
+            .write_atomic(&existing, "overwritten")
+            .expect("Dry-run atomic write should succeed");
+        writer
+            .write_executable(&existing, "#!/bin/sh\n")
+            .expect("Dry-run script write should succeed");
+        writer
+            .remove_file(&existing)
+            .expect("Dry-run delete should succeed");
+        writer
+            .create_dir_all(&temp_dir.path().join("dir"))
+            .expect("Dry-run mkdir should succeed");
+
+        assert!(!temp_dir.path().join("new.md").exists());
+        assert!(!temp_dir.path().join("dir").exists());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "keep me");
+    }
+}
@@ -0,0 +1,398 @@
+//! Line-based three-way merge of files changed on both sides of a sync, or on disk
+//! while being edited
+
+use std::collections::HashSet;
+
+use diffy::{ConflictStyle, MergeOptions};
+
+use crate::storage::events::Event;
+
+/// Start, middle and end of a conflict left in the text by `merge_marked`
+pub const LOCAL_MARKER: &str = "<<<<<<<";
+pub const SEPARATOR: &str = "=======";
+pub const REMOTE_MARKER: &str = ">>>>>>>";
+
+/// Length of the markers diffy is asked for. Far longer than ours, so they can't be
+/// mistaken for a line of the files when they are replaced by labeled ones.
+const DIFFY_MARKER_LEN: usize = 32;
+
+/// Result of a three-way merge that carried on past conflicting changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merged {
+    pub text: String,
+    /// Number of places where both versions were kept between conflict markers
+    pub conflicts: usize,
+}
+
+/// Merge the changes from `base` to `local` and from `base` to `remote`.
+/// Returns `None` when both sides changed the same lines differently.
+pub fn merge3(base: &str, local: &str, remote: &str) -> Option<String> {
+    let merged = merge_marked(base, local, remote, ("", ""));
+    (merged.conflicts == 0).then_some(merged.text)
+}
+
+/// Merge like `merge3`, but where both sides changed the same lines differently, keep
+/// both versions between conflict markers labeled with `labels` (local, remote) instead
+/// of giving up. The text ends with a newline if `local` does.
+pub fn merge_marked(base: &str, local: &str, remote: &str, labels: (&str, &str)) -> Merged {
+    // diffy keeps line endings as they are, a last line without one would run into
+    // the next line or marker
+    let terminated = |text: &str| {
+        if text.is_empty() || text.ends_with('\n') {
+            text.to_string()
+        } else {
+            format!("{}\n", text)
+        }
+    };
+    let merged = MergeOptions::new()
+        .set_conflict_marker_length(DIFFY_MARKER_LEN)
+        .set_conflict_style(ConflictStyle::Merge)
+        .merge(&terminated(base), &terminated(local), &terminated(remote));
+
+    let mut merged = match merged {
+        Ok(text) => Merged { text, conflicts: 0 },
+        Err(text) => {
+            let newline = if local.contains("\r\n") { "\r\n" } else { "\n" };
+            relabel(&text, labels, newline)
+        }
+    };
+    if !local.ends_with('\n') {
+        let end = merged.text.trim_end_matches(['\r', '\n']).len();
+        merged.text.truncate(end);
+    }
+    merged
+}
+
+/// Replace the markers of a diffy conflict with ours, labeled with `labels` and
+/// ending in `newline` like the lines around them
+fn relabel(text: &str, labels: (&str, &str), newline: &str) -> Merged {
+    let diffy_local = format!("{} ours", "<".repeat(DIFFY_MARKER_LEN));
+    let diffy_separator = "=".repeat(DIFFY_MARKER_LEN);
+    let diffy_remote = format!("{} theirs", ">".repeat(DIFFY_MARKER_LEN));
+    let local_marker = format!("{} {}", LOCAL_MARKER, labels.0);
+    let remote_marker = format!("{} {}", REMOTE_MARKER, labels.1);
+
+    let mut merged = String::with_capacity(text.len());
+    let mut conflicts = 0;
+    for line in text.split_inclusive('\n') {
+        let marker = match line.trim_end_matches('\n') {
+            content if content == diffy_local => {
+                conflicts += 1;
+                local_marker.trim_end()
+            }
+            content if content == diffy_separator => SEPARATOR,
+            content if content == diffy_remote => remote_marker.trim_end(),
+            _ => {
+                merged.push_str(line);
+                continue;
+            }
+        };
+        merged.push_str(marker);
+        merged.push_str(newline);
+    }
+    Merged {
+        text: merged,
+        conflicts,
+    }
+}
+
+/// Like `merge_marked` for Markdown starting with a `---` frontmatter. Markers would
+/// break the frontmatter, so where both sides changed it, `remote`'s is kept.
+pub fn merge_document(base: &str, local: &str, remote: &str, labels: (&str, &str)) -> Merged {
+    let (base_front, base_body) = split_frontmatter(base);
+    let (local_front, local_body) = split_frontmatter(local);
+    let (remote_front, remote_body) = split_frontmatter(remote);
+
+    let front =
+        merge3(base_front, local_front, remote_front).unwrap_or_else(|| remote_front.to_string());
+    let body = merge_marked(base_body, local_body, remote_body, labels);
+    Merged {
+        text: front + &body.text,
+        conflicts: body.conflicts,
+    }
+}
+
+/// The `---` frontmatter block, with its closing line, and the rest of `text`.
+/// Files written on Windows may use CRLF throughout.
+fn split_frontmatter(text: &str) -> (&str, &str) {
+    for newline in ["\n", "\r\n"] {
+        let open = format!("---{}", newline);
+        let close = format!("{}---{}", newline, newline);
+        if let Some(rest) = text.strip_prefix(open.as_str())
+            && let Some(end) = rest.find(close.as_str())
+        {
+            return text.split_at(open.len() + end + close.len());
+        }
+    }
+    ("", text)
+}
+
+/// Which version of a conflict to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Local,
+    Remote,
+    /// Both, the local lines first
+    Both,
+}
+
+/// Rows of a conflict left in a text by `merge_marked`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkedConflict {
+    /// The `<<<<<<<` line
+    pub start: usize,
+    /// The `=======` line
+    pub separator: usize,
+    /// The `>>>>>>>` line
+    pub end: usize,
+}
+
+impl MarkedConflict {
+    /// Every conflict marked in `lines`, in order. Unfinished markers are left alone.
+    pub fn find_all<S: AsRef<str>>(lines: &[S]) -> Vec<Self> {
+        let mut conflicts = Vec::new();
+        let (mut start, mut separator) = (None, None);
+        for (row, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            if line.starts_with(LOCAL_MARKER) {
+                (start, separator) = (Some(row), None);
+            } else if line == SEPARATOR && start.is_some() {
+                separator = Some(row);
+            } else if line.starts_with(REMOTE_MARKER)
+                && let (Some(start), Some(separator)) = (start.take(), separator.take())
+            {
+                conflicts.push(Self {
+                    start,
+                    separator,
+                    end: row,
+                });
+            }
+        }
+        conflicts
+    }
+
+    pub fn contains(&self, row: usize) -> bool {
+        (self.start..=self.end).contains(&row)
+    }
+
+    /// The lines replacing the whole conflict, markers included
+    pub fn resolve<'a, S: AsRef<str>>(
+        &self,
+        lines: &'a [S],
+        resolution: Resolution,
+    ) -> Vec<&'a str> {
+        let local = &lines[self.start + 1..self.separator];
+        let remote = &lines[self.separator + 1..self.end];
+        let kept: Vec<&[S]> = match resolution {
+            Resolution::Local => vec![local],
+            Resolution::Remote => vec![remote],
+            Resolution::Both => vec![local, remote],
+        };
+        kept.into_iter()
+            .flatten()
+            .map(|line| line.as_ref())
+            .collect()
+    }
+}
+
+/// Three-way merge of an event log, one JSON event per line. Lines either side added
+/// since `base` are kept, lines either side dropped since, e.g. by compacting the log
+/// into a snapshot, stay dropped. The result is ordered by the time of the events.
+pub fn merge_log(base: &str, local: &str, remote: &str) -> String {
+    let base: HashSet<&str> = base.lines().collect();
+    let in_local: HashSet<&str> = local.lines().collect();
+    let in_remote: HashSet<&str> = remote.lines().collect();
+
+    let mut seen = HashSet::new();
+    let mut merged: Vec<&str> = local
+        .lines()
+        .chain(remote.lines())
+        .filter(|line| {
+            !base.contains(line) || (in_local.contains(line) && in_remote.contains(line))
+        })
+        .filter(|line| seen.insert(*line))
+        .collect();
+    // Stable, lines that aren't events keep their place among each other
+    merged.sort_by_key(|line| {
+        serde_json::from_str::<Event>(line)
+            .ok()
+            .map(|event| event.at)
+    });
+
+    let mut merged = merged.join("\n");
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_separate_edits() {
+        let base = "# Standup\n- one\n- two\n\n# Notes\nnothing yet\n";
+        let local = "# Standup\n- one\n- two\n- three\n\n# Notes\nnothing yet\n";
+        let remote = "# Standup\n- one\n- two\n\n# Notes\nmet @alice\n";
+
+        assert_eq!(
+            merge3(base, local, remote).as_deref(),
+            Some("# Standup\n- one\n- two\n- three\n\n# Notes\nmet @alice\n")
+        );
+        assert_eq!(merge3(base, local, local).as_deref(), Some(local));
+    }
+
+    #[test]
+    fn test_merge_conflicting_edits() {
+        let base = "- one\n- two\n";
+        assert_eq!(merge3(base, "- one\n- 2\n", "- one\n- deux\n"), None);
+        // Appending on both sides lands at the same place
+        assert_eq!(
+            merge3(base, "- one\n- two\n- a\n", "- one\n- two\n- b\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_log_without_base_keeps_every_line() {
+        assert_eq!(merge_log("", "a\nb\n", "a\nc\n"), "a\nb\nc\n");
+    }
+
+    #[test]
+    fn test_merge_log_keeps_compacted_lines_out() {
+        let event = |minute: u32, kind: &str| {
+            format!(
+                "{{\"at\":\"2025-09-20T10:{:02}:00+02:00\",\"kind\":\"{}\"}}",
+                minute, kind
+            )
+        };
+        let base = format!("{}\n{}\n", event(1, "created"), event(2, "updated"));
+        // Compacted on this machine, edited again on the other
+        let local = format!("{}\n", event(5, "snapshot"));
+        let remote = format!("{}{}\n", base, event(3, "updated"));
+
+        assert_eq!(
+            merge_log(&base, &local, &remote),
+            format!("{}\n{}\n", event(3, "updated"), event(5, "snapshot"))
+        );
+    }
+
+    #[test]
+    fn test_merge_marks_only_overlapping_changes() {
+        let base = "# Standup\n- one\n\n# Notes\nnothing yet\n";
+        let local = "# Standup\n- one\n- two\n\n# Notes\nmet @alice\n";
+        let remote = "# Standup\n- one\n\n# Notes\nmet @bob\n";
+
+        let merged = merge_marked(base, local, remote, ("laptop", "desktop"));
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.text,
+            "# Standup\n- one\n- two\n\n# Notes\n<<<<<<< laptop\nmet @alice\n=======\nmet @bob\n>>>>>>> desktop\n"
+        );
+        assert_eq!(
+            merge_marked(base, local, local, ("", "")),
+            Merged {
+                text: local.to_string(),
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_document_keeps_frontmatter_clean() {
+        let doc = |updated: &str, body: &str| {
+            format!(
+                "---\nid: 20250920\nupdated_at: {}\n---\n\n{}",
+                updated, body
+            )
+        };
+        let merged = merge_document(
+            &doc("1", "- one\n- two\n"),
+            &doc("2", "- one\n- two\n- mine\n"),
+            &doc("3", "- zero\n- one\n- two\n"),
+            ("", ""),
+        );
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(merged.text, doc("3", "- zero\n- one\n- two\n- mine\n"));
+    }
+
+    #[test]
+    fn test_merge_edge_cases() {
+        let base = "a\nb\nc\n";
+        // A replaced line right next to an insertion is a conflict, as in git
+        assert_eq!(
+            merge_marked(base, "a\nB\nc\n", "a\nb\nx\nc\n", ("l", "r")),
+            Merged {
+                text: "a\n<<<<<<< l\nB\n=======\nb\nx\n>>>>>>> r\nc\n".to_string(),
+                conflicts: 1
+            }
+        );
+        // A last line without a newline doesn't run into the markers
+        assert_eq!(
+            merge_marked("a\nb", "a\nB", "a\nC", ("l", "r")).text,
+            "a\n<<<<<<< l\nB\n=======\nC\n>>>>>>> r"
+        );
+        assert_eq!(merge3("", "", "x\n").as_deref(), Some("x"));
+        // Marker-like lines of the files themselves are left alone
+        let text = "<<<<<<< ours\n=======\n";
+        assert_eq!(merge3(text, text, text).as_deref(), Some(text));
+        // CRLF files get CRLF markers
+        assert_eq!(
+            merge_marked("a\r\n", "b\r\n", "c\r\n", ("", "")).text,
+            "<<<<<<<\r\nb\r\n=======\r\nc\r\n>>>>>>>\r\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_document_with_crlf_frontmatter() {
+        let doc = |updated: &str, body: &str| {
+            format!(
+                "---\r\nid: 20250920\r\nupdated_at: {}\r\n---\r\n\r\n{}",
+                updated, body
+            )
+        };
+        let merged = merge_document(
+            &doc("1", "one\r\n"),
+            &doc("2", "mine\r\n"),
+            &doc("3", "theirs\r\n"),
+            ("", ""),
+        );
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.text,
+            doc("3", "<<<<<<<\r\nmine\r\n=======\r\ntheirs\r\n>>>>>>>\r\n")
+        );
+    }
+
+    #[test]
+    fn test_resolve_marked_conflicts() {
+        let lines = [
+            "before",
+            "<<<<<<< yours",
+            "mine",
+            "=======",
+            "theirs",
+            ">>>>>>> on disk",
+            "after",
+        ];
+        let conflicts = MarkedConflict::find_all(&lines);
+        assert_eq!(
+            conflicts,
+            vec![MarkedConflict {
+                start: 1,
+                separator: 3,
+                end: 5
+            }]
+        );
+        let conflict = conflicts[0];
+        assert!(conflict.contains(4) && !conflict.contains(6));
+        assert_eq!(conflict.resolve(&lines, Resolution::Local), vec!["mine"]);
+        assert_eq!(conflict.resolve(&lines, Resolution::Remote), vec!["theirs"]);
+        assert_eq!(
+            conflict.resolve(&lines, Resolution::Both),
+            vec!["mine", "theirs"]
+        );
+        assert!(MarkedConflict::find_all(&["<<<<<<< unfinished", "======="]).is_empty());
+    }
+}
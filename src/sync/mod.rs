@@ -0,0 +1,877 @@
+pub mod merge;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Local};
+use color_eyre::eyre::{Context, Result, bail};
+use flate2::{Compression, read::MultiGzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::config::SyncConfig;
+use crate::error::{DevlogError, ErrorKind as DevlogErrorKind};
+use crate::storage::Storage;
+use crate::utils::lock::FileLock;
+
+const STATE_FILE_NAME: &str = "sync.json";
+/// Copies of text files as of the last sync, the base of three-way merges
+const BASE_DIR_NAME: &str = "sync-base";
+/// Files merged line by line, anything else becomes a conflict copy when both sides
+/// changed. Conflicting lines in Markdown are kept between conflict markers.
+const MERGEABLE_EXTENSIONS: [&str; 2] = ["md", "jsonl"];
+/// Gzipped archives of compacted event logs, merged like the logs once unpacked
+const EVENT_ARCHIVE_SUFFIX: &str = ".jsonl.gz";
+/// Directory in the remote folder shared by all machines, never synced itself
+const REMOTE_META_DIR: &str = ".devlog-sync";
+const TOMBSTONES_FILE_NAME: &str = "tombstones.json";
+/// Held in `REMOTE_META_DIR` as `sync.lock` while a machine syncs
+const REMOTE_LOCK_NAME: &str = "sync";
+/// Tombstones are dropped after this many days, a machine offline for longer
+/// brings its copies of files deleted meanwhile back
+const TOMBSTONE_DAYS: i64 = 180;
+
+/// Content hashes of the synced files as of the last sync, by path relative to
+/// the data directory. A file whose hash differs was changed since.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub synced_at: Option<DateTime<FixedOffset>>,
+    pub files: BTreeMap<String, String>,
+}
+
+/// A file deleted on some machine, kept in the remote folder so machines that
+/// never synced the file delete their copy instead of bringing it back
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tombstone {
+    /// Hash of the deleted content, a copy that differs is kept
+    pub hash: String,
+    pub deleted_at: DateTime<FixedOffset>,
+    pub host: String,
+}
+
+/// What a sync does to one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Only the remote copy changed, take it
+    Pull,
+    /// Only the local copy changed, send it
+    Push,
+    /// Deleted remotely or by a tombstone, and unchanged here
+    DeleteLocal,
+    /// Deleted here and unchanged remotely
+    DeleteRemote,
+    /// Changed on both sides, merged, possibly with conflict markers, or kept as a
+    /// conflict copy
+    Merge,
+    /// Deleted on both sides, only the state is updated
+    Forget,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Path relative to the data directory, with `/` separators
+    pub path: String,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub changes: Vec<Change>,
+    /// Files changed on both sides that couldn't be merged cleanly
+    pub conflicts: Vec<Conflict>,
+    /// Deletions left for a later sync because deleting was turned off
+    pub skipped: Vec<Change>,
+}
+
+impl Change {
+    pub fn is_deletion(&self) -> bool {
+        matches!(self.action, Action::DeleteLocal | Action::DeleteRemote)
+    }
+}
+
+/// Both versions of `path` are kept between conflict markers, or, with a `copy`, the
+/// remote version was kept under `path` and the local one was saved as `copy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub path: String,
+    pub copy: Option<String>,
+}
+
+/// Syncs the data directory with a folder shared between machines, e.g. a
+/// Syncthing or network share mount. Files changed on one side since the last
+/// sync are copied over, files changed on both sides are merged or kept twice.
+pub struct SyncEngine<'a> {
+    storage: &'a Storage,
+    remote: PathBuf,
+    host: String,
+    deletions: bool,
+}
+
+impl<'a> SyncEngine<'a> {
+    pub fn new(storage: &'a Storage, remote: impl Into<PathBuf>) -> Self {
+        Self {
+            storage,
+            remote: remote.into(),
+            host: hostname(),
+            deletions: true,
+        }
+    }
+
+    /// Engine for the configured remote, fails if sync isn't set up
+    pub fn from_config(storage: &'a Storage, config: &SyncConfig) -> Result<Self> {
+        if let Some(provider) = config.provider.as_deref()
+            && provider != "folder"
+        {
+            bail!(DevlogError::new(
+                DevlogErrorKind::Validation,
+                format!(
+                    "Sync provider '{}' isn't supported yet, use 'folder' with sync.remote",
+                    provider
+                )
+            ));
+        }
+        let Some(remote) = &config.remote else {
+            bail!(DevlogError::new(
+                DevlogErrorKind::Validation,
+                "No sync folder configured, set one with `devlog config set sync.remote <dir>`"
+            ));
+        };
+        Ok(Self::new(storage, remote))
+    }
+
+    /// Name conflict copies after `host` instead of this machine
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// Whether files deleted on one side are deleted on the other, otherwise
+    /// deletions are left for a later sync
+    pub fn with_deletions(mut self, deletions: bool) -> Self {
+        self.deletions = deletions;
+        self
+    }
+
+    /// Work out what a sync would do, without touching any file
+    pub fn plan(&self) -> Result<Vec<Change>> {
+        let state = self.load_state()?;
+        let tombstones = self.load_tombstones()?;
+        let local = self.hashes(self.storage.data_path())?;
+        let remote = self.hashes(&self.remote)?;
+
+        let paths: BTreeSet<&String> = local
+            .keys()
+            .chain(remote.keys())
+            .chain(state.files.keys())
+            .collect();
+        Ok(paths
+            .into_iter()
+            .filter_map(|path| {
+                let action = plan_file(
+                    state.files.get(path),
+                    local.get(path),
+                    remote.get(path),
+                    tombstones.get(path).map(|tombstone| &tombstone.hash),
+                )?;
+                Some(Change {
+                    path: path.clone(),
+                    action,
+                })
+            })
+            .collect())
+    }
+
+    /// Number of files changed here since the last sync
+    pub fn pending(&self) -> Result<usize> {
+        let state = self.load_state()?;
+        let local = self.hashes(self.storage.data_path())?;
+        let paths: BTreeSet<&String> = local.keys().chain(state.files.keys()).collect();
+        Ok(paths
+            .into_iter()
+            .filter(|path| local.get(*path) != state.files.get(*path))
+            .count())
+    }
+
+    /// Bring the data directory and the remote folder in line
+    pub fn sync(&self) -> Result<SyncReport> {
+        if !self.remote.is_dir() {
+            bail!(DevlogError::new(
+                DevlogErrorKind::NotFound,
+                format!("Sync folder {} doesn't exist", self.remote.display())
+            ));
+        }
+
+        // One sync at a time here and on the remote, otherwise the last one to save
+        // the state drops what the others recorded
+        let _locks = if self.storage.writer().is_dry_run() {
+            None
+        } else {
+            let meta = self.remote.join(REMOTE_META_DIR);
+            self.storage.writer().create_dir_all(&meta)?;
+            Some((
+                FileLock::acquire(&self.storage.state_path().join(STATE_FILE_NAME))?,
+                FileLock::acquire(&meta.join(REMOTE_LOCK_NAME))?,
+            ))
+        };
+
+        let now = Local::now().fixed_offset();
+        let mut state = self.load_state()?;
+        let known_tombstones = self.load_tombstones()?;
+        let mut tombstones = known_tombstones.clone();
+        let mut report = SyncReport::default();
+        for change in self.plan()? {
+            if change.is_deletion() && !self.deletions {
+                report.skipped.push(change);
+                continue;
+            }
+            match change.action {
+                Action::Pull => {
+                    let contents = read(&self.remote_path(&change.path))?;
+                    self.write_local(&mut state, &change.path, &contents)?;
+                    tombstones.remove(&change.path);
+                }
+                Action::Push => {
+                    let contents = read(&self.local_path(&change.path))?;
+                    self.write_remote(&change.path, &contents)?;
+                    self.record(&mut state, &change.path, &contents)?;
+                    tombstones.remove(&change.path);
+                }
+                Action::DeleteLocal => {
+                    let path = self.local_path(&change.path);
+                    let hash = hash(&read(&path)?);
+                    tombstones
+                        .entry(change.path.clone())
+                        .or_insert_with(|| self.tombstone(hash, now));
+                    self.storage.writer().remove_file(&path)?;
+                    self.forget(&mut state, &change.path)?;
+                }
+                Action::DeleteRemote => {
+                    if let Some(hash) = state.files.get(&change.path) {
+                        tombstones.insert(change.path.clone(), self.tombstone(hash.clone(), now));
+                    }
+                    self.storage
+                        .writer()
+                        .remove_file(&self.remote_path(&change.path))?;
+                    self.forget(&mut state, &change.path)?;
+                }
+                Action::Merge => {
+                    if let Some(conflict) = self.merge(&mut state, &change.path)? {
+                        report.conflicts.push(conflict);
+                    }
+                    tombstones.remove(&change.path);
+                }
+                Action::Forget => self.forget(&mut state, &change.path)?,
+            }
+            report.changes.push(change);
+        }
+
+        tombstones
+            .retain(|_, tombstone| now - tombstone.deleted_at < Duration::days(TOMBSTONE_DAYS));
+        if tombstones != known_tombstones {
+            self.save_tombstones(&tombstones)?;
+        }
+        state.synced_at = Some(now);
+        self.save_state(&state)?;
+        if report
+            .changes
+            .iter()
+            .any(|change| change.path.starts_with("entries/"))
+            && !self.storage.writer().is_dry_run()
+        {
+            self.storage.index()?;
+        }
+        Ok(report)
+    }
+
+    fn tombstone(&self, hash: String, now: DateTime<FixedOffset>) -> Tombstone {
+        Tombstone {
+            hash,
+            deleted_at: now,
+            host: self.host.clone(),
+        }
+    }
+
+    /// Merge a file changed on both sides. Lines of Markdown files changed differently
+    /// on both sides are kept between conflict markers, event logs and their archives
+    /// get the events of both sides. Files that can't be merged at all keep the remote
+    /// version, and the local one next to it, on both sides, as a conflict copy.
+    fn merge(&self, state: &mut SyncState, path: &str) -> Result<Option<Conflict>> {
+        let local = read(&self.local_path(path))?;
+        let remote = read(&self.remote_path(path))?;
+        let base = fs::read(self.base_path(path)).unwrap_or_default();
+
+        // The merged contents, and how many conflicts were left in them
+        let merged: Option<(Vec<u8>, usize)> = if !is_mergeable(path) {
+            None
+        } else if path.ends_with(EVENT_ARCHIVE_SUFFIX) {
+            match (gunzip(&local), gunzip(&remote)) {
+                (Some(local), Some(remote)) => {
+                    let base = gunzip(&base).unwrap_or_default();
+                    Some((gzip(&merge::merge_log(&base, &local, &remote))?, 0))
+                }
+                _ => None,
+            }
+        } else {
+            match (std::str::from_utf8(&local), std::str::from_utf8(&remote)) {
+                (Ok(local), Ok(remote)) => {
+                    let base = String::from_utf8_lossy(&base);
+                    if path.ends_with(".jsonl") {
+                        // Appended to, and rewritten into a snapshot when compacted
+                        Some((merge::merge_log(&base, local, remote).into_bytes(), 0))
+                    } else {
+                        let merged = merge::merge_document(
+                            &base,
+                            local,
+                            remote,
+                            (&self.host, "other machine"),
+                        );
+                        Some((merged.text.into_bytes(), merged.conflicts))
+                    }
+                }
+                _ => None,
+            }
+        };
+        if let Some((merged, conflicts)) = merged {
+            self.write_remote(path, &merged)?;
+            self.write_local(state, path, &merged)?;
+            return Ok((conflicts > 0).then(|| Conflict {
+                path: path.to_string(),
+                copy: None,
+            }));
+        }
+
+        let copy = self.conflict_copy(path);
+        self.write_remote(&copy, &local)?;
+        self.write_local(state, &copy, &local)?;
+        self.write_local(state, path, &remote)?;
+        Ok(Some(Conflict {
+            path: path.to_string(),
+            copy: Some(copy),
+        }))
+    }
+
+    /// `entries/20250920.md` becomes `entries/20250920.conflict-<host>.md`, numbered
+    /// if an earlier conflict copy exists
+    fn conflict_copy(&self, path: &str) -> String {
+        let (stem, extension) = match path.rsplit_once('.') {
+            Some((stem, extension)) if !extension.contains('/') => {
+                (stem, format!(".{}", extension))
+            }
+            _ => (path, String::new()),
+        };
+        (1..)
+            .map(|n| match n {
+                1 => format!("{}.conflict-{}{}", stem, self.host, extension),
+                n => format!("{}.conflict-{}-{}{}", stem, self.host, n, extension),
+            })
+            .find(|copy| !self.local_path(copy).exists() && !self.remote_path(copy).exists())
+            .unwrap_or_default()
+    }
+
+    fn write_local(&self, state: &mut SyncState, path: &str, contents: &[u8]) -> Result<()> {
+        let target = self.local_path(path);
+        if let Some(parent) = target.parent() {
+            self.storage.writer().create_dir_all(parent)?;
+        }
+        self.storage.writer().write_atomic(&target, contents)?;
+        self.record(state, path, contents)
+    }
+
+    fn write_remote(&self, path: &str, contents: &[u8]) -> Result<()> {
+        let target = self.remote_path(path);
+        if let Some(parent) = target.parent() {
+            self.storage.writer().create_dir_all(parent)?;
+        }
+        self.storage.writer().write_atomic(&target, contents)
+    }
+
+    /// Remember `contents` as the synced version of `path`
+    fn record(&self, state: &mut SyncState, path: &str, contents: &[u8]) -> Result<()> {
+        state.files.insert(path.to_string(), hash(contents));
+        if is_mergeable(path) {
+            let base = self.base_path(path);
+            if let Some(parent) = base.parent() {
+                self.storage.writer().create_dir_all(parent)?;
+            }
+            self.storage.writer().write_atomic(&base, contents)?;
+        }
+        Ok(())
+    }
+
+    fn forget(&self, state: &mut SyncState, path: &str) -> Result<()> {
+        state.files.remove(path);
+        let base = self.base_path(path);
+        if base.exists() {
+            self.storage.writer().remove_file(&base)?;
+        }
+        Ok(())
+    }
+
+    /// Hashes of the files under `root`, by relative path, without those `.devlogignore`
+    /// excludes from the vault
+    fn hashes(&self, root: &Path) -> Result<BTreeMap<String, String>> {
+        let ignore_rules = self.storage.ignore_rules()?;
+        let mut hashes = BTreeMap::new();
+        let files = WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                // A sync folder inside the data directory must not sync into itself
+                let own_folder = (e.path() == self.remote && root != self.remote)
+                    || e.path() == self.remote.join(REMOTE_META_DIR);
+                // Matched as if in the data directory, so both sides skip the same files
+                let ignored = e.depth() > 0
+                    && e.path().strip_prefix(root).is_ok_and(|relative| {
+                        ignore_rules.is_ignored(
+                            &self.storage.data_path().join(relative),
+                            e.file_type().is_dir(),
+                        )
+                    });
+                !own_folder && !ignored
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            // Half-written files of an atomic write
+            .filter(|e| !e.file_name().to_string_lossy().ends_with(".tmp"));
+        for file in files {
+            let Ok(relative) = file.path().strip_prefix(root) else {
+                continue;
+            };
+            let relative: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            hashes.insert(relative.join("/"), hash(&read(file.path())?));
+        }
+        Ok(hashes)
+    }
+
+    fn local_path(&self, path: &str) -> PathBuf {
+        self.storage.data_path().join(path)
+    }
+
+    fn remote_path(&self, path: &str) -> PathBuf {
+        self.remote.join(path)
+    }
+
+    fn base_path(&self, path: &str) -> PathBuf {
+        self.storage.state_path().join(BASE_DIR_NAME).join(path)
+    }
+
+    fn load_state(&self) -> Result<SyncState> {
+        let path = self.storage.state_path().join(STATE_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(SyncState::default()),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn load_tombstones(&self) -> Result<BTreeMap<String, Tombstone>> {
+        let path = self.remote.join(REMOTE_META_DIR).join(TOMBSTONES_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn save_tombstones(&self, tombstones: &BTreeMap<String, Tombstone>) -> Result<()> {
+        let dir = self.remote.join(REMOTE_META_DIR);
+        let raw =
+            serde_json::to_string_pretty(tombstones).wrap_err("Failed to serialize tombstones")?;
+        self.storage.writer().create_dir_all(&dir)?;
+        self.storage
+            .writer()
+            .write_atomic(&dir.join(TOMBSTONES_FILE_NAME), raw)
+    }
+
+    fn save_state(&self, state: &SyncState) -> Result<()> {
+        let raw = serde_json::to_string_pretty(state).wrap_err("Failed to serialize sync state")?;
+        self.storage
+            .writer()
+            .write_atomic(&self.storage.state_path().join(STATE_FILE_NAME), raw)
+    }
+}
+
+/// Decide what to do with one file from its hash at the last sync, now on both
+/// sides and when it was deleted elsewhere
+fn plan_file(
+    base: Option<&String>,
+    local: Option<&String>,
+    remote: Option<&String>,
+    tombstone: Option<&String>,
+) -> Option<Action> {
+    match (local, remote) {
+        (None, None) => base.map(|_| Action::Forget),
+        (Some(local), Some(remote)) if local == remote => None,
+        (Some(local), Some(remote)) => Some(if base == Some(local) {
+            Action::Pull
+        } else if base == Some(remote) {
+            Action::Push
+        } else {
+            Action::Merge
+        }),
+        // A deletion only wins against an unchanged copy
+        (Some(local), None) if base == Some(local) => Some(Action::DeleteLocal),
+        // Never synced from here, but deleted by a machine that had the same copy
+        (Some(local), None) if base.is_none() && tombstone == Some(local) => {
+            Some(Action::DeleteLocal)
+        }
+        (Some(_), None) => Some(Action::Push),
+        (None, Some(remote)) if base == Some(remote) => Some(Action::DeleteRemote),
+        (None, Some(_)) => Some(Action::Pull),
+    }
+}
+
+/// FNV-1a, stable across Rust versions and machines unlike `DefaultHasher`
+fn hash(contents: &[u8]) -> String {
+    let hash = contents.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Whether both sides' changes to `path` can be merged instead of kept as a conflict copy
+fn is_mergeable(path: &str) -> bool {
+    path.ends_with(EVENT_ARCHIVE_SUFFIX)
+        || Path::new(path)
+            .extension()
+            .is_some_and(|ext| MERGEABLE_EXTENSIONS.iter().any(|m| ext == *m))
+}
+
+/// The text of a gzipped event archive, `None` if it isn't one
+fn gunzip(bytes: &[u8]) -> Option<String> {
+    let mut text = String::new();
+    MultiGzDecoder::new(bytes).read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+fn gzip(text: &str) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(text.as_bytes())
+        .wrap_err("Failed to compress events")?;
+    encoder.finish().wrap_err("Failed to compress events")
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).wrap_err_with(|| format!("Failed to read {}", path.display()))
+}
+
+/// Name of this machine for conflict copies, reduced to characters safe in file names
+fn hostname() -> String {
+    let name = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| {
+            Command::new("hostname")
+                .output()
+                .ok()
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+        })
+        .unwrap_or_default();
+    let name: String = name
+        .trim()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if name.is_empty() {
+        "unknown".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        (storage, temp_dir)
+    }
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_sync_copies_changes_both_ways() {
+        let (storage, temp_dir) = create_test_storage();
+        let remote = temp_dir.path().join("remote");
+        fs::create_dir_all(&remote).unwrap();
+        let engine = SyncEngine::new(&storage, &remote).with_host("laptop");
+
+        storage
+            .save_entry(&Entry::new("20250920".parse().unwrap(), "here".to_string()))
+            .unwrap();
+        write(&remote, "entries/20250921.md", "there\n");
+        assert_eq!(engine.pending().unwrap(), 2);
+
+        let report = engine.sync().unwrap();
+        assert!(report.conflicts.is_empty());
+        assert!(remote.join("entries/20250920.md").exists());
+        assert!(storage.data_path().join("entries/20250921.md").exists());
+        assert_eq!(engine.pending().unwrap(), 0);
+        assert!(engine.plan().unwrap().is_empty());
+
+        fs::remove_file(remote.join("entries/20250921.md")).unwrap();
+        assert_eq!(
+            engine.plan().unwrap(),
+            vec![Change {
+                path: "entries/20250921.md".to_string(),
+                action: Action::DeleteLocal
+            }]
+        );
+        engine.sync().unwrap();
+        assert!(!storage.data_path().join("entries/20250921.md").exists());
+    }
+
+    #[test]
+    fn test_ignored_files_are_not_synced() {
+        let (storage, temp_dir) = create_test_storage();
+        let remote = temp_dir.path().join("remote");
+        fs::create_dir_all(&remote).unwrap();
+        let engine = SyncEngine::new(&storage, &remote).with_host("laptop");
+
+        let data = storage.data_path();
+        write(data, ".devlogignore", ".obsidian/\n");
+        write(data, ".obsidian/workspace.json", "{}");
+        write(data, "entries/20250920.md", "here\n");
+        write(&remote, ".obsidian/workspace.json", "{\"other\": true}");
+        engine.sync().unwrap();
+
+        assert!(remote.join("entries/20250920.md").exists());
+        assert!(remote.join(".devlogignore").exists());
+        assert_eq!(
+            fs::read_to_string(remote.join(".obsidian/workspace.json")).unwrap(),
+            "{\"other\": true}"
+        );
+
+        // Deleting an ignored folder on one side leaves the other alone
+        fs::remove_dir_all(data.join(".obsidian")).unwrap();
+        assert!(engine.plan().unwrap().is_empty());
+        engine.sync().unwrap();
+        assert!(remote.join(".obsidian/workspace.json").exists());
+    }
+
+    #[test]
+    fn test_one_sync_at_a_time() {
+        let (storage, temp_dir) = create_test_storage();
+        let remote = temp_dir.path().join("remote");
+        fs::create_dir_all(&remote).unwrap();
+        let engine = SyncEngine::new(&storage, &remote).with_host("laptop");
+
+        {
+            let _running = FileLock::acquire(&storage.state_path().join(STATE_FILE_NAME))
+                .expect("Failed to acquire lock");
+            let err = engine
+                .sync()
+                .expect_err("Sync should wait for the other one");
+            assert!(
+                err.to_string()
+                    .contains("Another devlog process is running")
+            );
+        }
+
+        // Another machine syncing to the same folder
+        {
+            let meta = remote.join(REMOTE_META_DIR);
+            fs::create_dir_all(&meta).unwrap();
+            let _running =
+                FileLock::acquire(&meta.join(REMOTE_LOCK_NAME)).expect("Failed to acquire lock");
+            assert!(engine.sync().is_err());
+        }
+
+        engine.sync().unwrap();
+        assert!(!storage.state_path().join("sync.json.lock").exists());
+    }
+
+    #[test]
+    fn test_concurrent_edits_merge_or_conflict() {
+        let (storage, temp_dir) = create_test_storage();
+        let remote = temp_dir.path().join("remote");
+        fs::create_dir_all(&remote).unwrap();
+        let engine = SyncEngine::new(&storage, &remote).with_host("laptop");
+        let data = storage.data_path().to_path_buf();
+
+        write(&data, "entries/20250920.md", "# Morning\n\n# Evening\n");
+        engine.sync().unwrap();
+
+        write(
+            &data,
+            "entries/20250920.md",
+            "# Morning\nstandup\n\n# Evening\n",
+        );
+        write(
+            &remote,
+            "entries/20250920.md",
+            "# Morning\n\n# Evening\nreview\n",
+        );
+        let report = engine.sync().unwrap();
+        assert!(report.conflicts.is_empty());
+        let merged = "# Morning\nstandup\n\n# Evening\nreview\n";
+        assert_eq!(
+            fs::read_to_string(data.join("entries/20250920.md")).unwrap(),
+            merged
+        );
+        assert_eq!(
+            fs::read_to_string(remote.join("entries/20250920.md")).unwrap(),
+            merged
+        );
+
+        write(
+            &data,
+            "entries/20250920.md",
+            "# Morning\nstandup\n\n# Evening\nmine\n",
+        );
+        write(
+            &remote,
+            "entries/20250920.md",
+            "# Morning\nstandup\n\n# Evening\ntheirs\n",
+        );
+        let report = engine.sync().unwrap();
+        assert_eq!(
+            report.conflicts,
+            vec![Conflict {
+                path: "entries/20250920.md".to_string(),
+                copy: None,
+            }]
+        );
+        for root in [&data, &remote] {
+            assert_eq!(
+                fs::read_to_string(root.join("entries/20250920.md")).unwrap(),
+                "# Morning\nstandup\n\n# Evening\n<<<<<<< laptop\nmine\n=======\ntheirs\n>>>>>>> other machine\n"
+            );
+        }
+        assert!(engine.plan().unwrap().is_empty());
+        assert_eq!(storage.list_entries().unwrap().len(), 1);
+
+        write(&data, "attachments/diagram.png", "mine");
+        engine.sync().unwrap();
+        write(&data, "attachments/diagram.png", "mine, again");
+        write(&remote, "attachments/diagram.png", "theirs");
+        let report = engine.sync().unwrap();
+        assert_eq!(
+            report.conflicts,
+            vec![Conflict {
+                path: "attachments/diagram.png".to_string(),
+                copy: Some("attachments/diagram.conflict-laptop.png".to_string()),
+            }]
+        );
+        for root in [&data, &remote] {
+            assert_eq!(
+                fs::read_to_string(root.join("attachments/diagram.png")).unwrap(),
+                "theirs"
+            );
+            assert_eq!(
+                fs::read_to_string(root.join("attachments/diagram.conflict-laptop.png")).unwrap(),
+                "mine, again"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compacted_event_logs_merge_by_event() {
+        let (storage, temp_dir) = create_test_storage();
+        let remote = temp_dir.path().join("remote");
+        fs::create_dir_all(&remote).unwrap();
+        let engine = SyncEngine::new(&storage, &remote).with_host("laptop");
+        let data = storage.data_path().to_path_buf();
+        let event = |minute: u32, kind: &str| {
+            format!(
+                "{{\"at\":\"2025-09-20T10:{:02}:00+02:00\",\"kind\":\"{}\"}}\n",
+                minute, kind
+            )
+        };
+
+        let log = event(1, "created") + &event(2, "updated");
+        write(&data, "events/20250920.jsonl", &log);
+        fs::write(
+            data.join("events/20250920.jsonl.gz"),
+            gzip(&event(0, "created")).unwrap(),
+        )
+        .unwrap();
+        engine.sync().unwrap();
+
+        // Compacted here, edited again on the other machine
+        write(&data, "events/20250920.jsonl", &event(5, "snapshot"));
+        fs::write(
+            data.join("events/20250920.jsonl.gz"),
+            gzip(&(event(0, "created") + &log)).unwrap(),
+        )
+        .unwrap();
+        write(
+            &remote,
+            "events/20250920.jsonl",
+            &(log.clone() + &event(3, "updated")),
+        );
+        fs::write(
+            remote.join("events/20250920.jsonl.gz"),
+            gzip(&(event(0, "created") + &event(0, "updated"))).unwrap(),
+        )
+        .unwrap();
+
+        let report = engine.sync().unwrap();
+        assert!(report.conflicts.is_empty());
+        for root in [&data, &remote] {
+            assert_eq!(
+                fs::read_to_string(root.join("events/20250920.jsonl")).unwrap(),
+                event(3, "updated") + &event(5, "snapshot")
+            );
+            let archived = gunzip(&fs::read(root.join("events/20250920.jsonl.gz")).unwrap());
+            assert_eq!(
+                archived.unwrap(),
+                event(0, "created") + &event(0, "updated") + &log
+            );
+        }
+    }
+
+    #[test]
+    fn test_deletions_reach_machines_that_never_synced() {
+        let (laptop, laptop_dir) = create_test_storage();
+        let (desktop, _desktop_dir) = create_test_storage();
+        let remote = laptop_dir.path().join("remote");
+        fs::create_dir_all(&remote).unwrap();
+
+        // The desktop has a copy of the vault but never synced it
+        for storage in [&laptop, &desktop] {
+            write(storage.data_path(), "entries/20250920.md", "draft\n");
+        }
+        SyncEngine::new(&laptop, &remote).sync().unwrap();
+
+        fs::remove_file(laptop.data_path().join("entries/20250920.md")).unwrap();
+        let kept = SyncEngine::new(&laptop, &remote)
+            .with_deletions(false)
+            .sync()
+            .unwrap();
+        assert_eq!(kept.skipped.len(), 1);
+        assert!(remote.join("entries/20250920.md").exists());
+
+        SyncEngine::new(&laptop, &remote).sync().unwrap();
+        assert!(!remote.join("entries/20250920.md").exists());
+
+        let report = SyncEngine::new(&desktop, &remote).sync().unwrap();
+        assert_eq!(
+            report.changes,
+            vec![Change {
+                path: "entries/20250920.md".to_string(),
+                action: Action::DeleteLocal
+            }]
+        );
+        assert!(!desktop.data_path().join("entries/20250920.md").exists());
+        assert!(!remote.join("entries/20250920.md").exists());
+    }
+}
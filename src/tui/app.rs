@@ -1,38 +1,55 @@
-use std::io;
+use std::{io, time::Duration};
 
 use color_eyre::Result;
 use crossterm::{
-    event::{self, Event, KeyEventKind},
+    event::{DisableBracketedPaste, EnableBracketedPaste},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{init, widgets::ListState, DefaultTerminal};
 
 use crate::{
+    config::{Config, ConfigStore},
+    editing::EditorBuffer,
     storage::Storage,
     tui::{
         components::renderer::UIRenderer,
-        handlers::keyboard::KeyboardHandler,
-        models::state::AppState,
+        event::{AppEvent, EventLoop},
+        handlers::{keyboard::KeyboardHandler, text_input},
+        models::{
+            layout::PanelLayout,
+            state::{AppState, Panel},
+        },
         tree::{builder::TreeBuilder, flattener::TreeFlattener},
     },
+    utils::{
+        output,
+        text::{dropped_path, normalize_paste},
+    },
 };
 
 pub struct App {
     app_state: AppState,
     tree_state: ListState,
     keyboard_handler: KeyboardHandler,
+    config: Config,
 }
 
 impl App {
-    pub fn new(storage: &Storage) -> Result<Self> {
+    pub fn new(storage: &Storage, config: Config) -> Result<Self> {
         let tree_builder = TreeBuilder::new(storage.clone());
         let tree_nodes = tree_builder.build_tree()?;
         let flat_items = TreeFlattener::flatten(&tree_nodes);
 
         let mut app_state = AppState::new();
+        app_state.collapse_code_lines = config.tui.collapse_code_lines;
         app_state.tree_nodes = tree_nodes;
         app_state.flat_items = flat_items;
+        // A broken layout file only costs the saved panel widths
+        app_state.layout = PanelLayout::load(storage).unwrap_or_default();
+        if app_state.layout.tree_collapsed {
+            app_state.current_panel = Panel::Content;
+        }
 
         // Initialize tree_state with first item selected
         let mut tree_state = ListState::default();
@@ -43,37 +60,72 @@ impl App {
         Ok(Self {
             app_state,
             tree_state,
-            keyboard_handler: KeyboardHandler::new(storage.clone()),
+            keyboard_handler: KeyboardHandler::new(storage.clone(), &config),
+            config,
         })
     }
 
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        // A zero tick rate would make the loop spin
+        let tick_rate = Duration::from_millis(self.config.tui.tick_rate_ms.max(1));
+        let mut event_loop = EventLoop::new(tick_rate);
+        let mut dirty = true;
+
         loop {
             // Clear terminal if a redraw is needed (e.g., after editor)
             if self.app_state.needs_redraw {
                 terminal.clear()?;
                 self.app_state.needs_redraw = false;
+                dirty = true;
             }
 
-            // Draw the UI
-            terminal.draw(|f| UIRenderer::render(&self.app_state, &mut self.tree_state, f))?;
-
-            // Handle events
-            if let Event::Key(key) = event::read()? {
-                // Only handle KeyPress events to avoid double-triggering on Windows
-                // Windows generate both KeyPress and KeyRelease events, while Unit-like systems
-                // typically only generate KeyPress events
-                // This means when a user press `Enter` on Windows:
-                // 1. `KeyPress` Enter event -> node expands
-                // 2. `KeyRelease` Enter event -> node collapse
-                // The result is that a node briefly expands then immediately collapses
-                if key.kind == KeyEventKind::Press {
-                    self.keyboard_handler.handle_key_event(
-                        key.code,
-                        &mut self.app_state,
-                        &mut self.tree_state,
-                    )?;
+            // Draw the UI, only when something changed since the last frame
+            if dirty {
+                terminal.draw(|f| UIRenderer::render(&self.app_state, &mut self.tree_state, f))?;
+            }
+
+            // Handle events, input never waits past the next tick
+            match event_loop.next_event()? {
+                AppEvent::Key(key) => {
+                    // Keys go to the entry being edited first, the rest navigates.
+                    // A pending question about a dropped file is answered first.
+                    let edited = self.app_state.pending_attachment.is_none()
+                        && self.app_state.editor.as_mut().is_some_and(|session| {
+                            !session.confirm_close
+                                && session.conflict.is_none()
+                                && text_input::handle_key(&mut session.buffer, key)
+                        });
+                    if edited {
+                        self.app_state.notice = None;
+                    } else if self.app_state.editor.is_some()
+                        && self.app_state.pending_attachment.is_none()
+                    {
+                        self.keyboard_handler.handle_edit_key(
+                            key,
+                            &mut self.app_state,
+                            &mut self.tree_state,
+                        )?;
+                    } else {
+                        self.keyboard_handler.handle_key_event(
+                            key.code,
+                            &mut self.app_state,
+                            &mut self.tree_state,
+                        )?;
+                    }
+                    dirty = true;
+                }
+                AppEvent::Paste(text) => {
+                    self.paste(&text);
+                    dirty = true;
                 }
+                AppEvent::Resize => dirty = true,
+                AppEvent::Tick => dirty = self.app_state.on_tick(),
+            }
+
+            // Warnings can't go to stderr while the TUI owns the screen
+            if let Some(warning) = output::take_warnings().pop() {
+                self.app_state.notice = Some(warning);
+                dirty = true;
             }
 
             if self.app_state.should_quit {
@@ -83,6 +135,29 @@ impl App {
 
         Ok(())
     }
+
+    /// Insert pasted text into the entry being edited as a single undo step. The path
+    /// of a file dropped onto the terminal is offered as an attachment instead.
+    fn paste(&mut self, text: &str) {
+        if let Some(path) = dropped_path(text) {
+            self.keyboard_handler
+                .offer_attachment(path, &mut self.app_state);
+            return;
+        }
+
+        let Some(session) = self.app_state.editor.as_mut() else {
+            return;
+        };
+
+        let tab_width = self
+            .config
+            .editor
+            .expand_tabs
+            .then_some(self.config.editor.tab_width);
+        session
+            .buffer
+            .insert_text(&normalize_paste(text, tab_width));
+    }
 }
 
 pub fn launch_tui(storage: &Storage) -> Result<()> {
@@ -91,19 +166,22 @@ pub fn launch_tui(storage: &Storage) -> Result<()> {
     // `LeaveAlternateScreen` goes back to the original state
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    output::hold_warnings(true);
 
     // `terminal` is ratatui's abstraction for drawing to the screen.
     // It manages the screen buffer, handles drawing operations, and coordinates with the backend
     // `app` is the logic and the state of our application.
     // It handles events, and maintains app states.
     let mut terminal = init();
-    let mut app = App::new(storage)?;
+    let config = ConfigStore::new(storage.config_path()).load()?;
+    let mut app = App::new(storage, config)?;
 
     let result = app.run(&mut terminal);
 
     disable_raw_mode()?;
-    execute!(stdout, LeaveAlternateScreen)?;
+    execute!(stdout, DisableBracketedPaste, LeaveAlternateScreen)?;
+    output::hold_warnings(false);
 
     result
 }
@@ -2,12 +2,15 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
 };
 
+use crate::tui::models::layout::PanelLayout;
+
 /// Manages the main application layout structure
 pub struct MainLayout;
 
 impl MainLayout {
-    /// Creates the main application layout with header, content, and footer areas
-    pub fn create_layout(area: Rect) -> MainLayoutAreas {
+    /// Creates the main application layout with header, content, and footer areas,
+    /// splitting the tree and content panel as `layout` says
+    pub fn create_layout(area: Rect, layout: &PanelLayout) -> MainLayoutAreas {
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(3), Constraint::Length(3)])
@@ -15,7 +18,10 @@ impl MainLayout {
 
         let content_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .constraints([
+                Constraint::Percentage(layout.tree_width()),
+                Constraint::Percentage(100 - layout.tree_width()),
+            ])
             .split(main_chunks[0]);
 
         MainLayoutAreas {
@@ -0,0 +1,497 @@
+use std::{ops::Range, sync::OnceLock};
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme, ThemeSet},
+    parsing::SyntaxSet,
+};
+
+use crate::export::mermaid;
+use crate::models::link;
+use crate::tui::models::content::{RowKind, VisibleRow};
+use crate::utils::math;
+
+/// Lines longer than this are shown without inline styles, every row in view
+/// would parse the whole line again
+const MAX_STYLED_LINE_BYTES: usize = 4096;
+
+/// Inline code, where annotations aren't highlighted either
+const INLINE_CODE: Style = Style::new().fg(Color::Green);
+
+/// Footnote references like `[^1]` and the labels of footnotes
+const FOOTNOTE_LABEL: Style = Style::new().fg(Color::Cyan);
+
+/// `$..$` math, shown as its Unicode approximation
+const MATH: Style = Style::new()
+    .fg(Color::LightCyan)
+    .add_modifier(Modifier::ITALIC);
+
+/// Style a laid out row of entry content according to its markdown role
+pub fn render_row<'a>(row: &VisibleRow<'a>) -> Line<'a> {
+    let text = row.text();
+    match row.kind {
+        RowKind::Heading => Line::from(inline_spans(
+            row,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        RowKind::CodeFence { .. } => Line::styled(text, Style::default().fg(Color::DarkGray)),
+        RowKind::CollapsedCode { lines } if row.lang == Some(mermaid::LANG) => Line::styled(
+            match row.title {
+                Some(title) => format!("[ diagram: {} — press Enter to show its source ]", title),
+                None => format!(
+                    "[ {}-line diagram — press Enter to show its source ]",
+                    lines
+                ),
+            },
+            Style::default().fg(Color::Magenta),
+        ),
+        RowKind::CollapsedCode { lines } => Line::styled(
+            format!(
+                "[ {}-line {}block — press Enter to expand ]",
+                lines,
+                row.lang
+                    .map(|lang| format!("{} ", lang))
+                    .unwrap_or_default()
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+        RowKind::Code => match row.lang.and_then(|lang| highlight_code(row, lang)) {
+            Some(spans) => Line::from(spans),
+            None => Line::styled(text, Style::default().fg(Color::Green)),
+        },
+        RowKind::Quote => Line::from(inline_spans(
+            row,
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::ITALIC),
+        )),
+        RowKind::DefinitionTerm => Line::from(inline_spans(
+            row,
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        RowKind::Footnote => Line::from(inline_spans(row, Style::default().fg(Color::Gray))),
+        RowKind::MathFence { .. } => Line::styled(text, Style::default().fg(Color::DarkGray)),
+        // A wrapped line of a math block is converted as a whole on its first row
+        RowKind::Math if row.range.start == 0 => Line::styled(math::to_unicode(row.line), MATH),
+        RowKind::Math => Line::default(),
+        RowKind::Task { done: true } => Line::from(inline_spans(row, Style::default()))
+            .style(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+        RowKind::Text | RowKind::ListItem | RowKind::Definition | RowKind::Task { done: false } => {
+            Line::from(inline_spans(row, Style::default()))
+        }
+    }
+}
+
+/// Highlight the occurrences of a search query in a styled row, ignoring case
+pub fn highlight_matches<'a>(line: Line<'a>, query: &str) -> Line<'a> {
+    let text: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    let matches = match_ranges(&text, query);
+    if matches.is_empty() {
+        return line;
+    }
+
+    let highlight = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in &line.spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        // Cut the span wherever a match starts or ends inside it
+        let mut cuts: Vec<usize> = matches
+            .iter()
+            .flat_map(|range| [range.start, range.end])
+            .filter(|&cut| offset < cut && cut < end)
+            .collect();
+        cuts.push(end);
+
+        let mut start = offset;
+        for cut in cuts {
+            let matched = matches.iter().any(|range| range.contains(&start));
+            let style = if matched {
+                span.style.patch(highlight)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(
+                content[start - offset..cut - offset].to_string(),
+                style,
+            ));
+            start = cut;
+        }
+        offset = end;
+    }
+
+    let mut highlighted = line;
+    highlighted.spans = spans;
+    highlighted
+}
+
+/// Byte ranges of `query` in `text`, ignoring case. Text whose lowercase form has
+/// a different length can't be mapped back and is left alone.
+fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let lower = text.to_lowercase();
+    let query = query.to_lowercase();
+    if query.is_empty() || lower.len() != text.len() {
+        return Vec::new();
+    }
+    lower
+        .match_indices(&query)
+        .map(|(start, found)| start..start + found.len())
+        .filter(|range| text.is_char_boundary(range.start) && text.is_char_boundary(range.end))
+        .collect()
+}
+
+/// Split a row into spans styled by the inline markdown of its whole line, with
+/// `@person`, `::project` and `+tag` annotations outside of code and math in their
+/// own colors. Math is replaced by its Unicode approximation.
+fn inline_spans<'a>(row: &VisibleRow<'a>, base: Style) -> Vec<Span<'a>> {
+    let (mut styles, math) = if row.line.len() <= MAX_STYLED_LINE_BYTES {
+        (inline_styles(row.line), math_ranges(row.line))
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    styles.extend(math.iter().map(|(range, _)| (range.clone(), MATH)));
+    let in_code = |range: &Range<usize>| {
+        styles.iter().any(|(code, style)| {
+            (*style == INLINE_CODE || *style == MATH)
+                && code.start < range.end
+                && range.start < code.end
+        })
+    };
+    let annotations: Vec<(Range<usize>, Style)> = annotation_ranges(row.line)
+        .chain(footnote_ranges(row.line))
+        .chain(wiki_link_ranges(row.line))
+        .filter(|(range, _)| !in_code(range))
+        .collect();
+    styles.extend(annotations);
+    if row.kind == RowKind::Definition {
+        // The `:` in front of a definition
+        let marker = row.line.len() - row.line.trim_start().len();
+        styles.push((marker..marker + 1, Style::default().fg(Color::DarkGray)));
+    }
+
+    // Cut the row wherever a style starts or ends inside it
+    let mut cuts: Vec<usize> = styles
+        .iter()
+        .flat_map(|(range, _)| [range.start, range.end])
+        .filter(|&cut| row.range.start < cut && cut < row.range.end)
+        .collect();
+    cuts.push(row.range.end);
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let mut spans = Vec::new();
+    let mut start = row.range.start;
+    for cut in cuts {
+        let style = styles
+            .iter()
+            .filter(|(range, _)| range.contains(&start))
+            .fold(base, |style, (_, patch)| style.patch(*patch));
+        match math.iter().find(|(range, _)| range.contains(&start)) {
+            // A formula wrapped over rows is shown on the row it starts on
+            Some((range, unicode)) if range.start == start => {
+                spans.push(Span::styled(unicode.clone(), style))
+            }
+            Some(_) => {}
+            None => spans.push(Span::styled(&row.line[start..cut], style)),
+        }
+        start = cut;
+    }
+    spans
+}
+
+/// Byte ranges of the emphasis, strikethrough, inline code and links of a line
+fn inline_styles(line: &str) -> Vec<(Range<usize>, Style)> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    Parser::new_ext(line, options)
+        .into_offset_iter()
+        .filter_map(|(event, range)| {
+            let style = match event {
+                Event::Start(Tag::Strong) => Style::default().add_modifier(Modifier::BOLD),
+                Event::Start(Tag::Emphasis) => Style::default().add_modifier(Modifier::ITALIC),
+                Event::Start(Tag::Strikethrough) => {
+                    Style::default().add_modifier(Modifier::CROSSED_OUT)
+                }
+                Event::Start(Tag::Link { .. }) => Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::UNDERLINED),
+                Event::Code(_) => INLINE_CODE,
+                _ => return None,
+            };
+            Some((range, style))
+        })
+        .collect()
+}
+
+/// Byte ranges of the `$..$` and `$$..$$` math of a line, with its Unicode approximation
+fn math_ranges(line: &str) -> Vec<(Range<usize>, String)> {
+    if !line.contains('$') {
+        return Vec::new();
+    }
+    Parser::new_ext(line, Options::ENABLE_MATH)
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::InlineMath(latex) | Event::DisplayMath(latex) => {
+                Some((range, math::to_unicode(&latex)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Byte ranges of the `@person`, `::project` and `+tag` words of a line
+fn annotation_ranges(line: &str) -> impl Iterator<Item = (Range<usize>, Style)> + '_ {
+    line.split(' ')
+        .scan(0, |offset, word| {
+            let start = *offset;
+            *offset += word.len() + 1;
+            Some((start..start + word.len(), word))
+        })
+        .filter_map(|(range, word)| {
+            let color = if word.starts_with('@') {
+                Color::Magenta
+            } else if word.starts_with("::") {
+                Color::Blue
+            } else if word.starts_with('+') && word.len() > 1 {
+                Color::Yellow
+            } else {
+                return None;
+            };
+            Some((range, Style::default().fg(color)))
+        })
+}
+
+/// Byte ranges of the footnote references like `[^1]` of a line
+fn footnote_ranges(line: &str) -> impl Iterator<Item = (Range<usize>, Style)> + '_ {
+    line.match_indices("[^").filter_map(|(start, _)| {
+        let label = line[start + 2..].split(']').next()?;
+        let end = start + 2 + label.len() + 1;
+        (end <= line.len() && !label.is_empty() && !label.contains(char::is_whitespace))
+            .then_some((start..end, FOOTNOTE_LABEL))
+    })
+}
+
+/// Byte ranges of the `[[20250920]]` links between entries of a line
+fn wiki_link_ranges(line: &str) -> impl Iterator<Item = (Range<usize>, Style)> + '_ {
+    link::line_links(line).into_iter().map(|link| {
+        let style = Style::default()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::UNDERLINED);
+        (link.range, style)
+    })
+}
+
+/// Highlight a row of a fenced code block with syntect, None for languages it
+/// doesn't know. Lines are highlighted on their own so huge entries stay lazy,
+/// constructs spanning lines like block comments only color their first line.
+fn highlight_code<'a>(row: &VisibleRow<'a>, lang: &str) -> Option<Vec<Span<'a>>> {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    static THEME: OnceLock<Theme> = OnceLock::new();
+
+    let syntaxes = SYNTAXES.get_or_init(SyntaxSet::load_defaults_nonewlines);
+    let syntax = syntaxes.find_syntax_by_token(lang)?;
+    let theme = THEME.get_or_init(|| {
+        ThemeSet::load_defaults()
+            .themes
+            .remove("base16-ocean.dark")
+            .unwrap_or_default()
+    });
+    let regions = HighlightLines::new(syntax, theme)
+        .highlight_line(row.line, syntaxes)
+        .ok()?;
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for (style, text) in regions {
+        let range = offset.max(row.range.start)..(offset + text.len()).min(row.range.end);
+        offset += text.len();
+        if range.start < range.end {
+            let color = style.foreground;
+            spans.push(Span::styled(
+                &row.line[range],
+                Style::default().fg(Color::Rgb(color.r, color.g, color.b)),
+            ));
+        }
+    }
+    Some(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(kind: RowKind, line: &str) -> VisibleRow<'_> {
+        VisibleRow {
+            kind,
+            line,
+            range: 0..line.len(),
+            lang: None,
+            title: None,
+        }
+    }
+
+    fn styled<'a>(line: &'a Line, matches: impl Fn(Style) -> bool) -> Vec<&'a str> {
+        line.spans
+            .iter()
+            .filter(|span| matches(span.style))
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_annotations_are_highlighted() {
+        let line = render_row(&row(
+            RowKind::Text,
+            "paired with @alice on ::devlog +rust, not `ssh @host`",
+        ));
+        assert_eq!(
+            styled(&line, |style| style.fg.is_some() && style != INLINE_CODE),
+            vec!["@alice", "::devlog", "+rust,"]
+        );
+    }
+
+    #[test]
+    fn test_wiki_links_are_underlined() {
+        let line = render_row(&row(RowKind::Text, "see [[20250920]] and `[[20250921]]`"));
+        assert_eq!(
+            styled(&line, |style| style
+                .add_modifier
+                .contains(Modifier::UNDERLINED)),
+            vec!["[[20250920]]"]
+        );
+    }
+
+    #[test]
+    fn test_inline_markdown_is_styled_across_wrapped_rows() {
+        let line = "Shipped **the big fix** and *more*";
+        let second_row = VisibleRow {
+            range: 14..line.len(),
+            ..row(RowKind::ListItem, line)
+        };
+        let rendered = render_row(&second_row);
+        assert_eq!(
+            styled(&rendered, |style| style
+                .add_modifier
+                .contains(Modifier::BOLD)),
+            vec!["big fix**"]
+        );
+        assert_eq!(
+            styled(&rendered, |style| style
+                .add_modifier
+                .contains(Modifier::ITALIC)),
+            vec!["*more*"]
+        );
+    }
+
+    #[test]
+    fn test_footnotes_and_definitions_are_styled() {
+        let line = render_row(&row(RowKind::Text, "Fixed[^1] and [^no label] `[^2]`"));
+        assert_eq!(styled(&line, |style| style == FOOTNOTE_LABEL), vec!["[^1]"]);
+
+        let footnote = render_row(&row(RowKind::Footnote, "[^1]: See the RFC"));
+        assert_eq!(
+            styled(&footnote, |style| style.fg == Some(Color::Cyan)),
+            vec!["[^1]"]
+        );
+
+        let definition = render_row(&row(RowKind::Definition, ": A Rust package"));
+        assert_eq!(
+            styled(&definition, |style| style.fg == Some(Color::DarkGray)),
+            vec![":"]
+        );
+        let term = render_row(&row(RowKind::DefinitionTerm, "Crate"));
+        assert!(term.spans[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_math_is_shown_as_unicode() {
+        let line = render_row(&row(
+            RowKind::Text,
+            r"Loss $\alpha x^2 +b$ for $5 and $$\sum_i x_i$$",
+        ));
+        let text: String = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "Loss α x² +b for $5 and ∑ᵢ xᵢ");
+        assert_eq!(
+            styled(&line, |style| style == MATH),
+            vec!["α x² +b", "∑ᵢ xᵢ"]
+        );
+
+        let block = render_row(&row(RowKind::Math, r"\frac{1}{2} \sigma^2"));
+        assert_eq!(block.spans[0].content, "½ σ²");
+    }
+
+    #[test]
+    fn test_diagram_placeholder_shows_its_title() {
+        let diagram = VisibleRow {
+            lang: Some("mermaid"),
+            title: Some("Checkout flow"),
+            ..row(RowKind::CollapsedCode { lines: 4 }, "```mermaid")
+        };
+        assert_eq!(
+            render_row(&diagram).spans[0].content,
+            "[ diagram: Checkout flow — press Enter to show its source ]"
+        );
+    }
+
+    #[test]
+    fn test_code_is_highlighted_for_known_languages() {
+        let code = VisibleRow {
+            lang: Some("rust"),
+            ..row(RowKind::Code, "let x = @y + 1;")
+        };
+        let line = render_row(&code);
+        assert!(line.spans.len() > 1);
+        assert!(
+            line.spans
+                .iter()
+                .all(|span| matches!(span.style.fg, Some(Color::Rgb(..))))
+        );
+
+        let unknown = VisibleRow {
+            lang: Some("no-such-language"),
+            ..row(RowKind::Code, "let x = @y + 1;")
+        };
+        assert_eq!(render_row(&unknown).spans.len(), 1);
+        assert_eq!(
+            render_row(&row(RowKind::Code, "let x = @y + 1;"))
+                .spans
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_search_matches_are_highlighted_across_spans() {
+        let line = highlight_matches(
+            render_row(&row(RowKind::Text, "Paired WITH @alice")),
+            "with @al",
+        );
+
+        assert_eq!(
+            styled(&line, |style| style.bg == Some(Color::Yellow)),
+            vec!["WITH ", "@al"]
+        );
+        assert_eq!(
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>(),
+            "Paired WITH @alice"
+        );
+    }
+}
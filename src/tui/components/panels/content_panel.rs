@@ -1,9 +1,12 @@
-use crate::tui::models::state::{AppState, Panel};
+use crate::tui::{
+    components::markdown,
+    models::state::{AppState, Panel},
+};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
     text::Line,
-    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    widgets::{Block, Borders, Padding, Paragraph},
     Frame,
 };
 
@@ -13,34 +16,52 @@ pub struct ContentPanel;
 impl ContentPanel {
     /// Renders the content display panel
     pub fn render(app_state: &AppState, f: &mut Frame, area: Rect) {
-        let content_lines: Vec<Line> = app_state
-            .selected_entry_content
-            .lines()
-            .map(|line| Line::from(line.to_string()))
-            .collect();
+        let content = &app_state.selected_entry_content;
+
+        // Account for borders and horizontal padding
+        let content_height = area.height.saturating_sub(2) as usize;
+        let content_width = area.width.saturating_sub(4) as usize;
 
-        // Calculate scrolling - account for borders and horizontal padding
-        let content_height = area.height.saturating_sub(2) as usize; // Account for borders
-        let scroll_offset = app_state.content_scroll as usize;
-        let visible_lines: Vec<Line> = content_lines
+        let query = app_state
+            .search
+            .as_ref()
+            .filter(|search| !search.editing && !search.hits.is_empty())
+            .map(|search| search.query.trim());
+
+        // Only the rows in view are wrapped and turned into lines, huge entries stay cheap
+        let visible_lines: Vec<Line> = content
+            .visible_rows(content_width, app_state.content_scroll, content_height)
             .into_iter()
-            .skip(scroll_offset)
-            .take(content_height)
+            .map(|row| {
+                let line = markdown::render_row(&row);
+                match query {
+                    Some(query) => markdown::highlight_matches(line, query),
+                    None => line,
+                }
+            })
             .collect();
 
-        let paragraph = Paragraph::new(visible_lines)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .padding(Padding::horizontal(1)) // Add horizontal padding
-                    .title("Content")
-                    .border_style(if app_state.current_panel == Panel::Content {
-                        Style::default().fg(Color::Yellow)
-                    } else {
-                        Style::default()
-                    }),
-            )
-            .wrap(Wrap { trim: true });
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(1)) // Add horizontal padding
+            .title("Content")
+            .border_style(if app_state.current_panel == Panel::Content {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            });
+
+        if content.is_large() {
+            block = block.title_bottom(
+                Line::from(format!(
+                    " Large entry ({:.1} MB), press o to open externally ",
+                    content.len() as f64 / (1024.0 * 1024.0)
+                ))
+                .style(Style::default().fg(Color::Black).bg(Color::Yellow)),
+            );
+        }
+
+        let paragraph = Paragraph::new(visible_lines).block(block);
 
         f.render_widget(paragraph, area);
     }
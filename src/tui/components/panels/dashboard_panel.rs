@@ -0,0 +1,161 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{BarChart, Block, Borders, Padding, Paragraph, Sparkline},
+};
+
+use crate::models::annotation::AnnotationKind;
+use crate::tui::models::dashboard::Dashboard;
+
+/// Component responsible for rendering the dashboard over the tree and content panels
+pub struct DashboardPanel;
+
+impl DashboardPanel {
+    pub fn render(dashboard: &Dashboard, f: &mut Frame, area: Rect) {
+        let overview = &dashboard.overview;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(8),
+                Constraint::Length(7),
+                Constraint::Length(6),
+            ])
+            .split(area);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(rows[0]);
+        let stale = !dashboard.stale_projects.is_empty();
+        let side = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6 + u16::from(stale)), Constraint::Min(3)])
+            .split(top[1]);
+        let annotations = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows[1]);
+
+        let months = dashboard.entries_per_month();
+        let months: Vec<(&str, u64)> = months
+            .iter()
+            .map(|(label, count)| (label.as_str(), *count))
+            .collect();
+        let chart = BarChart::default()
+            .block(block("Entries per month"))
+            .data(&months)
+            .bar_width(3)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(Color::Cyan))
+            .value_style(Style::default().fg(Color::Black).bg(Color::Cyan));
+        f.render_widget(chart, top[0]);
+
+        let sync = match dashboard.pending_sync {
+            Some(0) => "up to date".to_string(),
+            Some(count) => format!("{} file(s) pending", count),
+            None => "not set up".to_string(),
+        };
+        let mut summary = vec![
+            Line::from(vec![
+                Span::raw("Streak:  "),
+                Span::styled(
+                    format!("{} day(s)", overview.current_streak),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(", longest {}", overview.longest_streak)),
+            ]),
+            Line::from(format!("Entries: {}", overview.entries)),
+            Line::from(format!(
+                "Words:   {}, {} per entry",
+                overview.total_words, overview.average_words
+            )),
+            Line::from(format!("Sync:    {}", sync)),
+        ];
+        if stale {
+            let projects: Vec<String> = dashboard
+                .stale_projects
+                .iter()
+                .map(|project| format!("::{}", project))
+                .collect();
+            summary.push(Line::from(vec![
+                Span::raw("Stale:   "),
+                Span::styled(projects.join(" "), Style::default().fg(Color::Red)),
+            ]));
+        }
+        let summary = Paragraph::new(summary).block(block("Summary"));
+        f.render_widget(summary, side[0]);
+
+        let projects: Vec<Line> = if dashboard.top_projects.is_empty() {
+            vec![Line::from("No ::projects this quarter")]
+        } else {
+            dashboard
+                .top_projects
+                .iter()
+                .map(|(project, count)| Line::from(format!("{:>3}  ::{}", count, project)))
+                .collect()
+        };
+        f.render_widget(
+            Paragraph::new(projects).block(block("Top projects this quarter")),
+            side[1],
+        );
+
+        render_top(f, annotations[0], AnnotationKind::Tag, &overview.top_tags);
+        render_top(
+            f,
+            annotations[1],
+            AnnotationKind::Person,
+            &overview.top_people,
+        );
+
+        let sparkline = Sparkline::default()
+            .block(block("Words per day, last 30 days"))
+            .data(&dashboard.daily_words)
+            .style(Style::default().fg(Color::Green));
+        f.render_widget(sparkline, rows[2]);
+    }
+}
+
+/// Horizontal bars of the most mentioned annotations of one kind
+fn render_top(f: &mut Frame, area: Rect, kind: AnnotationKind, top: &[(String, usize)]) {
+    let title = match kind {
+        AnnotationKind::Person => "Top people",
+        AnnotationKind::Project => "Top projects",
+        AnnotationKind::Tag => "Top tags",
+    };
+    if top.is_empty() {
+        let empty =
+            Paragraph::new(format!("No {} annotations yet", kind.name())).block(block(title));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let labels: Vec<String> = top
+        .iter()
+        .map(|(value, _)| format!("{}{}", kind.prefix(), value))
+        .collect();
+    let bars: Vec<(&str, u64)> = labels
+        .iter()
+        .zip(top)
+        .map(|(label, (_, count))| (label.as_str(), *count as u64))
+        .collect();
+    let chart = BarChart::default()
+        .block(block(title))
+        .direction(Direction::Horizontal)
+        .data(&bars)
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(Style::default().fg(Color::Magenta))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Magenta));
+    f.render_widget(chart, area);
+}
+
+fn block(title: &str) -> Block<'_> {
+    Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .title(title)
+        .border_style(Style::default().fg(Color::Blue))
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph},
+};
+
+use crate::editing::{EditorBuffer, buffer::Cursor};
+use crate::tui::models::edit::EditSession;
+
+/// Component responsible for rendering the embedded editor in place of the content panel
+pub struct EditorPanel;
+
+impl EditorPanel {
+    pub fn render(session: &EditSession, f: &mut Frame, area: Rect) {
+        // Account for borders and horizontal padding
+        let height = area.height.saturating_sub(2) as usize;
+        let width = area.width.saturating_sub(4) as usize;
+        let (top, left) = session.scroll_to_cursor(height, width);
+        let selection = session.buffer.selection();
+
+        let lines: Vec<Line> = session
+            .buffer
+            .lines()
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(row, line)| visible_line(row, line, left, width, selection))
+            .collect();
+
+        let title = format!(
+            "Editing {}{}",
+            session.id,
+            if session.buffer.is_dirty() {
+                " [+]"
+            } else {
+                ""
+            }
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(1))
+            .title(title)
+            .title_bottom(" Ctrl+S: Save | Esc: Close ")
+            .border_style(Style::default().fg(Color::Green));
+
+        f.render_widget(Paragraph::new(lines).block(block), area);
+
+        let cursor = session.buffer.cursor();
+        f.set_cursor_position(Position::new(
+            area.x + 2 + (cursor.col - left) as u16,
+            area.y + 1 + (cursor.row - top) as u16,
+        ));
+    }
+}
+
+/// The part of line `row` in view, with the selected characters reversed
+fn visible_line(
+    row: usize,
+    line: &str,
+    left: usize,
+    width: usize,
+    selection: Option<(Cursor, Cursor)>,
+) -> Line<'static> {
+    let chars: Vec<char> = line.chars().skip(left).take(width).collect();
+    let Some((start, end)) = selection.filter(|(start, end)| start.row <= row && row <= end.row)
+    else {
+        return Line::from(chars.into_iter().collect::<String>());
+    };
+
+    let from = if start.row == row { start.col } else { 0 };
+    let to = if end.row == row { end.col } else { usize::MAX };
+    let (from, to) = (
+        from.saturating_sub(left).min(chars.len()),
+        to.saturating_sub(left).min(chars.len()),
+    );
+    let mut spans = vec![
+        Span::raw(chars[..from].iter().collect::<String>()),
+        Span::styled(
+            chars[from..to].iter().collect::<String>(),
+            Style::default().add_modifier(Modifier::REVERSED),
+        ),
+        Span::raw(chars[to..].iter().collect::<String>()),
+    ];
+    // Mark the line break of a selection running into the next line
+    if end.row > row && chars.len() < width {
+        spans.push(Span::styled(
+            " ",
+            Style::default().add_modifier(Modifier::REVERSED),
+        ));
+    }
+    Line::from(spans)
+}
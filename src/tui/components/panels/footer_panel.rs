@@ -6,6 +6,8 @@ use ratatui::{
     Frame,
 };
 
+use crate::editing::EditorBuffer;
+use crate::sync::merge::MarkedConflict;
 use crate::tui::models::state::{AppState, Panel};
 
 /// Component responsible for rendering the help footer panel
@@ -23,6 +25,30 @@ impl FooterPanel {
             Span::raw(": Expand | "),
             Span::styled("←/h/Enter", Style::default().fg(Color::Yellow)),
             Span::raw(": Collapse | "),
+            Span::styled("n", Style::default().fg(Color::Yellow)),
+            Span::raw(": New | "),
+            Span::styled("d", Style::default().fg(Color::Yellow)),
+            Span::raw(": Delete | "),
+            Span::styled("m", Style::default().fg(Color::Yellow)),
+            Span::raw(": Move Sub-entry | "),
+            Span::styled("p", Style::default().fg(Color::Yellow)),
+            Span::raw(": Paste Image | "),
+            Span::styled("a", Style::default().fg(Color::Yellow)),
+            Span::raw(": Attach File | "),
+            Span::styled("x", Style::default().fg(Color::Yellow)),
+            Span::raw(": Export | "),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::raw(": Search | "),
+            Span::styled("[/]", Style::default().fg(Color::Yellow)),
+            Span::raw(": Resize | "),
+            Span::styled("z", Style::default().fg(Color::Yellow)),
+            Span::raw(": Hide Tree | "),
+            Span::styled("F2", Style::default().fg(Color::Yellow)),
+            Span::raw(": Scratch | "),
+            Span::styled("D", Style::default().fg(Color::Yellow)),
+            Span::raw(": Dashboard | "),
+            Span::styled("R", Style::default().fg(Color::Yellow)),
+            Span::raw(": Review Week | "),
             Span::styled("q", Style::default().fg(Color::Yellow)),
             Span::raw(": Quit"),
         ])];
@@ -40,11 +66,166 @@ impl FooterPanel {
             Span::raw(": Page Up | "),
             Span::styled("PageDown", Style::default().fg(Color::Yellow)),
             Span::raw(": Page Down | "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(": Follow Link/Expand Code | "),
+            Span::styled("e", Style::default().fg(Color::Yellow)),
+            Span::raw(": Edit | "),
+            Span::styled("E", Style::default().fg(Color::Yellow)),
+            Span::raw(": Edit Externally | "),
+            Span::styled("o", Style::default().fg(Color::Yellow)),
+            Span::raw(": Open Externally | "),
+            Span::styled("p", Style::default().fg(Color::Yellow)),
+            Span::raw(": Paste Image | "),
+            Span::styled("a", Style::default().fg(Color::Yellow)),
+            Span::raw(": Attach File | "),
+            Span::styled("x", Style::default().fg(Color::Yellow)),
+            Span::raw(": Export | "),
+            Span::styled("z", Style::default().fg(Color::Yellow)),
+            Span::raw(if app_state.layout.tree_collapsed {
+                ": Show Tree | "
+            } else {
+                ": Hide Tree | "
+            }),
             Span::styled("q", Style::default().fg(Color::Yellow)),
             Span::raw(": Quit"),
         ])];
 
+        let help_text_scratch = vec![Line::from(vec![
+            Span::styled("↑↓/jk", Style::default().fg(Color::Yellow)),
+            Span::raw(": Move | "),
+            Span::styled("Space", Style::default().fg(Color::Yellow)),
+            Span::raw(": Mark | "),
+            Span::styled("m", Style::default().fg(Color::Yellow)),
+            Span::raw(": Move to Today | "),
+            Span::styled("e", Style::default().fg(Color::Yellow)),
+            Span::raw(": Edit | "),
+            Span::styled("F2/Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": Close"),
+        ])];
+
+        let help_text_dashboard = vec![Line::from(vec![
+            Span::styled("D/Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": Close"),
+        ])];
+
+        if let Some(notice) = &app_state.notice {
+            let notice = Paragraph::new(notice.as_str()).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Notice")
+                    .border_style(Style::default().fg(Color::Yellow)),
+            );
+            f.render_widget(notice, area);
+            return;
+        }
+
+        if let Some(session) = &app_state.editor {
+            let help_text = if session.conflict.is_some() {
+                vec![Line::from(vec![
+                    Span::raw(format!("{} changed on disk since it was opened. ", session.id)),
+                    Span::styled("m", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Merge | "),
+                    Span::styled("o", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Overwrite | "),
+                    Span::styled("r", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Reload from Disk | "),
+                    Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Keep Editing"),
+                ])]
+            } else if session.confirm_close {
+                vec![Line::from(vec![
+                    Span::raw(format!("Save changes to {}? ", session.id)),
+                    Span::styled("y", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Save | "),
+                    Span::styled("n", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Discard | "),
+                    Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Keep Editing"),
+                ])]
+            } else if !MarkedConflict::find_all(session.buffer.lines()).is_empty() {
+                vec![Line::from(vec![
+                    Span::raw("Conflicts left. "),
+                    Span::styled("Ctrl+N", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Next Conflict | "),
+                    Span::styled("Ctrl+O", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Keep Yours | "),
+                    Span::styled("Ctrl+T", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Keep Theirs | "),
+                    Span::styled("Ctrl+A", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Keep Both | "),
+                    Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Save"),
+                ])]
+            } else {
+                vec![Line::from(vec![
+                    Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Save | "),
+                    Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Close | "),
+                    Span::styled("Ctrl+Z/Y", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Undo/Redo | "),
+                    Span::styled("Ctrl+B/I", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Bold/Italic | "),
+                    Span::styled("Tab", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Snippet/Indent | "),
+                    Span::styled("Ctrl+V", Style::default().fg(Color::Yellow)),
+                    Span::raw(": Paste Image"),
+                ])]
+            };
+            let help_paragraph = Paragraph::new(help_text).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Edit")
+                    .border_style(Style::default().fg(Color::Green)),
+            );
+            f.render_widget(help_paragraph, area);
+            return;
+        }
+
+        if let Some(search) = app_state.search.as_ref().filter(|search| search.editing) {
+            let prompt = Paragraph::new(Line::from(vec![
+                Span::styled("/", Style::default().fg(Color::Yellow)),
+                Span::raw(search.query.as_str()),
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Search (Enter: search | Esc: cancel)")
+                    .border_style(Style::default().fg(Color::Yellow)),
+            );
+            f.render_widget(prompt, area);
+            return;
+        }
+
+        let help_text_search = vec![Line::from(vec![
+            Span::styled("n/N", Style::default().fg(Color::Yellow)),
+            Span::raw(": Next/Previous Match | "),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::raw(": Search Again | "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": Clear Search | "),
+            Span::styled("Tab", Style::default().fg(Color::Yellow)),
+            Span::raw(": Switch Panel | "),
+            Span::styled("q", Style::default().fg(Color::Yellow)),
+            Span::raw(": Quit"),
+        ])];
+
+        let help_text_review = vec![Line::from(vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(": Add Note | "),
+            Span::styled("Tab", Style::default().fg(Color::Yellow)),
+            Span::raw(": Next Prompt | "),
+            Span::styled("←/→", Style::default().fg(Color::Yellow)),
+            Span::raw(": Previous/Next Entry | "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": Discard"),
+        ])];
+
         let help_text = match app_state.current_panel {
+            _ if app_state.dashboard.is_some() => help_text_dashboard,
+            _ if app_state.review.is_some() => help_text_review,
+            _ if app_state.scratch.is_some() => help_text_scratch,
+            _ if app_state.search.is_some() => help_text_search,
             Panel::Nav => help_text_nav,
             Panel::Content => help_text_content,
         };
@@ -1,3 +1,7 @@
 pub mod content_panel;
+pub mod dashboard_panel;
+pub mod editor_panel;
 pub mod footer_panel;
+pub mod review_panel;
+pub mod scratch_panel;
 pub mod tree_panel;
\ No newline at end of file
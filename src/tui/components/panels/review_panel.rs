@@ -0,0 +1,74 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+};
+
+use crate::tui::models::review::{PROMPTS, WeeklyReview};
+
+/// Component responsible for rendering the weekly review in place of the tree and content panels
+pub struct ReviewPanel;
+
+impl ReviewPanel {
+    pub fn render(review: &WeeklyReview, f: &mut Frame, area: Rect) {
+        let Some(entry) = review.current() else {
+            let rollup = Paragraph::new(review.rollup())
+                .wrap(Wrap { trim: false })
+                .block(Self::block(
+                    "Weekly review done, Enter adds it to today's entry".to_string(),
+                ));
+            f.render_widget(rollup, area);
+            return;
+        };
+
+        let [entry_area, prompts_area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .areas(area);
+
+        let title = format!(
+            "Weekly review {}/{}: {}",
+            review.position + 1,
+            review.entries.len(),
+            entry.id.date().format("%A, %d %B")
+        );
+        let content = Paragraph::new(entry.content.as_str())
+            .wrap(Wrap { trim: false })
+            .block(Self::block(title));
+        f.render_widget(content, entry_area);
+
+        let mut lines = Vec::new();
+        for (index, prompt) in PROMPTS.iter().enumerate() {
+            let style = if index == review.prompt {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            lines.push(Line::from(Span::styled(*prompt, style)));
+            lines.extend(
+                review
+                    .current_answers(index)
+                    .map(|answer| Line::from(format!("  - {}", answer))),
+            );
+            if index == review.prompt {
+                lines.push(Line::from(format!("  > {}█", review.input)));
+            }
+        }
+        let prompts = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Self::block("Notes".to_string()));
+        f.render_widget(prompts, prompts_area);
+    }
+
+    fn block(title: String) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(1))
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan))
+    }
+}
@@ -0,0 +1,48 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Padding},
+};
+
+use crate::tui::models::scratch::ScratchPad;
+
+/// Component responsible for rendering the scratch pad in place of the content panel
+pub struct ScratchPanel;
+
+impl ScratchPanel {
+    pub fn render(pad: &ScratchPad, f: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = pad
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let mark = if pad.marked.contains(&idx) {
+                    "● "
+                } else {
+                    "  "
+                };
+                ListItem::new(Line::from(format!("{}{}", mark, line)))
+            })
+            .collect();
+
+        let title = if pad.lines.is_empty() {
+            "Scratch (empty, press e to write)".to_string()
+        } else {
+            format!("Scratch ({} marked)", pad.marked.len())
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .padding(Padding::horizontal(1))
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Magenta)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let mut state = ListState::default().with_selected(Some(pad.cursor));
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
@@ -27,11 +27,18 @@ impl TreePanel {
             })
             .collect();
 
+        let title = match &app_state.search {
+            Some(search) if search.saved_tree.is_some() => {
+                format!("Entries (search: {})", search.query.trim())
+            }
+            _ => "Entries".to_string(),
+        };
+
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Entries")
+                    .title(title)
                     .border_style(if app_state.current_panel == Panel::Nav {
                         Style::default().fg(Color::Yellow)
                     } else {
@@ -1,6 +1,10 @@
 use super::{
     layout::main_layout::MainLayout,
-    panels::{content_panel::ContentPanel, footer_panel::FooterPanel, tree_panel::TreePanel},
+    panels::{
+        content_panel::ContentPanel, dashboard_panel::DashboardPanel, editor_panel::EditorPanel,
+        footer_panel::FooterPanel, review_panel::ReviewPanel, scratch_panel::ScratchPanel,
+        tree_panel::TreePanel,
+    },
 };
 use crate::tui::models::state::AppState;
 use ratatui::{widgets::ListState, Frame};
@@ -12,11 +16,31 @@ impl UIRenderer {
     /// Renders the complete application UI by coordinating all panels and layout
     pub fn render(app_state: &AppState, tree_state: &mut ListState, f: &mut Frame) {
         // Create the main layout areas
-        let layout_areas = MainLayout::create_layout(f.area());
+        let layout_areas = MainLayout::create_layout(f.area(), &app_state.layout);
+
+        // The dashboard takes the place of both the tree and the content panel
+        if let Some(dashboard) = &app_state.dashboard {
+            let area = layout_areas.tree_area.union(layout_areas.content_area);
+            DashboardPanel::render(dashboard, f, area);
+            FooterPanel::render(app_state, f, layout_areas.footer_area);
+            return;
+        }
+        if let Some(review) = &app_state.review {
+            let area = layout_areas.tree_area.union(layout_areas.content_area);
+            ReviewPanel::render(review, f, area);
+            FooterPanel::render(app_state, f, layout_areas.footer_area);
+            return;
+        }
 
         // Render each panel in its designated area
-        TreePanel::render(app_state, tree_state, f, layout_areas.tree_area);
-        ContentPanel::render(app_state, f, layout_areas.content_area);
+        if !app_state.layout.tree_collapsed {
+            TreePanel::render(app_state, tree_state, f, layout_areas.tree_area);
+        }
+        match (&app_state.editor, &app_state.scratch) {
+            (Some(session), _) => EditorPanel::render(session, f, layout_areas.content_area),
+            (None, Some(pad)) => ScratchPanel::render(pad, f, layout_areas.content_area),
+            (None, None) => ContentPanel::render(app_state, f, layout_areas.content_area),
+        }
         FooterPanel::render(app_state, f, layout_areas.footer_area);
     }
 }
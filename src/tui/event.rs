@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::Result;
+use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+
+/// Events driving the TUI, input and timer ticks alike
+#[derive(Debug)]
+pub enum AppEvent {
+    Key(KeyEvent),
+    /// Text pasted in one go, reported by terminals supporting bracketed paste
+    Paste(String),
+    Resize,
+    /// Emitted every tick, for timers, animations and autosave
+    Tick,
+}
+
+/// Waits for terminal input without blocking past the next tick
+pub struct EventLoop {
+    tick_rate: Duration,
+    last_tick: Instant,
+}
+
+impl EventLoop {
+    pub fn new(tick_rate: Duration) -> Self {
+        Self {
+            tick_rate,
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Wait for the next input event, or a tick once the tick rate has elapsed
+    pub fn next_event(&mut self) -> Result<AppEvent> {
+        loop {
+            let timeout = self.tick_rate.saturating_sub(self.last_tick.elapsed());
+
+            if event::poll(timeout)? {
+                match event::read()? {
+                    // Only handle KeyPress events to avoid double-triggering on Windows
+                    // Windows generate both KeyPress and KeyRelease events, while Unit-like systems
+                    // typically only generate KeyPress events
+                    // This means when a user press `Enter` on Windows:
+                    // 1. `KeyPress` Enter event -> node expands
+                    // 2. `KeyRelease` Enter event -> node collapse
+                    // The result is that a node briefly expands then immediately collapses
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        return Ok(AppEvent::Key(key));
+                    }
+                    Event::Paste(text) => return Ok(AppEvent::Paste(text)),
+                    Event::Resize(_, _) => return Ok(AppEvent::Resize),
+                    _ => {}
+                }
+            }
+
+            if self.last_tick.elapsed() >= self.tick_rate {
+                self.last_tick = Instant::now();
+                return Ok(AppEvent::Tick);
+            }
+        }
+    }
+}
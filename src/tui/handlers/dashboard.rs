@@ -0,0 +1,56 @@
+use chrono::Local;
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+
+use crate::config::ConfigStore;
+use crate::storage::Storage;
+use crate::sync::SyncEngine;
+use crate::tui::models::{dashboard::Dashboard, state::AppState};
+
+/// Keys of the dashboard, toggled with `D`
+pub struct DashboardHandler {
+    storage: Storage,
+}
+
+impl DashboardHandler {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Open the dashboard, or close it if it is open
+    pub fn toggle(&self, app_state: &mut AppState) -> Result<()> {
+        app_state.dashboard = match app_state.dashboard {
+            Some(_) => None,
+            None => {
+                let index = self.storage.index()?;
+                let today = Local::now().date_naive();
+                let mut dashboard = Dashboard::from_index(&index, today);
+                if let Ok(config) = ConfigStore::new(self.storage.config_path()).load() {
+                    dashboard.pending_sync = SyncEngine::from_config(&self.storage, &config.sync)
+                        .ok()
+                        .and_then(|engine| engine.pending().ok());
+                    if config.tui.stale_project_days > 0 {
+                        let closed = self.storage.closed_projects().unwrap_or_default();
+                        dashboard.stale_projects = index
+                            .stale_projects(today, config.tui.stale_project_days)
+                            .into_iter()
+                            .map(|(project, _)| project)
+                            .filter(|project| !closed.contains(project))
+                            .collect();
+                    }
+                }
+                Some(dashboard)
+            }
+        };
+        Ok(())
+    }
+
+    pub fn handle_key(&self, key_code: KeyCode, app_state: &mut AppState) -> Result<()> {
+        match key_code {
+            KeyCode::Char('D') => self.toggle(app_state)?,
+            KeyCode::Esc | KeyCode::Char('q') => app_state.dashboard = None,
+            _ => {}
+        }
+        Ok(())
+    }
+}
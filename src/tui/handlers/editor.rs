@@ -1,43 +1,448 @@
-use crate::tui::models::state::AppState;
-use crate::{storage::Storage, utils::editor};
-use color_eyre::Result;
+use crate::models::entry_id::{EntryId, EntryRef};
+use crate::models::note::{AnyId, NoteId};
+use crate::storage::{attachment::pasted_image_name, focus::FocusSession};
+use crate::sync::merge::{self, MarkedConflict, Resolution};
+use crate::tui::handlers::navigator::tree::show_written_entry;
+use crate::editing::{
+    EditorBuffer,
+    buffer::{Cursor, TextBuffer},
+};
+use crate::tui::models::{edit::EditSession, state::AppState};
+use crate::{
+    config::Config,
+    storage::Storage,
+    utils::{clipboard, editor},
+};
+use chrono::Local;
+use color_eyre::{Result, eyre::Context};
 use crossterm::{
-    cursor, execute,
+    cursor,
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    execute,
     terminal::{
-        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
-        LeaveAlternateScreen,
+        Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
     },
 };
 use ratatui::widgets::ListState;
-use std::io;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 pub struct EditorHandler {
     storage: Storage,
+    auto_pairs: bool,
+    snippets: BTreeMap<String, String>,
 }
 
 impl EditorHandler {
-    pub fn new(storage: Storage) -> Self {
-        Self { storage }
+    pub fn new(storage: Storage, config: &Config) -> Self {
+        Self {
+            storage,
+            auto_pairs: config.editor.auto_pairs,
+            snippets: config.snippets.clone(),
+        }
     }
 
+    /// Start editing the selected entry or note inside the TUI. Sub-entries are
+    /// edited as part of their day's entry.
     pub fn edit_current_entry(
         &self,
         app_state: &mut AppState,
         tree_state: &ListState,
+    ) -> Result<()> {
+        let Some((entry_id, _, true)) = tree_state
+            .selected()
+            .and_then(|selected| app_state.flat_items.get(selected))
+        else {
+            return Ok(());
+        };
+        let id = match entry_id.parse()? {
+            AnyId::Entry(entry_ref) => AnyId::Entry(entry_ref.id.into()),
+            AnyId::Note(id) => AnyId::Note(id),
+        };
+        if self.is_blocked(&id, app_state)? {
+            return Ok(());
+        }
+
+        let content = self.load_content(&id)?;
+        let buffer = TextBuffer::new(&content)
+            .with_auto_pairs(self.auto_pairs)
+            .with_snippets(self.snippets.clone());
+        app_state.editor = Some(EditSession::new(id, content, buffer));
+        Ok(())
+    }
+
+    fn load_content(&self, id: &AnyId) -> Result<String> {
+        Ok(match id {
+            AnyId::Entry(entry_ref) => self.storage.load_entry(&entry_ref.id)?.content,
+            AnyId::Note(id) => self.storage.load_note(id)?.content,
+        })
+    }
+
+    /// Handle the keys of the embedded editor that aren't typing, returns whether
+    /// editing ended
+    pub fn handle_edit_key(
+        &self,
+        key: KeyEvent,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<bool> {
+        let Some(session) = app_state.editor.as_mut() else {
+            return Ok(true);
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        if session.conflict.is_some() {
+            self.answer_conflict(key.code, app_state, tree_state)?;
+            return Ok(false);
+        }
+
+        if session.confirm_close {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    // A conflict keeps the editor open until it is resolved
+                    let saved = self.save(app_state, tree_state)?;
+                    if saved {
+                        app_state.editor = None;
+                    }
+                }
+                KeyCode::Char('n') => {
+                    app_state.editor = None;
+                    app_state.notice = Some("Changes discarded".to_string());
+                }
+                KeyCode::Esc => session.confirm_close = false,
+                _ => {}
+            }
+            return Ok(app_state.editor.is_none());
+        }
+
+        app_state.notice = None;
+        match key.code {
+            KeyCode::Char('s') if ctrl => {
+                self.save(app_state, tree_state)?;
+            }
+            KeyCode::Char('v') if ctrl => self.paste_image(app_state, tree_state)?,
+            KeyCode::Char('n') if ctrl => next_conflict(app_state),
+            KeyCode::Char('o') if ctrl => resolve_conflict(Resolution::Local, app_state),
+            KeyCode::Char('t') if ctrl => resolve_conflict(Resolution::Remote, app_state),
+            KeyCode::Char('a') if ctrl => resolve_conflict(Resolution::Both, app_state),
+            KeyCode::Esc if session.buffer.is_dirty() => session.confirm_close = true,
+            KeyCode::Esc => app_state.editor = None,
+            _ => {}
+        }
+        Ok(app_state.editor.is_none())
+    }
+
+    /// Write the edited text back to its entry or note, returns false when it changed on
+    /// disk since it was opened, e.g. by a sync, and the answer to the conflict is awaited
+    fn save(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<bool> {
+        let Some(session) = app_state.editor.as_mut() else {
+            return Ok(true);
+        };
+        let on_disk = self.load_content(&session.id)?;
+        if on_disk != session.base {
+            session.conflict = Some(on_disk);
+            session.confirm_close = false;
+            return Ok(false);
+        }
+        self.write(app_state, tree_state)?;
+        Ok(true)
+    }
+
+    /// Handle the answer to a conflict found by `save`, other keys leave it open
+    fn answer_conflict(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(session) = app_state.editor.as_mut() else {
+            return Ok(());
+        };
+        let Some(on_disk) = session.conflict.clone() else {
+            return Ok(());
+        };
+        match key_code {
+            KeyCode::Char('m') => {
+                let merged = merge::merge_marked(
+                    &session.base,
+                    &session.buffer.text(),
+                    &on_disk,
+                    ("yours", "on disk"),
+                );
+                replace_text(&mut session.buffer, &merged.text);
+                session.base = on_disk;
+                session.conflict = None;
+                if merged.conflicts == 0 {
+                    self.write(app_state, tree_state)?;
+                    app_state.notice =
+                        Some("Merged the changes made on disk and saved".to_string());
+                } else {
+                    // Saved once every conflict is resolved
+                    session.buffer.set_cursor(Cursor::default());
+                    next_conflict(app_state);
+                    app_state.notice = Some(format!(
+                        "{} conflicting change{} left between markers, resolve them and save",
+                        merged.conflicts,
+                        if merged.conflicts == 1 { "" } else { "s" }
+                    ));
+                }
+            }
+            KeyCode::Char('o') => {
+                session.base = on_disk;
+                session.conflict = None;
+                self.write(app_state, tree_state)?;
+            }
+            KeyCode::Char('r') => {
+                // Undo brings the discarded edits back
+                replace_text(&mut session.buffer, &on_disk);
+                session.buffer.mark_saved();
+                session.base = on_disk;
+                session.conflict = None;
+                app_state.notice = Some(format!("Reloaded {} from disk", session.id));
+            }
+            KeyCode::Esc => session.conflict = None,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Write the edited text to its entry or note, whatever is on disk
+    fn write(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<()> {
+        let Some(session) = app_state.editor.as_mut() else {
+            return Ok(());
+        };
+        let content = session.buffer.text();
+        let id = session.id.clone();
+        match &id {
+            AnyId::Entry(entry_ref) => {
+                let mut entry = self.storage.load_entry(&entry_ref.id)?;
+                entry.update_content(content);
+                self.storage.save_entry(&entry)?;
+            }
+            AnyId::Note(id) => {
+                let mut note = self.storage.load_note(id)?;
+                note.update_content(content);
+                self.storage.save_note(&note)?;
+            }
+        }
+        session.base = self.load_content(&id)?;
+        session.buffer.mark_saved();
+
+        if let AnyId::Entry(entry_ref) = &id {
+            show_written_entry(&self.storage, &entry_ref.id, false, app_state, tree_state)?;
+        }
+        app_state.notice = Some(format!("Saved {}", id));
+        Ok(())
+    }
+
+    /// Edit the selected entry or note in the external editor, leaving the TUI meanwhile
+    pub fn edit_current_entry_externally(
+        &self,
+        app_state: &mut AppState,
+        tree_state: &ListState,
     ) -> Result<()> {
         if let Some(selected) = tree_state.selected() {
             // Extract the needed values from the immutable borrow first
             let entry_info = app_state.flat_items.get(selected).cloned();
-            if let Some((entry_id, _, is_entry)) = entry_info {
-                if is_entry {
-                    self.launch_editor_for_entry(&entry_id, app_state)?;
+            if let Some((entry_id, _, is_entry)) = entry_info
+                && is_entry
+            {
+                match entry_id.parse()? {
+                    // Sub-entries are edited as part of their day's entry
+                    AnyId::Entry(entry_ref) => {
+                        self.launch_editor_for_entry(&entry_ref.id, app_state)?
+                    }
+                    AnyId::Note(id) => self.launch_editor_for_note(&id, app_state)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the selected entry in an external pager, meant for entries too big
+    /// to comfortably browse in the content panel
+    pub fn open_current_entry_externally(
+        &self,
+        app_state: &mut AppState,
+        tree_state: &ListState,
+    ) -> Result<()> {
+        if let Some(selected) = tree_state.selected()
+            && let Some((entry_id, _, true)) = app_state.flat_items.get(selected)
+        {
+            let path = match entry_id.parse()? {
+                AnyId::Entry(entry_ref) => self.storage.entry_path(&entry_ref.id)?,
+                AnyId::Note(id) => self.storage.note_path(&id),
+            };
+
+            self.exit_tui_mode()?;
+            let result = editor::open_in_pager(&path);
+            self.enter_tui_mode()?;
+
+            app_state.needs_redraw = true;
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Save the image on the clipboard as an attachment of the selected entry
+    pub fn paste_image(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<()> {
+        let Some(id) = self.attachment_target(app_state, tree_state)? else {
+            return Ok(());
+        };
+        let image = match clipboard::read_image() {
+            Ok(Some(image)) => image,
+            Ok(None) => {
+                app_state.notice = Some("The clipboard holds no image".to_string());
+                return Ok(());
+            }
+            Err(e) => {
+                app_state.notice = Some(format!("{:#}", e));
+                return Ok(());
+            }
+        };
+
+        let name = pasted_image_name(Local::now().naive_local(), image.extension);
+        self.attach(&id, &name, image.contents, app_state, tree_state)
+    }
+
+    /// Ask whether a file dropped onto the TUI should be attached to the selected
+    /// entry. Without an entry to attach to, the path is pasted as it is.
+    pub fn offer_attachment(&self, path: PathBuf, app_state: &mut AppState) {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        app_state.notice = Some(format!(
+            "Attach {}? y: copy it into the entry's attachments and link it | n: paste the path",
+            name
+        ));
+        app_state.pending_attachment = Some(path);
+    }
+
+    /// Handle the answer to `offer_attachment`, other keys leave the question open
+    pub fn answer_attachment(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(path) = app_state.pending_attachment.take() else {
+            return Ok(());
+        };
+        match key_code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.attach_file(&path, app_state, tree_state)?;
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                if let Some(session) = app_state.editor.as_mut() {
+                    session.buffer.insert_text(&path.display().to_string());
                 }
             }
+            _ => self.offer_attachment(path, app_state),
         }
         Ok(())
     }
 
-    fn launch_editor_for_entry(&self, entry_id: &str, app_state: &mut AppState) -> Result<()> {
+    /// Copy the file at `path` into the attachments of the selected entry and link it
+    pub fn attach_file(
+        &self,
+        path: &Path,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(id) = self.attachment_target(app_state, tree_state)? else {
+            return Ok(());
+        };
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "attachment".to_string());
+        let contents =
+            fs::read(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        self.attach(&id, &name, contents, app_state, tree_state)
+    }
+
+    /// The entry files are attached to: the selected one, unless focus mode locks it
+    fn attachment_target(
+        &self,
+        app_state: &mut AppState,
+        tree_state: &ListState,
+    ) -> Result<Option<EntryId>> {
+        let Some((entry_id, _, true)) = tree_state
+            .selected()
+            .and_then(|selected| app_state.flat_items.get(selected))
+        else {
+            app_state.notice = Some("Select an entry to attach files to".to_string());
+            return Ok(None);
+        };
+        let AnyId::Entry(EntryRef { id, .. }) = entry_id.parse()? else {
+            app_state.notice = Some("Attachments belong to dated entries".to_string());
+            return Ok(None);
+        };
+
+        let now = Local::now().fixed_offset();
+        if let Some(message) =
+            FocusSession::load(&self.storage, now)?.and_then(|session| session.blocks(&id, now))
+        {
+            app_state.notice = Some(message);
+            return Ok(None);
+        }
+        Ok(Some(id))
+    }
+
+    /// Save an attachment of entry `id` and link it at the cursor of the entry being
+    /// edited, or at the end of the entry
+    fn attach(
+        &self,
+        id: &EntryId,
+        name: &str,
+        contents: Vec<u8>,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let attachment = self.storage.save_attachment(id, name, contents)?;
+        match app_state.editor.as_mut() {
+            Some(session) => session.buffer.insert_text(&attachment.markdown()),
+            None => {
+                self.storage.append_to_entry(id, &attachment.markdown())?;
+                show_written_entry(&self.storage, id, false, app_state, tree_state)?;
+            }
+        }
+        app_state.notice = Some(format!("Attached {} to {}", name, id));
+        Ok(())
+    }
+
+    /// Edit the scratch pad in the external editor
+    pub fn edit_scratch(&self, app_state: &mut AppState) -> Result<()> {
+        self.exit_tui_mode()?;
+        let result = editor::open_file(&self.storage.scratch_path());
+        self.enter_tui_mode()?;
+
+        app_state.needs_redraw = true;
+        result
+    }
+
+    /// Whether focus mode keeps `id` from being edited, telling the user why
+    fn is_blocked(&self, id: &AnyId, app_state: &mut AppState) -> Result<bool> {
+        let now = Local::now().fixed_offset();
+        let message = FocusSession::load(&self.storage, now)?.and_then(|session| match id {
+            AnyId::Entry(entry_ref) => session.blocks(&entry_ref.id, now),
+            AnyId::Note(_) => session.blocks_notes(),
+        });
+        let blocked = message.is_some();
+        app_state.notice = message;
+        Ok(blocked)
+    }
+
+    fn launch_editor_for_entry(&self, entry_id: &EntryId, app_state: &mut AppState) -> Result<()> {
+        if self.is_blocked(&AnyId::Entry((*entry_id).into()), app_state)? {
+            return Ok(());
+        }
+
         // Save current terminal state and exit TUI mode
         self.exit_tui_mode()?;
 
@@ -51,8 +456,7 @@ impl EditorHandler {
             Ok(_) => {
                 // Refresh the content in the TUI by reloading the entry
                 if let Ok(entry) = self.storage.load_entry(entry_id) {
-                    app_state.selected_entry_content = entry.content;
-                    app_state.reset_content_scroll();
+                    app_state.update_entry_content(entry.content);
                 }
                 app_state.needs_redraw = true;
             }
@@ -62,7 +466,26 @@ impl EditorHandler {
         Ok(())
     }
 
-    fn edit_entry_content(&self, entry_id: &str) -> Result<()> {
+    fn launch_editor_for_note(&self, id: &NoteId, app_state: &mut AppState) -> Result<()> {
+        if self.is_blocked(&AnyId::Note(id.clone()), app_state)? {
+            return Ok(());
+        }
+
+        self.exit_tui_mode()?;
+        let result = self.storage.load_note(id).and_then(|mut note| {
+            let new_content = editor::launch_editor(Some(&note.content))?;
+            note.update_content(new_content);
+            self.storage.save_note(&note)?;
+            Ok(note)
+        });
+        self.enter_tui_mode()?;
+
+        app_state.update_entry_content(result?.content);
+        app_state.needs_redraw = true;
+        Ok(())
+    }
+
+    fn edit_entry_content(&self, entry_id: &EntryId) -> Result<()> {
         let mut entry = self.storage.load_entry(entry_id)?;
         let new_content = editor::launch_editor(Some(&entry.content))?;
         entry.update_content(new_content);
@@ -88,3 +511,59 @@ impl EditorHandler {
         Ok(())
     }
 }
+
+/// Move the cursor to the next conflict left by a merge, wrapping around at the end
+fn next_conflict(app_state: &mut AppState) {
+    let Some(session) = app_state.editor.as_mut() else {
+        return;
+    };
+    let conflicts = MarkedConflict::find_all(session.buffer.lines());
+    let row = session.buffer.cursor().row;
+    match conflicts
+        .iter()
+        .find(|conflict| conflict.start > row)
+        .or(conflicts.first())
+    {
+        Some(conflict) => session.buffer.set_cursor(Cursor {
+            row: conflict.start,
+            col: 0,
+        }),
+        None => app_state.notice = Some("No conflicts left".to_string()),
+    }
+}
+
+/// Replace the conflict under the cursor with the version picked by `resolution`
+fn resolve_conflict(resolution: Resolution, app_state: &mut AppState) {
+    let Some(session) = app_state.editor.as_mut() else {
+        return;
+    };
+    let lines = session.buffer.lines();
+    let row = session.buffer.cursor().row;
+    let Some(conflict) = MarkedConflict::find_all(lines)
+        .into_iter()
+        .find(|conflict| conflict.contains(row))
+    else {
+        app_state.notice =
+            Some("The cursor isn't in a conflict, Ctrl+N finds the next one".to_string());
+        return;
+    };
+    let kept: Vec<String> = conflict
+        .resolve(lines, resolution)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    session
+        .buffer
+        .replace_lines(conflict.start, conflict.end + 1 - conflict.start, &kept);
+    session.buffer.set_cursor(Cursor {
+        row: conflict.start,
+        col: 0,
+    });
+}
+
+/// Replace the whole text of `buffer`, keeping the edits before in the undo history
+fn replace_text(buffer: &mut TextBuffer, text: &str) {
+    let count = buffer.lines().len();
+    let lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+    buffer.replace_lines(0, count, &lines);
+}
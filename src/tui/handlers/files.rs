@@ -0,0 +1,144 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use ratatui::widgets::ListState;
+
+use crate::config::Config;
+use crate::export::{self, ExportFormat, ExportedEntry};
+use crate::models::entry_id::{EntryId, EntryRef};
+use crate::models::note::AnyId;
+use crate::storage::Storage;
+use crate::tui::models::state::{AppState, FileAction, PathPrompt};
+use crate::utils::file_dialog;
+
+/// Picking the file to attach to the selected entry or to export it to: in the native
+/// file dialog, or typed in the footer with Tab completion where there is no display
+pub struct FileHandler {
+    storage: Storage,
+    file_dialog: bool,
+}
+
+impl FileHandler {
+    pub fn new(storage: Storage, config: &Config) -> Self {
+        Self {
+            storage,
+            file_dialog: config.tui.file_dialog,
+        }
+    }
+
+    /// Ask for the file `action` needs. Returns it right away when the file dialog
+    /// answered, otherwise the footer prompts for it and `answer` returns it.
+    pub fn start(
+        &self,
+        action: FileAction,
+        app_state: &mut AppState,
+        tree_state: &ListState,
+    ) -> Option<(EntryId, PathBuf)> {
+        let Some(id) = selected_entry(app_state, tree_state) else {
+            app_state.notice = Some("Select a dated entry first".to_string());
+            return None;
+        };
+
+        if self.file_dialog && file_dialog::has_display() {
+            let picked = match action {
+                FileAction::Attach => file_dialog::pick_file(&format!("Attach to {}", id)),
+                FileAction::Export => {
+                    file_dialog::save_file(&format!("Export {}", id), &format!("{}.md", id))
+                }
+            };
+            // The dialog may have drawn over the terminal
+            app_state.needs_redraw = true;
+            if picked.is_none() {
+                app_state.notice = Some("Cancelled".to_string());
+            }
+            return picked.map(|path| (id, path));
+        }
+
+        app_state.pending_path = Some(PathPrompt {
+            action,
+            entry: id,
+            path: String::new(),
+        });
+        prompt_path(app_state);
+        None
+    }
+
+    /// Handle a key typed while `start` waits for a path, returns it on Enter
+    pub fn answer(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+    ) -> Option<(FileAction, EntryId, PathBuf)> {
+        let mut pending = app_state.pending_path.take()?;
+        match key_code {
+            KeyCode::Esc => {}
+            KeyCode::Enter if !pending.path.trim().is_empty() => {
+                let path = file_dialog::expand_home(pending.path.trim());
+                return Some((pending.action, pending.entry, path));
+            }
+            key_code => {
+                match key_code {
+                    KeyCode::Tab => {
+                        let cwd = env::current_dir().unwrap_or_default();
+                        pending.path = file_dialog::complete_path(&pending.path, &cwd);
+                    }
+                    KeyCode::Backspace => {
+                        pending.path.pop();
+                    }
+                    KeyCode::Char(ch) => pending.path.push(ch),
+                    _ => {}
+                }
+                app_state.pending_path = Some(pending);
+                prompt_path(app_state);
+            }
+        }
+        None
+    }
+
+    /// Write entry `id` to `path`, as JSON or HTML for those extensions and else as
+    /// Markdown
+    pub fn export(&self, id: &EntryId, path: &Path, app_state: &mut AppState) -> Result<()> {
+        let format = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => ExportFormat::Json,
+            Some("html" | "htm") => ExportFormat::Html,
+            _ => ExportFormat::Markdown,
+        };
+        let entry = ExportedEntry::from(self.storage.load_entry(id)?);
+        self.storage
+            .writer()
+            .write(path, export::render(&[entry], format)?)?;
+        app_state.notice = Some(format!("Exported {} to {}", id, path.display()));
+        Ok(())
+    }
+}
+
+/// The dated entry selected in the tree, the day of a selected sub-entry
+fn selected_entry(app_state: &AppState, tree_state: &ListState) -> Option<EntryId> {
+    let Some((name, _, true)) = tree_state
+        .selected()
+        .and_then(|selected| app_state.flat_items.get(selected))
+    else {
+        return None;
+    };
+    match name.parse().ok()? {
+        AnyId::Entry(EntryRef { id, .. }) => Some(id),
+        AnyId::Note(_) => None,
+    }
+}
+
+fn prompt_path(app_state: &mut AppState) {
+    if let Some(pending) = &app_state.pending_path {
+        let question = match pending.action {
+            FileAction::Attach => format!("Attach to {}", pending.entry),
+            FileAction::Export => format!("Export {} to (.md, .json or .html)", pending.entry),
+        };
+        app_state.notice = Some(format!(
+            "{} (Tab: complete | Enter: ok | Esc: cancel): {}",
+            question, pending.path
+        ));
+    }
+}
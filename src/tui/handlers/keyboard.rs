@@ -1,24 +1,47 @@
+use crate::config::Config;
+use crate::models::entry_id::EntryId;
 use crate::storage::Storage;
+use crate::tui::handlers::dashboard::DashboardHandler;
 use crate::tui::handlers::editor::EditorHandler;
+use crate::tui::handlers::files::FileHandler;
 use crate::tui::handlers::navigator::content::ContentNavigator;
 use crate::tui::handlers::navigator::tree::TreeNavigator;
-use crate::tui::models::state::{AppState, Panel};
+use crate::tui::handlers::review::ReviewHandler;
+use crate::tui::handlers::scratch::ScratchHandler;
+use crate::tui::handlers::search::SearchHandler;
+use crate::tui::models::{
+    layout::PanelLayout,
+    state::{AppState, FileAction, Panel},
+};
 use color_eyre::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::widgets::ListState;
+use std::path::{Path, PathBuf};
 
 pub struct KeyboardHandler {
     tree_navigator: TreeNavigator,
     content_navigator: ContentNavigator,
     editor: EditorHandler,
+    files: FileHandler,
+    scratch: ScratchHandler,
+    dashboard: DashboardHandler,
+    review: ReviewHandler,
+    search: SearchHandler,
+    storage: Storage,
 }
 
 impl KeyboardHandler {
-    pub fn new(storage: Storage) -> Self {
+    pub fn new(storage: Storage, config: &Config) -> Self {
         Self {
             tree_navigator: TreeNavigator::new(storage.clone()),
             content_navigator: ContentNavigator::new(),
-            editor: EditorHandler::new(storage),
+            editor: EditorHandler::new(storage.clone(), config),
+            files: FileHandler::new(storage.clone(), config),
+            scratch: ScratchHandler::new(storage.clone()),
+            dashboard: DashboardHandler::new(storage.clone()),
+            review: ReviewHandler::new(storage.clone()),
+            search: SearchHandler::new(storage.clone()),
+            storage,
         }
     }
 
@@ -28,6 +51,65 @@ impl KeyboardHandler {
         app_state: &mut AppState,
         tree_state: &mut ListState,
     ) -> Result<()> {
+        app_state.notice = None;
+        if app_state.pending_path.is_some() {
+            if let Some((action, id, path)) = self.files.answer(key_code, app_state) {
+                self.use_file(action, &id, &path, app_state, tree_state);
+            }
+            return Ok(());
+        }
+        if app_state.pending_attachment.is_some() {
+            return self
+                .editor
+                .answer_attachment(key_code, app_state, tree_state);
+        }
+        if app_state.pending_move.is_some() {
+            return self
+                .tree_navigator
+                .answer_move(key_code, app_state, tree_state);
+        }
+        if app_state.pending_new.is_some() {
+            // A new entry opens in the editor right away, an existing one too
+            if self
+                .tree_navigator
+                .answer_new(key_code, app_state, tree_state)?
+            {
+                app_state.current_panel = Panel::Content;
+                self.editor.edit_current_entry(app_state, tree_state)?;
+            }
+            return Ok(());
+        }
+        if app_state.pending_delete.is_some() {
+            return self
+                .tree_navigator
+                .answer_delete(key_code, app_state, tree_state);
+        }
+        if app_state
+            .search
+            .as_ref()
+            .is_some_and(|search| search.editing)
+        {
+            return self
+                .search
+                .handle_prompt_key(key_code, app_state, tree_state);
+        }
+        if app_state.dashboard.is_some() {
+            return self.dashboard.handle_key(key_code, app_state);
+        }
+        if app_state.review.is_some() {
+            return self.review.handle_key(key_code, app_state, tree_state);
+        }
+        if key_code == KeyCode::F(2) {
+            return self.scratch.toggle(app_state);
+        }
+        if app_state.scratch.is_some() {
+            if key_code == KeyCode::Char('e') {
+                self.editor.edit_scratch(app_state)?;
+                return self.scratch.reload(app_state);
+            }
+            return self.scratch.handle_key(key_code, app_state, tree_state);
+        }
+
         match key_code {
             KeyCode::Char('q') => {
                 app_state.should_quit = true;
@@ -35,29 +117,157 @@ impl KeyboardHandler {
             KeyCode::Tab => {
                 self.toggle_panel(app_state);
             }
+            KeyCode::Char('D') => {
+                self.dashboard.toggle(app_state)?;
+            }
+            KeyCode::Char('R') => {
+                self.review.open(app_state)?;
+            }
+            KeyCode::Char('/') => {
+                self.search.open(app_state);
+            }
+            KeyCode::Char('[') => self.change_layout(app_state, |layout| layout.resize(-1)),
+            KeyCode::Char(']') => self.change_layout(app_state, |layout| layout.resize(1)),
+            KeyCode::Char('z') => self.change_layout(app_state, PanelLayout::toggle_tree),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc
+                if app_state.search.is_some() =>
+            {
+                match key_code {
+                    KeyCode::Esc => self.search.clear(app_state, tree_state),
+                    _ => self
+                        .search
+                        .jump(key_code == KeyCode::Char('n'), app_state, tree_state),
+                }
+            }
+            KeyCode::Char('p') => {
+                self.editor.paste_image(app_state, tree_state)?;
+            }
+            KeyCode::Char('a') | KeyCode::Char('x') => {
+                let action = match key_code {
+                    KeyCode::Char('a') => FileAction::Attach,
+                    _ => FileAction::Export,
+                };
+                if let Some((id, path)) = self.files.start(action, app_state, tree_state) {
+                    self.use_file(action, &id, &path, app_state, tree_state);
+                }
+            }
+            KeyCode::Char('m') => {
+                if app_state.current_panel == Panel::Nav {
+                    self.tree_navigator.start_move(app_state, tree_state);
+                }
+            }
+            KeyCode::Char('n') => {
+                if app_state.current_panel == Panel::Nav {
+                    self.tree_navigator.start_new(app_state);
+                }
+            }
+            KeyCode::Char('d') => {
+                if app_state.current_panel == Panel::Nav {
+                    self.tree_navigator.start_delete(app_state, tree_state);
+                }
+            }
             KeyCode::Char('e') => {
                 if app_state.current_panel == Panel::Content {
                     self.editor.edit_current_entry(app_state, tree_state)?;
                 }
             }
+            KeyCode::Char('E') => {
+                if app_state.current_panel == Panel::Content {
+                    self.editor
+                        .edit_current_entry_externally(app_state, tree_state)?;
+                }
+            }
+            KeyCode::Char('o') => {
+                if app_state.current_panel == Panel::Content {
+                    self.editor
+                        .open_current_entry_externally(app_state, tree_state)?;
+                }
+            }
             _ => match app_state.current_panel {
                 Panel::Nav => {
                     self.tree_navigator
                         .handle_navigation(key_code, app_state, tree_state)?;
                 }
                 Panel::Content => {
-                    self.content_navigator
-                        .handle_navigation(key_code, app_state)?;
+                    // Enter follows a link at the top of the view before it toggles
+                    // code blocks
+                    let link = (key_code == KeyCode::Enter)
+                        .then(|| {
+                            app_state
+                                .selected_entry_content
+                                .link_at(app_state.content_scroll)
+                        })
+                        .flatten();
+                    match link {
+                        Some(target) => {
+                            self.tree_navigator
+                                .follow_link(target, app_state, tree_state)?;
+                        }
+                        None => {
+                            self.content_navigator
+                                .handle_navigation(key_code, app_state)?;
+                        }
+                    }
                 }
             },
         }
         Ok(())
     }
 
+    /// Keys of the embedded editor that aren't typing, e.g. saving and closing
+    pub fn handle_edit_key(
+        &self,
+        key: KeyEvent,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        if self.editor.handle_edit_key(key, app_state, tree_state)? {
+            // A sub-entry shows its own section, which the edit may have changed
+            self.tree_navigator.reload_content(app_state, tree_state);
+        }
+        Ok(())
+    }
+
+    /// Ask whether to attach a file dropped onto the TUI
+    pub fn offer_attachment(&self, path: PathBuf, app_state: &mut AppState) {
+        self.editor.offer_attachment(path, app_state);
+    }
+
+    /// Attach the picked file or export to it, failures are shown in the footer
+    fn use_file(
+        &self,
+        action: FileAction,
+        id: &EntryId,
+        path: &Path,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) {
+        let result = match action {
+            FileAction::Attach => self.editor.attach_file(path, app_state, tree_state),
+            FileAction::Export => self.files.export(id, path, app_state),
+        };
+        if let Err(e) = result {
+            app_state.notice = Some(format!("{:#}", e));
+        }
+    }
+
     fn toggle_panel(&self, app_state: &mut AppState) {
         app_state.current_panel = match app_state.current_panel {
+            // A hidden tree can't take the focus
+            _ if app_state.layout.tree_collapsed => Panel::Content,
             Panel::Nav => Panel::Content,
             Panel::Content => Panel::Nav,
         };
     }
+
+    /// Resize or hide the tree panel and remember the layout for the next launch
+    fn change_layout(&self, app_state: &mut AppState, change: impl FnOnce(&mut PanelLayout)) {
+        change(&mut app_state.layout);
+        if app_state.layout.tree_collapsed {
+            app_state.current_panel = Panel::Content;
+        }
+        if let Err(e) = app_state.layout.save(&self.storage) {
+            app_state.notice = Some(format!("{:#}", e));
+        }
+    }
 }
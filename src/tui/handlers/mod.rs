@@ -1,3 +1,9 @@
+pub mod dashboard;
 pub mod editor;
+pub mod files;
 pub mod keyboard;
 pub mod navigator;
+pub mod review;
+pub mod scratch;
+pub mod search;
+pub mod text_input;
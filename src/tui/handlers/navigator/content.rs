@@ -5,6 +5,12 @@ use crate::tui::models::state::AppState;
 
 pub struct ContentNavigator {}
 
+impl Default for ContentNavigator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ContentNavigator {
     pub fn new() -> Self {
         Self {}
@@ -34,6 +40,9 @@ impl ContentNavigator {
                     self.scroll_content_down(app_state);
                 }
             }
+            KeyCode::Enter => {
+                self.toggle_code_block(app_state);
+            }
             _ => {}
         }
 
@@ -46,27 +55,39 @@ impl ContentNavigator {
     }
 
     fn scroll_content_down(&self, app_state: &mut AppState) {
-        let content_lines = app_state.selected_entry_content.lines().count();
-        let max_scroll = content_lines.saturating_sub(1) as u16;
+        let content_lines = app_state.selected_entry_content.row_count();
+        let max_scroll = content_lines.saturating_sub(1);
         if app_state.content_scroll < max_scroll {
             app_state.content_scroll += 1;
         }
     }
 
     fn scroll_to_bottom(&self, app_state: &mut AppState) {
-        let content_lines = app_state.selected_entry_content.lines().count();
-        let max_scroll = content_lines.saturating_sub(1) as u16;
+        let content_lines = app_state.selected_entry_content.row_count();
+        let max_scroll = content_lines.saturating_sub(1);
         app_state.content_scroll = max_scroll;
     }
 
     fn reset_content_scroll(&self, app_state: &mut AppState) {
         app_state.content_scroll = 0;
     }
+
+    /// Expand or collapse the long code block at the top of the view, or the next one
+    /// below, and scroll to it
+    fn toggle_code_block(&self, app_state: &mut AppState) {
+        if let Some(row) = app_state
+            .selected_entry_content
+            .toggle_code_block(app_state.content_scroll)
+        {
+            app_state.content_scroll = row;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::display::DisplayOptions;
 
     #[test]
     fn test_content_navigator_scroll_up() {
@@ -88,7 +109,10 @@ mod tests {
         let mut state = AppState::new();
 
         // Set up some content with multiple lines
-        state.selected_entry_content = "Line 1\nLine 2\nLine 3\nLine 4\nLine 5".to_string();
+        state.set_entry_content(
+            "Line 1\nLine 2\nLine 3\nLine 4\nLine 5",
+            &DisplayOptions::default(),
+        );
 
         navigator.scroll_content_down(&mut state);
         assert_eq!(state.content_scroll, 1);
@@ -1,12 +1,23 @@
+use chrono::Local;
 use color_eyre::Result;
 use crossterm::event::KeyCode;
 use ratatui::widgets::ListState;
 
 use crate::{
-    storage::Storage,
+    models::{
+        display::DisplayOptions,
+        entry::Entry,
+        entry_id::{EntryId, EntryRef},
+        link::LinkTarget,
+        note::AnyId,
+    },
+    storage::{Storage, focus::FocusSession, section::SectionTarget},
     tui::{
-        models::{node::TreeNode, state::AppState},
-        tree::flattener::TreeFlattener,
+        models::{
+            node::{TreeNode, keep_expanded, reveal},
+            state::{AppState, SectionMove},
+        },
+        tree::{builder::TreeBuilder, flattener::TreeFlattener},
     },
 };
 
@@ -46,6 +57,269 @@ impl TreeNavigator {
         Ok(())
     }
 
+    /// Ask where to move the selected sub-entry, the target is typed in the footer
+    pub fn start_move(&self, app_state: &mut AppState, tree_state: &ListState) {
+        let from = tree_state
+            .selected()
+            .and_then(|selected| app_state.flat_items.get(selected))
+            .filter(|(_, _, is_entry)| *is_entry)
+            .and_then(|(id, _, _)| id.parse::<EntryRef>().ok())
+            .filter(|entry_ref| entry_ref.time.is_some());
+        match from {
+            Some(from) => {
+                app_state.pending_move = Some(SectionMove {
+                    from,
+                    target: String::new(),
+                });
+                prompt_move(app_state);
+            }
+            None => app_state.notice = Some("Select a sub-entry to move it".to_string()),
+        }
+    }
+
+    /// Handle a key typed while `start_move` waits for the target
+    pub fn answer_move(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(mut pending) = app_state.pending_move.take() else {
+            return Ok(());
+        };
+        match key_code {
+            KeyCode::Esc => {}
+            KeyCode::Enter => {
+                let moved = SectionTarget::parse(&pending.target)
+                    .and_then(|to| self.move_section(&pending.from, &to, app_state, tree_state));
+                if let Err(e) = moved {
+                    app_state.notice = Some(format!("{:#}", e));
+                }
+            }
+            key_code => {
+                match key_code {
+                    KeyCode::Backspace => {
+                        pending.target.pop();
+                    }
+                    KeyCode::Char(ch) => pending.target.push(ch),
+                    _ => {}
+                }
+                app_state.pending_move = Some(pending);
+                prompt_move(app_state);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ask for the date of a new entry in the footer, starting from today
+    pub fn start_new(&self, app_state: &mut AppState) {
+        app_state.pending_new = Some(EntryId::today().to_string());
+        prompt_new(app_state);
+    }
+
+    /// Handle a key typed while `start_new` waits for the date, returns whether the
+    /// entry is ready to be written, new or already there
+    pub fn answer_new(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<bool> {
+        let Some(mut typed) = app_state.pending_new.take() else {
+            return Ok(false);
+        };
+        match key_code {
+            KeyCode::Esc => {}
+            KeyCode::Enter => {
+                match EntryId::parse(typed.trim())
+                    .and_then(|id| self.create_entry(&id, app_state, tree_state))
+                {
+                    Ok(()) => return Ok(true),
+                    Err(e) => app_state.notice = Some(format!("{:#}", e)),
+                }
+            }
+            key_code => {
+                match key_code {
+                    KeyCode::Backspace => {
+                        typed.pop();
+                    }
+                    KeyCode::Char(ch) => typed.push(ch),
+                    _ => {}
+                }
+                app_state.pending_new = Some(typed);
+                prompt_new(app_state);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Create entry `id` unless it exists, and select it in the tree
+    fn create_entry(
+        &self,
+        id: &EntryId,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        if self.storage.entry_path(id)?.exists() {
+            app_state.notice = Some(format!("Entry {} already exists", id));
+        } else {
+            self.storage.save_entry(&Entry::new(*id, String::new()))?;
+            self.rebuild_tree(app_state)?;
+        }
+
+        select(&id.to_string(), app_state, tree_state);
+        self.update_content_panel(app_state, tree_state)
+    }
+
+    /// Open the target of a `[[link]]`: the entry, sub-entry or note it names is
+    /// selected in the tree, a project shows its profile
+    pub fn follow_link(
+        &self,
+        target: LinkTarget,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let name = match &target {
+            LinkTarget::Entry(entry_ref) => entry_ref.to_string(),
+            LinkTarget::Name(name) => name.clone(),
+        };
+        if select(&name, app_state, tree_state) {
+            return self.update_content_panel(app_state, tree_state);
+        }
+        if let LinkTarget::Name(name) = &target
+            && let Some(profile) = self.storage.load_project(name)?
+        {
+            app_state.set_entry_content(profile.body, &DisplayOptions::default());
+            app_state.notice = Some(format!("Project ::{}", name));
+            return Ok(());
+        }
+        app_state.notice = Some(format!("Nothing to open for [[{}]]", name));
+        Ok(())
+    }
+
+    /// Ask whether to move the selected entry to the trash
+    pub fn start_delete(&self, app_state: &mut AppState, tree_state: &ListState) {
+        let id = tree_state
+            .selected()
+            .and_then(|selected| app_state.flat_items.get(selected))
+            .filter(|(_, _, is_entry)| *is_entry)
+            .and_then(|(id, _, _)| id.parse::<EntryRef>().ok())
+            .filter(|entry_ref| entry_ref.time.is_none())
+            .map(|entry_ref| entry_ref.id);
+        match id {
+            Some(id) => {
+                app_state.pending_delete = Some(id);
+                prompt_delete(app_state);
+            }
+            None => app_state.notice = Some("Select a day's entry to delete it".to_string()),
+        }
+    }
+
+    /// Handle the answer to `start_delete`, other keys leave the question open
+    pub fn answer_delete(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(id) = app_state.pending_delete.take() else {
+            return Ok(());
+        };
+        match key_code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Err(e) = self.delete_entry(&id, app_state, tree_state) {
+                    app_state.notice = Some(format!("{:#}", e));
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {}
+            _ => {
+                app_state.pending_delete = Some(id);
+                prompt_delete(app_state);
+            }
+        }
+        Ok(())
+    }
+
+    fn delete_entry(
+        &self,
+        id: &EntryId,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        self.storage.trash_entry(id, Local::now().naive_local())?;
+        self.rebuild_tree(app_state)?;
+        if let Some(selected) = tree_state.selected()
+            && selected >= app_state.flat_items.len()
+        {
+            tree_state.select(app_state.flat_items.len().checked_sub(1));
+        }
+        self.update_content_panel(app_state, tree_state)?;
+        app_state.notice = Some(format!(
+            "Moved {} to the trash, `devlog trash restore {}` brings it back",
+            id, id
+        ));
+        Ok(())
+    }
+
+    /// Rebuild the tree after an entry was added or removed, keeping open folders open.
+    /// A search is closed, its results no longer match the tree.
+    fn rebuild_tree(&self, app_state: &mut AppState) -> Result<()> {
+        let mut nodes = TreeBuilder::new(self.storage.clone()).build_tree()?;
+        keep_expanded(&app_state.tree_nodes, &mut nodes);
+        app_state.search = None;
+        app_state.tree_nodes = nodes;
+        app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
+        Ok(())
+    }
+
+    fn move_section(
+        &self,
+        from: &EntryRef,
+        to: &SectionTarget,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let destination = match to {
+            SectionTarget::Entry(id) => Some(*id),
+            SectionTarget::Project(_) => None,
+        };
+        let now = Local::now().fixed_offset();
+        if let Some(session) = FocusSession::load(&self.storage, now)?
+            && let Some(message) = [Some(from.id), destination]
+                .into_iter()
+                .flatten()
+                .find_map(|id| session.blocks(&id, now))
+        {
+            app_state.notice = Some(message);
+            return Ok(());
+        }
+
+        let is_new_entry = match destination {
+            Some(id) => !self.storage.entry_path(&id)?.exists(),
+            None => false,
+        };
+        let heading = from
+            .time
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_default();
+        let moved = self.storage.move_section(&from.id, &heading, to)?;
+
+        show_written_entry(&self.storage, &from.id, is_new_entry, app_state, tree_state)?;
+        if let Some(id) = destination
+            && !is_new_entry
+        {
+            show_written_entry(&self.storage, &id, false, app_state, tree_state)?;
+        }
+        if let Some(selected) = tree_state.selected()
+            && selected >= app_state.flat_items.len()
+        {
+            tree_state.select(app_state.flat_items.len().checked_sub(1));
+        }
+        self.update_content_panel(app_state, tree_state)?;
+        app_state.notice = Some(format!("Moved '{}' to {}", moved, to));
+        Ok(())
+    }
+
     /// Move the selection up by one position in the list widget
     fn move_up(&self, tree_state: &mut ListState) {
         let selected = tree_state.selected().unwrap_or(0);
@@ -62,19 +336,14 @@ impl TreeNavigator {
     }
 
     fn toggle_node(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<()> {
-        if let Some(selected) = tree_state.selected() {
-            if let Some((_, _, is_entry)) = app_state.flat_items.get(selected) {
-                if !is_entry {
-                    // It's a folder, toggle expansion
-                    let mut current_index = 0;
-                    Self::toggle_node_recursive(
-                        &mut app_state.tree_nodes,
-                        selected,
-                        &mut current_index,
-                    )?;
-                    app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
-                }
-            }
+        if let Some(selected) = tree_state.selected()
+            && app_state.flat_items.get(selected).is_some()
+        {
+            // Folders and entries with sub-entries expand, for other entries
+            // the flag has no effect
+            let mut current_index = 0;
+            Self::toggle_node_recursive(&mut app_state.tree_nodes, selected, &mut current_index)?;
+            app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
         }
         Ok(())
     }
@@ -132,31 +401,155 @@ impl TreeNavigator {
         Ok(false)
     }
 
+    /// Show the current content of the selected entry, keeping the scroll position
+    pub fn reload_content(&self, app_state: &mut AppState, tree_state: &ListState) {
+        if let Some((entry_id, _, true)) = tree_state
+            .selected()
+            .and_then(|selected| app_state.flat_items.get(selected))
+            && let Ok((content, _)) = entry_id.parse().and_then(|id| self.load_content(id))
+        {
+            app_state.update_entry_content(content);
+        }
+    }
+
+    /// Content of an entry, one of its sub-entries or a note, with the display
+    /// options of the entry
+    fn load_content(&self, id: AnyId) -> Result<(String, DisplayOptions)> {
+        match id {
+            AnyId::Entry(entry_ref) => {
+                let entry = self.storage.load_entry(&entry_ref.id)?;
+                let content = match entry_ref.time.and_then(|time| entry.sub_entry(time)) {
+                    Some(sub_entry) => sub_entry.content,
+                    None => entry.content,
+                };
+                Ok((content, entry.display))
+            }
+            AnyId::Note(id) => Ok((
+                self.storage.load_note(&id)?.content,
+                DisplayOptions::default(),
+            )),
+        }
+    }
+
     fn update_content_panel(
         &self,
         app_state: &mut AppState,
         tree_state: &mut ListState,
     ) -> Result<()> {
-        if let Some(selected) = tree_state.selected() {
-            if let Some((entry_id, _, is_entry)) = app_state.flat_items.get(selected) {
-                if *is_entry {
-                    match self.storage.load_entry(entry_id) {
-                        Ok(entry) => {
-                            app_state.selected_entry_content = entry.content;
-                            app_state.reset_content_scroll();
-                        }
-                        Err(_) => {
-                            app_state.selected_entry_content = "Error loading entry".to_string();
-                            app_state.reset_content_scroll();
-                        }
-                    }
-                } else {
-                    app_state.selected_entry_content =
-                        "Select an entry to view its content".to_string();
-                    app_state.reset_content_scroll();
+        if let Some(selected) = tree_state.selected()
+            && let Some((entry_id, _, is_entry)) = app_state.flat_items.get(selected)
+        {
+            if *is_entry {
+                let id: Result<AnyId> = entry_id.parse();
+                match id.and_then(|id| self.load_content(id)) {
+                    Ok((content, display)) => app_state.set_entry_content(content, &display),
+                    Err(_) => app_state
+                        .set_entry_content("Error loading entry", &DisplayOptions::default()),
                 }
+            } else {
+                app_state.set_entry_content(
+                    "Select an entry to view its content",
+                    &DisplayOptions::default(),
+                );
             }
         }
         Ok(())
     }
 }
+
+/// Select the entry, sub-entry or note `name` in the tree, expanding the folders it
+/// is in. Returns whether the tree has it.
+fn select(name: &str, app_state: &mut AppState, tree_state: &mut ListState) -> bool {
+    if reveal(&mut app_state.tree_nodes, name) {
+        app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
+    }
+    let index = app_state
+        .flat_items
+        .iter()
+        .position(|(item, _, is_entry)| *is_entry && *item == name);
+    if index.is_some() {
+        tree_state.select(index);
+    }
+    index.is_some()
+}
+
+fn prompt_move(app_state: &mut AppState) {
+    if let Some(pending) = &app_state.pending_move {
+        app_state.notice = Some(format!(
+            "Move {} to (YYYYMMDD or ::project, Enter: move | Esc: cancel): {}",
+            pending.from, pending.target
+        ));
+    }
+}
+
+fn prompt_new(app_state: &mut AppState) {
+    if let Some(typed) = &app_state.pending_new {
+        app_state.notice = Some(format!(
+            "New entry (YYYYMMDD, Enter: create | Esc: cancel): {}",
+            typed
+        ));
+    }
+}
+
+fn prompt_delete(app_state: &mut AppState) {
+    if let Some(id) = &app_state.pending_delete {
+        app_state.notice = Some(format!("Move {} to the trash? y: delete | n: keep", id));
+    }
+}
+
+/// Show entry `id` after it was written from the TUI: a new entry is added to the
+/// tree, an existing one is refreshed in the content panel if it is selected
+pub fn show_written_entry(
+    storage: &Storage,
+    id: &EntryId,
+    is_new_entry: bool,
+    app_state: &mut AppState,
+    tree_state: &mut ListState,
+) -> Result<()> {
+    if is_new_entry {
+        app_state.tree_nodes = TreeBuilder::new(storage.clone()).build_tree()?;
+        app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
+        tree_state.select((!app_state.flat_items.is_empty()).then_some(0));
+    } else {
+        let entry = storage.load_entry(id)?;
+        if refresh_sub_entries(&mut app_state.tree_nodes, &entry) {
+            app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
+        }
+        if let Some(selected) = tree_state.selected()
+            && app_state
+                .flat_items
+                .get(selected)
+                .is_some_and(|(selected_id, _, _)| *selected_id == id.to_string())
+        {
+            app_state.update_entry_content(entry.content);
+        }
+    }
+    Ok(())
+}
+
+/// Replace the sub-entry nodes of `entry`'s day, returning whether they changed
+fn refresh_sub_entries(nodes: &mut [TreeNode], entry: &Entry) -> bool {
+    let name = entry.id.to_string();
+    for node in nodes {
+        if node.is_entry && node.name == name {
+            let children: Vec<TreeNode> = entry
+                .sub_entries()
+                .iter()
+                .map(|sub_entry| {
+                    TreeNode::new_entry(EntryRef::sub_entry(entry.id, sub_entry.time).to_string())
+                })
+                .collect();
+            let changed = node
+                .children
+                .iter()
+                .map(|child| &child.name)
+                .ne(children.iter().map(|child| &child.name));
+            node.children = children;
+            return changed;
+        }
+        if refresh_sub_entries(&mut node.children, entry) {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,90 @@
+use chrono::{Days, Local};
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use ratatui::widgets::ListState;
+
+use crate::digest::DIGEST_DAYS;
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+use crate::tui::{
+    handlers::navigator::tree::show_written_entry,
+    models::{review::WeeklyReview, state::AppState},
+};
+
+/// Keys of the weekly review, opened with `R`
+pub struct ReviewHandler {
+    storage: Storage,
+}
+
+impl ReviewHandler {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Start reviewing the seven days up to yesterday, like the weekly digest
+    pub fn open(&self, app_state: &mut AppState) -> Result<()> {
+        let today = Local::now().date_naive();
+        let end = today.pred_opt().unwrap_or(today);
+        let start = end
+            .checked_sub_days(Days::new(DIGEST_DAYS - 1))
+            .unwrap_or(end);
+
+        let mut entries = Vec::new();
+        for id in self.storage.list_entries()? {
+            if (start..=end).contains(&id.date()) {
+                entries.push(self.storage.load_entry(&id)?);
+            }
+        }
+        entries.sort_by_key(|entry| entry.id);
+
+        if entries.is_empty() {
+            app_state.notice = Some(format!(
+                "No entries from {} to {} to review",
+                start.format("%a %d %b"),
+                end.format("%a %d %b")
+            ));
+        } else {
+            app_state.review = Some(WeeklyReview::new(start, end, entries));
+        }
+        Ok(())
+    }
+
+    pub fn handle_key(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(review) = app_state.review.as_mut() else {
+            return Ok(());
+        };
+
+        match key_code {
+            KeyCode::Esc => app_state.review = None,
+            KeyCode::Enter if review.is_finished() => self.save(app_state, tree_state)?,
+            KeyCode::Enter => review.submit(),
+            KeyCode::Tab => review.next_prompt(),
+            KeyCode::BackTab => review.previous_prompt(),
+            KeyCode::Right | KeyCode::PageDown => review.next(),
+            KeyCode::Left | KeyCode::PageUp => review.previous(),
+            KeyCode::Backspace => review.backspace(),
+            KeyCode::Char(c) => review.type_char(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Append the rollup to today's entry and close the review
+    fn save(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<()> {
+        let Some(review) = app_state.review.take() else {
+            return Ok(());
+        };
+
+        let today = EntryId::today();
+        let is_new_entry = !self.storage.entry_path(&today)?.exists();
+        self.storage.append_to_entry(&today, &review.rollup())?;
+        app_state.notice = Some("Weekly review added to today's entry".to_string());
+
+        show_written_entry(&self.storage, &today, is_new_entry, app_state, tree_state)
+    }
+}
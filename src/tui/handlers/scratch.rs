@@ -0,0 +1,74 @@
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use ratatui::widgets::ListState;
+
+use crate::models::entry_id::EntryId;
+use crate::storage::Storage;
+use crate::tui::{
+    handlers::navigator::tree::show_written_entry,
+    models::{scratch::ScratchPad, state::AppState},
+};
+
+/// Keys of the scratch pad, toggled with F2
+pub struct ScratchHandler {
+    storage: Storage,
+}
+
+impl ScratchHandler {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Open the scratch pad, or close it if it is open
+    pub fn toggle(&self, app_state: &mut AppState) -> Result<()> {
+        app_state.scratch = match app_state.scratch {
+            Some(_) => None,
+            None => Some(ScratchPad::new(&self.storage.load_scratch()?)),
+        };
+        Ok(())
+    }
+
+    /// Re-read the scratch pad, e.g. after it was edited externally
+    pub fn reload(&self, app_state: &mut AppState) -> Result<()> {
+        let cursor = app_state.scratch.as_ref().map_or(0, |pad| pad.cursor);
+        let mut pad = ScratchPad::new(&self.storage.load_scratch()?);
+        pad.cursor = cursor.min(pad.lines.len().saturating_sub(1));
+        app_state.scratch = Some(pad);
+        Ok(())
+    }
+
+    pub fn handle_key(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(pad) = app_state.scratch.as_mut() else {
+            return Ok(());
+        };
+
+        match key_code {
+            KeyCode::Esc | KeyCode::Char('q') => app_state.scratch = None,
+            KeyCode::Up | KeyCode::Char('k') => pad.move_up(),
+            KeyCode::Down | KeyCode::Char('j') => pad.move_down(),
+            KeyCode::Char(' ') => pad.toggle_mark(),
+            KeyCode::Char('m') => self.move_to_today(app_state, tree_state)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Move the selected lines into today's entry
+    fn move_to_today(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<()> {
+        let Some(pad) = app_state.scratch.as_ref() else {
+            return Ok(());
+        };
+
+        let today = EntryId::today();
+        let is_new_entry = !self.storage.entry_path(&today)?.exists();
+        self.storage.move_scratch_lines(&pad.selected(), &today)?;
+        self.reload(app_state)?;
+
+        show_written_entry(&self.storage, &today, is_new_entry, app_state, tree_state)
+    }
+}
@@ -0,0 +1,140 @@
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use ratatui::widgets::ListState;
+
+use crate::models::display::DisplayOptions;
+use crate::models::note::AnyId;
+use crate::search;
+use crate::storage::Storage;
+use crate::tui::{
+    models::{
+        search::{SearchState, filter_tree},
+        state::AppState,
+    },
+    tree::flattener::TreeFlattener,
+};
+
+/// Keys of the `/` search: typing the query, then `n`/`N` through the matches
+pub struct SearchHandler {
+    storage: Storage,
+}
+
+impl SearchHandler {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Open the search prompt, starting from the last query
+    pub fn open(&self, app_state: &mut AppState) {
+        app_state
+            .search
+            .get_or_insert_with(SearchState::default)
+            .edit();
+    }
+
+    pub fn handle_prompt_key(
+        &self,
+        key_code: KeyCode,
+        app_state: &mut AppState,
+        tree_state: &mut ListState,
+    ) -> Result<()> {
+        let Some(search) = app_state.search.as_mut() else {
+            return Ok(());
+        };
+
+        match key_code {
+            KeyCode::Esc if search.saved_tree.is_some() => search.editing = false,
+            KeyCode::Esc => app_state.search = None,
+            KeyCode::Enter if search.query.trim().is_empty() => {
+                self.clear(app_state, tree_state);
+            }
+            KeyCode::Enter => self.run(app_state, tree_state)?,
+            KeyCode::Backspace => {
+                search.query.pop();
+            }
+            KeyCode::Char(c) => search.query.push(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Jump to the next match, or the previous one, opening its entry or note
+    pub fn jump(&self, forward: bool, app_state: &mut AppState, tree_state: &mut ListState) {
+        let Some((id, line)) = app_state
+            .search
+            .as_mut()
+            .and_then(|search| search.jump(forward))
+        else {
+            return;
+        };
+        let name = id.to_string();
+        let Some(index) = app_state
+            .flat_items
+            .iter()
+            .position(|(item, _, is_entry)| *is_entry && *item == name)
+        else {
+            return;
+        };
+
+        if tree_state.selected() != Some(index) {
+            tree_state.select(Some(index));
+            let loaded = match &id {
+                AnyId::Entry(entry_ref) => self
+                    .storage
+                    .load_entry(&entry_ref.id)
+                    .map(|entry| (entry.content, entry.display)),
+                AnyId::Note(id) => self
+                    .storage
+                    .load_note(id)
+                    .map(|note| (note.content, DisplayOptions::default())),
+            };
+            match loaded {
+                Ok((content, display)) => app_state.set_entry_content(content, &display),
+                Err(_) => {
+                    app_state.set_entry_content("Error loading entry", &DisplayOptions::default())
+                }
+            }
+        }
+        app_state.content_scroll = app_state.selected_entry_content.row_of_line(line);
+    }
+
+    /// Close the search and put the full tree back
+    pub fn clear(&self, app_state: &mut AppState, tree_state: &mut ListState) {
+        let Some(search) = app_state.search.take() else {
+            return;
+        };
+        if let Some(tree) = search.saved_tree {
+            app_state.tree_nodes = tree;
+            app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
+            let last = app_state.flat_items.len().checked_sub(1);
+            tree_state.select(tree_state.selected().zip(last).map(|(s, l)| s.min(l)));
+        }
+    }
+
+    /// Search every entry and note for the query and show only the matching ones
+    fn run(&self, app_state: &mut AppState, tree_state: &mut ListState) -> Result<()> {
+        let Some(search) = app_state.search.as_mut() else {
+            return Ok(());
+        };
+        let found = search::search(&self.storage, search.query.trim())?;
+        search.set_results(&found.matches);
+        if found.matches.is_empty() {
+            app_state.notice = Some(format!("No entries match '{}'", search.query.trim()));
+        } else if !found.unreadable.is_empty() {
+            app_state.notice = Some(format!(
+                "Skipped {} unreadable entries",
+                found.unreadable.len()
+            ));
+        }
+
+        let names = search.entry_names();
+        let saved = search
+            .saved_tree
+            .get_or_insert_with(|| app_state.tree_nodes.clone());
+        app_state.tree_nodes = filter_tree(saved, &names);
+        app_state.flat_items = TreeFlattener::flatten(&app_state.tree_nodes);
+        tree_state.select(None);
+        self.jump(true, app_state, tree_state);
+        Ok(())
+    }
+}
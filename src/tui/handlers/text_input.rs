@@ -0,0 +1,71 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::editing::{EditorBuffer, buffer::TextBuffer};
+
+/// Apply a key press to the text being edited, returns false for keys
+/// that aren't editing keys so callers can handle them, e.g. Esc
+pub fn handle_key(buffer: &mut TextBuffer, key: KeyEvent) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    match key.code {
+        // Terminals report Ctrl+Space as Ctrl+@ or as a NUL character
+        KeyCode::Char(' ' | '@') if ctrl => buffer.toggle_checkbox(),
+        KeyCode::Null => buffer.toggle_checkbox(),
+        KeyCode::Char('b') if ctrl => buffer.toggle_wrap("**"),
+        // Most terminals only tell Ctrl+I apart from Tab with keyboard enhancements on
+        KeyCode::Char('i') if ctrl => buffer.toggle_wrap("*"),
+        KeyCode::Tab => {
+            let handled =
+                buffer.next_tab_stop() || buffer.expand_snippet() || buffer.indent_list_item();
+            if !handled {
+                buffer.insert_char('\t');
+            }
+        }
+        KeyCode::BackTab => {
+            buffer.outdent_list_item();
+        }
+        _ => return handle_editing_key(buffer, key),
+    }
+    true
+}
+
+/// The keys any [`EditorBuffer`] understands: typing, movement, selection and undo.
+/// Returns false for keys it doesn't handle.
+pub fn handle_editing_key<B: EditorBuffer>(buffer: &mut B, key: KeyEvent) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    // Shift+movement selects, any other movement drops the selection
+    let is_movement = matches!(
+        key.code,
+        KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down | KeyCode::Home | KeyCode::End
+    );
+    if is_movement {
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            buffer.extend_selection();
+        } else {
+            buffer.clear_selection();
+        }
+    }
+
+    match key.code {
+        KeyCode::Char('z') if ctrl => {
+            buffer.undo();
+        }
+        KeyCode::Char('y') if ctrl => {
+            buffer.redo();
+        }
+        KeyCode::Char(_) if ctrl => return false,
+        KeyCode::Char(ch) => buffer.insert_char(ch),
+        KeyCode::Enter => buffer.insert_newline(),
+        KeyCode::Backspace => buffer.backspace(),
+        KeyCode::Delete => buffer.delete(),
+        KeyCode::Left => buffer.move_left(),
+        KeyCode::Right => buffer.move_right(),
+        KeyCode::Up => buffer.move_up(),
+        KeyCode::Down => buffer.move_down(),
+        KeyCode::Home => buffer.move_home(),
+        KeyCode::End => buffer.move_end(),
+        _ => return false,
+    }
+    true
+}
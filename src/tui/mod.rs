@@ -1,5 +1,6 @@
 pub mod app;
 pub mod components;
+pub mod event;
 pub mod handlers;
 pub mod models;
 pub mod tree;
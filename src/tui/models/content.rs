@@ -0,0 +1,955 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+    mem,
+    ops::Range,
+};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::export::mermaid;
+use crate::models::link::{self, LinkTarget};
+
+/// Entries bigger than this show a warning banner suggesting to open them externally
+pub const LARGE_CONTENT_BYTES: usize = 1024 * 1024;
+
+/// Number of source lines wrapped at a time, so huge entries are only laid out
+/// as far as the user actually scrolls
+const LAYOUT_CHUNK_LINES: usize = 500;
+
+/// Number of laid out entries kept around for quickly switching back to them
+const LAYOUT_CACHE_SIZE: usize = 16;
+
+/// Markdown role of a row, decided once when the row is laid out. A code block too
+/// long to show, or a mermaid diagram, is a single `CollapsedCode` row of `lines` lines. `DefinitionTerm` is
+/// the line right before a `: definition`, `Footnote` a `[^1]: footnote` line.
+/// `Math` rows are the LaTeX between `$$` lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowKind {
+    Text,
+    Heading,
+    ListItem,
+    Task { done: bool },
+    Quote,
+    CodeFence { opening: bool },
+    Code,
+    CollapsedCode { lines: usize },
+    DefinitionTerm,
+    Definition,
+    Footnote,
+    MathFence { opening: bool },
+    Math,
+}
+
+/// A row in view together with the source line it was wrapped from, so styles
+/// spanning the whole line, like `**bold**` wrapped over two rows, come out right
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibleRow<'a> {
+    pub kind: RowKind,
+    /// The source line without its line break
+    pub line: &'a str,
+    /// Byte range of the row within `line`
+    pub range: Range<usize>,
+    /// Language of the fenced code block the row belongs to, e.g. `rust`
+    pub lang: Option<&'a str>,
+    /// Title of the diagram a collapsed mermaid block stands for
+    pub title: Option<&'a str>,
+}
+
+impl<'a> VisibleRow<'a> {
+    pub fn text(&self) -> &'a str {
+        &self.line[self.range.clone()]
+    }
+}
+
+/// Content of the selected entry with a lazily built, cached wrapped-line layout.
+///
+/// Rendering only touches the rows in view, and the layout is rebuilt only when
+/// the content or the panel width changes. Edits only lay out the lines from
+/// the first changed one onwards again.
+#[derive(Debug, Default)]
+pub struct EntryContent {
+    text: String,
+    hash: u64,
+    layout: RefCell<WrappedLayout>,
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    range: Range<usize>,
+    /// Byte offset of the source line this row was wrapped from
+    line_start: usize,
+    kind: RowKind,
+    /// Byte range of the code block's language, on fences and code rows
+    lang: Option<Range<usize>>,
+    /// Byte range of the diagram's title, on collapsed mermaid blocks
+    title: Option<Range<usize>>,
+}
+
+/// Wrapped rows for a single panel width
+#[derive(Debug, Default)]
+struct WrappedLayout {
+    width: usize,
+    rows: Vec<Row>,
+    /// Byte offset of the first source line that hasn't been wrapped yet
+    next_line_start: usize,
+    in_code_block: bool,
+    in_math_block: bool,
+    /// Byte range of the language after the opening fence of the current code block
+    code_lang: Option<Range<usize>>,
+    /// Code blocks longer than this many lines are collapsed, 0 never collapses
+    collapse_after: usize,
+    /// Keep each line on a single row instead of wrapping it, the panel cuts it off
+    no_wrap: bool,
+    /// Byte offsets of the opening fences of long code blocks shown in full
+    expanded: BTreeSet<usize>,
+}
+
+impl EntryContent {
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            hash: content_hash(&text),
+            text,
+            layout: RefCell::default(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Whether the entry is big enough to warrant the "open externally" banner
+    pub fn is_large(&self) -> bool {
+        self.text.len() > LARGE_CONTENT_BYTES
+    }
+
+    /// Replace the content after an edit, keeping the layout of every line
+    /// before the first change
+    pub fn replace(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        let unchanged = unchanged_prefix_len(&self.text, &text);
+        let unchanged_lines_end = self.text[..unchanged].rfind('\n').map_or(0, |i| i + 1);
+
+        let layout = self.layout.get_mut();
+        layout.truncate(unchanged_lines_end);
+        layout
+            .expanded
+            .retain(|&fence_start| fence_start < unchanged_lines_end);
+        self.hash = content_hash(&text);
+        self.text = text;
+    }
+
+    /// Collapse fenced code blocks longer than `lines` lines into a summary row, 0
+    /// shows every block in full
+    pub fn collapse_code_after(&mut self, lines: usize) {
+        let layout = self.layout.get_mut();
+        if layout.collapse_after != lines {
+            *layout = WrappedLayout {
+                width: layout.width,
+                collapse_after: lines,
+                no_wrap: layout.no_wrap,
+                expanded: mem::take(&mut layout.expanded),
+                ..WrappedLayout::default()
+            };
+        }
+    }
+
+    /// Wrap long lines to the panel width, or keep each on a single row for entries
+    /// with `wrap: false` in their frontmatter
+    pub fn wrap_lines(&mut self, wrap: bool) {
+        let layout = self.layout.get_mut();
+        if layout.no_wrap == wrap {
+            *layout = WrappedLayout {
+                width: layout.width,
+                collapse_after: layout.collapse_after,
+                no_wrap: !wrap,
+                expanded: mem::take(&mut layout.expanded),
+                ..WrappedLayout::default()
+            };
+        }
+    }
+
+    /// Expand the long code block at row `row`, or the next one after it, or collapse
+    /// it again. Returns the row the block now starts at, `None` without a long block.
+    pub fn toggle_code_block(&mut self, row: usize) -> Option<usize> {
+        let layout = self.layout.get_mut();
+        if layout.width == 0 {
+            return None;
+        }
+        layout.extend_to(&self.text, row.saturating_add(1));
+
+        let is_long_block = |row: &Row, expanded: &BTreeSet<usize>| match row.kind {
+            RowKind::CollapsedCode { .. } => true,
+            RowKind::CodeFence { opening: true } => expanded.contains(&row.line_start),
+            _ => false,
+        };
+        // Inside an expanded block, its own opening fence
+        let enclosing = layout.rows.get(row).and_then(|current| match current.kind {
+            RowKind::Code | RowKind::CodeFence { opening: false } => layout.rows[..row]
+                .iter()
+                .rev()
+                .find(|row| row.kind == RowKind::CodeFence { opening: true }),
+            _ => None,
+        });
+        let mut fence_start = enclosing
+            .filter(|fence| is_long_block(fence, &layout.expanded))
+            .map(|fence| fence.line_start);
+        let mut next = row;
+        while fence_start.is_none() {
+            if next >= layout.rows.len() {
+                if layout.next_line_start >= self.text.len() {
+                    return None;
+                }
+                layout.extend_to(&self.text, layout.rows.len() + 1);
+                continue;
+            }
+            fence_start = Some(&layout.rows[next])
+                .filter(|row| is_long_block(row, &layout.expanded))
+                .map(|row| row.line_start);
+            next += 1;
+        }
+        let fence_start = fence_start?;
+
+        if !layout.expanded.remove(&fence_start) {
+            layout.expanded.insert(fence_start);
+        }
+        layout.truncate(fence_start);
+        Some(layout.rows.len())
+    }
+
+    /// Total number of rows at the last rendered width, or the number of lines
+    /// if nothing has been rendered yet
+    pub fn row_count(&self) -> usize {
+        let mut layout = self.layout.borrow_mut();
+        if layout.width == 0 {
+            return self.text.lines().count();
+        }
+        layout.extend_to(&self.text, usize::MAX);
+        layout.rows.len()
+    }
+
+    /// The first row of source line `line`, to scroll a search match into view.
+    /// Before the first render this is the line number itself.
+    pub fn row_of_line(&self, line: usize) -> usize {
+        let mut layout = self.layout.borrow_mut();
+        if layout.width == 0 {
+            return line;
+        }
+        let line_start: usize = self
+            .text
+            .split_inclusive('\n')
+            .take(line)
+            .map(str::len)
+            .sum();
+        while layout.next_line_start <= line_start && layout.next_line_start < self.text.len() {
+            let row_count = layout.rows.len() + 1;
+            layout.extend_to(&self.text, row_count);
+        }
+        let row = layout
+            .rows
+            .partition_point(|row| row.line_start < line_start);
+        // A line inside a collapsed code block is shown by the block's summary
+        let collapsed = row > 0
+            && matches!(layout.rows[row - 1].kind, RowKind::CollapsedCode { .. })
+            && layout
+                .rows
+                .get(row)
+                .is_none_or(|next| next.line_start > line_start);
+        if collapsed { row - 1 } else { row }
+    }
+
+    /// Target of the first `[[link]]` on row `row`, followed with Enter. Before the
+    /// first render rows are lines.
+    pub fn link_at(&self, row: usize) -> Option<LinkTarget> {
+        let mut layout = self.layout.borrow_mut();
+        let (line_start, range) = if layout.width == 0 {
+            let line_start: usize = self
+                .text
+                .split_inclusive('\n')
+                .take(row)
+                .map(str::len)
+                .sum();
+            (line_start, line_start..self.text.len())
+        } else {
+            layout.extend_to(&self.text, row.saturating_add(1));
+            let row = layout.rows.get(row)?;
+            if matches!(
+                row.kind,
+                RowKind::CodeFence { .. }
+                    | RowKind::Code
+                    | RowKind::CollapsedCode { .. }
+                    | RowKind::MathFence { .. }
+                    | RowKind::Math
+            ) {
+                return None;
+            }
+            (row.line_start, row.range.clone())
+        };
+        let line = self.text.get(line_start..)?;
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+        link::line_links(line)
+            .into_iter()
+            .find(|link| {
+                line_start + link.range.start < range.end
+                    && range.start < line_start + link.range.end
+            })
+            .map(|link| link.target)
+    }
+
+    /// The rows in view, wrapped to `width` columns
+    pub fn visible_rows(&self, width: usize, offset: usize, height: usize) -> Vec<VisibleRow<'_>> {
+        let width = width.max(1);
+        let mut layout = self.layout.borrow_mut();
+        if layout.width != width {
+            *layout = WrappedLayout {
+                width,
+                collapse_after: layout.collapse_after,
+                no_wrap: layout.no_wrap,
+                expanded: mem::take(&mut layout.expanded),
+                ..WrappedLayout::default()
+            };
+        }
+
+        layout.extend_to(&self.text, offset.saturating_add(height));
+        layout
+            .rows
+            .iter()
+            .skip(offset)
+            .take(height)
+            .map(|row| {
+                let line = &self.text[row.line_start..];
+                let line = line[..line.find('\n').unwrap_or(line.len())].trim_end_matches('\r');
+                VisibleRow {
+                    kind: row.kind,
+                    line,
+                    range: row.range.start - row.line_start..row.range.end - row.line_start,
+                    lang: row
+                        .lang
+                        .clone()
+                        .map(|lang| &self.text[lang])
+                        .filter(|lang| !lang.is_empty()),
+                    title: row.title.clone().map(|title| &self.text[title]),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Layouts of recently shown entries keyed on their content hash, so moving back
+/// and forth between entries doesn't lay them out again
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    layouts: HashMap<u64, WrappedLayout>,
+    /// Content hashes from least to most recently stored
+    order: Vec<u64>,
+}
+
+impl LayoutCache {
+    /// Keep the layout of content that is about to be replaced
+    pub fn store(&mut self, content: &mut EntryContent) {
+        let layout = mem::take(content.layout.get_mut());
+        if layout.width == 0 {
+            return;
+        }
+
+        self.order.retain(|hash| *hash != content.hash);
+        self.order.push(content.hash);
+        self.layouts.insert(content.hash, layout);
+
+        if self.order.len() > LAYOUT_CACHE_SIZE {
+            let oldest = self.order.remove(0);
+            self.layouts.remove(&oldest);
+        }
+    }
+
+    /// Create content, reusing the cached layout if the same content was shown before
+    pub fn restore(&mut self, text: impl Into<String>) -> EntryContent {
+        let content = EntryContent::new(text);
+        if let Some(layout) = self.layouts.remove(&content.hash) {
+            self.order.retain(|hash| *hash != content.hash);
+            *content.layout.borrow_mut() = layout;
+        }
+        content
+    }
+}
+
+impl WrappedLayout {
+    /// Wrap further chunks of source lines until at least `row_count` rows exist
+    fn extend_to(&mut self, text: &str, row_count: usize) {
+        while self.rows.len() < row_count && self.next_line_start < text.len() {
+            for _ in 0..LAYOUT_CHUNK_LINES {
+                let Some(line) = text[self.next_line_start..].split_inclusive('\n').next() else {
+                    break;
+                };
+                let start = self.next_line_start;
+                self.next_line_start += line.len();
+
+                let line = line.trim_end_matches(['\n', '\r']);
+                let kind = self.classify(line, start);
+                let block_start = self.next_line_start;
+                if kind == (RowKind::CodeFence { opening: true })
+                    && let Some(lines) = self.skip_long_block(text, start)
+                {
+                    let lang = self.code_lang.take();
+                    let title = is_diagram(text, lang.as_ref())
+                        .then(|| diagram_title(text, block_start..self.next_line_start))
+                        .flatten();
+                    self.rows.push(Row {
+                        range: start..start + line.len(),
+                        line_start: start,
+                        kind: RowKind::CollapsedCode { lines },
+                        lang,
+                        title,
+                    });
+                    self.in_code_block = false;
+                    continue;
+                }
+                if kind == RowKind::Definition {
+                    self.mark_term();
+                }
+                let lang = match kind {
+                    RowKind::Code | RowKind::CodeFence { opening: true } => self.code_lang.clone(),
+                    _ => None,
+                };
+                let width = if self.no_wrap { usize::MAX } else { self.width };
+                for range in wrap_line(line, width) {
+                    self.rows.push(Row {
+                        range: start + range.start..start + range.end,
+                        line_start: start,
+                        kind,
+                        lang: lang.clone(),
+                        title: None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Right after the opening fence at byte `start` of a code block too long to show,
+    /// or of a diagram, move past the block and return its number of lines. Blocks
+    /// without a closing fence run to the end of the text.
+    fn skip_long_block(&mut self, text: &str, start: usize) -> Option<usize> {
+        let diagram = is_diagram(text, self.code_lang.as_ref());
+        if self.expanded.contains(&start) || (self.collapse_after == 0 && !diagram) {
+            return None;
+        }
+        let mut lines = 0;
+        let mut block_end = text.len();
+        let mut offset = self.next_line_start;
+        for line in text[self.next_line_start..].split_inclusive('\n') {
+            offset += line.len();
+            if line.trim_start().starts_with("```") {
+                block_end = offset;
+                break;
+            }
+            lines += 1;
+        }
+        if lines <= self.collapse_after && !diagram {
+            return None;
+        }
+        self.next_line_start = block_end;
+        Some(lines)
+    }
+
+    /// Make the line laid out last the term of the definition that follows it, unless
+    /// it is empty or already something else
+    fn mark_term(&mut self) {
+        let Some(term_start) = self
+            .rows
+            .last()
+            .filter(|row| row.kind == RowKind::Text && !row.range.is_empty())
+            .map(|row| row.line_start)
+        else {
+            return;
+        };
+        for row in self
+            .rows
+            .iter_mut()
+            .rev()
+            .take_while(|row| row.line_start == term_start)
+        {
+            row.kind = RowKind::DefinitionTerm;
+        }
+    }
+
+    /// Drop the rows of lines starting at or after `lines_end`, they are laid out
+    /// again on demand
+    fn truncate(&mut self, lines_end: usize) {
+        if self.next_line_start <= lines_end {
+            return;
+        }
+
+        let mut keep = self.rows.partition_point(|row| row.line_start < lines_end);
+        let mut lines_end = lines_end;
+        // A collapsed code block spans many lines and a term depends on the line after
+        // it, both are laid out again as a whole
+        if let Some(row) = keep.checked_sub(1).map(|last| &self.rows[last])
+            && matches!(
+                row.kind,
+                RowKind::CollapsedCode { .. } | RowKind::DefinitionTerm
+            )
+        {
+            lines_end = row.line_start;
+            keep = self.rows.partition_point(|row| row.line_start < lines_end);
+        }
+        self.rows.truncate(keep);
+        self.next_line_start = lines_end;
+        self.in_code_block = matches!(
+            self.rows.last().map(|row| row.kind),
+            Some(RowKind::Code | RowKind::CodeFence { opening: true })
+        );
+        self.code_lang = self
+            .rows
+            .last()
+            .filter(|_| self.in_code_block)
+            .and_then(|row| row.lang.clone());
+        self.in_math_block = matches!(
+            self.rows.last().map(|row| row.kind),
+            Some(RowKind::Math | RowKind::MathFence { opening: true })
+        );
+    }
+
+    /// Decide the markdown role of the source line at byte `start`, tracking
+    /// fenced code blocks and their language and `$$` math blocks
+    fn classify(&mut self, line: &str, start: usize) -> RowKind {
+        let trimmed = line.trim_start();
+
+        if let Some(info) = trimmed.strip_prefix("```") {
+            self.in_code_block = !self.in_code_block;
+            self.code_lang = self.in_code_block.then(|| {
+                let lang_start = start + line.len() - info.trim_start().len();
+                let lang_len = info.split_whitespace().next().map_or(0, str::len);
+                lang_start..lang_start + lang_len
+            });
+            return RowKind::CodeFence {
+                opening: self.in_code_block,
+            };
+        }
+        if self.in_code_block {
+            return RowKind::Code;
+        }
+        if trimmed.trim_end() == "$$" {
+            self.in_math_block = !self.in_math_block;
+            return RowKind::MathFence {
+                opening: self.in_math_block,
+            };
+        }
+        if self.in_math_block {
+            return RowKind::Math;
+        }
+
+        if trimmed.starts_with('#') {
+            RowKind::Heading
+        } else if trimmed.starts_with("- [ ] ") || trimmed.starts_with("* [ ] ") {
+            RowKind::Task { done: false }
+        } else if trimmed.starts_with("- [x] ") || trimmed.starts_with("* [x] ") {
+            RowKind::Task { done: true }
+        } else if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+            RowKind::ListItem
+        } else if trimmed.starts_with('>') {
+            RowKind::Quote
+        } else if trimmed.starts_with(": ") {
+            RowKind::Definition
+        } else if is_footnote_definition(trimmed) {
+            RowKind::Footnote
+        } else {
+            RowKind::Text
+        }
+    }
+}
+
+/// Whether a code block's language range names a mermaid diagram
+fn is_diagram(text: &str, lang: Option<&Range<usize>>) -> bool {
+    lang.is_some_and(|lang| &text[lang.clone()] == mermaid::LANG)
+}
+
+/// Byte range within `text` of the title of the diagram in `text[block]`, if it has one
+fn diagram_title(text: &str, block: Range<usize>) -> Option<Range<usize>> {
+    let title = mermaid::title(&text[block.start..block.end.min(text.len())])?;
+    // The title is a slice of `text`, so its offset is where it starts in memory
+    let start = title.as_ptr() as usize - text.as_ptr() as usize;
+    Some(start..start + title.len())
+}
+
+/// Whether a line starts with a footnote label like `[^1]:`
+fn is_footnote_definition(line: &str) -> bool {
+    line.strip_prefix("[^")
+        .and_then(|rest| rest.split_once("]:"))
+        .is_some_and(|(label, _)| !label.is_empty() && !label.contains(char::is_whitespace))
+}
+
+fn content_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Length in bytes of the common prefix of both texts
+fn unchanged_prefix_len(old: &str, new: &str) -> usize {
+    old.char_indices()
+        .zip(new.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(old.len().min(new.len()), |((idx, _), _)| idx)
+}
+
+/// Wrap a single line at word boundaries, falling back to breaking inside
+/// words that are wider than the panel. Returns byte ranges into `line`.
+fn wrap_line(line: &str, width: usize) -> Vec<Range<usize>> {
+    if line.is_empty() {
+        return vec![Range { start: 0, end: 0 }];
+    }
+
+    let mut rows = Vec::new();
+    let mut row_start = 0;
+    let mut row_width = 0;
+    let mut last_space = None;
+
+    for (idx, ch) in line.char_indices() {
+        let char_width = ch.width().unwrap_or(0);
+
+        if row_width + char_width > width && idx > row_start {
+            let break_at = match last_space {
+                Some(space) if ch != ' ' && space > row_start => space,
+                _ => idx,
+            };
+            rows.push(row_start..break_at);
+
+            row_start = line[break_at..]
+                .char_indices()
+                .find(|(_, c)| *c != ' ')
+                .map_or(idx, |(i, _)| break_at + i);
+            row_width = line
+                .get(row_start..idx)
+                .map_or(0, |skipped| skipped.chars().filter_map(|c| c.width()).sum());
+            last_space = None;
+        }
+
+        // Spaces at the wrap point are dropped instead of starting the next row
+        if idx < row_start {
+            continue;
+        }
+
+        if ch == ' ' {
+            last_space = Some(idx);
+        }
+        row_width += char_width;
+    }
+
+    rows.push(row_start..line.len());
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(content: &EntryContent, width: usize) -> Vec<&str> {
+        content
+            .visible_rows(width, 0, usize::MAX)
+            .into_iter()
+            .map(|row| row.text())
+            .collect()
+    }
+
+    fn kinds(content: &EntryContent) -> Vec<RowKind> {
+        content
+            .visible_rows(80, 0, usize::MAX)
+            .into_iter()
+            .map(|row| row.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_wraps_at_word_boundaries() {
+        let content = EntryContent::new("the quick brown fox\n\njumps");
+
+        assert_eq!(
+            rows(&content, 10),
+            vec!["the quick", "brown fox", "", "jumps"]
+        );
+        assert_eq!(content.row_count(), 4);
+    }
+
+    #[test]
+    fn test_breaks_words_wider_than_panel() {
+        let content = EntryContent::new("abcdefghij");
+        assert_eq!(rows(&content, 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_drops_spaces_at_wrap_point() {
+        let content = EntryContent::new("abcd    efgh");
+        assert_eq!(rows(&content, 4), vec!["abcd", "efgh"]);
+    }
+
+    #[test]
+    fn test_layout_follows_width_changes() {
+        let content = EntryContent::new("one two three");
+
+        assert_eq!(rows(&content, 7), vec!["one two", "three"]);
+        assert_eq!(rows(&content, 20), vec!["one two three"]);
+        assert_eq!(content.row_count(), 1);
+    }
+
+    #[test]
+    fn test_unwrapped_lines_keep_a_single_row() {
+        let mut content = EntryContent::new("one two three\nfour");
+        assert_eq!(rows(&content, 7), vec!["one two", "three", "four"]);
+
+        content.wrap_lines(false);
+        assert_eq!(rows(&content, 7), vec!["one two three", "four"]);
+        content.wrap_lines(true);
+        assert_eq!(rows(&content, 7), vec!["one two", "three", "four"]);
+    }
+
+    #[test]
+    fn test_only_wraps_what_is_needed() {
+        let text = "line\n".repeat(10 * LAYOUT_CHUNK_LINES);
+        let content = EntryContent::new(text);
+
+        let visible = content.visible_rows(80, 10, 5);
+        assert_eq!(visible.len(), 5);
+        assert_eq!(content.layout.borrow().rows.len(), LAYOUT_CHUNK_LINES);
+
+        assert_eq!(content.row_count(), 10 * LAYOUT_CHUNK_LINES);
+    }
+
+    #[test]
+    fn test_row_of_line_counts_wrapped_rows() {
+        let content = EntryContent::new("one two three\nfour\nfive");
+        assert_eq!(content.row_of_line(2), 2);
+
+        rows(&content, 7);
+        assert_eq!(content.row_of_line(1), 2);
+        assert_eq!(content.row_of_line(2), 3);
+    }
+
+    #[test]
+    fn test_link_at_row() {
+        let content = EntryContent::new("see [[20250920]]\n```\n[[20250921]]\n```\nno link");
+        let target = LinkTarget::parse("20250920");
+        assert_eq!(content.link_at(0), target);
+
+        rows(&content, 8);
+        assert_eq!(content.link_at(0), None);
+        assert_eq!(content.link_at(1), target);
+        assert_eq!(content.link_at(2), target);
+        assert_eq!(content.link_at(4), None);
+        assert_eq!(content.link_at(6), None);
+    }
+
+    #[test]
+    fn test_large_content_detection() {
+        assert!(!EntryContent::new("small").is_large());
+        assert!(EntryContent::new("x".repeat(LARGE_CONTENT_BYTES + 1)).is_large());
+    }
+
+    #[test]
+    fn test_row_kinds() {
+        let content = EntryContent::new(
+            "# Title\n- item\n- [ ] todo\n- [x] done\n> quote\n```\n# not a heading\n```\ntext",
+        );
+
+        assert_eq!(
+            kinds(&content),
+            vec![
+                RowKind::Heading,
+                RowKind::ListItem,
+                RowKind::Task { done: false },
+                RowKind::Task { done: true },
+                RowKind::Quote,
+                RowKind::CodeFence { opening: true },
+                RowKind::Code,
+                RowKind::CodeFence { opening: false },
+                RowKind::Text,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rows_know_their_line_and_code_language() {
+        let content = EntryContent::new("a **bold** word\n```rust\nlet x = 1;\n```\n```\nplain");
+
+        let visible = content.visible_rows(8, 0, usize::MAX);
+        assert_eq!(visible[2].line, "a **bold** word");
+        assert_eq!(visible[2].range, 11..15);
+        assert_eq!(visible[2].text(), "word");
+
+        let langs: Vec<Option<&str>> = visible[3..].iter().map(|row| row.lang).collect();
+        assert_eq!(
+            langs,
+            vec![Some("rust"), Some("rust"), Some("rust"), None, None, None]
+        );
+    }
+
+    #[test]
+    fn test_replace_keeps_layout_before_the_change() {
+        let mut content = EntryContent::new("first line\nsecond line\nthird line");
+        assert_eq!(rows(&content, 80).len(), 3);
+
+        content.replace("first line\nsecond edited line\nthird line\nfourth");
+        assert_eq!(content.layout.borrow().rows.len(), 1);
+        assert_eq!(
+            rows(&content, 80),
+            vec!["first line", "second edited line", "third line", "fourth"]
+        );
+    }
+
+    #[test]
+    fn test_replace_appending_to_last_line() {
+        let mut content = EntryContent::new("first\nlast");
+        rows(&content, 80);
+
+        content.replace("first\nlast line");
+        assert_eq!(rows(&content, 80), vec!["first", "last line"]);
+    }
+
+    #[test]
+    fn test_replace_restores_code_block_state() {
+        let mut content = EntryContent::new("```\ncode\nmore code\n```");
+        rows(&content, 80);
+
+        content.replace("```\ncode\n# still code\n```");
+        assert_eq!(kinds(&content)[2], RowKind::Code);
+    }
+
+    #[test]
+    fn test_layout_cache_reuses_layout_for_same_content() {
+        let mut cache = LayoutCache::default();
+        let mut content = cache.restore("some entry");
+        rows(&content, 80);
+        cache.store(&mut content);
+
+        let restored = cache.restore("some entry");
+        assert_eq!(restored.layout.borrow().width, 80);
+        assert_eq!(restored.layout.borrow().rows.len(), 1);
+
+        let other = cache.restore("another entry");
+        assert_eq!(other.layout.borrow().width, 0);
+    }
+
+    #[test]
+    fn test_definitions_and_footnotes() {
+        let mut content =
+            EntryContent::new("Crate\n: A package\n: A box\n\n: orphan\nSee[^1]\n\n[^1]: A note");
+        assert_eq!(
+            kinds(&content),
+            vec![
+                RowKind::DefinitionTerm,
+                RowKind::Definition,
+                RowKind::Definition,
+                RowKind::Text,
+                RowKind::Definition,
+                RowKind::Text,
+                RowKind::Text,
+                RowKind::Footnote,
+            ]
+        );
+
+        // Without its definition a term is text again
+        content.replace("Crate\nA package");
+        assert_eq!(kinds(&content), vec![RowKind::Text, RowKind::Text]);
+    }
+
+    #[test]
+    fn test_math_blocks() {
+        let mut content = EntryContent::new("Loss $x$\n$$\nL = \\sum_i x_i\n$$\n```\n$$\n```");
+        assert_eq!(
+            kinds(&content),
+            vec![
+                RowKind::Text,
+                RowKind::MathFence { opening: true },
+                RowKind::Math,
+                RowKind::MathFence { opening: false },
+                RowKind::CodeFence { opening: true },
+                RowKind::Code,
+                RowKind::CodeFence { opening: false },
+            ]
+        );
+
+        content.replace("Loss $x$\n$$\nL = \\sum_i x_i\n# still math\n$$");
+        assert_eq!(kinds(&content)[3], RowKind::Math);
+    }
+
+    #[test]
+    fn test_long_code_blocks_collapse_until_expanded() {
+        let text = "intro\n```log\none\ntwo\nthree\n```\n```\nshort\n```\nend";
+        let mut content = EntryContent::new(text);
+        content.collapse_code_after(2);
+        assert_eq!(
+            kinds(&content),
+            vec![
+                RowKind::Text,
+                RowKind::CollapsedCode { lines: 3 },
+                RowKind::CodeFence { opening: true },
+                RowKind::Code,
+                RowKind::CodeFence { opening: false },
+                RowKind::Text,
+            ]
+        );
+        assert_eq!(content.visible_rows(80, 1, 1)[0].lang, Some("log"));
+        assert_eq!(content.row_of_line(3), 1);
+        assert_eq!(content.row_of_line(9), 5);
+
+        // The short block after it isn't collapsible
+        assert_eq!(content.toggle_code_block(2), None);
+        assert_eq!(content.toggle_code_block(0), Some(1));
+        assert_eq!(content.row_count(), 10);
+        assert_eq!(kinds(&content)[2], RowKind::Code);
+
+        // From inside the expanded block it collapses again
+        assert_eq!(content.toggle_code_block(3), Some(1));
+        assert_eq!(content.row_count(), 6);
+    }
+
+    #[test]
+    fn test_diagrams_collapse_to_their_title() {
+        let text =
+            "```mermaid\n---\ntitle: Checkout\n---\ngraph TD\n```\n```mermaid\ngraph TD\n```";
+        let mut content = EntryContent::new(text);
+        content.collapse_code_after(0);
+        assert_eq!(
+            kinds(&content),
+            vec![
+                RowKind::CollapsedCode { lines: 4 },
+                RowKind::CollapsedCode { lines: 1 },
+            ]
+        );
+        let rows = content.visible_rows(80, 0, 2);
+        assert_eq!(rows[0].title, Some("Checkout"));
+        assert_eq!(rows[1].title, None);
+
+        // Enter shows the source even when code blocks never collapse
+        assert_eq!(content.toggle_code_block(0), Some(0));
+        assert_eq!(content.row_count(), 7);
+    }
+
+    #[test]
+    fn test_replace_inside_collapsed_block() {
+        let mut content = EntryContent::new("```\none\ntwo\nthree\n```\nend");
+        content.collapse_code_after(2);
+        rows(&content, 80);
+
+        content.replace("```\none\ntwo\n```\nend");
+        assert_eq!(
+            kinds(&content),
+            vec![
+                RowKind::CodeFence { opening: true },
+                RowKind::Code,
+                RowKind::Code,
+                RowKind::CodeFence { opening: false },
+                RowKind::Text,
+            ]
+        );
+    }
+}
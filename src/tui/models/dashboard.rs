@@ -0,0 +1,119 @@
+use chrono::{Datelike, Days, NaiveDate};
+
+use crate::models::annotation::AnnotationKind;
+use crate::models::entry_id::EntryId;
+use crate::stats::{self, Overview};
+use crate::storage::index::EntryIndex;
+
+/// Number of months in the entries-per-month chart
+const MONTHS: u32 = 12;
+/// Number of days in the word count sparkline
+const SPARKLINE_DAYS: u64 = 30;
+/// Number of tags, people and projects charted
+const TOP_ANNOTATIONS: usize = 5;
+
+/// Vault statistics shown on the dashboard, toggled with `D`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dashboard {
+    /// Streaks, word counts, entries per month and top annotations of all time
+    pub overview: Overview,
+    /// Most referenced projects of the current quarter with their entry counts
+    pub top_projects: Vec<(String, u64)>,
+    /// Words written per day, oldest day first, ending today
+    pub daily_words: Vec<u64>,
+    /// Files waiting to be synced, `None` while sync isn't set up
+    pub pending_sync: Option<usize>,
+    /// Projects not mentioned for `tui.stale_project_days`, the longest quiet first
+    pub stale_projects: Vec<String>,
+}
+
+impl Dashboard {
+    /// Compute the dashboard from the entry index, no entry is read
+    pub fn from_index(index: &EntryIndex, today: NaiveDate) -> Self {
+        let quarter_start =
+            NaiveDate::from_ymd_opt(today.year(), (today.month0() / 3) * 3 + 1, 1).unwrap_or(today);
+        let quarter = index
+            .entries
+            .range(EntryId::from_date(quarter_start)..=EntryId::from_date(today))
+            .map(|(_, entry)| entry);
+        let top_projects =
+            stats::top_annotations(quarter, AnnotationKind::Project, TOP_ANNOTATIONS)
+                .into_iter()
+                .map(|(project, count)| (project, count as u64))
+                .collect();
+
+        let daily_words = (0..SPARKLINE_DAYS)
+            .rev()
+            .filter_map(|ago| today.checked_sub_days(Days::new(ago)))
+            .map(|day| {
+                index
+                    .get(&EntryId::from_date(day))
+                    .map_or(0, |entry| entry.words as u64)
+            })
+            .collect();
+
+        Self {
+            overview: Overview::from_index(index, today, MONTHS, TOP_ANNOTATIONS),
+            top_projects,
+            daily_words,
+            pending_sync: None,
+            stale_projects: Vec::new(),
+        }
+    }
+
+    /// Month label (e.g. `Sep`) and number of entries, oldest month first
+    pub fn entries_per_month(&self) -> Vec<(String, u64)> {
+        self.overview
+            .entries_per_month
+            .iter()
+            .map(|(month, count)| (month.format("%b").to_string(), *count as u64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use crate::storage::index::IndexedEntry;
+
+    fn index(entries: &[(&str, &str)]) -> EntryIndex {
+        let mut index = EntryIndex::default();
+        for (id, content) in entries {
+            let entry = Entry::new(id.parse().unwrap(), content.to_string());
+            index
+                .entries
+                .insert(entry.id, IndexedEntry::of(&entry, None));
+        }
+        index
+    }
+
+    #[test]
+    fn test_dashboard_from_index() {
+        let index = index(&[
+            ("20250615", "::old project"),
+            ("20250801", "::devlog one"),
+            ("20250919", "::devlog ::devlog two ::web"),
+            ("20250920", "three words here"),
+        ]);
+        let today = NaiveDate::from_ymd_opt(2025, 9, 20).unwrap();
+        let dashboard = Dashboard::from_index(&index, today);
+
+        let entries_per_month = dashboard.entries_per_month();
+        assert_eq!(entries_per_month.len(), 12);
+        assert_eq!(entries_per_month.last(), Some(&("Sep".to_string(), 2)));
+        assert_eq!(entries_per_month[10], ("Aug".to_string(), 1));
+        assert_eq!(dashboard.overview.current_streak, 2);
+        // June is last quarter
+        assert_eq!(
+            dashboard.top_projects,
+            vec![("devlog".to_string(), 2), ("web".to_string(), 1)]
+        );
+        assert_eq!(
+            dashboard.overview.top_projects[0],
+            ("devlog".to_string(), 2)
+        );
+        assert_eq!(dashboard.daily_words.len(), 30);
+        assert_eq!(dashboard.daily_words[28..], [4, 3]);
+    }
+}
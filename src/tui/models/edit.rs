@@ -0,0 +1,89 @@
+use std::cell::Cell;
+
+use crate::models::note::AnyId;
+use crate::editing::{EditorBuffer, buffer::TextBuffer};
+
+/// An entry or note being edited inside the TUI, opened with `e`
+#[derive(Debug)]
+pub struct EditSession {
+    /// The entry or note written on save, sub-entries are edited with their whole day
+    pub id: AnyId,
+    pub buffer: TextBuffer,
+    /// The content on disk when the session started or last saved, a save finding
+    /// anything else on disk would overwrite someone else's changes
+    pub base: String,
+    /// The content found on disk instead of `base`, waiting for the merge/overwrite/reload
+    /// answer
+    pub conflict: Option<String>,
+    /// Whether closing with unsaved changes waits for the save/discard answer
+    pub confirm_close: bool,
+    /// First row and column in view, moved by rendering to keep the cursor visible
+    viewport: Cell<(usize, usize)>,
+}
+
+impl EditSession {
+    pub fn new(id: AnyId, base: String, buffer: TextBuffer) -> Self {
+        Self {
+            id,
+            buffer,
+            base,
+            conflict: None,
+            confirm_close: false,
+            viewport: Cell::new((0, 0)),
+        }
+    }
+
+    /// First row and column to show in a `height` x `width` view, scrolling only as
+    /// far as needed to bring the cursor into view
+    pub fn scroll_to_cursor(&self, height: usize, width: usize) -> (usize, usize) {
+        let cursor = self.buffer.cursor();
+        let (mut top, mut left) = self.viewport.get();
+        let (height, width) = (height.max(1), width.max(1));
+
+        if cursor.row < top {
+            top = cursor.row;
+        } else if cursor.row >= top + height {
+            top = cursor.row + 1 - height;
+        }
+        if cursor.col < left {
+            left = cursor.col;
+        } else if cursor.col >= left + width {
+            left = cursor.col + 1 - width;
+        }
+
+        self.viewport.set((top, left));
+        (top, left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_follows_cursor() {
+        let text = (0..10)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut session = EditSession::new(
+            "notes".parse().unwrap(),
+            text.clone(),
+            TextBuffer::new(&text),
+        );
+        assert_eq!(session.scroll_to_cursor(4, 20), (0, 0));
+
+        for _ in 0..6 {
+            session.buffer.move_down();
+        }
+        assert_eq!(session.scroll_to_cursor(4, 20), (3, 0));
+
+        // Moving back up inside the view doesn't scroll
+        session.buffer.move_up();
+        assert_eq!(session.scroll_to_cursor(4, 20), (3, 0));
+        session.buffer.move_up();
+        session.buffer.move_up();
+        session.buffer.move_up();
+        assert_eq!(session.scroll_to_cursor(4, 20), (2, 0));
+    }
+}
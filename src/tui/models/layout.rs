@@ -0,0 +1,135 @@
+use std::{fs, io::ErrorKind};
+
+use color_eyre::eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+const LAYOUT_FILE_NAME: &str = "tui.toml";
+
+/// Narrowest and widest the tree panel gets, in percent of the width
+const MIN_TREE_PERCENT: u16 = 15;
+const MAX_TREE_PERCENT: u16 = 70;
+
+/// How much `[` and `]` resize the tree panel
+const RESIZE_STEP: u16 = 5;
+
+/// Width of the tree panel next to the content panel, kept across launches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub tree_percent: u16,
+    /// The tree is hidden and the content panel takes the whole width
+    pub tree_collapsed: bool,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            tree_percent: 40,
+            tree_collapsed: false,
+        }
+    }
+}
+
+impl PanelLayout {
+    /// The layout saved by the last session, the default one if there is none
+    pub fn load(storage: &Storage) -> Result<Self> {
+        let path = storage.state_path().join(LAYOUT_FILE_NAME);
+        let layout: Self = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .wrap_err_with(|| format!("Failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {}", path.display())),
+        };
+        // A hand edited file can't make the tree unusable
+        Ok(Self {
+            tree_percent: layout
+                .tree_percent
+                .clamp(MIN_TREE_PERCENT, MAX_TREE_PERCENT),
+            ..layout
+        })
+    }
+
+    pub fn save(&self, storage: &Storage) -> Result<()> {
+        let content = toml::to_string(self).wrap_err("Failed to serialize panel layout")?;
+        storage
+            .writer()
+            .write_atomic(&storage.state_path().join(LAYOUT_FILE_NAME), &content)
+            .wrap_err("Failed to save panel layout")
+    }
+
+    /// Make the tree panel wider, or narrower with a negative `steps`. Showing a
+    /// collapsed tree again counts as the first step.
+    pub fn resize(&mut self, steps: i16) {
+        if self.tree_collapsed {
+            self.tree_collapsed = false;
+            return;
+        }
+        let percent = self.tree_percent as i16 + steps * RESIZE_STEP as i16;
+        self.tree_percent = (percent.max(0) as u16).clamp(MIN_TREE_PERCENT, MAX_TREE_PERCENT);
+    }
+
+    pub fn toggle_tree(&mut self) {
+        self.tree_collapsed = !self.tree_collapsed;
+    }
+
+    /// Percent of the width the tree panel takes, 0 while collapsed
+    pub fn tree_width(&self) -> u16 {
+        if self.tree_collapsed {
+            0
+        } else {
+            self.tree_percent
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resize_stays_in_bounds() {
+        let mut layout = PanelLayout::default();
+        layout.resize(-1);
+        assert_eq!(layout.tree_width(), 35);
+        layout.resize(-10);
+        assert_eq!(layout.tree_width(), MIN_TREE_PERCENT);
+        layout.resize(20);
+        assert_eq!(layout.tree_width(), MAX_TREE_PERCENT);
+
+        // Resizing a collapsed tree brings it back at its old width
+        layout.toggle_tree();
+        assert_eq!(layout.tree_width(), 0);
+        layout.resize(1);
+        assert_eq!(layout.tree_width(), MAX_TREE_PERCENT);
+    }
+
+    #[test]
+    fn test_layout_is_kept_across_launches() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage = Storage::new_with_base_dir(temp_dir.path()).unwrap();
+        assert_eq!(PanelLayout::load(&storage).unwrap(), PanelLayout::default());
+
+        let layout = PanelLayout {
+            tree_percent: 25,
+            tree_collapsed: true,
+        };
+        layout.save(&storage).unwrap();
+        assert_eq!(PanelLayout::load(&storage).unwrap(), layout);
+
+        fs::write(
+            storage.state_path().join(LAYOUT_FILE_NAME),
+            "tree_percent = 100\n",
+        )
+        .unwrap();
+        assert_eq!(
+            PanelLayout::load(&storage).unwrap(),
+            PanelLayout {
+                tree_percent: MAX_TREE_PERCENT,
+                tree_collapsed: false,
+            }
+        );
+    }
+}
@@ -1,2 +1,9 @@
+pub mod content;
+pub mod dashboard;
+pub mod edit;
+pub mod layout;
 pub mod node;
+pub mod review;
+pub mod scratch;
+pub mod search;
 pub mod state;
@@ -17,10 +17,43 @@ impl TreeNode {
     }
 }
 
+/// Open the same folders in a rebuilt tree as were open in the old one
+pub fn keep_expanded(old: &[TreeNode], new: &mut [TreeNode]) {
+    for node in new {
+        if let Some(previous) = old.iter().find(|previous| previous.name == node.name) {
+            node.is_expanded = previous.is_expanded;
+            keep_expanded(&previous.children, &mut node.children);
+        }
+    }
+}
+
+/// Expand the folders above the node called `name`, returns whether it was found
+pub fn reveal(nodes: &mut [TreeNode], name: &str) -> bool {
+    for node in nodes {
+        if node.name == name {
+            return true;
+        }
+        if reveal(&mut node.children, name) {
+            node.is_expanded = true;
+            return true;
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn folder(name: &str, children: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            name: name.to_string(),
+            children,
+            is_expanded: false,
+            is_entry: false,
+        }
+    }
+
     #[test]
     fn test_tree_node_new_entry() {
         let name = "20250920".to_string();
@@ -31,4 +64,48 @@ mod tests {
         assert!(!node.is_expanded);
         assert!(node.is_entry);
     }
+
+    #[test]
+    fn test_keep_expanded() {
+        let mut old = vec![folder(
+            "2025",
+            vec![folder(
+                "09",
+                vec![TreeNode::new_entry("20250920".to_string())],
+            )],
+        )];
+        old[0].is_expanded = true;
+        let mut new = vec![
+            folder("2026", vec![]),
+            folder(
+                "2025",
+                vec![folder(
+                    "09",
+                    vec![TreeNode::new_entry("20250921".to_string())],
+                )],
+            ),
+        ];
+
+        keep_expanded(&old, &mut new);
+        assert!(!new[0].is_expanded);
+        assert!(new[1].is_expanded);
+        assert!(!new[1].children[0].is_expanded);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut nodes = vec![folder(
+            "2025",
+            vec![
+                folder("10", vec![]),
+                folder("09", vec![TreeNode::new_entry("20250920".to_string())]),
+            ],
+        )];
+
+        assert!(reveal(&mut nodes, "20250920"));
+        assert!(nodes[0].is_expanded);
+        assert!(nodes[0].children[1].is_expanded);
+        assert!(!nodes[0].children[0].is_expanded);
+        assert!(!reveal(&mut nodes, "20250921"));
+    }
 }
@@ -0,0 +1,224 @@
+use chrono::NaiveDate;
+
+use crate::models::entry::Entry;
+use crate::models::entry_id::EntryId;
+
+/// Questions asked for every entry, in the order their sections appear in the rollup
+pub const PROMPTS: [&str; 3] = ["Highlights?", "Todos to carry over?", "Lessons?"];
+const SECTIONS: [&str; 3] = ["Highlights", "Carried over", "Lessons"];
+/// Index of the prompt whose answers become todos in the rollup
+const CARRY_OVER: usize = 1;
+
+/// The guided weekly review opened with `R`, stepping through last week's entries
+/// and collecting answers to the prompts for each of them
+#[derive(Debug, Clone)]
+pub struct WeeklyReview {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Entries of the week, oldest first
+    pub entries: Vec<Entry>,
+    /// Index of the entry shown, the number of entries once every entry was reviewed
+    pub position: usize,
+    /// Index of the prompt being answered
+    pub prompt: usize,
+    /// Answer being typed, added to the prompt's answers with Enter
+    pub input: String,
+    /// Answers by prompt, with the entry they were written for
+    pub answers: [Vec<(EntryId, String)>; 3],
+}
+
+impl WeeklyReview {
+    pub fn new(start: NaiveDate, end: NaiveDate, entries: Vec<Entry>) -> Self {
+        Self {
+            start,
+            end,
+            entries,
+            position: 0,
+            prompt: 0,
+            input: String::new(),
+            answers: Default::default(),
+        }
+    }
+
+    /// The entry being reviewed, none once the review reached the rollup
+    pub fn current(&self) -> Option<&Entry> {
+        self.entries.get(self.position)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.entries.len()
+    }
+
+    /// Answers to `prompt` written for the current entry
+    pub fn current_answers(&self, prompt: usize) -> impl Iterator<Item = &str> {
+        let id = self.current().map(|entry| entry.id);
+        self.answers[prompt]
+            .iter()
+            .filter(move |(answer_id, _)| Some(*answer_id) == id)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if !self.is_finished() {
+            self.input.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// Add the typed answer to the current prompt
+    pub fn submit(&mut self) {
+        let answer = self.input.trim().to_string();
+        self.input.clear();
+        if let Some(id) = self.current().map(|entry| entry.id)
+            && !answer.is_empty()
+        {
+            self.answers[self.prompt].push((id, answer));
+        }
+    }
+
+    pub fn next_prompt(&mut self) {
+        self.submit();
+        self.prompt = (self.prompt + 1) % PROMPTS.len();
+    }
+
+    pub fn previous_prompt(&mut self) {
+        self.submit();
+        self.prompt = (self.prompt + PROMPTS.len() - 1) % PROMPTS.len();
+    }
+
+    /// Move on to the next entry, or to the rollup after the last one
+    pub fn next(&mut self) {
+        self.submit();
+        if !self.is_finished() {
+            self.position += 1;
+            self.prompt = 0;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.submit();
+        if self.position > 0 {
+            self.position -= 1;
+            self.prompt = 0;
+        }
+    }
+
+    /// The review as a markdown section: the answers by prompt, and the todos
+    /// left open during the week carried over
+    pub fn rollup(&self) -> String {
+        let mut out = format!(
+            "## Weekly review {} to {}\n",
+            self.start.format("%Y-%m-%d"),
+            self.end.format("%Y-%m-%d")
+        );
+        for (index, section) in SECTIONS.iter().enumerate() {
+            let mut items: Vec<String> = self.answers[index]
+                .iter()
+                .map(|(id, answer)| (*id, answer.as_str()))
+                .chain(
+                    (index == CARRY_OVER)
+                        .then(|| self.open_todos())
+                        .into_iter()
+                        .flatten(),
+                )
+                .map(|(id, text)| {
+                    let bullet = if index == CARRY_OVER { "- [ ]" } else { "-" };
+                    format!("{} {} ({})", bullet, text, id.date().format("%a %d %b"))
+                })
+                .collect();
+            items.dedup();
+            if items.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n### {}\n\n{}\n", section, items.join("\n")));
+        }
+        out
+    }
+
+    /// Unchecked `- [ ]` items of the week's entries
+    fn open_todos(&self) -> Vec<(EntryId, &str)> {
+        self.entries
+            .iter()
+            .flat_map(|entry| {
+                entry.content.lines().filter_map(|line| {
+                    let line = line.trim_start();
+                    let todo = line
+                        .strip_prefix("- [ ]")
+                        .or_else(|| line.strip_prefix("* [ ]"))?
+                        .trim();
+                    (!todo.is_empty()).then_some((entry.id, todo))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review() -> WeeklyReview {
+        let entries = vec![
+            Entry::new(
+                "20250915".parse().unwrap(),
+                "- [x] ship search\n- [ ] write docs".to_string(),
+            ),
+            Entry::new("20250917".parse().unwrap(), "incident".to_string()),
+        ];
+        WeeklyReview::new(
+            NaiveDate::from_ymd_opt(2025, 9, 15).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 9, 21).unwrap(),
+            entries,
+        )
+    }
+
+    fn type_answer(review: &mut WeeklyReview, text: &str) {
+        text.chars().for_each(|c| review.type_char(c));
+    }
+
+    #[test]
+    fn test_steps_through_entries_and_prompts() {
+        let mut review = review();
+        type_answer(&mut review, "shipped search");
+        review.next_prompt();
+        assert_eq!(review.prompt, 1);
+        assert_eq!(
+            review.current_answers(0).collect::<Vec<_>>(),
+            ["shipped search"]
+        );
+
+        review.next();
+        assert_eq!(review.current().unwrap().id.to_string(), "20250917");
+        assert_eq!(review.prompt, 0);
+        assert_eq!(review.current_answers(0).count(), 0);
+
+        review.previous_prompt();
+        type_answer(&mut review, "  ");
+        review.next();
+        assert!(review.is_finished());
+        assert!(review.answers[2].is_empty());
+        review.type_char('x');
+        assert!(review.input.is_empty());
+    }
+
+    #[test]
+    fn test_rollup_carries_open_todos_over() {
+        let mut review = review();
+        type_answer(&mut review, "shipped search");
+        review.next();
+        review.previous_prompt();
+        type_answer(&mut review, "write postmortems early");
+        review.next();
+
+        assert_eq!(
+            review.rollup(),
+            "## Weekly review 2025-09-15 to 2025-09-21\n\
+             \n### Highlights\n\n- shipped search (Mon 15 Sep)\n\
+             \n### Carried over\n\n- [ ] write docs (Mon 15 Sep)\n\
+             \n### Lessons\n\n- write postmortems early (Wed 17 Sep)\n"
+        );
+    }
+}
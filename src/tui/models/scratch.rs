@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+
+/// The scratch pad as shown in the TUI, with a line cursor and marked lines
+#[derive(Debug, Clone, Default)]
+pub struct ScratchPad {
+    pub lines: Vec<String>,
+    pub cursor: usize,
+    pub marked: BTreeSet<usize>,
+}
+
+impl ScratchPad {
+    pub fn new(text: &str) -> Self {
+        Self {
+            lines: text.lines().map(str::to_string).collect(),
+            cursor: 0,
+            marked: BTreeSet::new(),
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.lines.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn toggle_mark(&mut self) {
+        if self.cursor < self.lines.len() && !self.marked.remove(&self.cursor) {
+            self.marked.insert(self.cursor);
+        }
+    }
+
+    /// Lines to act on: the marked ones, or the line under the cursor
+    pub fn selected(&self) -> BTreeSet<usize> {
+        if !self.marked.is_empty() {
+            self.marked.clone()
+        } else if self.cursor < self.lines.len() {
+            BTreeSet::from([self.cursor])
+        } else {
+            BTreeSet::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_lines() {
+        let mut pad = ScratchPad::new("a\nb\nc");
+        assert_eq!(pad.selected(), BTreeSet::from([0]));
+
+        pad.move_down();
+        pad.toggle_mark();
+        pad.move_down();
+        pad.move_down();
+        pad.toggle_mark();
+        assert_eq!(pad.selected(), BTreeSet::from([1, 2]));
+
+        assert!(ScratchPad::new("").selected().is_empty());
+    }
+}
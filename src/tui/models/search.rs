@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use crate::models::note::AnyId;
+use crate::search::EntryMatches;
+use crate::tui::models::node::TreeNode;
+
+/// The `/` search of the TUI: the query typed in the footer, then the lines it matched.
+/// While results are shown the tree only holds the matching entries and notes.
+#[derive(Debug, Default)]
+pub struct SearchState {
+    pub query: String,
+    /// Whether the query is still being typed
+    pub editing: bool,
+    /// Every matching line as entry or note and line number, oldest entry first
+    pub hits: Vec<(AnyId, usize)>,
+    /// Index into `hits` of the match jumped to with `n`/`N`
+    pub current: Option<usize>,
+    /// The full tree, put back when the search is closed
+    pub saved_tree: Option<Vec<TreeNode>>,
+}
+
+impl SearchState {
+    /// Start typing a new query, keeping the results of the last one until it runs
+    pub fn edit(&mut self) {
+        self.editing = true;
+    }
+
+    pub fn set_results(&mut self, results: &[EntryMatches]) {
+        self.hits = results
+            .iter()
+            .flat_map(|matches| matches.hits.iter().map(|&hit| (matches.id.clone(), hit)))
+            .collect();
+        self.current = None;
+        self.editing = false;
+    }
+
+    /// Names of the tree nodes of the matching entries and notes
+    pub fn entry_names(&self) -> HashSet<String> {
+        self.hits.iter().map(|(id, _)| id.to_string()).collect()
+    }
+
+    /// Jump to the next match, or the previous one, wrapping around at either end
+    pub fn jump(&mut self, forward: bool) -> Option<(AnyId, usize)> {
+        let len = self.hits.len();
+        if len == 0 {
+            return None;
+        }
+        let current = match (self.current, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(current), true) => (current + 1) % len,
+            (Some(current), false) => (current + len - 1) % len,
+        };
+        self.current = Some(current);
+        self.hits.get(current).cloned()
+    }
+}
+
+/// The nodes leading to the entries in `names`, expanded so every match is in view
+pub fn filter_tree(nodes: &[TreeNode], names: &HashSet<String>) -> Vec<TreeNode> {
+    nodes
+        .iter()
+        .filter_map(|node| {
+            if node.is_entry {
+                return names.contains(&node.name).then(|| node.clone());
+            }
+            let children = filter_tree(&node.children, names);
+            (!children.is_empty()).then(|| TreeNode {
+                name: node.name.clone(),
+                children,
+                is_expanded: true,
+                is_entry: false,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(s: &str) -> AnyId {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_jump_wraps_around() {
+        let mut search = SearchState::default();
+        search.set_results(&[
+            EntryMatches {
+                id: id("20250919"),
+                lines: vec!["a".to_string(), "b".to_string()],
+                hits: vec![0, 1],
+            },
+            EntryMatches {
+                id: id("20250920"),
+                lines: vec!["c".to_string()],
+                hits: vec![0],
+            },
+        ]);
+
+        assert_eq!(search.jump(false), Some((id("20250920"), 0)));
+        assert_eq!(search.jump(true), Some((id("20250919"), 0)));
+        assert_eq!(search.jump(true), Some((id("20250919"), 1)));
+        assert_eq!(search.entry_names().len(), 2);
+    }
+
+    #[test]
+    fn test_filter_tree_keeps_matching_entries() {
+        let month = TreeNode {
+            name: "09".to_string(),
+            children: vec![
+                TreeNode::new_entry("20250919".to_string()),
+                TreeNode::new_entry("20250920".to_string()),
+            ],
+            is_expanded: false,
+            is_entry: false,
+        };
+        let empty = TreeNode {
+            name: "08".to_string(),
+            children: vec![TreeNode::new_entry("20250801".to_string())],
+            is_expanded: false,
+            is_entry: false,
+        };
+
+        let names = HashSet::from(["20250920".to_string()]);
+        let filtered = filter_tree(&[month, empty], &names);
+
+        assert_eq!(filtered.len(), 1);
+        assert!(filtered[0].is_expanded);
+        assert_eq!(filtered[0].children.len(), 1);
+        assert_eq!(filtered[0].children[0].name, "20250920");
+    }
+}
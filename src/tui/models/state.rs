@@ -1,4 +1,20 @@
-use crate::tui::{models::node::TreeNode, tree::flattener::FlatTreeItem};
+use std::path::PathBuf;
+
+use crate::models::display::DisplayOptions;
+use crate::models::entry_id::{EntryId, EntryRef};
+use crate::tui::{
+    models::{
+        content::{EntryContent, LayoutCache},
+        dashboard::Dashboard,
+        edit::EditSession,
+        layout::PanelLayout,
+        node::TreeNode,
+        review::WeeklyReview,
+        scratch::ScratchPad,
+        search::SearchState,
+    },
+    tree::flattener::FlatTreeItem,
+};
 
 #[derive(PartialEq, Debug)]
 pub enum Panel {
@@ -6,6 +22,31 @@ pub enum Panel {
     Content,
 }
 
+/// A sub-entry being moved from the TUI, waiting for the entry or project it goes to
+#[derive(Debug)]
+pub struct SectionMove {
+    pub from: EntryRef,
+    /// What was typed so far, e.g. `2025092` or `::sea`
+    pub target: String,
+}
+
+/// What a file picked from the TUI is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    /// Copy it into the attachments of the entry
+    Attach,
+    /// Write the entry to it
+    Export,
+}
+
+/// A path typed in the footer where no file dialog can be shown
+#[derive(Debug)]
+pub struct PathPrompt {
+    pub action: FileAction,
+    pub entry: EntryId,
+    pub path: String,
+}
+
 #[derive(Debug)]
 pub struct AppState {
     /// Hierarchical tree structure organizing entries by year/month/day
@@ -20,15 +61,61 @@ pub struct AppState {
     /// Currently active panel (navigation or content view)
     pub current_panel: Panel,
 
+    /// Width of the tree panel, or whether it is hidden
+    pub layout: PanelLayout,
+
     /// Content of the currently selected journal entry
-    pub selected_entry_content: String,
+    pub selected_entry_content: EntryContent,
+
+    /// Layouts of recently viewed entries, reused when switching back to them
+    pub layout_cache: LayoutCache,
+
+    /// The entry being edited inside the TUI, shown instead of the content panel
+    pub editor: Option<EditSession>,
+
+    /// The scratch pad, shown instead of the content panel while open
+    pub scratch: Option<ScratchPad>,
+
+    /// The dashboard, shown instead of the tree and content panels while open
+    pub dashboard: Option<Dashboard>,
+
+    /// The weekly review, shown instead of the tree and content panels while open
+    pub review: Option<WeeklyReview>,
+
+    /// Message shown in the footer until the next key press
+    pub notice: Option<String>,
+
+    /// A file dropped onto the TUI, waiting for the choice between attaching it
+    /// and pasting its path
+    pub pending_attachment: Option<PathBuf>,
+
+    /// A file to attach or export to, as typed in the footer so far
+    pub pending_path: Option<PathPrompt>,
+
+    /// A sub-entry waiting for the target typed in the footer
+    pub pending_move: Option<SectionMove>,
+
+    /// The date of a new entry, as typed in the footer so far
+    pub pending_new: Option<String>,
+
+    /// An entry waiting for the confirmation to move it to the trash
+    pub pending_delete: Option<EntryId>,
+
+    /// The `/` search, filtering the tree to matching entries while it has results
+    pub search: Option<SearchState>,
 
     /// Vertical scroll position within the content panel
-    pub content_scroll: u16,
+    pub content_scroll: usize,
+
+    /// Code blocks longer than this many lines start collapsed, 0 never collapses
+    pub collapse_code_lines: usize,
 
     /// Forces a complete UI redraw on next render cycle
     pub needs_redraw: bool,
 
+    /// Number of ticks since the app started, drives timers and animations
+    pub tick_count: u64,
+
     /// Signals the application to terminate gracefully
     pub should_quit: bool,
 }
@@ -45,8 +132,23 @@ impl AppState {
             tree_nodes: Vec::new(),
             flat_items: Vec::new(),
             current_panel: Panel::Nav,
-            selected_entry_content: String::new(),
+            layout: PanelLayout::default(),
+            selected_entry_content: EntryContent::default(),
+            layout_cache: LayoutCache::default(),
+            editor: None,
+            scratch: None,
+            dashboard: None,
+            review: None,
+            notice: None,
+            pending_attachment: None,
+            pending_path: None,
+            pending_move: None,
+            pending_new: None,
+            pending_delete: None,
+            search: None,
             content_scroll: 0,
+            collapse_code_lines: 0,
+            tick_count: 0,
             should_quit: false,
             needs_redraw: false,
         }
@@ -55,4 +157,30 @@ impl AppState {
     pub fn reset_content_scroll(&mut self) {
         self.content_scroll = 0;
     }
+
+    /// Advance timers by one tick, returns whether the UI needs to be redrawn
+    pub fn on_tick(&mut self) -> bool {
+        self.tick_count = self.tick_count.wrapping_add(1);
+        // Nothing is animated yet, idle ticks don't cost a frame
+        false
+    }
+
+    /// Show new content in the content panel, scrolled back to the top, laid out
+    /// the way the entry's display options ask for
+    pub fn set_entry_content(&mut self, content: impl Into<String>, display: &DisplayOptions) {
+        self.layout_cache.store(&mut self.selected_entry_content);
+        self.selected_entry_content = self.layout_cache.restore(content);
+        self.selected_entry_content
+            .collapse_code_after(self.collapse_code_lines);
+        self.selected_entry_content.wrap_lines(display.wraps());
+        self.reset_content_scroll();
+    }
+
+    /// Show the edited content of the current entry, keeping the scroll position
+    /// and the layout of everything before the first change
+    pub fn update_entry_content(&mut self, content: impl Into<String>) {
+        self.selected_entry_content.replace(content);
+        let max_scroll = self.selected_entry_content.row_count().saturating_sub(1);
+        self.content_scroll = self.content_scroll.min(max_scroll);
+    }
 }
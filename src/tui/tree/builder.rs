@@ -2,7 +2,14 @@ use std::collections::HashMap;
 
 use color_eyre::eyre::Result;
 
-use crate::{storage::Storage, tui::models::node::TreeNode};
+use crate::{
+    models::entry_id::{EntryId, EntryRef},
+    storage::{Storage, index::EntryIndex, repository::EntryRepository},
+    tui::models::node::TreeNode,
+};
+
+/// Name of the folder holding the notes, not a valid note name itself
+const NOTES_FOLDER: &str = "Notes";
 
 pub struct TreeBuilder {
     storage: Storage,
@@ -14,23 +21,21 @@ impl TreeBuilder {
     }
 
     /// Builds a hierarchical map of entries organized by year -> month -> days
-    fn build_entry_map(&self) -> Result<HashMap<String, HashMap<String, Vec<String>>>> {
-        let entry_ids = self.storage.list_entries()?;
+    fn build_entry_map(
+        repository: &impl EntryRepository,
+    ) -> Result<HashMap<String, HashMap<String, Vec<String>>>> {
+        let entry_ids = repository.list_entries()?;
 
         // Build year -> month -> day hierarchy
         let mut year_map: HashMap<String, HashMap<String, Vec<String>>> = HashMap::new();
 
         for entry_id in entry_ids {
-            // entry id format: YYYYMMDD
-            let year = entry_id[0..4].to_string();
-            let month = entry_id[4..6].to_string();
-
             year_map
-                .entry(year)
+                .entry(entry_id.year())
                 .or_default()
-                .entry(month)
+                .entry(entry_id.month())
                 .or_default()
-                .push(entry_id);
+                .push(entry_id.to_string());
         }
 
         Ok(year_map)
@@ -38,22 +43,47 @@ impl TreeBuilder {
 
     /// Builds the complete tree structure from storage
     pub fn build_tree(&self) -> Result<Vec<TreeNode>> {
-        let year_map = self.build_entry_map()?;
         let mut tree_nodes = Vec::new();
 
+        // Notes come first, the years below can get long
+        let notes = self.storage.list_notes()?;
+        if !notes.is_empty() {
+            tree_nodes.push(TreeNode {
+                name: NOTES_FOLDER.to_string(),
+                children: notes
+                    .into_iter()
+                    .map(|id| TreeNode::new_entry(id.to_string()))
+                    .collect(),
+                is_expanded: false,
+                is_entry: false,
+            });
+        }
+
+        tree_nodes.extend(Self::build_entry_nodes(&self.storage)?);
+        Ok(tree_nodes)
+    }
+
+    /// Builds the year nodes of every entry in `repository`, newest first, with the
+    /// sub-entries from its index below each day
+    pub fn build_entry_nodes(repository: &impl EntryRepository) -> Result<Vec<TreeNode>> {
+        let year_map = Self::build_entry_map(repository)?;
+        let index = repository.index()?;
+
         // Sort years newest first
         let mut years: Vec<_> = year_map.keys().collect();
         years.sort_by(|a, b| b.cmp(a));
 
-        for year in years {
-            let year_node = self.build_year_node(year, &year_map[year]);
-            tree_nodes.push(year_node);
-        }
-
-        Ok(tree_nodes)
+        Ok(years
+            .into_iter()
+            .map(|year| Self::build_year_node(year, &year_map[year], &index))
+            .collect())
     }
 
-    fn build_year_node(&self, year: &str, months: &HashMap<String, Vec<String>>) -> TreeNode {
+    fn build_year_node(
+        year: &str,
+        months: &HashMap<String, Vec<String>>,
+        index: &EntryIndex,
+    ) -> TreeNode {
         let mut month_nodes = Vec::new();
 
         // Sort months newest first
@@ -61,7 +91,7 @@ impl TreeBuilder {
         sorted_months.sort_by(|a, b| b.cmp(a));
 
         for month in sorted_months {
-            let month_node = self.build_month_node(month, &months[month]);
+            let month_node = Self::build_month_node(month, &months[month], index);
             month_nodes.push(month_node);
         }
 
@@ -73,12 +103,15 @@ impl TreeBuilder {
         }
     }
 
-    fn build_month_node(&self, month: &str, days: &[String]) -> TreeNode {
+    fn build_month_node(month: &str, days: &[String], index: &EntryIndex) -> TreeNode {
         // Sort days newest first
         let mut sorted_days = days.to_vec();
         sorted_days.sort_by(|a, b| b.cmp(a));
 
-        let day_nodes: Vec<TreeNode> = sorted_days.into_iter().map(TreeNode::new_entry).collect();
+        let day_nodes: Vec<TreeNode> = sorted_days
+            .into_iter()
+            .map(|day| Self::build_day_node(day, index))
+            .collect();
 
         TreeNode {
             name: month.to_string(),
@@ -87,12 +120,30 @@ impl TreeBuilder {
             is_entry: false,
         }
     }
+
+    /// A day's entry, with its timestamped sub-entries as children in the order
+    /// they were written
+    fn build_day_node(day: String, index: &EntryIndex) -> TreeNode {
+        let mut node = TreeNode::new_entry(day);
+        if let Ok(id) = EntryId::parse(&node.name)
+            && let Some(indexed) = index.get(&id)
+        {
+            node.children = indexed
+                .sub_entries
+                .iter()
+                .map(|time| TreeNode::new_entry(EntryRef::sub_entry(id, *time).to_string()))
+                .collect();
+        }
+        node
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::entry::Entry;
+    use crate::models::note::Note;
+    use crate::storage::memory::MemoryRepository;
     use tempfile::TempDir;
 
     /// Create a test storage instance in a temporary directory
@@ -106,7 +157,7 @@ mod tests {
     /// Helper function to create and save test entries
     fn create_test_entries(storage: &Storage, entry_ids: &[&str]) {
         for &id in entry_ids {
-            let entry = Entry::new(id.to_string(), format!("Content for {}", id));
+            let entry = Entry::new(id.parse().unwrap(), format!("Content for {}", id));
             storage
                 .save_entry(&entry)
                 .expect("Failed to save test entry");
@@ -126,8 +177,7 @@ mod tests {
             ],
         );
 
-        let tree_builder = TreeBuilder::new(storage);
-        let result = tree_builder.build_entry_map().expect("Failed to build map");
+        let result = TreeBuilder::build_entry_map(&storage).expect("Failed to build map");
 
         assert_eq!(result.len(), 2); // Two years
         assert!(result.contains_key("2025"));
@@ -270,4 +320,62 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_build_tree_sub_entries() {
+        let (storage, _temp_dir) = create_test_storage();
+        create_test_entries(&storage, &["20250919"]);
+        let entry = Entry::new(
+            "20250920".parse().unwrap(),
+            "## 09:15\n\nStandup\n\n## 14:32\n\nReview".to_string(),
+        );
+        storage.save_entry(&entry).unwrap();
+
+        let tree_builder = TreeBuilder::new(storage);
+        let result = tree_builder.build_tree().expect("Failed to build tree");
+
+        let days = &result[0].children[0].children;
+        let names: Vec<&str> = days[0].children.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["20250920-0915", "20250920-1432"]);
+        assert!(days[0].children.iter().all(|n| n.is_entry));
+        assert!(days[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_build_tree_notes_first() {
+        let (storage, _temp_dir) = create_test_storage();
+        create_test_entries(&storage, &["20250920"]);
+        for name in ["runbook", "interview-prep"] {
+            let note = Note::new(name.parse().unwrap(), String::new());
+            storage.save_note(&note).unwrap();
+        }
+
+        let tree_builder = TreeBuilder::new(storage);
+        let result = tree_builder.build_tree().expect("Failed to build tree");
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].name, "Notes");
+        assert!(!result[0].is_entry);
+        let names: Vec<&str> = result[0].children.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["interview-prep", "runbook"]);
+        assert_eq!(result[1].name, "2025");
+    }
+
+    #[test]
+    fn test_build_entry_nodes_from_memory() {
+        let repository = MemoryRepository::new();
+        repository
+            .save_entry(&Entry::new(
+                "20250920".parse().unwrap(),
+                "## 09:15\n\nStandup".to_string(),
+            ))
+            .unwrap();
+
+        let result = TreeBuilder::build_entry_nodes(&repository).expect("Failed to build tree");
+
+        assert_eq!(result[0].name, "2025");
+        let day = &result[0].children[0].children[0];
+        assert_eq!(day.name, "20250920");
+        assert_eq!(day.children[0].name, "20250920-0915");
+    }
 }
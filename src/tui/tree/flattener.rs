@@ -1,7 +1,7 @@
-use crate::tui::models::node::TreeNode;
+use crate::{models::entry_id::EntryRef, tui::models::node::TreeNode};
 
 /// Represents a flattened tree item with node name, display text and entry status
-/// Node name could be (YYYY, MM, or YYYYMMDD)
+/// Node name could be (YYYY, MM, YYYYMMDD or YYYYMMDD-HHMM for sub-entries)
 /// Display text is node name + file structure ascii art text
 pub type FlatTreeItem = (String, String, bool);
 
@@ -48,12 +48,25 @@ impl TreeFlattener {
 
         format!(
             "{}{}{}{}",
-            prefix, connector, expansion_indicator, node.name
+            prefix,
+            connector,
+            expansion_indicator,
+            Self::label(node)
         )
     }
 
+    /// Sub-entries are shown by their time, everything else by name
+    fn label(node: &TreeNode) -> String {
+        match node.name.parse::<EntryRef>() {
+            Ok(EntryRef {
+                time: Some(time), ..
+            }) => time.format("%H:%M").to_string(),
+            _ => node.name.clone(),
+        }
+    }
+
     fn get_expansion_indicator(node: &TreeNode) -> &'static str {
-        if node.is_entry {
+        if node.is_entry && node.children.is_empty() {
             ""
         } else if node.is_expanded {
             "[-] "
@@ -281,4 +294,18 @@ mod tests {
             "│   ├─ 20250920"
         );
     }
+
+    #[test]
+    fn test_flatten_entry_with_sub_entries() {
+        let mut day = create_entry_node("20250920");
+        day.children = vec![create_entry_node("20250920-0915")];
+        day.is_expanded = true;
+        let result = TreeFlattener::flatten(&[day]);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].1, "└─ [-] 20250920");
+        assert_eq!(result[1].0, "20250920-0915");
+        assert_eq!(result[1].1, "    └─ 09:15");
+        assert!(result[1].2);
+    }
 }
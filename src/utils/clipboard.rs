@@ -0,0 +1,102 @@
+use std::{
+    env,
+    io::ErrorKind,
+    process::{Command, Stdio},
+};
+
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{self, DevlogError};
+
+/// Image types asked for, in order of preference, with their file extension
+const IMAGE_TYPES: [(&str, &str); 4] = [
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+];
+
+/// An image read from the clipboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub contents: Vec<u8>,
+    pub extension: &'static str,
+}
+
+/// Read an image from the system clipboard with the platform's clipboard tool:
+/// `wl-paste` on Wayland, `xclip` on X11 and `pngpaste` on macOS. Returns `None`
+/// if the clipboard holds no image.
+pub fn read_image() -> Result<Option<ClipboardImage>> {
+    if cfg!(target_os = "macos") {
+        return Ok(run("pngpaste", &["-"])?.map(|contents| ClipboardImage {
+            contents,
+            extension: "png",
+        }));
+    }
+
+    let (program, list_args, read_args): (&str, &[&str], &[&str]) =
+        if env::var_os("WAYLAND_DISPLAY").is_some() {
+            ("wl-paste", &["--list-types"], &["--no-newline", "--type"])
+        } else {
+            (
+                "xclip",
+                &["-selection", "clipboard", "-target", "TARGETS", "-out"],
+                &["-selection", "clipboard", "-out", "-target"],
+            )
+        };
+    let Some(types) = run(program, list_args)? else {
+        return Ok(None);
+    };
+    let Some((mime, extension)) = image_type(&String::from_utf8_lossy(&types)) else {
+        return Ok(None);
+    };
+    let mut args = read_args.to_vec();
+    args.push(mime);
+    Ok(run(program, &args)?
+        .filter(|contents| !contents.is_empty())
+        .map(|contents| ClipboardImage {
+            contents,
+            extension,
+        }))
+}
+
+/// The preferred image type among the clipboard's types, one per line
+fn image_type(types: &str) -> Option<(&'static str, &'static str)> {
+    IMAGE_TYPES
+        .into_iter()
+        .find(|(mime, _)| types.lines().any(|line| line.trim() == *mime))
+}
+
+/// Output of a clipboard tool, `None` if it failed, e.g. because the clipboard is empty
+fn run(program: &str, args: &[&str]) -> Result<Option<Vec<u8>>> {
+    let output = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => bail!(DevlogError::new(
+            error::ErrorKind::NotFound,
+            format!(
+                "{} is not installed, it is needed to read the clipboard",
+                program
+            )
+        )),
+        Err(e) => return Err(e).wrap_err_with(|| format!("Failed to run {}", program)),
+    };
+    Ok(output.status.success().then_some(output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_type_prefers_png() {
+        let types = "TARGETS\ntext/html\nimage/jpeg\nimage/png\n";
+        assert_eq!(image_type(types), Some(("image/png", "png")));
+        assert_eq!(image_type("image/jpeg"), Some(("image/jpeg", "jpg")));
+        assert_eq!(image_type("UTF8_STRING\ntext/plain"), None);
+    }
+}
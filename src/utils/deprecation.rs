@@ -0,0 +1,109 @@
+/// An invocation that still works but is going away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Stable code, e.g. for grepping logs or silencing in scripts
+    pub code: &'static str,
+    pub since: &'static str,
+    pub message: &'static str,
+    pub replacement: &'static str,
+}
+
+/// `devlog show --id 20250920`, entry ids became positional
+pub const ID_FLAG: Deprecation = Deprecation {
+    code: "id-flag",
+    since: "0.2.0",
+    message: "`--id` is deprecated",
+    replacement: "pass the entry id as an argument, e.g. `devlog show 20250920`",
+};
+
+/// Subcommands whose entry id moved from `--id` to a positional argument
+const ID_COMMANDS: &[&str] = &["new", "edit", "show"];
+
+impl Deprecation {
+    /// Print a warning in a format that is easy to grep for
+    pub fn warn(&self) {
+        eprintln!(
+            "warning[deprecated:{}]: {} since {}, {}. Run `devlog migrate-cli` to update your scripts",
+            self.code, self.message, self.since, self.replacement
+        );
+    }
+}
+
+/// Rewrite deprecated devlog invocations in a line of shell, e.g. an alias or script.
+/// Returns `None` if nothing needs to change.
+pub fn rewrite_line(line: &str) -> Option<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut tokens = line.split(' ').peekable();
+    let mut in_id_command = false;
+
+    while let Some(token) = tokens.next() {
+        if in_id_command {
+            if let Some(value) = token.strip_prefix("--id=") {
+                out.push(value.to_string());
+            } else if let Some(rest) = token.strip_prefix("--id")
+                && rest.chars().all(ends_command)
+            {
+                // The flag may end an alias, e.g. `alias ds='devlog show --id'`
+                match out.last_mut() {
+                    Some(last) if !rest.is_empty() => last.push_str(rest),
+                    _ if !rest.is_empty() => out.push(rest.to_string()),
+                    _ => {}
+                }
+            } else {
+                out.push(token.to_string());
+            }
+        } else {
+            out.push(token.to_string());
+        }
+
+        // Strip alias assignments and quoting, e.g. `ds='devlog`
+        let bare = token
+            .rsplit('=')
+            .next()
+            .unwrap_or(token)
+            .trim_start_matches(['\'', '"', '(', '`']);
+        if bare == "devlog" || bare.ends_with("/devlog") {
+            in_id_command = tokens
+                .peek()
+                .map(|next| next.trim_end_matches(ends_command))
+                .is_some_and(|next| ID_COMMANDS.contains(&next));
+        } else if token.chars().last().is_some_and(ends_command) {
+            in_id_command = false;
+        }
+    }
+
+    let rewritten = out.join(" ");
+    (rewritten != line).then_some(rewritten)
+}
+
+fn ends_command(ch: char) -> bool {
+    matches!(ch, ';' | '|' | '&' | ')' | '\'' | '"' | '`')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_id_flags() {
+        assert_eq!(
+            rewrite_line("devlog show --id 20250920").as_deref(),
+            Some("devlog show 20250920")
+        );
+        assert_eq!(
+            rewrite_line("~/bin/devlog edit --id=20250920 && echo done").as_deref(),
+            Some("~/bin/devlog edit 20250920 && echo done")
+        );
+        assert_eq!(
+            rewrite_line("alias ds='devlog show --id'").as_deref(),
+            Some("alias ds='devlog show'")
+        );
+    }
+
+    #[test]
+    fn test_rewrite_leaves_other_commands_alone() {
+        assert_eq!(rewrite_line("devlog list --interactive"), None);
+        assert_eq!(rewrite_line("mytool show --id 1; devlog list"), None);
+        assert_eq!(rewrite_line("devlog new; other --id 1").as_deref(), None);
+    }
+}
@@ -0,0 +1,52 @@
+/// The lines that differ between `old` and `new`, removed ones prefixed with `- ` and
+/// added ones with `+ `, in the order they appear. Lines both have in common, by their
+/// longest common subsequence, are left out.
+pub fn changed_lines(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            changes.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_lines() {
+        let old = "# Notes\nFixed the build\nLunch\n";
+        let new = "# Notes\nFixed the build ::ci\nLunch\nReview\n";
+        assert_eq!(
+            changed_lines(old, new),
+            vec!["- Fixed the build", "+ Fixed the build ::ci", "+ Review"]
+        );
+        assert!(changed_lines(old, old).is_empty());
+        assert_eq!(changed_lines("", "one"), vec!["+ one"]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fs, process};
+use std::{fs, path::Path, process};
 
 use color_eyre::eyre::{Context, Result, bail};
 
@@ -43,8 +43,43 @@ pub fn launch_editor(existing_content: Option<&str>) -> Result<String> {
     Ok(processed_content)
 }
 
+/// Open an existing file in the text editor, e.g. the config file
+pub fn open_file(path: &Path) -> Result<()> {
+    let editor = find_available_editor();
+
+    let status = process::Command::new(&editor)
+        .arg(path)
+        .status()
+        .wrap_err_with(|| format!("Failed to launch editor: {}", editor))?;
+
+    if !status.success() {
+        bail!("Editor exited with error");
+    }
+
+    Ok(())
+}
+
+/// Show a file read-only in `$PAGER`, falling back to `less`
+pub fn open_in_pager(path: &Path) -> Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+
+    let status = process::Command::new(&pager)
+        .arg(path)
+        .status()
+        .wrap_err_with(|| format!("Failed to launch pager: {}", pager))?;
+
+    if !status.success() {
+        bail!("Pager exited with error");
+    }
+
+    Ok(())
+}
+
 /// Find the first available editor
-fn find_available_editor() -> String {
+pub fn find_available_editor() -> String {
     let editors = ["vi", "vim", "nano"];
 
     for editor in editors {
@@ -0,0 +1,130 @@
+use std::{
+    env, fs,
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+};
+
+/// Whether a native file dialog can be shown: only in builds with the `file-dialog`
+/// feature, then always on macOS and Windows and with an X11 or Wayland display
+/// elsewhere. Over SSH or on a bare console there is none.
+pub fn has_display() -> bool {
+    if !cfg!(feature = "file-dialog") {
+        return false;
+    }
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        return true;
+    }
+    env::var_os("DISPLAY").is_some() || env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Ask for a file to open with the platform's file picker, `None` if it was cancelled
+#[cfg(feature = "file-dialog")]
+pub fn pick_file(title: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new().set_title(title).pick_file()
+}
+
+/// Without the `file-dialog` feature there is no picker, the path is typed instead
+#[cfg(not(feature = "file-dialog"))]
+pub fn pick_file(_title: &str) -> Option<PathBuf> {
+    None
+}
+
+/// Ask where to save a file, suggesting `file_name`, `None` if it was cancelled
+#[cfg(feature = "file-dialog")]
+pub fn save_file(title: &str, file_name: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .set_title(title)
+        .set_file_name(file_name)
+        .save_file()
+}
+
+#[cfg(not(feature = "file-dialog"))]
+pub fn save_file(_title: &str, _file_name: &str) -> Option<PathBuf> {
+    None
+}
+
+/// `typed` with a leading `~` standing for the home directory
+pub fn expand_home(typed: &str) -> PathBuf {
+    match typed.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', MAIN_SEPARATOR]) => {
+            match dirs::home_dir() {
+                Some(home) => home.join(rest.trim_start_matches(['/', MAIN_SEPARATOR])),
+                None => PathBuf::from(typed),
+            }
+        }
+        _ => PathBuf::from(typed),
+    }
+}
+
+/// Complete the last component of a typed path like a shell does on Tab: to the one
+/// file or directory it starts, directories with a trailing separator, or else as far
+/// as all candidates agree. Relative paths are completed in `cwd`.
+pub fn complete_path(typed: &str, cwd: &Path) -> String {
+    let split = typed.rfind(['/', MAIN_SEPARATOR]).map_or(0, |i| i + 1);
+    let (dir, prefix) = typed.split_at(split);
+    let dir_path = match dir {
+        "" => cwd.to_path_buf(),
+        dir => cwd.join(expand_home(dir)),
+    };
+    let Ok(items) = fs::read_dir(&dir_path) else {
+        return typed.to_string();
+    };
+
+    let mut candidates: Vec<(String, bool)> = items
+        .filter_map(|item| item.ok())
+        .filter_map(|item| {
+            let name = item.file_name().into_string().ok()?;
+            // Hidden files only when asked for, like shells
+            let wanted =
+                name.starts_with(prefix) && (prefix.starts_with('.') || !name.starts_with('.'));
+            wanted.then(|| (name, item.path().is_dir()))
+        })
+        .collect();
+    candidates.sort();
+
+    match candidates.as_slice() {
+        [] => typed.to_string(),
+        [(name, is_dir)] => format!("{}{}{}", dir, name, if *is_dir { "/" } else { "" }),
+        [(first, _), rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |common, (name, _)| {
+                let len = common
+                    .char_indices()
+                    .zip(name.chars())
+                    .take_while(|((_, a), b)| a == b)
+                    .last()
+                    .map_or(0, |((i, a), _)| i + a.len_utf8());
+                &common[..len]
+            });
+            format!("{}{}", dir, common)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_complete_path() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        fs::create_dir(root.join("diagrams")).unwrap();
+        fs::write(root.join("diagrams/flow.png"), b"").unwrap();
+        fs::write(root.join("notes-monday.md"), b"").unwrap();
+        fs::write(root.join("notes-tuesday.md"), b"").unwrap();
+        fs::write(root.join(".hidden"), b"").unwrap();
+
+        assert_eq!(complete_path("dia", root), "diagrams/");
+        assert_eq!(complete_path("diagrams/f", root), "diagrams/flow.png");
+        assert_eq!(complete_path("no", root), "notes-");
+        assert_eq!(complete_path("notes-t", root), "notes-tuesday.md");
+        assert_eq!(complete_path("missing/x", root), "missing/x");
+        assert_eq!(complete_path(".h", root), ".hidden");
+
+        let absolute = format!("{}/diag", root.display());
+        assert_eq!(
+            complete_path(&absolute, Path::new("/")),
+            format!("{}/diagrams/", root.display())
+        );
+    }
+}
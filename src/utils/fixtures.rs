@@ -0,0 +1,236 @@
+use chrono::{Days, NaiveDate};
+use color_eyre::eyre::Result;
+
+use crate::{
+    models::{entry::Entry, entry_id::EntryId},
+    storage::repository::EntryRepository,
+};
+
+const WORDS: &[&str] = &[
+    "refactor",
+    "deploy",
+    "review",
+    "pipeline",
+    "latency",
+    "cache",
+    "schema",
+    "migration",
+    "incident",
+    "rollout",
+    "benchmark",
+    "parser",
+    "endpoint",
+    "query",
+    "index",
+    "retry",
+    "timeout",
+    "config",
+    "feature",
+    "flag",
+    "test",
+    "fixture",
+    "release",
+    "branch",
+    "merge",
+    "design",
+    "doc",
+    "sync",
+    "storage",
+    "tree",
+    "render",
+    "terminal",
+    "widget",
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "tempor",
+];
+const PEOPLE: &[&str] = &["alice", "bob", "carol", "dave", "erin", "huijing_huang"];
+const PROJECTS: &[&str] = &["devlog", "search-service", "billing", "infra", "mobile-app"];
+const TAGS: &[&str] = &[
+    "rust", "cli", "oncall", "learning", "meeting", "perf", "bug",
+];
+const HEADINGS: &[&str] = &["Today", "Notes", "Blockers", "Next Steps", "Learnings"];
+
+/// Generates realistic looking, reproducible journal entries for demos,
+/// benchmarks and performance investigations
+pub struct VaultGenerator {
+    rng: fastrand::Rng,
+}
+
+impl VaultGenerator {
+    /// The same seed always produces the same vault
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    /// Save one entry per day for the `days` days up to and including `last_day`
+    pub fn populate(
+        &mut self,
+        storage: &impl EntryRepository,
+        days: usize,
+        last_day: NaiveDate,
+    ) -> Result<()> {
+        for offset in 0..days {
+            let Some(date) = last_day.checked_sub_days(Days::new(offset as u64)) else {
+                break;
+            };
+            let entry = Entry::new(EntryId::from_date(date), self.entry_content());
+            storage.save_entry(&entry)?;
+        }
+        Ok(())
+    }
+
+    /// Generate the markdown content of a single entry, from a one-liner
+    /// up to a long multi-section entry
+    pub fn entry_content(&mut self) -> String {
+        let mut sections = vec![self.sentence()];
+
+        let section_count = match self.rng.u8(0..10) {
+            0..=4 => 0,
+            5..=8 => self.rng.usize(1..3),
+            _ => self.rng.usize(3..6),
+        };
+
+        for _ in 0..section_count {
+            let heading = HEADINGS[self.rng.usize(..HEADINGS.len())];
+            let body = if self.rng.bool() {
+                self.paragraph()
+            } else {
+                self.list()
+            };
+            sections.push(format!("## {}\n\n{}", heading, body));
+        }
+
+        // Occasionally paste a big log dump, those are the entries that stress the TUI
+        if self.rng.u8(0..50) == 0 {
+            sections.push(self.log_dump());
+        }
+
+        sections.join("\n\n")
+    }
+
+    fn log_dump(&mut self) -> String {
+        let line_count = self.rng.usize(200..800);
+        let lines: Vec<String> = (0..line_count)
+            .map(|i| {
+                let level = ["INFO", "WARN", "DEBUG", "ERROR"][self.rng.usize(..4)];
+                let word = WORDS[self.rng.usize(..WORDS.len())];
+                format!(
+                    "2025-09-20T12:{:02}:{:02}Z {} {} request_id={} took {}ms",
+                    (i / 60) % 60,
+                    i % 60,
+                    level,
+                    word,
+                    self.rng.u32(..),
+                    self.rng.u16(..5000)
+                )
+            })
+            .collect();
+
+        format!("## Logs\n\n```\n{}\n```", lines.join("\n"))
+    }
+
+    fn paragraph(&mut self) -> String {
+        let count = self.rng.usize(2..6);
+        (0..count)
+            .map(|_| self.sentence())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn list(&mut self) -> String {
+        let count = self.rng.usize(2..6);
+        (0..count)
+            .map(|_| {
+                let marker = match self.rng.u8(0..4) {
+                    0 => "- [ ] ",
+                    1 => "- [x] ",
+                    _ => "- ",
+                };
+                format!("{}{}", marker, self.sentence())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// A sentence of lorem words sprinkled with annotations
+    fn sentence(&mut self) -> String {
+        let count = self.rng.usize(4..14);
+        let mut words: Vec<String> = (0..count)
+            .map(|_| WORDS[self.rng.usize(..WORDS.len())].to_string())
+            .collect();
+
+        if self.rng.u8(0..3) == 0 {
+            words.push(format!("@{}", PEOPLE[self.rng.usize(..PEOPLE.len())]));
+        }
+        if self.rng.u8(0..3) == 0 {
+            words.push(format!("::{}", PROJECTS[self.rng.usize(..PROJECTS.len())]));
+        }
+        if self.rng.u8(0..2) == 0 {
+            words.push(format!("+{}", TAGS[self.rng.usize(..TAGS.len())]));
+        }
+
+        let mut sentence = words.join(" ");
+        if let Some(first) = sentence.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        sentence.push('.');
+        sentence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::annotation::Annotations;
+    use crate::storage::{Storage, memory::MemoryRepository};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generator_is_reproducible() {
+        let first = VaultGenerator::new(42).entry_content();
+        let second = VaultGenerator::new(42).entry_content();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_generated_content_contains_annotations() {
+        let mut generator = VaultGenerator::new(7);
+        let content: String = (0..20).map(|_| generator.entry_content()).collect();
+
+        let annotations = Annotations::parse(&content);
+        assert!(!annotations.people.is_empty());
+        assert!(!annotations.projects.is_empty());
+        assert!(!annotations.tags.is_empty());
+    }
+
+    #[test]
+    fn test_populate_creates_consecutive_days() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let storage =
+            Storage::new_with_base_dir(temp_dir.path()).expect("Failed to create storage");
+        let last_day = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+
+        VaultGenerator::new(1)
+            .populate(&storage, 3, last_day)
+            .expect("Failed to populate vault");
+
+        let entries = storage.list_entries().expect("Failed to list entries");
+        assert_eq!(entries, vec!["20250302", "20250301", "20250228"]);
+
+        let memory = MemoryRepository::new();
+        VaultGenerator::new(1)
+            .populate(&memory, 3, last_day)
+            .expect("Failed to populate vault");
+        assert_eq!(memory.list_entries().unwrap(), entries);
+    }
+}
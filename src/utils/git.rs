@@ -0,0 +1,37 @@
+use std::{path::Path, process};
+
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+
+/// Run git in the current directory and return its trimmed output
+pub fn run(args: &[&str]) -> Result<String> {
+    let output = process::Command::new("git")
+        .args(args)
+        .output()
+        .wrap_err("Failed to run git, is it installed?")?;
+    if !output.status.success() {
+        bail!(DevlogError::new(
+            ErrorKind::NotFound,
+            format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Root directory of the repository the current directory is in
+pub fn toplevel() -> Result<String> {
+    run(&["rev-parse", "--show-toplevel"])
+}
+
+/// Name of a repository, the last component of its root directory
+pub fn repo_name(toplevel: &str) -> String {
+    Path::new(toplevel)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| toplevel.to_string())
+}
@@ -0,0 +1,181 @@
+use std::{
+    fs::{self, File, TryLockError},
+    io::Write,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::eyre::{Context, Result, bail};
+
+use crate::error::{self, DevlogError};
+
+/// How long to keep retrying before giving up on a held lock
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Delay between two acquisition attempts
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An exclusive lock on a `<file>.lock` sibling file.
+///
+/// The lock is an OS file lock, so a crashed process can't leave it behind: the OS
+/// releases it with the process, and a leftover lock file is simply locked again.
+/// The file is removed on drop while still locked.
+#[derive(Debug)]
+pub struct FileLock {
+    path: PathBuf,
+    file: File,
+}
+
+impl FileLock {
+    /// Acquire the lock guarding `target`, waiting briefly if another process holds it
+    pub fn acquire(target: &Path) -> Result<Self> {
+        let path = Self::lock_path(target);
+        let started = SystemTime::now();
+
+        loop {
+            let file = fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .wrap_err_with(|| format!("Failed to create lock file {}", path.display()))?;
+
+            match file.try_lock() {
+                // The holder may have removed the file between our open and lock,
+                // then the lock is on a file nobody else will look at
+                Ok(()) if is_same_file(&file, &path) => {
+                    let mut lock = Self { path, file };
+                    // The PID is informational only, it helps users find the other process
+                    let _ = lock.file.set_len(0);
+                    let _ = writeln!(lock.file, "{}", std::process::id());
+                    return Ok(lock);
+                }
+                Ok(()) => continue,
+                Err(TryLockError::WouldBlock) => {
+                    if started.elapsed().unwrap_or_default() >= ACQUIRE_TIMEOUT {
+                        bail!(DevlogError::new(
+                            error::ErrorKind::LockHeld,
+                            format!(
+                                "Another devlog process is running (lock held on {})",
+                                target.display()
+                            )
+                        ));
+                    }
+                    thread::sleep(RETRY_INTERVAL);
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(e).wrap_err_with(|| format!("Failed to lock {}", path.display()));
+                }
+            }
+        }
+    }
+
+    /// Path of the lock file guarding `target`
+    fn lock_path(target: &Path) -> PathBuf {
+        let mut name = target.file_name().unwrap_or_default().to_os_string();
+        name.push(".lock");
+        target.with_file_name(name)
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Removed before the file is closed, which releases the lock
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Whether `path` still names the file open as `file`
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(open), Ok(named)) => open.dev() == named.dev() && open.ino() == named.ino(),
+        _ => false,
+    }
+}
+
+/// Whether `path` still names the file open as `file`. Windows doesn't create a new
+/// file in place of one that is deleted but still open, so it's enough that it exists.
+#[cfg(not(unix))]
+fn is_same_file(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_released_on_drop() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let target = temp_dir.path().join("config.toml");
+
+        {
+            let _lock = FileLock::acquire(&target).expect("Failed to acquire lock");
+            assert!(temp_dir.path().join("config.toml.lock").exists());
+        }
+
+        assert!(!temp_dir.path().join("config.toml.lock").exists());
+        FileLock::acquire(&target).expect("Lock should be free again");
+    }
+
+    #[test]
+    fn test_held_lock_reports_other_process() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let target = temp_dir.path().join("config.toml");
+
+        let _lock = FileLock::acquire(&target).expect("Failed to acquire lock");
+        let err = FileLock::acquire(&target).expect_err("Second acquire should fail");
+
+        assert!(
+            err.to_string()
+                .contains("Another devlog process is running")
+        );
+    }
+
+    #[test]
+    fn test_leftover_lock_file_is_taken_over() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let target = temp_dir.path().join("config.toml");
+        // Left behind by a crashed process, which released its OS lock on exit
+        fs::write(temp_dir.path().join("config.toml.lock"), "12345\n").unwrap();
+
+        let _lock = FileLock::acquire(&target).expect("Leftover lock should be free");
+        let err = FileLock::acquire(&target).expect_err("Second acquire should fail");
+        assert!(
+            err.to_string()
+                .contains("Another devlog process is running")
+        );
+    }
+
+    #[test]
+    fn test_one_holder_at_a_time() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let target = temp_dir.path().join("sync.json");
+        let held = Arc::new(AtomicBool::new(false));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let (target, held) = (target.clone(), held.clone());
+                thread::spawn(move || {
+                    for _ in 0..5 {
+                        let _lock = FileLock::acquire(&target).expect("Failed to acquire lock");
+                        assert!(!held.swap(true, Ordering::SeqCst), "Lock held twice");
+                        thread::sleep(Duration::from_millis(1));
+                        held.store(false, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().expect("A holder overlapped with another");
+        }
+    }
+}
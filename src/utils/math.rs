@@ -0,0 +1,388 @@
+use std::{iter::Peekable, str::Chars};
+
+/// Commands replaced by a single symbol
+const SYMBOLS: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ε"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "φ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+    ("times", "×"),
+    ("cdot", "·"),
+    ("div", "÷"),
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("leq", "≤"),
+    ("le", "≤"),
+    ("geq", "≥"),
+    ("ge", "≥"),
+    ("neq", "≠"),
+    ("ne", "≠"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("sim", "∼"),
+    ("propto", "∝"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("infty", "∞"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("int", "∫"),
+    ("oint", "∮"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("emptyset", "∅"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("land", "∧"),
+    ("lor", "∨"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("iff", "⇔"),
+    ("mapsto", "↦"),
+    ("ldots", "…"),
+    ("dots", "…"),
+    ("cdots", "⋯"),
+    ("top", "ᵀ"),
+    ("circ", "∘"),
+    ("star", "⋆"),
+    ("langle", "⟨"),
+    ("rangle", "⟩"),
+    ("lfloor", "⌊"),
+    ("rfloor", "⌋"),
+    ("lceil", "⌈"),
+    ("rceil", "⌉"),
+    ("quad", "  "),
+    ("qquad", "    "),
+];
+
+/// Commands that only change how their argument looks
+const FONTS: &[&str] = &[
+    "text",
+    "mathrm",
+    "mathit",
+    "mathbf",
+    "mathsf",
+    "mathtt",
+    "mathcal",
+    "boldsymbol",
+    "operatorname",
+    "textbf",
+    "textit",
+];
+
+/// Sizing commands in front of delimiters, dropped
+const SIZES: &[&str] = &[
+    "left",
+    "right",
+    "big",
+    "Big",
+    "bigg",
+    "Bigg",
+    "bigl",
+    "bigr",
+    "Bigl",
+    "Bigr",
+    "displaystyle",
+];
+
+/// Operators like `\sin` and `\max`, which read fine as their name
+const OPERATORS: &[&str] = &[
+    "sin", "cos", "tan", "log", "ln", "exp", "lim", "max", "min", "arg", "det", "sup", "inf",
+];
+
+/// Accents as combining characters
+const ACCENTS: &[(&str, char)] = &[
+    ("hat", '\u{302}'),
+    ("bar", '\u{304}'),
+    ("overline", '\u{305}'),
+    ("tilde", '\u{303}'),
+    ("vec", '\u{20d7}'),
+    ("dot", '\u{307}'),
+    ("ddot", '\u{308}'),
+];
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('a', 'ᵃ'),
+    ('b', 'ᵇ'),
+    ('c', 'ᶜ'),
+    ('d', 'ᵈ'),
+    ('e', 'ᵉ'),
+    ('f', 'ᶠ'),
+    ('g', 'ᵍ'),
+    ('h', 'ʰ'),
+    ('i', 'ⁱ'),
+    ('j', 'ʲ'),
+    ('k', 'ᵏ'),
+    ('l', 'ˡ'),
+    ('m', 'ᵐ'),
+    ('n', 'ⁿ'),
+    ('o', 'ᵒ'),
+    ('p', 'ᵖ'),
+    ('r', 'ʳ'),
+    ('s', 'ˢ'),
+    ('t', 'ᵗ'),
+    ('u', 'ᵘ'),
+    ('v', 'ᵛ'),
+    ('w', 'ʷ'),
+    ('x', 'ˣ'),
+    ('y', 'ʸ'),
+    ('z', 'ᶻ'),
+    ('T', 'ᵀ'),
+    ('ᵀ', 'ᵀ'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('h', 'ₕ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('l', 'ₗ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('p', 'ₚ'),
+    ('r', 'ᵣ'),
+    ('s', 'ₛ'),
+    ('t', 'ₜ'),
+    ('u', 'ᵤ'),
+    ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+/// Approximate LaTeX math with Unicode for plain text displays, e.g.
+/// `\frac{1}{2} \alpha x_i^2` becomes `½ α xᵢ²`. Commands it doesn't know are
+/// kept as they are.
+pub fn to_unicode(latex: &str) -> String {
+    let mut chars = latex.chars().peekable();
+    sequence(&mut chars, false)
+}
+
+/// Convert up to the closing brace of the group, or the end with `in_group` unset
+fn sequence(chars: &mut Peekable<Chars>, in_group: bool) -> String {
+    let mut out = String::new();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '}' if in_group => break,
+            '{' => out.push_str(&sequence(chars, true)),
+            '\\' => out.push_str(&command(chars)),
+            '^' => out.push_str(&script(&argument(chars), SUPERSCRIPTS, '^')),
+            '_' => out.push_str(&script(&argument(chars), SUBSCRIPTS, '_')),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// The argument of a command or script: a group, a command or a single character
+fn argument(chars: &mut Peekable<Chars>) -> String {
+    while chars.next_if(|ch| *ch == ' ').is_some() {}
+    match chars.next() {
+        Some('{') => sequence(chars, true),
+        Some('\\') => command(chars),
+        Some(ch) => ch.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Right after a backslash, the command and its arguments
+fn command(chars: &mut Peekable<Chars>) -> String {
+    let mut name = String::new();
+    while let Some(ch) = chars.next_if(char::is_ascii_alphabetic) {
+        name.push(ch);
+    }
+    if name.is_empty() {
+        return match chars.next() {
+            Some(',' | ';' | ':' | ' ') => " ".to_string(),
+            Some('!') | None => String::new(),
+            Some('\\') => "; ".to_string(),
+            Some(ch) => ch.to_string(),
+        };
+    }
+
+    match name.as_str() {
+        "frac" | "dfrac" | "tfrac" => {
+            let numerator = argument(chars);
+            let denominator = argument(chars);
+            fraction(&numerator, &denominator)
+        }
+        "sqrt" => {
+            let index: Option<String> = chars
+                .next_if_eq(&'[')
+                .map(|_| chars.by_ref().take_while(|ch| *ch != ']').collect());
+            let root = match index.as_deref() {
+                Some("3") => "∛",
+                Some("4") => "∜",
+                _ => "√",
+            };
+            format!("{}{}", root, parenthesized(&argument(chars)))
+        }
+        "mathbb" => argument(chars)
+            .chars()
+            .map(|ch| match ch {
+                'R' => 'ℝ',
+                'N' => 'ℕ',
+                'Z' => 'ℤ',
+                'Q' => 'ℚ',
+                'C' => 'ℂ',
+                _ => ch,
+            })
+            .collect(),
+        name if FONTS.contains(&name) => argument(chars),
+        name if SIZES.contains(&name) => String::new(),
+        name => {
+            if let Some((_, symbol)) = SYMBOLS.iter().find(|(command, _)| *command == name) {
+                symbol.to_string()
+            } else if let Some((_, accent)) = ACCENTS.iter().find(|(command, _)| *command == name) {
+                let mut base = argument(chars);
+                base.push(*accent);
+                base
+            } else if OPERATORS.contains(&name) {
+                name.to_string()
+            } else if chars.peek() == Some(&'{') {
+                format!("\\{}{{{}}}", name, argument(chars))
+            } else {
+                format!("\\{}", name)
+            }
+        }
+    }
+}
+
+fn fraction(numerator: &str, denominator: &str) -> String {
+    let vulgar = match (numerator, denominator) {
+        ("1", "2") => Some("½"),
+        ("1", "3") => Some("⅓"),
+        ("2", "3") => Some("⅔"),
+        ("1", "4") => Some("¼"),
+        ("3", "4") => Some("¾"),
+        _ => None,
+    };
+    vulgar.map(str::to_string).unwrap_or_else(|| {
+        format!(
+            "{}/{}",
+            parenthesized(numerator),
+            parenthesized(denominator)
+        )
+    })
+}
+
+/// Raise or lower `text` with Unicode super- or subscripts, falling back to
+/// `^(text)` when one of its characters has none
+fn script(text: &str, table: &[(char, char)], marker: char) -> String {
+    let mapped: Option<String> = text
+        .chars()
+        .map(|ch| {
+            table
+                .iter()
+                .find(|(from, _)| *from == ch)
+                .map(|(_, to)| *to)
+        })
+        .collect();
+    match mapped {
+        Some(mapped) if !mapped.is_empty() => mapped,
+        _ => format!("{}{}", marker, parenthesized(text)),
+    }
+}
+
+/// Text in parentheses unless it is a single character
+fn parenthesized(text: &str) -> String {
+    if text.chars().count() > 1 {
+        format!("({})", text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_unicode() {
+        assert_eq!(to_unicode(r"\alpha + \beta \leq \infty"), "α + β ≤ ∞");
+        assert_eq!(to_unicode(r"x_i^2 + e^{-1} + W^\top"), "xᵢ² + e⁻¹ + Wᵀ");
+        assert_eq!(to_unicode(r"\frac{1}{2} \frac{a+b}{n}"), "½ (a+b)/n");
+        assert_eq!(to_unicode(r"\sqrt{x+1} \sqrt[3]{8}"), "√(x+1) ∛8");
+        assert_eq!(
+            to_unicode(r"\sum_{i=1}^{n} \mathbb{R}^d \text{loss}"),
+            "∑ᵢ₌₁ⁿ ℝᵈ loss"
+        );
+        assert_eq!(
+            to_unicode(r"\hat{y} \left( x_{max} - x_{best} \right)"),
+            "y\u{302} ( xₘₐₓ - x_(best) )"
+        );
+        assert_eq!(to_unicode(r"\unknown{x} \sin x"), r"\unknown{x} sin x");
+    }
+}
@@ -1 +1,13 @@
+pub mod clipboard;
+pub mod deprecation;
+pub mod diff;
 pub mod editor;
+pub mod file_dialog;
+pub mod fixtures;
+pub mod git;
+pub mod lock;
+pub mod math;
+pub mod output;
+pub mod template;
+pub mod text;
+pub mod workspace;
@@ -0,0 +1,73 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+/// Warnings kept back while the TUI owns the terminal, `None` otherwise
+static HELD_WARNINGS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Suppress informational output, set once from `--quiet`
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a warning to stderr unless `--quiet` is set. While warnings are held, e.g.
+/// by the TUI, it is kept for `take_warnings` instead.
+pub fn warn(message: String) {
+    let mut held = HELD_WARNINGS.lock().unwrap_or_else(|e| e.into_inner());
+    match held.as_mut() {
+        Some(warnings) => warnings.push(message),
+        None if !is_quiet() => eprintln!("Warning: {}", message),
+        None => {}
+    }
+}
+
+/// Start or stop holding warnings back. Warnings still held when stopping are printed.
+pub fn hold_warnings(hold: bool) {
+    let held = {
+        let mut held = HELD_WARNINGS.lock().unwrap_or_else(|e| e.into_inner());
+        if hold {
+            held.get_or_insert_with(Vec::new);
+            return;
+        }
+        held.take()
+    };
+    for message in held.into_iter().flatten() {
+        warn(message);
+    }
+}
+
+/// The warnings held back since the last call, oldest first
+pub fn take_warnings() -> Vec<String> {
+    let mut held = HELD_WARNINGS.lock().unwrap_or_else(|e| e.into_inner());
+    held.as_mut().map(std::mem::take).unwrap_or_default()
+}
+
+/// Ask a yes/no question on the terminal, Enter answers yes. None when stdin isn't a
+/// terminal, so scripts never block on a prompt.
+pub fn confirm(question: &str) -> Option<bool> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+    print!("{} [Y/n] ", question);
+    io::stdout().flush().ok()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).ok()?;
+    let answer = answer.trim().to_lowercase();
+    Some(answer.is_empty() || answer == "y" || answer == "yes")
+}
+
+/// Print an informational message, e.g. a success notice, unless `--quiet` is set.
+/// Requested data like entries or config values is printed with `println!` instead.
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::utils::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
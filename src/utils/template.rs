@@ -0,0 +1,52 @@
+/// Fill the `{name}` placeholders of a template, e.g. `- {message} ::{repo}`.
+/// Placeholders without a value are kept as they are.
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            values
+                .iter()
+                .find(|(name, _)| *name == &after[..end])
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        let rendered = render(
+            "- {message} ::{repo} +commit ({branch}, {unknown})",
+            &[
+                ("message", "Fix {repo} typo"),
+                ("repo", "devlog"),
+                ("branch", "main"),
+            ],
+        );
+        // Values aren't rendered again, so braces in a commit message are safe
+        assert_eq!(
+            rendered,
+            "- Fix {repo} typo ::devlog +commit (main, {unknown})"
+        );
+    }
+}
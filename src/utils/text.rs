@@ -0,0 +1,205 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Line endings used when writing entries to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+    /// CRLF on Windows, LF everywhere else
+    Native,
+}
+
+impl LineEnding {
+    /// Convert LF-only text to these line endings
+    pub fn apply(self, text: &str) -> String {
+        let crlf = match self {
+            LineEnding::Lf => false,
+            LineEnding::Crlf => true,
+            LineEnding::Native => cfg!(windows),
+        };
+
+        if crlf {
+            text.replace('\n', "\r\n")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Convert CRLF and lone CR line endings to LF
+pub fn normalize_line_endings(text: &str) -> String {
+    if !text.contains('\r') {
+        return text.to_string();
+    }
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Replace tabs with spaces up to the next multiple of `tab_width` on each line
+pub fn expand_tabs(text: &str, tab_width: usize) -> String {
+    let tab_width = tab_width.max(1);
+    let mut expanded = String::with_capacity(text.len());
+    let mut col = 0;
+
+    for ch in text.chars() {
+        match ch {
+            '\t' => {
+                let spaces = tab_width - col % tab_width;
+                expanded.extend(std::iter::repeat_n(' ', spaces));
+                col += spaces;
+            }
+            '\n' => {
+                expanded.push(ch);
+                col = 0;
+            }
+            _ => {
+                expanded.push(ch);
+                col += 1;
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Clean up pasted text before inserting it into an entry: normalize line endings,
+/// drop terminal escape sequences and control characters, and expand tabs when
+/// `tab_width` is given
+pub fn normalize_paste(text: &str, tab_width: Option<usize>) -> String {
+    let text = normalize_line_endings(text);
+    let mut cleaned = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            // ANSI escape sequences, e.g. colors in copied terminal output
+            '\u{1b}' => {
+                if chars.next_if_eq(&'[').is_some() {
+                    while chars.next().is_some_and(|c| !('@'..='~').contains(&c)) {}
+                }
+            }
+            '\n' | '\t' => cleaned.push(ch),
+            c if c.is_control() => {}
+            c => cleaned.push(c),
+        }
+    }
+
+    match tab_width {
+        Some(tab_width) => expand_tabs(&cleaned, tab_width),
+        None => cleaned,
+    }
+}
+
+/// The file a paste stands for, if it is nothing but the path of an existing file.
+/// Terminals deliver files dropped onto them as pasted paths, quoted, with escaped
+/// spaces or as a `file://` URL depending on the terminal.
+pub fn dropped_path(text: &str) -> Option<PathBuf> {
+    parse_dropped_path(text).filter(|path| path.is_file())
+}
+
+fn parse_dropped_path(text: &str) -> Option<PathBuf> {
+    let text = text.trim();
+    if text.is_empty() || text.contains('\n') {
+        return None;
+    }
+
+    let unquoted = ['\'', '"']
+        .into_iter()
+        .find_map(|quote| text.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(text);
+    let path = match unquoted.strip_prefix("file://") {
+        Some(url) => percent_decode(url)?,
+        None if unquoted.len() < text.len() => unquoted.to_string(),
+        // Spaces and other shell specials arrive escaped with backslashes
+        None => {
+            let mut path = String::with_capacity(unquoted.len());
+            let mut chars = unquoted.chars();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '\\' if cfg!(not(windows)) => path.extend(chars.next()),
+                    ch => path.push(ch),
+                }
+            }
+            path
+        }
+    };
+
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()?.join(rest),
+        None => PathBuf::from(path),
+    };
+    path.is_absolute().then_some(path)
+}
+
+/// Decode `%XX` escapes of a URL path
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+        assert_eq!(normalize_line_endings("unchanged\n"), "unchanged\n");
+    }
+
+    #[test]
+    fn test_line_ending_apply() {
+        assert_eq!(LineEnding::Lf.apply("a\nb"), "a\nb");
+        assert_eq!(LineEnding::Crlf.apply("a\nb"), "a\r\nb");
+    }
+
+    #[test]
+    fn test_expand_tabs_to_tab_stops() {
+        assert_eq!(expand_tabs("\tx\nab\tc", 4), "    x\nab  c");
+    }
+
+    #[test]
+    fn test_normalize_paste() {
+        let pasted = "\u{1b}[31merror\u{1b}[0m:\tfailed\r\nnext\u{7}";
+
+        assert_eq!(normalize_paste(pasted, Some(4)), "error:  failed\nnext");
+        assert_eq!(normalize_paste(pasted, None), "error:\tfailed\nnext");
+    }
+
+    #[test]
+    fn test_parse_dropped_path() {
+        let expected = Some(PathBuf::from("/tmp/my report.pdf"));
+        assert_eq!(parse_dropped_path("/tmp/my\\ report.pdf"), expected);
+        assert_eq!(parse_dropped_path("'/tmp/my report.pdf' "), expected);
+        assert_eq!(parse_dropped_path("file:///tmp/my%20report.pdf"), expected);
+
+        assert_eq!(parse_dropped_path("relative/path.pdf"), None);
+        assert_eq!(parse_dropped_path("/tmp/a\n/tmp/b"), None);
+        assert_eq!(parse_dropped_path("file:///tmp/bad%2"), None);
+    }
+
+    #[test]
+    fn test_dropped_path_must_exist() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "notes").unwrap();
+
+        assert_eq!(dropped_path(&file.display().to_string()), Some(file));
+        assert_eq!(dropped_path(&dir.path().display().to_string()), None);
+        assert_eq!(dropped_path("/does/not/exist.txt"), None);
+    }
+}
@@ -0,0 +1,187 @@
+use std::{collections::BTreeMap, path::Path};
+
+use color_eyre::eyre::{Result, bail};
+
+use crate::error::{DevlogError, ErrorKind};
+use crate::models::annotation::Annotations;
+use crate::utils::git;
+
+/// The git repository devlog runs in, and the `::project` its entries mention
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub repo: String,
+    pub project: String,
+    /// Root directory of the repository
+    pub root: String,
+    /// `origin` without scheme, user and `.git`, e.g. `github.com/acme/api`
+    pub remote: Option<String>,
+    /// Whether `workspaces` maps the repository, or it is only its own project
+    pub mapped: bool,
+}
+
+impl Workspace {
+    /// The workspace of the current directory, with the project `workspaces` maps it to
+    pub fn detect(workspaces: &BTreeMap<String, String>) -> Result<Self> {
+        let Some(workspace) = Self::current(workspaces) else {
+            bail!(DevlogError::new(
+                ErrorKind::Validation,
+                "--here only works inside a git repository"
+            ));
+        };
+        Ok(workspace)
+    }
+
+    /// Like `detect`, `None` outside of a git repository
+    pub fn current(workspaces: &BTreeMap<String, String>) -> Option<Self> {
+        let toplevel = git::toplevel().ok()?;
+        let remote = git::run(&["remote", "get-url", "origin"]).ok();
+        Some(Self::resolve(&toplevel, remote.as_deref(), workspaces))
+    }
+
+    /// A repository is mapped by its root path, `~` standing for the home directory, by
+    /// its `origin` remote, or by its name. Unmapped repositories are their own project,
+    /// like in commit hook entries.
+    pub fn resolve(
+        toplevel: &str,
+        remote: Option<&str>,
+        workspaces: &BTreeMap<String, String>,
+    ) -> Self {
+        let mut workspace = Self {
+            repo: git::repo_name(toplevel),
+            project: String::new(),
+            root: toplevel.to_string(),
+            remote: remote
+                .map(normalize_remote)
+                .filter(|remote| !remote.is_empty()),
+            mapped: false,
+        };
+        let mapping = workspaces
+            .iter()
+            .find(|(key, _)| workspace.is_at(key))
+            .or_else(|| workspaces.iter().find(|(key, _)| workspace.is_from(key)))
+            .or_else(|| workspaces.get_key_value(&workspace.repo));
+
+        workspace.mapped = mapping.is_some();
+        workspace.project = mapping
+            .map_or(workspace.repo.as_str(), |(_, project)| {
+                project.trim_start_matches("::")
+            })
+            .to_string();
+        workspace
+    }
+
+    /// Whether `key` names the repository by path, remote or name
+    pub fn matches(&self, key: &str) -> bool {
+        self.is_at(key) || self.is_from(key) || key == self.repo
+    }
+
+    /// How a first seen repository is remembered in the config: by its remote, which
+    /// stays the same across clones, or else by its root path
+    pub fn key(&self) -> String {
+        self.remote.clone().unwrap_or_else(|| self.root.clone())
+    }
+
+    fn is_at(&self, key: &str) -> bool {
+        let path = match (key.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(relative), Some(home)) => home.join(relative),
+            _ => Path::new(key).to_path_buf(),
+        };
+        path == Path::new(&self.root)
+    }
+
+    fn is_from(&self, key: &str) -> bool {
+        self.remote
+            .as_deref()
+            .is_some_and(|remote| normalize_remote(key) == remote)
+    }
+
+    /// `text` mentioning the workspace's project, which is added at the end if missing
+    pub fn mention_in(&self, text: &str) -> String {
+        if Annotations::parse(text).projects.contains(&self.project) {
+            text.to_string()
+        } else {
+            format!("{} ::{}", text.trim_end(), self.project)
+        }
+    }
+}
+
+/// `url` of a remote without scheme, user and `.git`, so that the ssh and https URLs of
+/// a repository are the same, e.g. `github.com/acme/api`
+fn normalize_remote(url: &str) -> String {
+    let url = url.trim();
+    let (url, scp_like) = match url.split_once("://") {
+        Some((_, rest)) => (rest, false),
+        None => (url, true),
+    };
+    let url = match url.split_once('@') {
+        Some((user, rest)) if !user.contains('/') => rest,
+        _ => url,
+    };
+    let url = match url.split_once(':') {
+        Some((host, path)) if scp_like && !host.contains('/') => format!("{}/{}", host, path),
+        _ => url.to_string(),
+    };
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_repo_to_project() {
+        let workspaces = BTreeMap::from([
+            ("api".to_string(), "::payments".to_string()),
+            ("/src/web".to_string(), "storefront".to_string()),
+            ("github.com/acme/docs".to_string(), "handbook".to_string()),
+        ]);
+
+        let here = |toplevel, remote| Workspace::resolve(toplevel, remote, &workspaces);
+        assert_eq!(here("/home/me/src/api", None).project, "payments");
+        assert_eq!(here("/src/web", None).project, "storefront");
+        assert_eq!(here("/src/web", None).repo, "web");
+        let docs = here("/tmp/docs-clone", Some("git@github.com:acme/docs.git"));
+        assert_eq!(docs.project, "handbook");
+        assert!(docs.mapped);
+
+        let web = here("/home/me/src/web", Some("https://github.com/acme/web.git"));
+        assert_eq!(web.project, "web");
+        assert!(!web.mapped);
+        assert_eq!(web.key(), "github.com/acme/web");
+        assert!(web.matches("https://github.com/acme/web"));
+        assert!(web.matches("web"));
+        assert!(!web.matches("api"));
+        assert_eq!(here("/src/tools", None).key(), "/src/tools");
+    }
+
+    #[test]
+    fn test_normalize_remote() {
+        for url in [
+            "git@github.com:acme/api.git",
+            "https://github.com/acme/api.git",
+            "ssh://git@github.com/acme/api",
+            "https://user@github.com/acme/api/",
+        ] {
+            assert_eq!(normalize_remote(url), "github.com/acme/api", "{}", url);
+        }
+        assert_eq!(normalize_remote("/srv/git/api.git"), "/srv/git/api");
+    }
+
+    #[test]
+    fn test_mention_in() {
+        let workspace = Workspace::resolve(
+            "/src/api",
+            None,
+            &BTreeMap::from([("api".to_string(), "payments".to_string())]),
+        );
+        assert_eq!(
+            workspace.mention_in("Fixed retries\n"),
+            "Fixed retries ::payments"
+        );
+        assert_eq!(
+            workspace.mention_in("Fixed ::payments."),
+            "Fixed ::payments."
+        );
+    }
+}